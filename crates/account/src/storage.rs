@@ -47,6 +47,28 @@ where
     storage.read(&threshold_key)
 }
 
+/// Write the vesting of an account
+pub fn write_vesting<S>(
+    storage: &mut S,
+    owner: &Address,
+    vesting: Vesting,
+) -> Result<()>
+where
+    S: StorageWrite + StorageRead,
+{
+    let vesting_key = vesting_key(owner);
+    storage.write(&vesting_key, vesting)
+}
+
+/// Get the vesting of an account, if any
+pub fn vesting<S>(storage: &S, owner: &Address) -> Result<Option<Vesting>>
+where
+    S: StorageRead,
+{
+    let vesting_key = vesting_key(owner);
+    storage.read(&vesting_key)
+}
+
 /// Get the public keys associated with an account
 pub fn public_keys<S>(
     storage: &S,
@@ -2,6 +2,8 @@ use namada_core::address::Address;
 use namada_core::borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use namada_core::hash::Hash;
 use namada_core::key::common;
+use namada_core::storage::Epoch;
+use namada_core::token;
 use namada_macros::BorshDeserializer;
 #[cfg(feature = "migrations")]
 use namada_migrations::*;
@@ -28,6 +30,8 @@ pub struct InitAccount {
     pub vp_code_hash: Hash,
     /// The account signature threshold
     pub threshold: u8,
+    /// The vesting of the account's tokens, if any
+    pub vesting: Option<Vesting>,
 }
 
 /// A tx data type to update an account's validity predicate
@@ -55,12 +59,184 @@ pub struct UpdateAccount {
     pub threshold: Option<u8>,
 }
 
+/// A step of a custom vesting schedule
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct VestingUnlock {
+    /// The epoch from which the amount is unlocked
+    pub epoch: Epoch,
+    /// The amount unlocked
+    pub amount: token::Amount,
+}
+
+/// The schedule by which vesting tokens are unlocked
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum VestingSchedule {
+    /// Tokens are unlocked linearly every epoch from the start epoch until
+    /// the end epoch, but none of them before the cliff epoch
+    Linear {
+        /// The epoch from which tokens start to vest
+        start_epoch: Epoch,
+        /// The first epoch in which vested tokens are unlocked
+        cliff_epoch: Epoch,
+        /// The epoch from which all the tokens are unlocked
+        end_epoch: Epoch,
+    },
+    /// Tokens are unlocked by the given steps
+    Custom(Vec<VestingUnlock>),
+}
+
+/// Tokens of an account locked by a vesting schedule
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct Vesting {
+    /// The vesting token
+    pub token: Address,
+    /// The total amount of tokens locked by the schedule
+    pub amount: token::Amount,
+    /// The unlock schedule
+    pub schedule: VestingSchedule,
+}
+
+impl Vesting {
+    /// The amount of tokens unlocked at the given epoch
+    pub fn unlocked_amount(&self, epoch: Epoch) -> token::Amount {
+        match &self.schedule {
+            VestingSchedule::Linear {
+                start_epoch,
+                cliff_epoch,
+                end_epoch,
+            } => {
+                if epoch < *cliff_epoch {
+                    token::Amount::zero()
+                } else if epoch >= *end_epoch {
+                    self.amount
+                } else {
+                    let elapsed = epoch.0.saturating_sub(start_epoch.0);
+                    let duration = end_epoch.0.saturating_sub(start_epoch.0);
+                    self.amount
+                        .checked_mul(token::Amount::from(elapsed))
+                        .and_then(|amount| {
+                            amount.checked_div(token::Amount::from(duration))
+                        })
+                        .unwrap_or_default()
+                }
+            }
+            VestingSchedule::Custom(unlocks) => {
+                let unlocked = unlocks
+                    .iter()
+                    .filter(|unlock| unlock.epoch <= epoch)
+                    .try_fold(token::Amount::zero(), |acc, unlock| {
+                        acc.checked_add(unlock.amount)
+                    })
+                    .unwrap_or_default();
+                std::cmp::min(unlocked, self.amount)
+            }
+        }
+    }
+
+    /// The amount of tokens still locked at the given epoch
+    pub fn locked_amount(&self, epoch: Epoch) -> token::Amount {
+        self.amount
+            .checked_sub(self.unlocked_amount(epoch))
+            .unwrap_or_default()
+    }
+
+    /// Check that the vesting is consistent, returning the reason why it
+    /// isn't otherwise
+    pub fn validate(&self) -> Result<(), String> {
+        self.schedule.validate(self.amount)
+    }
+}
+
+impl VestingSchedule {
+    /// Check that the schedule is consistent for the given vesting amount,
+    /// returning the reason why it isn't otherwise
+    pub fn validate(&self, amount: token::Amount) -> Result<(), String> {
+        if amount.is_zero() {
+            return Err("the vesting amount must be positive".to_string());
+        }
+        match self {
+            VestingSchedule::Linear {
+                start_epoch,
+                cliff_epoch,
+                end_epoch,
+            } => {
+                if start_epoch > cliff_epoch || cliff_epoch > end_epoch {
+                    return Err("the vesting epochs must satisfy start epoch \
+                                <= cliff epoch <= end epoch"
+                        .to_string());
+                }
+            }
+            VestingSchedule::Custom(unlocks) => {
+                if unlocks.is_empty() {
+                    return Err(
+                        "a custom vesting schedule cannot be empty".to_string()
+                    );
+                }
+                if !unlocks
+                    .windows(2)
+                    .all(|unlocks| unlocks[0].epoch < unlocks[1].epoch)
+                {
+                    return Err("the vesting unlocks must be sorted by \
+                                strictly increasing epochs"
+                        .to_string());
+                }
+                let total = unlocks
+                    .iter()
+                    .try_fold(token::Amount::zero(), |acc, unlock| {
+                        acc.checked_add(unlock.amount)
+                    });
+                if total != Some(amount) {
+                    return Err("the vesting unlocks must sum up to the \
+                                vesting amount"
+                        .to_string());
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
 #[cfg(any(test, feature = "testing"))]
 /// Tests and strategies for accounts
 pub mod tests {
-    use namada_core::address::testing::arb_non_internal_address;
+    use namada_core::address::testing::{arb_non_internal_address, nam};
     use namada_core::hash::testing::arb_hash;
     use namada_core::key::testing::arb_common_pk;
+    use namada_core::token::testing::arb_amount;
     use proptest::prelude::Just;
     use proptest::{collection, option, prop_compose};
 
@@ -74,15 +250,137 @@ pub mod tests {
             threshold in 0..=public_keys.len() as u8,
             public_keys in Just(public_keys),
             vp_code_hash in arb_hash(),
+            vesting in option::of(arb_vesting()),
         ) -> InitAccount {
             InitAccount {
                 public_keys,
                 vp_code_hash,
                 threshold,
+                vesting,
             }
         }
     }
 
+    prop_compose! {
+        /// Generate an arbitrary linear vesting
+        pub fn arb_vesting()(
+            token in arb_non_internal_address(),
+            amount in arb_amount(),
+            start_epoch in 0..1_000_000u64,
+            cliff_offset in 0..100u64,
+            end_offset in 0..100u64,
+        ) -> Vesting {
+            let start_epoch = Epoch(start_epoch);
+            let cliff_epoch = start_epoch + cliff_offset;
+            Vesting {
+                token,
+                amount,
+                schedule: VestingSchedule::Linear {
+                    start_epoch,
+                    cliff_epoch,
+                    end_epoch: cliff_epoch + end_offset,
+                },
+            }
+        }
+    }
+
+    #[test]
+    fn test_vesting_cliff_unlock() {
+        let vesting = Vesting {
+            token: nam(),
+            amount: token::Amount::from(100),
+            schedule: VestingSchedule::Linear {
+                start_epoch: Epoch(0),
+                cliff_epoch: Epoch(10),
+                end_epoch: Epoch(10),
+            },
+        };
+        assert!(vesting.validate().is_ok());
+
+        // Nothing is unlocked before the cliff, everything from it
+        assert_eq!(vesting.unlocked_amount(Epoch(0)), token::Amount::zero());
+        assert_eq!(vesting.unlocked_amount(Epoch(9)), token::Amount::zero());
+        assert_eq!(vesting.locked_amount(Epoch(9)), vesting.amount);
+        assert_eq!(vesting.unlocked_amount(Epoch(10)), vesting.amount);
+        assert_eq!(vesting.locked_amount(Epoch(10)), token::Amount::zero());
+    }
+
+    #[test]
+    fn test_vesting_linear_unlock() {
+        let vesting = Vesting {
+            token: nam(),
+            amount: token::Amount::from(100),
+            schedule: VestingSchedule::Linear {
+                start_epoch: Epoch(10),
+                cliff_epoch: Epoch(12),
+                end_epoch: Epoch(20),
+            },
+        };
+        assert!(vesting.validate().is_ok());
+
+        assert_eq!(vesting.unlocked_amount(Epoch(5)), token::Amount::zero());
+        assert_eq!(vesting.unlocked_amount(Epoch(11)), token::Amount::zero());
+        // From the cliff, the tokens vested since the start are unlocked
+        assert_eq!(vesting.unlocked_amount(Epoch(12)), token::Amount::from(20));
+        assert_eq!(vesting.unlocked_amount(Epoch(15)), token::Amount::from(50));
+        assert_eq!(vesting.locked_amount(Epoch(15)), token::Amount::from(50));
+        assert_eq!(vesting.unlocked_amount(Epoch(19)), token::Amount::from(90));
+        assert_eq!(vesting.unlocked_amount(Epoch(20)), vesting.amount);
+        assert_eq!(vesting.unlocked_amount(Epoch(100)), vesting.amount);
+
+        // The epochs must be ordered
+        let invalid = Vesting {
+            schedule: VestingSchedule::Linear {
+                start_epoch: Epoch(10),
+                cliff_epoch: Epoch(21),
+                end_epoch: Epoch(20),
+            },
+            ..vesting
+        };
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn test_vesting_custom_unlock() {
+        let unlock = |epoch: u64, amount: u64| VestingUnlock {
+            epoch: Epoch(epoch),
+            amount: token::Amount::from(amount),
+        };
+        let vesting = Vesting {
+            token: nam(),
+            amount: token::Amount::from(100),
+            schedule: VestingSchedule::Custom(vec![
+                unlock(5, 30),
+                unlock(10, 70),
+            ]),
+        };
+        assert!(vesting.validate().is_ok());
+
+        assert_eq!(vesting.unlocked_amount(Epoch(4)), token::Amount::zero());
+        assert_eq!(vesting.unlocked_amount(Epoch(5)), token::Amount::from(30));
+        assert_eq!(vesting.unlocked_amount(Epoch(9)), token::Amount::from(30));
+        assert_eq!(vesting.locked_amount(Epoch(9)), token::Amount::from(70));
+        assert_eq!(vesting.unlocked_amount(Epoch(10)), vesting.amount);
+
+        // The unlocks must be sorted and sum up to the vesting amount
+        let unsorted = Vesting {
+            schedule: VestingSchedule::Custom(vec![
+                unlock(10, 70),
+                unlock(5, 30),
+            ]),
+            ..vesting.clone()
+        };
+        assert!(unsorted.validate().is_err());
+        let inconsistent = Vesting {
+            schedule: VestingSchedule::Custom(vec![
+                unlock(5, 30),
+                unlock(10, 60),
+            ]),
+            ..vesting
+        };
+        assert!(inconsistent.validate().is_err());
+    }
+
     prop_compose! {
         /// Generate an arbitrary account update
         pub fn arb_update_account()(
@@ -2,7 +2,7 @@
 //! `BenchShell` and helper functions to generate transactions.
 
 use std::cell::RefCell;
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs::{File, OpenOptions};
 use std::io::{Read, Write};
 use std::ops::{Deref, DerefMut};
@@ -256,6 +256,7 @@ impl Default for BenchShell {
                 voting_start_epoch,
                 voting_end_epoch: voting_start_epoch + 3_u64,
                 grace_epoch: voting_start_epoch + 9_u64,
+                expedited: false,
             },
             None,
             Some(vec![content_section]),
@@ -1003,6 +1004,7 @@ impl BenchShieldedCtx {
                 None,
                 &[spending_key.into()],
                 &[],
+                &BTreeMap::new(),
             ))
             .unwrap();
         let native_token = self.shell.state.in_mem().native_token.clone();
@@ -1031,6 +1033,7 @@ impl BenchShieldedCtx {
                      masp_tx,
                      metadata: _,
                      epoch: _,
+                     spend_randomizers: _,
                  }| masp_tx,
             );
 
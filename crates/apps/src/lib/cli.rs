@@ -222,6 +222,7 @@ pub mod cmds {
                 // Governance transactions
                 .subcommand(TxInitProposal::def().display_order(1))
                 .subcommand(TxVoteProposal::def().display_order(1))
+                .subcommand(TxChangeRepresentative::def().display_order(1))
                 // PoS transactions
                 .subcommand(TxBecomeValidator::def().display_order(2))
                 .subcommand(TxInitValidator::def().display_order(2))
@@ -236,19 +237,27 @@ pub mod cmds {
                 .subcommand(TxCommissionRateChange::def().display_order(2))
                 .subcommand(TxChangeConsensusKey::def().display_order(2))
                 .subcommand(TxMetadataChange::def().display_order(2))
+                .subcommand(TxDelegationPolicyChange::def().display_order(2))
                 // Ethereum bridge transactions
                 .subcommand(AddToEthBridgePool::def().display_order(3))
                 // PGF transactions
                 .subcommand(TxUpdateStewardCommission::def().display_order(4))
                 .subcommand(TxResignSteward::def().display_order(4))
+                .subcommand(TxRegisterPgfProject::def().display_order(4))
+                .subcommand(TxVotePgfFundingRound::def().display_order(4))
                 // Queries
                 .subcommand(QueryEpoch::def().display_order(5))
                 .subcommand(QueryNextEpochInfo::def().display_order(5))
                 .subcommand(QueryStatus::def().display_order(5))
                 .subcommand(QueryAccount::def().display_order(5))
                 .subcommand(QueryTransfers::def().display_order(5))
+                .subcommand(QueryShieldedHistory::def().display_order(5))
+                .subcommand(DiscloseNotes::def().display_order(5))
+                .subcommand(VerifyNoteDisclosure::def().display_order(5))
                 .subcommand(QueryConversions::def().display_order(5))
                 .subcommand(QueryMaspRewardTokens::def().display_order(5))
+                .subcommand(QueryMaspTokenLimits::def().display_order(5))
+                .subcommand(QueryShieldedRewards::def().display_order(5))
                 .subcommand(QueryBlock::def().display_order(5))
                 .subcommand(QueryBalance::def().display_order(5))
                 .subcommand(QueryBonds::def().display_order(5))
@@ -260,15 +269,18 @@ pub mod cmds {
                 .subcommand(QueryRawBytes::def().display_order(5))
                 .subcommand(QueryProposal::def().display_order(5))
                 .subcommand(QueryProposalVotes::def().display_order(5))
+                .subcommand(QueryRepresentatives::def().display_order(5))
                 .subcommand(QueryProposalResult::def().display_order(5))
                 .subcommand(QueryProtocolParameters::def().display_order(5))
                 .subcommand(QueryPgf::def().display_order(5))
                 .subcommand(QueryValidatorState::def().display_order(5))
+                .subcommand(QueryValidatorLiveness::def().display_order(5))
                 .subcommand(QueryCommissionRate::def().display_order(5))
                 .subcommand(QueryRewards::def().display_order(5))
                 .subcommand(QueryMetaData::def().display_order(5))
                 // Actions
                 .subcommand(SignTx::def().display_order(6))
+                .subcommand(SignMaspSpends::def().display_order(6))
                 .subcommand(ShieldedSync::def().display_order(6))
                 .subcommand(GenIbcShieldedTransfer::def().display_order(6))
                 // Utils
@@ -298,16 +310,24 @@ pub mod cmds {
                 Self::parse_with_ctx(matches, TxInitProposal);
             let tx_vote_proposal =
                 Self::parse_with_ctx(matches, TxVoteProposal);
+            let tx_change_representative =
+                Self::parse_with_ctx(matches, TxChangeRepresentative);
             let tx_update_steward_commission =
                 Self::parse_with_ctx(matches, TxUpdateStewardCommission);
             let tx_resign_steward =
                 Self::parse_with_ctx(matches, TxResignSteward);
+            let tx_register_pgf_project =
+                Self::parse_with_ctx(matches, TxRegisterPgfProject);
+            let tx_vote_pgf_funding_round =
+                Self::parse_with_ctx(matches, TxVotePgfFundingRound);
             let tx_commission_rate_change =
                 Self::parse_with_ctx(matches, TxCommissionRateChange);
             let tx_change_consensus_key =
                 Self::parse_with_ctx(matches, TxChangeConsensusKey);
             let tx_change_metadata =
                 Self::parse_with_ctx(matches, TxMetadataChange);
+            let tx_change_delegation_policy =
+                Self::parse_with_ctx(matches, TxDelegationPolicyChange);
             let bond = Self::parse_with_ctx(matches, Bond);
             let unbond = Self::parse_with_ctx(matches, Unbond);
             let withdraw = Self::parse_with_ctx(matches, Withdraw);
@@ -319,10 +339,19 @@ pub mod cmds {
             let query_status = Self::parse_with_ctx(matches, QueryStatus);
             let query_account = Self::parse_with_ctx(matches, QueryAccount);
             let query_transfers = Self::parse_with_ctx(matches, QueryTransfers);
+            let query_shielded_history =
+                Self::parse_with_ctx(matches, QueryShieldedHistory);
+            let disclose_notes = Self::parse_with_ctx(matches, DiscloseNotes);
+            let verify_note_disclosure =
+                Self::parse_with_ctx(matches, VerifyNoteDisclosure);
             let query_conversions =
                 Self::parse_with_ctx(matches, QueryConversions);
             let query_masp_reward_tokens =
                 Self::parse_with_ctx(matches, QueryMaspRewardTokens);
+            let query_masp_token_limits =
+                Self::parse_with_ctx(matches, QueryMaspTokenLimits);
+            let query_shielded_rewards =
+                Self::parse_with_ctx(matches, QueryShieldedRewards);
             let query_block = Self::parse_with_ctx(matches, QueryBlock);
             let query_balance = Self::parse_with_ctx(matches, QueryBalance);
             let query_bonds = Self::parse_with_ctx(matches, QueryBonds);
@@ -339,6 +368,8 @@ pub mod cmds {
             let query_proposal = Self::parse_with_ctx(matches, QueryProposal);
             let query_proposal_votes =
                 Self::parse_with_ctx(matches, QueryProposalVotes);
+            let query_representatives =
+                Self::parse_with_ctx(matches, QueryRepresentatives);
             let query_proposal_result =
                 Self::parse_with_ctx(matches, QueryProposalResult);
             let query_protocol_parameters =
@@ -346,12 +377,16 @@ pub mod cmds {
             let query_pgf = Self::parse_with_ctx(matches, QueryPgf);
             let query_validator_state =
                 Self::parse_with_ctx(matches, QueryValidatorState);
+            let query_validator_liveness =
+                Self::parse_with_ctx(matches, QueryValidatorLiveness);
             let query_commission =
                 Self::parse_with_ctx(matches, QueryCommissionRate);
             let query_metadata = Self::parse_with_ctx(matches, QueryMetaData);
             let add_to_eth_bridge_pool =
                 Self::parse_with_ctx(matches, AddToEthBridgePool);
             let sign_tx = Self::parse_with_ctx(matches, SignTx);
+            let sign_masp_spends =
+                Self::parse_with_ctx(matches, SignMaspSpends);
             let shielded_sync = Self::parse_with_ctx(matches, ShieldedSync);
             let gen_ibc_shielded =
                 Self::parse_with_ctx(matches, GenIbcShieldedTransfer);
@@ -364,11 +399,13 @@ pub mod cmds {
                 .or(tx_reveal_pk)
                 .or(tx_init_proposal)
                 .or(tx_vote_proposal)
+                .or(tx_change_representative)
                 .or(tx_become_validator)
                 .or(tx_init_validator)
                 .or(tx_commission_rate_change)
                 .or(tx_change_consensus_key)
                 .or(tx_change_metadata)
+                .or(tx_change_delegation_policy)
                 .or(tx_unjail_validator)
                 .or(tx_deactivate_validator)
                 .or(tx_reactivate_validator)
@@ -380,12 +417,19 @@ pub mod cmds {
                 .or(add_to_eth_bridge_pool)
                 .or(tx_update_steward_commission)
                 .or(tx_resign_steward)
+                .or(tx_register_pgf_project)
+                .or(tx_vote_pgf_funding_round)
                 .or(query_epoch)
                 .or(query_next_epoch_info)
                 .or(query_status)
                 .or(query_transfers)
+                .or(query_shielded_history)
+                .or(disclose_notes)
+                .or(verify_note_disclosure)
                 .or(query_conversions)
                 .or(query_masp_reward_tokens)
+                .or(query_masp_token_limits)
+                .or(query_shielded_rewards)
                 .or(query_block)
                 .or(query_balance)
                 .or(query_bonds)
@@ -398,14 +442,17 @@ pub mod cmds {
                 .or(query_raw_bytes)
                 .or(query_proposal)
                 .or(query_proposal_votes)
+                .or(query_representatives)
                 .or(query_proposal_result)
                 .or(query_protocol_parameters)
                 .or(query_pgf)
                 .or(query_validator_state)
+                .or(query_validator_liveness)
                 .or(query_commission)
                 .or(query_metadata)
                 .or(query_account)
                 .or(sign_tx)
+                .or(sign_masp_spends)
                 .or(shielded_sync)
                 .or(gen_ibc_shielded)
                 .or(utils)
@@ -456,11 +503,13 @@ pub mod cmds {
         TxCommissionRateChange(TxCommissionRateChange),
         TxChangeConsensusKey(TxChangeConsensusKey),
         TxMetadataChange(TxMetadataChange),
+        TxDelegationPolicyChange(TxDelegationPolicyChange),
         TxUnjailValidator(TxUnjailValidator),
         TxDeactivateValidator(TxDeactivateValidator),
         TxReactivateValidator(TxReactivateValidator),
         TxInitProposal(TxInitProposal),
         TxVoteProposal(TxVoteProposal),
+        TxChangeRepresentative(TxChangeRepresentative),
         TxRevealPk(TxRevealPk),
         Bond(Bond),
         Unbond(Unbond),
@@ -470,13 +519,20 @@ pub mod cmds {
         AddToEthBridgePool(AddToEthBridgePool),
         TxUpdateStewardCommission(TxUpdateStewardCommission),
         TxResignSteward(TxResignSteward),
+        TxRegisterPgfProject(TxRegisterPgfProject),
+        TxVotePgfFundingRound(TxVotePgfFundingRound),
         QueryEpoch(QueryEpoch),
         QueryNextEpochInfo(QueryNextEpochInfo),
         QueryStatus(QueryStatus),
         QueryAccount(QueryAccount),
         QueryTransfers(QueryTransfers),
+        QueryShieldedHistory(QueryShieldedHistory),
+        DiscloseNotes(DiscloseNotes),
+        VerifyNoteDisclosure(VerifyNoteDisclosure),
         QueryConversions(QueryConversions),
         QueryMaspRewardTokens(QueryMaspRewardTokens),
+        QueryMaspTokenLimits(QueryMaspTokenLimits),
+        QueryShieldedRewards(QueryShieldedRewards),
         QueryBlock(QueryBlock),
         QueryBalance(QueryBalance),
         QueryBonds(QueryBonds),
@@ -489,12 +545,15 @@ pub mod cmds {
         QueryRawBytes(QueryRawBytes),
         QueryProposal(QueryProposal),
         QueryProposalVotes(QueryProposalVotes),
+        QueryRepresentatives(QueryRepresentatives),
         QueryProposalResult(QueryProposalResult),
         QueryProtocolParameters(QueryProtocolParameters),
         QueryPgf(QueryPgf),
         QueryValidatorState(QueryValidatorState),
+        QueryValidatorLiveness(QueryValidatorLiveness),
         QueryRewards(QueryRewards),
         SignTx(SignTx),
+        SignMaspSpends(SignMaspSpends),
         ShieldedSync(ShieldedSync),
         GenIbcShieldedTransfer(GenIbcShieldedTransfer),
     }
@@ -842,6 +901,7 @@ pub mod cmds {
         UpdateDB(LedgerUpdateDB),
         QueryDB(LedgerQueryDB),
         RollBack(LedgerRollBack),
+        SimulateSlash(LedgerSimulateSlash),
     }
 
     impl SubCmd for Ledger {
@@ -856,12 +916,15 @@ pub mod cmds {
                 let query_db = SubCmd::parse(matches).map(Self::QueryDB);
                 let rollback = SubCmd::parse(matches).map(Self::RollBack);
                 let run_until = SubCmd::parse(matches).map(Self::RunUntil);
+                let simulate_slash =
+                    SubCmd::parse(matches).map(Self::SimulateSlash);
                 run.or(reset)
                     .or(dump_db)
                     .or(update_db)
                     .or(query_db)
                     .or(rollback)
                     .or(run_until)
+                    .or(simulate_slash)
                     // The `run` command is the default if no sub-command given
                     .or(Some(Self::Run(LedgerRun(args::LedgerRun {
                         start_time: None,
@@ -882,6 +945,7 @@ pub mod cmds {
                 .subcommand(LedgerUpdateDB::def())
                 .subcommand(LedgerQueryDB::def())
                 .subcommand(LedgerRollBack::def())
+                .subcommand(LedgerSimulateSlash::def())
         }
     }
 
@@ -1028,6 +1092,30 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct LedgerSimulateSlash(pub args::LedgerSimulateSlash);
+
+    impl SubCmd for LedgerSimulateSlash {
+        const CMD: &'static str = "simulate-slash";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::LedgerSimulateSlash::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Simulate the slashing of a hypothetical validator \
+                     infraction on top of the last committed state (or the \
+                     genesis state) while the ledger is not running. No \
+                     changes are written to the DB.",
+                )
+                .add_args::<args::LedgerSimulateSlash>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub enum Config {
         Gen(ConfigGen),
@@ -1133,6 +1221,33 @@ pub mod cmds {
         }
     }
 
+    #[derive(Debug, Clone)]
+    pub struct QueryRepresentatives(
+        pub args::QueryRepresentatives<args::CliTypes>,
+    );
+
+    impl SubCmd for QueryRepresentatives {
+        const CMD: &'static str = "query-representatives";
+
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                QueryRepresentatives(args::QueryRepresentatives::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Query the governance representatives voting on behalf of \
+                     delegators.",
+                )
+                .add_args::<args::QueryRepresentatives<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct QueryProposal(pub args::QueryProposal<args::CliTypes>);
 
@@ -1222,7 +1337,10 @@ pub mod cmds {
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Query pgf stewards and continuous funding.")
+                .about(
+                    "Query pgf stewards and continuous funding, the pgf \
+                     treasury history or the pgf funding rounds.",
+                )
                 .add_args::<args::QueryPgf<args::CliTypes>>()
         }
     }
@@ -1685,6 +1803,57 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct QueryMaspTokenLimits(
+        pub args::QueryMaspTokenLimits<args::CliTypes>,
+    );
+
+    impl SubCmd for QueryMaspTokenLimits {
+        const CMD: &'static str = "masp-token-limits";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                QueryMaspTokenLimits(args::QueryMaspTokenLimits::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Query the MASP limits of a token and the amounts that \
+                     can still be shielded and unshielded in the current \
+                     epoch.",
+                )
+                .add_args::<args::QueryMaspTokenLimits<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct QueryShieldedRewards(
+        pub args::QueryShieldedRewards<args::CliTypes>,
+    );
+
+    impl SubCmd for QueryShieldedRewards {
+        const CMD: &'static str = "shielded-rewards";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                QueryShieldedRewards(args::QueryShieldedRewards::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Query the MASP rewards that the notes of a viewing key \
+                     can claim, their projection for the next epoch and the \
+                     rewards of the last epochs. Run shielded-sync beforehand \
+                     to include the latest transactions.",
+                )
+                .add_args::<args::QueryShieldedRewards<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct QueryBlock(pub args::Query<args::CliTypes>);
 
@@ -1780,6 +1949,28 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct SignMaspSpends(pub args::SignMaspSpends<args::CliTypes>);
+
+    impl SubCmd for SignMaspSpends {
+        const CMD: &'static str = "sign-masp-spends";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                SignMaspSpends(args::SignMaspSpends::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Sign offline the shielded spends of a transaction built \
+                     with the proof generation key of a spending key.",
+                )
+                .add_args::<args::SignMaspSpends<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct QueryValidatorState(
         pub args::QueryValidatorState<args::CliTypes>,
@@ -1801,6 +1992,32 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct QueryValidatorLiveness(
+        pub args::QueryValidatorLiveness<args::CliTypes>,
+    );
+
+    impl SubCmd for QueryValidatorLiveness {
+        const CMD: &'static str = "validator-liveness";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                QueryValidatorLiveness(args::QueryValidatorLiveness::parse(
+                    matches,
+                ))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Query the missed votes of a PoS validator within the \
+                     liveness window.",
+                )
+                .add_args::<args::QueryValidatorLiveness<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct QueryTransfers(pub args::QueryTransfers<args::CliTypes>);
 
@@ -1820,6 +2037,79 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct QueryShieldedHistory(
+        pub args::QueryShieldedHistory<args::CliTypes>,
+    );
+
+    impl SubCmd for QueryShieldedHistory {
+        const CMD: &'static str = "export-shielded-history";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                QueryShieldedHistory(args::QueryShieldedHistory::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Export the history of the shielded balance of a viewing \
+                     key as JSON or CSV. Run shielded-sync beforehand to \
+                     include the latest transactions.",
+                )
+                .add_args::<args::QueryShieldedHistory<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct DiscloseNotes(pub args::DiscloseNotes<args::CliTypes>);
+
+    impl SubCmd for DiscloseNotes {
+        const CMD: &'static str = "disclose-notes";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                DiscloseNotes(args::DiscloseNotes::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Disclose the notes received or sent by a viewing key in \
+                     a transaction, to prove the payment to an auditor \
+                     without sharing the viewing key. Run shielded-sync \
+                     beforehand.",
+                )
+                .add_args::<args::DiscloseNotes<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct VerifyNoteDisclosure(
+        pub args::VerifyNoteDisclosure<args::CliTypes>,
+    );
+
+    impl SubCmd for VerifyNoteDisclosure {
+        const CMD: &'static str = "verify-note-disclosure";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                VerifyNoteDisclosure(args::VerifyNoteDisclosure::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Verify a disclosure of notes against the note commitment \
+                     tree anchors of the chain.",
+                )
+                .add_args::<args::VerifyNoteDisclosure<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct QueryCommissionRate(
         pub args::QueryCommissionRate<args::CliTypes>,
@@ -2040,6 +2330,60 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct TxRegisterPgfProject(
+        pub args::RegisterPgfProject<args::CliTypes>,
+    );
+
+    impl SubCmd for TxRegisterPgfProject {
+        const CMD: &'static str = "register-pgf-project";
+
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                TxRegisterPgfProject(args::RegisterPgfProject::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Register a project to receive funds from a pgf funding \
+                     round.",
+                )
+                .add_args::<args::RegisterPgfProject<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct TxVotePgfFundingRound(
+        pub args::VotePgfFundingRound<args::CliTypes>,
+    );
+
+    impl SubCmd for TxVotePgfFundingRound {
+        const CMD: &'static str = "vote-pgf-round";
+
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                TxVotePgfFundingRound(args::VotePgfFundingRound::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Cast quadratic votes for the projects of a pgf funding \
+                     round.",
+                )
+                .add_args::<args::VotePgfFundingRound<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct TxCommissionRateChange(
         pub args::CommissionRateChange<args::CliTypes>,
@@ -2091,6 +2435,35 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct TxDelegationPolicyChange(
+        pub args::DelegationPolicyChange<args::CliTypes>,
+    );
+
+    impl SubCmd for TxDelegationPolicyChange {
+        const CMD: &'static str = "change-delegation-policy";
+
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                TxDelegationPolicyChange(args::DelegationPolicyChange::parse(
+                    matches,
+                ))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Change who is allowed to delegate to a validator: \
+                     anyone, only an allowlist of addresses or no one.",
+                )
+                .add_args::<args::DelegationPolicyChange<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct TxChangeConsensusKey(
         pub args::ConsensusKeyChange<args::CliTypes>,
@@ -2137,6 +2510,35 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct TxChangeRepresentative(
+        pub args::ChangeRepresentative<args::CliTypes>,
+    );
+
+    impl SubCmd for TxChangeRepresentative {
+        const CMD: &'static str = "change-representative";
+
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                TxChangeRepresentative(args::ChangeRepresentative::parse(
+                    matches,
+                ))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Set or revoke the representative voting on governance \
+                     proposals on behalf of a delegator.",
+                )
+                .add_args::<args::ChangeRepresentative<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct TxRevealPk(pub args::RevealPk<args::CliTypes>);
 
@@ -2229,6 +2631,9 @@ pub mod cmds {
         TestGenesis(TestGenesis),
         SignGenesisTxs(SignGenesisTxs),
         ParseMigrationJson(MigrationJson),
+        ExportMaspBuilder(ExportMaspBuilder),
+        ImportMaspBuilder(ImportMaspBuilder),
+        AuthorizeMaspSpends(AuthorizeMaspSpends),
     }
 
     impl SubCmd for Utils {
@@ -2264,6 +2669,12 @@ pub mod cmds {
                     SubCmd::parse(matches).map(Self::TestGenesis);
                 let parse_migrations_json =
                     SubCmd::parse(matches).map(Self::ParseMigrationJson);
+                let export_masp_builder =
+                    SubCmd::parse(matches).map(Self::ExportMaspBuilder);
+                let import_masp_builder =
+                    SubCmd::parse(matches).map(Self::ImportMaspBuilder);
+                let authorize_masp_spends =
+                    SubCmd::parse(matches).map(Self::AuthorizeMaspSpends);
                 join_network
                     .or(fetch_wasms)
                     .or(validate_wasm)
@@ -2279,6 +2690,9 @@ pub mod cmds {
                     .or(test_genesis)
                     .or(genesis_tx)
                     .or(parse_migrations_json)
+                    .or(export_masp_builder)
+                    .or(import_masp_builder)
+                    .or(authorize_masp_spends)
             })
         }
 
@@ -2300,6 +2714,9 @@ pub mod cmds {
                 .subcommand(TestGenesis::def())
                 .subcommand(SignGenesisTxs::def())
                 .subcommand(MigrationJson::def())
+                .subcommand(ExportMaspBuilder::def())
+                .subcommand(ImportMaspBuilder::def())
+                .subcommand(AuthorizeMaspSpends::def())
                 .subcommand_required(true)
                 .arg_required_else_help(true)
         }
@@ -2548,6 +2965,73 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct ExportMaspBuilder(pub args::ExportMaspBuilder);
+
+    impl SubCmd for ExportMaspBuilder {
+        const CMD: &'static str = "export-masp-builder";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::ExportMaspBuilder::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Export the masp builders of a transaction dumped with \
+                     --dump-tx, for the spends and outputs to be reviewed \
+                     separately.",
+                )
+                .add_args::<args::ExportMaspBuilder>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct ImportMaspBuilder(pub args::ImportMaspBuilder);
+
+    impl SubCmd for ImportMaspBuilder {
+        const CMD: &'static str = "import-masp-builder";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::ImportMaspBuilder::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Attach an exported masp builder back to the dumped \
+                     transaction that carries its masp transaction.",
+                )
+                .add_args::<args::ImportMaspBuilder>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct AuthorizeMaspSpends(pub args::AuthorizeMaspSpends);
+
+    impl SubCmd for AuthorizeMaspSpends {
+        const CMD: &'static str = "authorize-masp-spends";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::AuthorizeMaspSpends::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Apply the separately made signatures of the shielded \
+                     spends to the dumped transaction that carries them.",
+                )
+                .add_args::<args::AuthorizeMaspSpends>()
+        }
+    }
+
     /// Used as sub-commands (`SubCmd` instance) in `namadar` binary.
     #[derive(Clone, Debug)]
     pub enum EthBridgePool {
@@ -3023,15 +3507,18 @@ pub mod args {
     use namada::ibc::core::host::types::identifiers::{ChannelId, PortId};
     use namada::tx::data::GasLimit;
     pub use namada_sdk::args::*;
+    use namada_sdk::masp::ShieldedHistoryFormat;
     pub use namada_sdk::tx::{
         TX_BECOME_VALIDATOR_WASM, TX_BOND_WASM, TX_BRIDGE_POOL_WASM,
         TX_CHANGE_COMMISSION_WASM, TX_CHANGE_CONSENSUS_KEY_WASM,
-        TX_CHANGE_METADATA_WASM, TX_CLAIM_REWARDS_WASM,
+        TX_CHANGE_DELEGATION_POLICY_WASM, TX_CHANGE_METADATA_WASM,
+        TX_CHANGE_REPRESENTATIVE_WASM, TX_CLAIM_REWARDS_WASM,
         TX_DEACTIVATE_VALIDATOR_WASM, TX_IBC_WASM, TX_INIT_ACCOUNT_WASM,
         TX_INIT_PROPOSAL, TX_REACTIVATE_VALIDATOR_WASM, TX_REDELEGATE_WASM,
-        TX_RESIGN_STEWARD, TX_REVEAL_PK, TX_TRANSFER_WASM, TX_UNBOND_WASM,
-        TX_UNJAIL_VALIDATOR_WASM, TX_UPDATE_ACCOUNT_WASM,
-        TX_UPDATE_STEWARD_COMMISSION, TX_VOTE_PROPOSAL, TX_WITHDRAW_WASM,
+        TX_REGISTER_PGF_PROJECT_WASM, TX_RESIGN_STEWARD, TX_REVEAL_PK,
+        TX_TRANSFER_WASM, TX_UNBOND_WASM, TX_UNJAIL_VALIDATOR_WASM,
+        TX_UPDATE_ACCOUNT_WASM, TX_UPDATE_STEWARD_COMMISSION,
+        TX_VOTE_PGF_FUNDING_ROUND_WASM, TX_VOTE_PROPOSAL, TX_WITHDRAW_WASM,
         VP_USER_WASM,
     };
 
@@ -3050,6 +3537,8 @@ pub mod args {
     pub const ALIAS_FORCE: ArgFlag = flag("alias-force");
     pub const ALIAS_MANY: ArgMulti<String, GlobPlus> = arg_multi("aliases");
     pub const ALLOW_DUPLICATE_IP: ArgFlag = flag("allow-duplicate-ip");
+    pub const ALLOWLIST: ArgMulti<WalletAddress, GlobStar> =
+        arg_multi("allowlist");
     pub const AMOUNT: Arg<token::DenominatedAmount> = arg("amount");
     pub const ARCHIVE_DIR: ArgOpt<PathBuf> = arg_opt("archive-dir");
     pub const AVATAR_OPT: ArgOpt<String> = arg_opt("avatar");
@@ -3063,6 +3552,7 @@ pub mod args {
     );
     pub const BATCH_SIZE_OPT: ArgDefault<u64> =
         arg_default("batch-size", DefaultFn(|| 1));
+    pub const BIRTHDAY_OPT: ArgOpt<BlockHeight> = arg_opt("birthday");
     pub const BLOCK_HEIGHT: Arg<BlockHeight> = arg("block-height");
     pub const BLOCK_HEIGHT_OPT: ArgOpt<BlockHeight> = arg_opt("height");
     pub const BLOCK_HEIGHT_FROM_OPT: ArgOpt<BlockHeight> =
@@ -3102,6 +3592,7 @@ pub mod args {
     );
     pub const CONVERSION_TABLE: Arg<PathBuf> = arg("conversion-table");
     pub const DAEMON_MODE: ArgFlag = flag("daemon");
+    pub const DELEGATIONS_CLOSED: ArgFlag = flag("closed");
     pub const DAEMON_MODE_RETRY_DUR: ArgOpt<Duration> = arg_opt("retry-sleep");
     pub const DAEMON_MODE_SUCCESS_DUR: ArgOpt<Duration> =
         arg_opt("success-sleep");
@@ -3113,6 +3604,8 @@ pub mod args {
         DefaultFn(|| storage::SUBSPACE_CF.to_string()),
     );
     pub const DECRYPT: ArgFlag = flag("decrypt");
+    pub const DELEGATOR: Arg<WalletAddress> = arg("delegator");
+    pub const DELEGATOR_OPT: ArgOpt<WalletAddress> = DELEGATOR.opt();
     pub const DESCRIPTION_OPT: ArgOpt<String> = arg_opt("description");
     pub const DISPOSABLE_SIGNING_KEY: ArgFlag = flag("disposable-gas-payer");
     pub const DESTINATION_VALIDATOR: Arg<WalletAddress> =
@@ -3146,6 +3639,7 @@ pub mod args {
     pub const FEE_PAYER_OPT: ArgOpt<WalletPublicKey> = arg_opt("gas-payer");
     pub const FILE_PATH: Arg<String> = arg("file");
     pub const FORCE: ArgFlag = flag("force");
+    pub const FROM_GENESIS: ArgFlag = flag("genesis");
     pub const GAS_LIMIT: ArgDefault<GasLimit> =
         arg_default("gas-limit", DefaultFn(|| GasLimit::from(25_000)));
     pub const FEE_TOKEN: ArgDefaultFromCtx<WalletAddrOrNativeToken> =
@@ -3176,8 +3670,13 @@ pub mod args {
         flag("allow-non-compliant");
     pub const HD_PROMPT_BIP39_PASSPHRASE: ArgFlag = flag("bip39-passphrase");
     pub const HISTORIC: ArgFlag = flag("historic");
+    pub const HISTORY: ArgFlag = flag("history");
+    pub const HISTORY_FORMAT: ArgDefault<ShieldedHistoryFormat> =
+        arg_default("format", DefaultFn(|| ShieldedHistoryFormat::Json));
     pub const IBC_TRANSFER_MEMO_PATH: ArgOpt<PathBuf> = arg_opt("memo-path");
-    pub const INPUT_OPT: ArgOpt<PathBuf> = arg_opt("input");
+    pub const INPUT: Arg<PathBuf> = arg("input");
+    pub const INPUT_OPT: ArgOpt<PathBuf> = INPUT.opt();
+    pub const INFRACTIONS_PATH: ArgOpt<PathBuf> = arg_opt("infractions");
     pub const LEDGER_ADDRESS_ABOUT: &str =
         "Address of a ledger node as \"{scheme}://{host}:{port}\". If the \
          scheme is not supplied, it is assumed to be TCP.";
@@ -3189,9 +3688,11 @@ pub mod args {
             let raw = "http://127.0.0.1:26657";
             Url::from_str(raw).unwrap()
         }));
+    pub const LIGHT_CLIENT_ATTACK: ArgFlag = flag("light-client-attack");
     pub const LIST_FIND_ADDRESSES_ONLY: ArgFlag = flag("addr");
     pub const LIST_FIND_KEYS_ONLY: ArgFlag = flag("keys");
     pub const LOCALHOST: ArgFlag = flag("localhost");
+    pub const MASP_BUILDER_PATH: Arg<PathBuf> = arg("masp-builder-path");
     pub const MAX_COMMISSION_RATE_CHANGE: Arg<Dec> =
         arg("max-commission-rate-change");
     pub const MAX_ETH_GAS: ArgOpt<u64> = arg_opt("max_eth-gas");
@@ -3217,12 +3718,17 @@ pub mod args {
     pub const PROPOSAL_ETH: ArgFlag = flag("eth");
     pub const PROPOSAL_PGF_STEWARD: ArgFlag = flag("pgf-stewards");
     pub const PROPOSAL_PGF_FUNDING: ArgFlag = flag("pgf-funding");
+    pub const PROPOSAL_PARAMETER_CHANGE: ArgFlag = flag("parameter-change");
+    pub const PROPOSAL_UPGRADE: ArgFlag = flag("upgrade");
     pub const PROPOSAL_OFFLINE: ArgFlag = flag("offline");
     pub const PROTOCOL_KEY: ArgOpt<WalletPublicKey> = arg_opt("protocol-key");
     pub const PRE_GENESIS_PATH: ArgOpt<PathBuf> = arg_opt("pre-genesis-path");
     pub const PUBLIC_KEY: Arg<WalletPublicKey> = arg("public-key");
     pub const PUBLIC_KEYS: ArgMulti<WalletPublicKey, GlobStar> =
         arg_multi("public-keys");
+    pub const PROJECT: Arg<WalletAddress> = arg("project");
+    pub const PROJECTS: ArgMulti<WalletAddress, GlobPlus> =
+        arg_multi("projects");
     pub const PROPOSAL_ID: Arg<u64> = arg("proposal-id");
     pub const PROPOSAL_ID_OPT: ArgOpt<u64> = arg_opt("proposal-id");
     pub const PROPOSAL_VOTE_PGF_OPT: ArgOpt<String> = arg_opt("pgf");
@@ -3241,8 +3747,15 @@ pub mod args {
     pub const RAW_PUBLIC_KEY_HASH: Arg<String> = arg("public-key-hash");
     pub const RAW_PUBLIC_KEY_HASH_OPT: ArgOpt<String> =
         RAW_PUBLIC_KEY_HASH.opt();
+    pub const RAW_VALIDATOR: Arg<Address> = arg("validator");
+    pub const RAW_VALIDATOR_OPT: ArgOpt<Address> = RAW_VALIDATOR.opt();
     pub const RECEIVER: Arg<String> = arg("receiver");
     pub const RELAYER: Arg<Address> = arg("relayer");
+    pub const REPRESENTATIVE_OPT: ArgOpt<WalletAddress> =
+        arg_opt("representative");
+    pub const REVOKE: ArgFlag = flag("revoke");
+    pub const ROUND_ID: Arg<u64> = arg("round-id");
+    pub const ROUNDS: ArgFlag = flag("rounds");
     pub const SAFE_MODE: ArgFlag = flag("safe-mode");
     pub const SCHEME: ArgDefault<SchemeType> =
         arg_default("scheme", DefaultFn(|| SchemeType::Ed25519));
@@ -3256,6 +3769,10 @@ pub mod args {
     pub const SIGNATURES: ArgMulti<PathBuf, GlobStar> = arg_multi("signatures");
     pub const SOURCE: Arg<WalletAddress> = arg("source");
     pub const SOURCE_OPT: ArgOpt<WalletAddress> = SOURCE.opt();
+    pub const SPEND_AUTHORIZATIONS: ArgMulti<PathBuf, GlobStar> =
+        arg_multi("spend-authorizations");
+    pub const SPEND_RANDOMIZERS: Arg<PathBuf> = arg("spend-randomizers");
+    pub const SPENDING_KEY: Arg<WalletSpendingKey> = arg("spending-key");
     pub const SPENDING_KEYS: ArgMulti<WalletSpendingKey, GlobStar> =
         arg_multi("spending-keys");
     pub const STEWARD: Arg<WalletAddress> = arg("steward");
@@ -3273,6 +3790,7 @@ pub mod args {
     pub const TRANSFER_TARGET: Arg<WalletTransferTarget> = arg("target");
     pub const TRANSPARENT: ArgFlag = flag("transparent");
     pub const TX_HASH: Arg<String> = arg("tx-hash");
+    pub const TX_INDEX: Arg<u32> = arg("tx-index");
     pub const THRESHOLD: ArgOpt<u8> = arg_opt("threshold");
     pub const UNSAFE_DONT_ENCRYPT: ArgFlag = flag("unsafe-dont-encrypt");
     pub const UNSAFE_SHOW_SECRET: ArgFlag = flag("unsafe-show-secret");
@@ -3292,7 +3810,10 @@ pub mod args {
     pub const VALIDATOR_ETH_HOT_KEY: ArgOpt<WalletPublicKey> =
         arg_opt("eth-hot-key");
     pub const VALUE: Arg<String> = arg("value");
-    pub const VOTER_OPT: ArgOpt<WalletAddress> = arg_opt("voter");
+    pub const VESTING_PATH: ArgOpt<PathBuf> = arg_opt("vesting-path");
+    pub const VOTER: Arg<WalletAddress> = arg("voter");
+    pub const VOTER_OPT: ArgOpt<WalletAddress> = VOTER.opt();
+    pub const VOTES: ArgMulti<u64, GlobPlus> = arg_multi("votes");
     pub const VIEWING_KEY: Arg<WalletViewingKey> = arg("key");
     pub const VIEWING_KEYS: ArgMulti<WalletViewingKey, GlobStar> =
         arg_multi("viewing-keys");
@@ -3446,19 +3967,97 @@ pub mod args {
         }
 
         fn def(app: App) -> App {
-            app.arg(BLOCK_HEIGHT_OPT.def().help(
-                "The block height to dump. Defaults to latest committed
-                block.",
-            ))
-            .arg(OUT_FILE_PATH_OPT.def().help(
-                "Path for the output file (omitting file extension). Defaults \
-                 to \"db_dump.{block_height}.toml\" in the current working \
-                 directory.",
-            ))
+            app.arg(BLOCK_HEIGHT_OPT.def().help(
+                "The block height to dump. Defaults to latest committed
+                block.",
+            ))
+            .arg(OUT_FILE_PATH_OPT.def().help(
+                "Path for the output file (omitting file extension). Defaults \
+                 to \"db_dump.{block_height}.toml\" in the current working \
+                 directory.",
+            ))
+            .arg(
+                HISTORIC
+                    .def()
+                    .help("If provided, dump also the diff of the last height"),
+            )
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct LedgerSimulateSlash {
+        pub validator: Option<Address>,
+        pub epoch: Option<Epoch>,
+        pub block_height: Option<BlockHeight>,
+        pub light_client_attack: bool,
+        pub infractions: Option<PathBuf>,
+        pub from_genesis: bool,
+    }
+
+    impl Args for LedgerSimulateSlash {
+        fn parse(matches: &ArgMatches) -> Self {
+            let validator = RAW_VALIDATOR_OPT.parse(matches);
+            let epoch = EPOCH.parse(matches);
+            let block_height = BLOCK_HEIGHT_OPT.parse(matches);
+            let light_client_attack = LIGHT_CLIENT_ATTACK.parse(matches);
+            let infractions = INFRACTIONS_PATH.parse(matches);
+            let from_genesis = FROM_GENESIS.parse(matches);
+            Self {
+                validator,
+                epoch,
+                block_height,
+                light_client_attack,
+                infractions,
+                from_genesis,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(
+                RAW_VALIDATOR_OPT
+                    .def()
+                    .help("The address of the misbehaving validator."),
+            )
             .arg(
-                HISTORIC
+                EPOCH
                     .def()
-                    .help("If provided, dump also the diff of the last height"),
+                    .help(
+                        "The epoch of the infraction. Defaults to the current \
+                         epoch.",
+                    )
+                    .requires(RAW_VALIDATOR_OPT.name),
+            )
+            .arg(
+                BLOCK_HEIGHT_OPT
+                    .def()
+                    .help("The block height of the infraction. Defaults to 0.")
+                    .requires(RAW_VALIDATOR_OPT.name),
+            )
+            .arg(
+                LIGHT_CLIENT_ATTACK
+                    .def()
+                    .help(
+                        "Simulate a light client attack instead of a \
+                         duplicate vote.",
+                    )
+                    .requires(RAW_VALIDATOR_OPT.name),
+            )
+            .arg(INFRACTIONS_PATH.def().help(
+                "The path to a JSON file with a list of infractions to \
+                 simulate together, each with a `validator` address and \
+                 optionally an `epoch`, a `block_height` and a \
+                 `light_client_attack` flag. Can be combined with the \
+                 infraction given by the `--validator` argument.",
+            ))
+            .arg(FROM_GENESIS.def().help(
+                "Simulate on top of the chain's genesis state instead of the \
+                 last committed state.",
+            ))
+            .group(
+                ArgGroup::new("simulated_infractions")
+                    .args([RAW_VALIDATOR_OPT.name, INFRACTIONS_PATH.name])
+                    .multiple(true)
+                    .required(true),
             )
         }
     }
@@ -4300,6 +4899,10 @@ pub mod args {
                     .map(|pk| chain_ctx.get(pk))
                     .collect(),
                 threshold: self.threshold,
+                vesting: self.vesting.map(|path| {
+                    std::fs::read(path)
+                        .expect("Should be able to read the file.")
+                }),
             }
         }
     }
@@ -4313,11 +4916,13 @@ pub mod args {
             let tx_code_path = PathBuf::from(TX_INIT_ACCOUNT_WASM);
             let public_keys = PUBLIC_KEYS.parse(matches);
             let threshold = THRESHOLD.parse(matches);
+            let vesting = VESTING_PATH.parse(matches);
             Self {
                 tx,
                 vp_code_path,
                 public_keys,
                 threshold,
+                vesting,
                 tx_code_path,
             }
         }
@@ -4338,6 +4943,12 @@ pub mod args {
                      authorization. Must be less then the maximum number of \
                      public keys provided.",
                 ))
+                .arg(VESTING_PATH.def().help(
+                    "The path to a JSON file with the vesting of the new \
+                     account's tokens. The vested tokens can be bonded and \
+                     used to vote, but only the unlocked amount can be \
+                     transferred.",
+                ))
         }
     }
 
@@ -4842,6 +5453,99 @@ pub mod args {
         }
     }
 
+    impl CliToSdk<RegisterPgfProject<SdkTypes>> for RegisterPgfProject<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> RegisterPgfProject<SdkTypes> {
+            RegisterPgfProject::<SdkTypes> {
+                tx: self.tx.to_sdk(ctx),
+                round_id: self.round_id,
+                project: ctx.borrow_chain_or_exit().get(&self.project),
+                tx_code_path: self.tx_code_path.to_path_buf(),
+            }
+        }
+    }
+
+    impl Args for RegisterPgfProject<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let round_id = ROUND_ID.parse(matches);
+            let project = PROJECT.parse(matches);
+            let tx_code_path = PathBuf::from(TX_REGISTER_PGF_PROJECT_WASM);
+            Self {
+                tx,
+                round_id,
+                project,
+                tx_code_path,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>()
+                .arg(ROUND_ID.def().help("The pgf funding round id."))
+                .arg(PROJECT.def().help(
+                    "The address of the project, receiving its share of the \
+                     round budget.",
+                ))
+        }
+    }
+
+    impl CliToSdk<VotePgfFundingRound<SdkTypes>> for VotePgfFundingRound<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> VotePgfFundingRound<SdkTypes> {
+            let tx = self.tx.to_sdk(ctx);
+            let chain_ctx = ctx.borrow_chain_or_exit();
+            VotePgfFundingRound::<SdkTypes> {
+                tx,
+                round_id: self.round_id,
+                voter: chain_ctx.get(&self.voter),
+                votes: self
+                    .votes
+                    .iter()
+                    .map(|(project, votes)| (chain_ctx.get(project), *votes))
+                    .collect(),
+                tx_code_path: self.tx_code_path.to_path_buf(),
+            }
+        }
+    }
+
+    impl Args for VotePgfFundingRound<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let round_id = ROUND_ID.parse(matches);
+            let voter = VOTER.parse(matches);
+            let projects = PROJECTS.parse(matches);
+            let votes = VOTES.parse(matches);
+            if projects.len() != votes.len() {
+                eprintln!(
+                    "The number of `--{}` and `--{}` arguments must be the \
+                     same.",
+                    PROJECTS.name, VOTES.name
+                );
+                safe_exit(1)
+            }
+            let tx_code_path = PathBuf::from(TX_VOTE_PGF_FUNDING_ROUND_WASM);
+            Self {
+                tx,
+                round_id,
+                voter,
+                votes: projects.into_iter().zip(votes).collect(),
+                tx_code_path,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>()
+                .arg(ROUND_ID.def().help("The pgf funding round id."))
+                .arg(VOTER.def().help("The address of the voter."))
+                .arg(PROJECTS.def().help(
+                    "The comma separated addresses of the projects voted for.",
+                ))
+                .arg(VOTES.def().help(
+                    "The comma separated votes given to each project, in the \
+                     same order. Giving `n` votes to a project costs `n²` \
+                     voice credits.",
+                ))
+        }
+    }
+
     impl CliToSdk<Redelegate<SdkTypes>> for Redelegate<CliTypes> {
         fn to_sdk(self, ctx: &mut Context) -> Redelegate<SdkTypes> {
             let tx = self.tx.to_sdk(ctx);
@@ -4909,6 +5613,8 @@ pub mod args {
                 is_offline: self.is_offline,
                 is_pgf_stewards: self.is_pgf_stewards,
                 is_pgf_funding: self.is_pgf_funding,
+                is_parameter_change: self.is_parameter_change,
+                is_upgrade: self.is_upgrade,
                 tx_code_path: self.tx_code_path,
             }
         }
@@ -4921,6 +5627,8 @@ pub mod args {
             let is_offline = PROPOSAL_OFFLINE.parse(matches);
             let is_pgf_stewards = PROPOSAL_PGF_STEWARD.parse(matches);
             let is_pgf_funding = PROPOSAL_PGF_FUNDING.parse(matches);
+            let is_parameter_change = PROPOSAL_PARAMETER_CHANGE.parse(matches);
+            let is_upgrade = PROPOSAL_UPGRADE.parse(matches);
             let tx_code_path = PathBuf::from(TX_INIT_PROPOSAL);
 
             Self {
@@ -4930,6 +5638,8 @@ pub mod args {
                 is_offline,
                 is_pgf_stewards,
                 is_pgf_funding,
+                is_parameter_change,
+                is_upgrade,
             }
         }
 
@@ -4949,6 +5659,8 @@ pub mod args {
                             PROPOSAL_PGF_FUNDING.name,
                             PROPOSAL_PGF_STEWARD.name,
                             PROPOSAL_ETH.name,
+                            PROPOSAL_PARAMETER_CHANGE.name,
+                            PROPOSAL_UPGRADE.name,
                         ]),
                 )
                 .arg(
@@ -4958,6 +5670,8 @@ pub mod args {
                         .conflicts_with_all([
                             PROPOSAL_PGF_FUNDING.name,
                             PROPOSAL_PGF_STEWARD.name,
+                            PROPOSAL_PARAMETER_CHANGE.name,
+                            PROPOSAL_UPGRADE.name,
                         ]),
                 )
                 .arg(
@@ -4970,6 +5684,8 @@ pub mod args {
                         .conflicts_with_all([
                             PROPOSAL_ETH.name,
                             PROPOSAL_PGF_FUNDING.name,
+                            PROPOSAL_PARAMETER_CHANGE.name,
+                            PROPOSAL_UPGRADE.name,
                         ]),
                 )
                 .arg(
@@ -4982,6 +5698,38 @@ pub mod args {
                         .conflicts_with_all([
                             PROPOSAL_ETH.name,
                             PROPOSAL_PGF_STEWARD.name,
+                            PROPOSAL_PARAMETER_CHANGE.name,
+                            PROPOSAL_UPGRADE.name,
+                        ]),
+                )
+                .arg(
+                    PROPOSAL_PARAMETER_CHANGE
+                        .def()
+                        .help(
+                            "Flag if the proposal is of type \
+                             parameter-change. Used to update protocol, PoS, \
+                             governance, PGF and Ethereum bridge parameters \
+                             without a custom wasm.",
+                        )
+                        .conflicts_with_all([
+                            PROPOSAL_ETH.name,
+                            PROPOSAL_PGF_STEWARD.name,
+                            PROPOSAL_PGF_FUNDING.name,
+                            PROPOSAL_UPGRADE.name,
+                        ]),
+                )
+                .arg(
+                    PROPOSAL_UPGRADE
+                        .def()
+                        .help(
+                            "Flag if the proposal is of type upgrade. Used to \
+                             schedule a chain upgrade at a given block height.",
+                        )
+                        .conflicts_with_all([
+                            PROPOSAL_ETH.name,
+                            PROPOSAL_PGF_STEWARD.name,
+                            PROPOSAL_PGF_FUNDING.name,
+                            PROPOSAL_PARAMETER_CHANGE.name,
                         ]),
                 )
         }
@@ -5037,7 +5785,12 @@ pub mod args {
                         ]),
                 )
                 .arg(PROPOSAL_VOTE.def().help(
-                    "The vote for the proposal. Either yay, nay, or abstain.",
+                    "The vote for the proposal. Either yay, nay, abstain, \
+                     veto (a nay vote also requesting the proposal funds to \
+                     be burned) or a weighted vote splitting the voting power \
+                     between yay, nay and abstain, e.g. \
+                     `yay=0.6,nay=0.3,abstain=0.1`. The weights must sum up \
+                     to 1.",
                 ))
                 .arg(
                     PROPOSAL_OFFLINE
@@ -5059,6 +5812,67 @@ pub mod args {
         }
     }
 
+    impl CliToSdk<ChangeRepresentative<SdkTypes>>
+        for ChangeRepresentative<CliTypes>
+    {
+        fn to_sdk(self, ctx: &mut Context) -> ChangeRepresentative<SdkTypes> {
+            let tx = self.tx.to_sdk(ctx);
+            let chain_ctx = ctx.borrow_chain_or_exit();
+            ChangeRepresentative::<SdkTypes> {
+                tx,
+                delegator: chain_ctx.get(&self.delegator),
+                representative: self
+                    .representative
+                    .map(|representative| chain_ctx.get(&representative)),
+                tx_code_path: self.tx_code_path.to_path_buf(),
+            }
+        }
+    }
+
+    impl Args for ChangeRepresentative<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let delegator = DELEGATOR.parse(matches);
+            let revoke = REVOKE.parse(matches);
+            let representative = if revoke {
+                None
+            } else {
+                REPRESENTATIVE_OPT.parse(matches)
+            };
+            let tx_code_path = PathBuf::from(TX_CHANGE_REPRESENTATIVE_WASM);
+
+            Self {
+                tx,
+                delegator,
+                representative,
+                tx_code_path,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>()
+                .arg(DELEGATOR.def().help(
+                    "The address of the delegator whose representative is \
+                     being changed.",
+                ))
+                .arg(REPRESENTATIVE_OPT.def().help(
+                    "The address of the representative voting on behalf of \
+                     the delegator.",
+                ))
+                .arg(
+                    REVOKE
+                        .def()
+                        .help("Revoke the current representative.")
+                        .conflicts_with(REPRESENTATIVE_OPT.name),
+                )
+                .group(
+                    ArgGroup::new("representative_change")
+                        .args([REPRESENTATIVE_OPT.name, REVOKE.name])
+                        .required(true),
+                )
+        }
+    }
+
     impl CliToSdk<RevealPk<SdkTypes>> for RevealPk<CliTypes> {
         fn to_sdk(self, ctx: &mut Context) -> RevealPk<SdkTypes> {
             let tx = self.tx.to_sdk(ctx);
@@ -5137,6 +5951,50 @@ pub mod args {
         }
     }
 
+    impl CliToSdk<QueryRepresentatives<SdkTypes>>
+        for QueryRepresentatives<CliTypes>
+    {
+        fn to_sdk(self, ctx: &mut Context) -> QueryRepresentatives<SdkTypes> {
+            let query = self.query.to_sdk(ctx);
+            let chain_ctx = ctx.borrow_chain_or_exit();
+            QueryRepresentatives::<SdkTypes> {
+                query,
+                delegator: self.delegator.map(|x| chain_ctx.get(&x)),
+                representative: self.representative.map(|x| chain_ctx.get(&x)),
+            }
+        }
+    }
+
+    impl Args for QueryRepresentatives<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let delegator = DELEGATOR_OPT.parse(matches);
+            let representative = REPRESENTATIVE_OPT.parse(matches);
+
+            Self {
+                query,
+                delegator,
+                representative,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>()
+                .arg(DELEGATOR_OPT.def().help(
+                    "The address of a delegator whose representative to query.",
+                ))
+                .arg(
+                    REPRESENTATIVE_OPT
+                        .def()
+                        .help(
+                            "The address of a representative whose \
+                             represented delegators to query.",
+                        )
+                        .conflicts_with(DELEGATOR_OPT.name),
+                )
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct QueryProposalResult<C: NamadaTypes = SdkTypes> {
         /// Common query args
@@ -5237,12 +6095,42 @@ pub mod args {
     impl Args for QueryPgf<CliTypes> {
         fn parse(matches: &ArgMatches) -> Self {
             let query = Query::parse(matches);
+            let history = HISTORY.parse(matches);
+            let epoch = EPOCH.parse(matches);
+            let rounds = ROUNDS.parse(matches);
 
-            Self { query }
+            Self {
+                query,
+                history,
+                epoch,
+                rounds,
+            }
         }
 
         fn def(app: App) -> App {
             app.add_args::<Query<CliTypes>>()
+                .arg(HISTORY.def().help(
+                    "Show the report of the pgf inflation received, the \
+                     payments made and the payments failed in each epoch.",
+                ))
+                .arg(
+                    EPOCH
+                        .def()
+                        .help(
+                            "Restrict the pgf history report to the given \
+                             epoch.",
+                        )
+                        .requires(HISTORY.name),
+                )
+                .arg(
+                    ROUNDS
+                        .def()
+                        .help(
+                            "Show the pgf funding rounds with the projects \
+                             registered and the votes they received.",
+                        )
+                        .conflicts_with(HISTORY.name),
+                )
         }
     }
 
@@ -5250,6 +6138,9 @@ pub mod args {
         fn to_sdk(self, ctx: &mut Context) -> QueryPgf<SdkTypes> {
             QueryPgf::<SdkTypes> {
                 query: self.query.to_sdk(ctx),
+                history: self.history,
+                epoch: self.epoch,
+                rounds: self.rounds,
             }
         }
     }
@@ -5396,84 +6287,218 @@ pub mod args {
         fn to_sdk(self, ctx: &mut Context) -> QueryBalance<SdkTypes> {
             let query = self.query.to_sdk(ctx);
             let chain_ctx = ctx.borrow_mut_chain_or_exit();
-            QueryBalance::<SdkTypes> {
+            QueryBalance::<SdkTypes> {
+                query,
+                owner: self.owner.map(|x| chain_ctx.get_cached(&x)),
+                token: self.token.map(|x| chain_ctx.get(&x)),
+                no_conversions: self.no_conversions,
+            }
+        }
+    }
+
+    impl Args for QueryBalance<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let owner = BALANCE_OWNER.parse(matches);
+            let token = TOKEN_OPT.parse(matches);
+            let no_conversions = NO_CONVERSIONS.parse(matches);
+            Self {
+                query,
+                owner,
+                token,
+                no_conversions,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>()
+                .arg(
+                    BALANCE_OWNER
+                        .def()
+                        .help("The account address whose balance to query."),
+                )
+                .arg(
+                    TOKEN_OPT
+                        .def()
+                        .help("The token's address whose balance to query."),
+                )
+                .arg(
+                    NO_CONVERSIONS.def().help(
+                        "Whether not to automatically perform conversions.",
+                    ),
+                )
+        }
+    }
+
+    impl CliToSdk<QueryTransfers<SdkTypes>> for QueryTransfers<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> QueryTransfers<SdkTypes> {
+            let query = self.query.to_sdk(ctx);
+            let chain_ctx = ctx.borrow_mut_chain_or_exit();
+            QueryTransfers::<SdkTypes> {
+                query,
+                owner: self.owner.map(|x| chain_ctx.get_cached(&x)),
+                token: self.token.map(|x| chain_ctx.get(&x)),
+            }
+        }
+    }
+
+    impl Args for QueryTransfers<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let owner = BALANCE_OWNER.parse(matches);
+            let token = TOKEN_OPT.parse(matches);
+            Self {
+                query,
+                owner,
+                token,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>()
+                .arg(BALANCE_OWNER.def().help(
+                    "The account address that queried transfers must involve.",
+                ))
+                .arg(TOKEN_OPT.def().help(
+                    "The token address that queried transfers must involve.",
+                ))
+        }
+    }
+
+    impl CliToSdk<QueryShieldedHistory<SdkTypes>>
+        for QueryShieldedHistory<CliTypes>
+    {
+        fn to_sdk(self, ctx: &mut Context) -> QueryShieldedHistory<SdkTypes> {
+            let query = self.query.to_sdk(ctx);
+            let chain_ctx = ctx.borrow_mut_chain_or_exit();
+            QueryShieldedHistory::<SdkTypes> {
                 query,
-                owner: self.owner.map(|x| chain_ctx.get_cached(&x)),
+                viewing_key: chain_ctx.get_cached(&self.viewing_key),
                 token: self.token.map(|x| chain_ctx.get(&x)),
-                no_conversions: self.no_conversions,
+                format: self.format,
+                output: self.output,
             }
         }
     }
 
-    impl Args for QueryBalance<CliTypes> {
+    impl Args for QueryShieldedHistory<CliTypes> {
         fn parse(matches: &ArgMatches) -> Self {
             let query = Query::parse(matches);
-            let owner = BALANCE_OWNER.parse(matches);
+            let viewing_key = VIEWING_KEY.parse(matches);
             let token = TOKEN_OPT.parse(matches);
-            let no_conversions = NO_CONVERSIONS.parse(matches);
+            let format = HISTORY_FORMAT.parse(matches);
+            let output = OUTPUT.parse(matches);
             Self {
                 query,
-                owner,
+                viewing_key,
                 token,
-                no_conversions,
+                format,
+                output,
             }
         }
 
         fn def(app: App) -> App {
             app.add_args::<Query<CliTypes>>()
                 .arg(
-                    BALANCE_OWNER
+                    VIEWING_KEY
                         .def()
-                        .help("The account address whose balance to query."),
+                        .help("The viewing key whose history to export."),
                 )
                 .arg(
                     TOKEN_OPT
                         .def()
-                        .help("The token's address whose balance to query."),
-                )
-                .arg(
-                    NO_CONVERSIONS.def().help(
-                        "Whether not to automatically perform conversions.",
-                    ),
+                        .help("Only export the changes in the given token."),
                 )
+                .arg(HISTORY_FORMAT.def().help(
+                    "The format of the export, either json or csv. Defaults \
+                     to json.",
+                ))
+                .arg(OUTPUT.def().help(
+                    "The file to write the export to. Defaults to the \
+                     standard output.",
+                ))
         }
     }
 
-    impl CliToSdk<QueryTransfers<SdkTypes>> for QueryTransfers<CliTypes> {
-        fn to_sdk(self, ctx: &mut Context) -> QueryTransfers<SdkTypes> {
+    impl CliToSdk<DiscloseNotes<SdkTypes>> for DiscloseNotes<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> DiscloseNotes<SdkTypes> {
             let query = self.query.to_sdk(ctx);
             let chain_ctx = ctx.borrow_mut_chain_or_exit();
-            QueryTransfers::<SdkTypes> {
+            DiscloseNotes::<SdkTypes> {
                 query,
-                owner: self.owner.map(|x| chain_ctx.get_cached(&x)),
-                token: self.token.map(|x| chain_ctx.get(&x)),
+                viewing_key: chain_ctx.get_cached(&self.viewing_key),
+                height: self.height,
+                tx_index: self.tx_index,
+                output: self.output,
             }
         }
     }
 
-    impl Args for QueryTransfers<CliTypes> {
+    impl Args for DiscloseNotes<CliTypes> {
         fn parse(matches: &ArgMatches) -> Self {
             let query = Query::parse(matches);
-            let owner = BALANCE_OWNER.parse(matches);
-            let token = TOKEN_OPT.parse(matches);
+            let viewing_key = VIEWING_KEY.parse(matches);
+            let height = BLOCK_HEIGHT.parse(matches);
+            let tx_index = storage::TxIndex(TX_INDEX.parse(matches));
+            let output = OUTPUT.parse(matches);
             Self {
                 query,
-                owner,
-                token,
+                viewing_key,
+                height,
+                tx_index,
+                output,
             }
         }
 
         fn def(app: App) -> App {
             app.add_args::<Query<CliTypes>>()
-                .arg(BALANCE_OWNER.def().help(
-                    "The account address that queried transfers must involve.",
+                .arg(
+                    VIEWING_KEY.def().help(
+                        "The viewing key that received or sent the notes.",
+                    ),
+                )
+                .arg(BLOCK_HEIGHT.def().help(
+                    "The height of the block that included the transaction.",
                 ))
-                .arg(TOKEN_OPT.def().help(
-                    "The token address that queried transfers must involve.",
+                .arg(
+                    TX_INDEX
+                        .def()
+                        .help("The index of the transaction in its block."),
+                )
+                .arg(OUTPUT.def().help(
+                    "The file to write the disclosure to. Defaults to the \
+                     standard output.",
                 ))
         }
     }
 
+    impl CliToSdk<VerifyNoteDisclosure<SdkTypes>>
+        for VerifyNoteDisclosure<CliTypes>
+    {
+        fn to_sdk(self, ctx: &mut Context) -> VerifyNoteDisclosure<SdkTypes> {
+            VerifyNoteDisclosure::<SdkTypes> {
+                query: self.query.to_sdk(ctx),
+                input: self.input,
+            }
+        }
+    }
+
+    impl Args for VerifyNoteDisclosure<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let input = INPUT.parse(matches);
+            Self { query, input }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>().arg(
+                INPUT
+                    .def()
+                    .help("The file containing the disclosure to verify."),
+            )
+        }
+    }
+
     impl CliToSdk<QueryBonds<SdkTypes>> for QueryBonds<CliTypes> {
         fn to_sdk(self, ctx: &mut Context) -> QueryBonds<SdkTypes> {
             let query = self.query.to_sdk(ctx);
@@ -5585,6 +6610,33 @@ pub mod args {
         }
     }
 
+    impl CliToSdk<QueryValidatorLiveness<SdkTypes>>
+        for QueryValidatorLiveness<CliTypes>
+    {
+        fn to_sdk(self, ctx: &mut Context) -> QueryValidatorLiveness<SdkTypes> {
+            QueryValidatorLiveness::<SdkTypes> {
+                query: self.query.to_sdk(ctx),
+                validator: ctx.borrow_chain_or_exit().get(&self.validator),
+            }
+        }
+    }
+
+    impl Args for QueryValidatorLiveness<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let validator = VALIDATOR.parse(matches);
+            Self { query, validator }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>().arg(
+                VALIDATOR
+                    .def()
+                    .help("The validator's address whose liveness is queried."),
+            )
+        }
+    }
+
     impl CliToSdk<CommissionRateChange<SdkTypes>>
         for CommissionRateChange<CliTypes>
     {
@@ -5746,6 +6798,62 @@ pub mod args {
         }
     }
 
+    impl CliToSdk<DelegationPolicyChange<SdkTypes>>
+        for DelegationPolicyChange<CliTypes>
+    {
+        fn to_sdk(self, ctx: &mut Context) -> DelegationPolicyChange<SdkTypes> {
+            let tx = self.tx.to_sdk(ctx);
+            let chain_ctx = ctx.borrow_chain_or_exit();
+            DelegationPolicyChange::<SdkTypes> {
+                tx,
+                validator: chain_ctx.get(&self.validator),
+                closed: self.closed,
+                allowlist: self
+                    .allowlist
+                    .iter()
+                    .map(|addr| chain_ctx.get(addr))
+                    .collect(),
+                tx_code_path: self.tx_code_path.to_path_buf(),
+            }
+        }
+    }
+
+    impl Args for DelegationPolicyChange<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let validator = VALIDATOR.parse(matches);
+            let closed = DELEGATIONS_CLOSED.parse(matches);
+            let allowlist = ALLOWLIST.parse(matches);
+            let tx_code_path = PathBuf::from(TX_CHANGE_DELEGATION_POLICY_WASM);
+            Self {
+                tx,
+                validator,
+                closed,
+                allowlist,
+                tx_code_path,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>()
+                .arg(VALIDATOR.def().help(
+                    "The validator's address whose delegation policy to \
+                     change.",
+                ))
+                .arg(
+                    DELEGATIONS_CLOSED
+                        .def()
+                        .help("Reject all new delegations to the validator.")
+                        .conflicts_with(ALLOWLIST.name),
+                )
+                .arg(ALLOWLIST.def().help(
+                    "Only accept new delegations from these addresses. The \
+                     validator is open to delegations from anyone if neither \
+                     this nor the `--closed` flag is given.",
+                ))
+        }
+    }
+
     impl CliToSdk<TxUnjailValidator<SdkTypes>> for TxUnjailValidator<CliTypes> {
         fn to_sdk(self, ctx: &mut Context) -> TxUnjailValidator<SdkTypes> {
             TxUnjailValidator::<SdkTypes> {
@@ -5876,6 +6984,53 @@ pub mod args {
         }
     }
 
+    impl CliToSdk<SignMaspSpends<SdkTypes>> for SignMaspSpends<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> SignMaspSpends<SdkTypes> {
+            let chain_ctx = ctx.borrow_mut_chain_or_exit();
+            SignMaspSpends::<SdkTypes> {
+                tx_path: self.tx_path,
+                spend_randomizers_path: self.spend_randomizers_path,
+                spending_key: chain_ctx.get_cached(&self.spending_key),
+                output_folder: self.output_folder,
+            }
+        }
+    }
+
+    impl Args for SignMaspSpends<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx_path = TX_PATH.parse(matches);
+            let spend_randomizers_path = SPEND_RANDOMIZERS.parse(matches);
+            let spending_key = SPENDING_KEY.parse(matches);
+            let output_folder = OUTPUT_FOLDER_PATH.parse(matches);
+            Self {
+                tx_path,
+                spend_randomizers_path,
+                spending_key,
+                output_folder,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(
+                TX_PATH
+                    .def()
+                    .help("The path to the transaction dumped with --dump-tx."),
+            )
+            .arg(SPEND_RANDOMIZERS.def().help(
+                "The path to the spend randomizers dumped along with the \
+                 transaction.",
+            ))
+            .arg(SPENDING_KEY.def().help(
+                "The spending key whose proof generation key was used to \
+                 build the transaction.",
+            ))
+            .arg(OUTPUT_FOLDER_PATH.def().help(
+                "The output folder path where the signatures should be \
+                 written. Defaults to the current directory.",
+            ))
+        }
+    }
+
     impl Args for ShieldedSync<CliTypes> {
         fn parse(matches: &ArgMatches) -> Self {
             let ledger_address = LEDGER_ADDRESS.parse(matches);
@@ -5884,6 +7039,7 @@ pub mod args {
             let last_query_height = BLOCK_HEIGHT_TO_OPT.parse(matches);
             let spending_keys = SPENDING_KEYS.parse(matches);
             let viewing_keys = VIEWING_KEYS.parse(matches);
+            let birthday = BIRTHDAY_OPT.parse(matches);
             Self {
                 ledger_address,
                 batch_size,
@@ -5891,6 +7047,7 @@ pub mod args {
                 last_query_height,
                 spending_keys,
                 viewing_keys,
+                birthday,
             }
         }
 
@@ -5916,6 +7073,12 @@ pub mod args {
                     "List of new viewing keys with which to check note \
                      ownership. These will be added to the shielded context.",
                 ))
+                .arg(BIRTHDAY_OPT.def().help(
+                    "Optional block height before which the given spending \
+                     and viewing keys cannot have received any notes. \
+                     Overrides the birthdays stored in the wallet. Earlier \
+                     blocks are not scanned for these keys.",
+                ))
         }
     }
 
@@ -5937,6 +7100,7 @@ pub mod args {
                     .iter()
                     .map(|vk| chain_ctx.get_cached(vk))
                     .collect(),
+                birthday: self.birthday,
             }
         }
     }
@@ -6034,6 +7198,59 @@ pub mod args {
         }
     }
 
+    impl CliToSdk<QueryShieldedRewards<SdkTypes>>
+        for QueryShieldedRewards<CliTypes>
+    {
+        fn to_sdk(self, ctx: &mut Context) -> QueryShieldedRewards<SdkTypes> {
+            let query = self.query.to_sdk(ctx);
+            let chain_ctx = ctx.borrow_mut_chain_or_exit();
+            QueryShieldedRewards::<SdkTypes> {
+                query,
+                viewing_key: chain_ctx.get_cached(&self.viewing_key),
+            }
+        }
+    }
+
+    impl Args for QueryShieldedRewards<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let viewing_key = VIEWING_KEY.parse(matches);
+            Self { query, viewing_key }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>().arg(
+                VIEWING_KEY
+                    .def()
+                    .help("The viewing key whose rewards to project."),
+            )
+        }
+    }
+
+    impl CliToSdk<QueryMaspTokenLimits<SdkTypes>>
+        for QueryMaspTokenLimits<CliTypes>
+    {
+        fn to_sdk(self, ctx: &mut Context) -> QueryMaspTokenLimits<SdkTypes> {
+            QueryMaspTokenLimits::<SdkTypes> {
+                query: self.query.to_sdk(ctx),
+                token: ctx.borrow_chain_or_exit().get(&self.token),
+            }
+        }
+    }
+
+    impl Args for QueryMaspTokenLimits<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let token = TOKEN.parse(matches);
+            Self { query, token }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>()
+                .arg(TOKEN.def().help("The token whose MASP limits to query."))
+        }
+    }
+
     impl CliToSdk<QueryMetaData<SdkTypes>> for QueryMetaData<CliTypes> {
         fn to_sdk(self, ctx: &mut Context) -> QueryMetaData<SdkTypes> {
             QueryMetaData::<SdkTypes> {
@@ -6316,8 +7533,9 @@ pub mod args {
             ))
             .arg(FEE_TOKEN.def().help("The token for paying the gas"))
             .arg(FEE_UNSHIELD_SPENDING_KEY.def().help(
-                "The spending key to be used for fee unshielding. If none is \
-                 provided, fee will be paid from the unshielded balance only.",
+                "The spending key whose shielded balance pays the fee. If \
+                 none is provided, fee will be paid from the unshielded \
+                 balance only.",
             ))
             .arg(GAS_LIMIT.def().help(
                 "The multiplier of the gas limit resolution defining the \
@@ -6571,6 +7789,7 @@ pub mod args {
                 HD_ALLOW_NON_COMPLIANT_DERIVATION_PATH.parse(matches);
             let prompt_bip39_passphrase =
                 HD_PROMPT_BIP39_PASSPHRASE.parse(matches);
+            let birthday = BIRTHDAY_OPT.parse(matches);
             Self {
                 scheme,
                 shielded,
@@ -6581,6 +7800,7 @@ pub mod args {
                 derivation_path,
                 allow_non_compliant,
                 prompt_bip39_passphrase,
+                birthday,
             }
         }
 
@@ -6638,6 +7858,11 @@ pub mod args {
                     "Use an additional passphrase for HD-key generation.",
                 ),
             )
+            .arg(BIRTHDAY_OPT.def().requires(SHIELDED.name).help(
+                "Optional block height before which the spending key cannot \
+                 have received any notes. Shielded sync skips earlier blocks \
+                 for this key.",
+            ))
         }
     }
 
@@ -6654,6 +7879,7 @@ pub mod args {
                 HD_ALLOW_NON_COMPLIANT_DERIVATION_PATH.parse(matches);
             let prompt_bip39_passphrase =
                 HD_PROMPT_BIP39_PASSPHRASE.parse(matches);
+            let birthday = BIRTHDAY_OPT.parse(matches);
             Self {
                 scheme,
                 shielded,
@@ -6664,6 +7890,7 @@ pub mod args {
                 derivation_path,
                 allow_non_compliant,
                 prompt_bip39_passphrase,
+                birthday,
             }
         }
 
@@ -6724,6 +7951,11 @@ pub mod args {
                     "Use an additional passphrase for HD-key generation.",
                 ),
             )
+            .arg(BIRTHDAY_OPT.def().requires(SHIELDED.name).help(
+                "Optional block height before which the spending key cannot \
+                 have received any notes. Shielded sync skips earlier blocks \
+                 for this key.",
+            ))
         }
     }
 
@@ -6857,11 +8089,13 @@ pub mod args {
             let alias_force = ALIAS_FORCE.parse(matches);
             let value = VALUE.parse(matches);
             let unsafe_dont_encrypt = UNSAFE_DONT_ENCRYPT.parse(matches);
+            let birthday = BIRTHDAY_OPT.parse(matches);
             Self {
                 alias,
                 alias_force,
                 value,
                 unsafe_dont_encrypt,
+                birthday,
             }
         }
 
@@ -6884,6 +8118,11 @@ pub mod args {
                 "UNSAFE: Do not encrypt the added keys. Do not use this for \
                  keys used in a live network.",
             ))
+            .arg(BIRTHDAY_OPT.def().help(
+                "Optional block height before which the added spending or \
+                 viewing key cannot have received any notes. Shielded sync \
+                 skips earlier blocks for this key.",
+            ))
         }
     }
 
@@ -7449,6 +8688,107 @@ pub mod args {
             ))
         }
     }
+
+    #[derive(Clone, Debug)]
+    pub struct ExportMaspBuilder {
+        pub tx_path: PathBuf,
+        pub output_folder: Option<PathBuf>,
+    }
+
+    impl Args for ExportMaspBuilder {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx_path = TX_PATH.parse(matches);
+            let output_folder = OUTPUT_FOLDER_PATH.parse(matches);
+            Self {
+                tx_path,
+                output_folder,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(
+                TX_PATH
+                    .def()
+                    .help("The path to the transaction dumped with --dump-tx."),
+            )
+            .arg(OUTPUT_FOLDER_PATH.def().help(
+                "The output folder path where the masp builders should be \
+                 written. Defaults to the current directory.",
+            ))
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct ImportMaspBuilder {
+        pub tx_path: PathBuf,
+        pub masp_builder_path: PathBuf,
+        pub output_folder: Option<PathBuf>,
+    }
+
+    impl Args for ImportMaspBuilder {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx_path = TX_PATH.parse(matches);
+            let masp_builder_path = MASP_BUILDER_PATH.parse(matches);
+            let output_folder = OUTPUT_FOLDER_PATH.parse(matches);
+            Self {
+                tx_path,
+                masp_builder_path,
+                output_folder,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(
+                TX_PATH
+                    .def()
+                    .help("The path to the transaction dumped with --dump-tx."),
+            )
+            .arg(
+                MASP_BUILDER_PATH
+                    .def()
+                    .help("The path to the exported masp builder."),
+            )
+            .arg(OUTPUT_FOLDER_PATH.def().help(
+                "The output folder path where the transaction should be \
+                 written. Defaults to the current directory.",
+            ))
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct AuthorizeMaspSpends {
+        pub tx_path: PathBuf,
+        pub spend_authorizations: Vec<PathBuf>,
+        pub output_folder: Option<PathBuf>,
+    }
+
+    impl Args for AuthorizeMaspSpends {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx_path = TX_PATH.parse(matches);
+            let spend_authorizations = SPEND_AUTHORIZATIONS.parse(matches);
+            let output_folder = OUTPUT_FOLDER_PATH.parse(matches);
+            Self {
+                tx_path,
+                spend_authorizations,
+                output_folder,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(
+                TX_PATH
+                    .def()
+                    .help("The path to the transaction dumped with --dump-tx."),
+            )
+            .arg(SPEND_AUTHORIZATIONS.def().help(
+                "The paths to the spend signatures made with sign-masp-spends.",
+            ))
+            .arg(OUTPUT_FOLDER_PATH.def().help(
+                "The output folder path where the transaction should be \
+                 written. Defaults to the current directory.",
+            ))
+        }
+    }
 }
 
 pub fn namada_cli() -> (cmds::Namada, String) {
@@ -1,7 +1,8 @@
+use std::collections::BTreeMap;
 use std::io::Read;
 
 use color_eyre::eyre::Result;
-use masp_primitives::zip32::ExtendedFullViewingKey;
+use masp_primitives::zip32::{ExtendedFullViewingKey, ExtendedSpendingKey};
 use namada::io::Io;
 use namada_sdk::{display_line, Namada, NamadaImpl};
 
@@ -189,6 +190,20 @@ impl CliApi {
                         let namada = ctx.to_sdk(client, io);
                         tx::submit_vote_proposal(&namada, args).await?;
                     }
+                    Sub::TxChangeRepresentative(TxChangeRepresentative(
+                        args,
+                    )) => {
+                        let chain_ctx = ctx.borrow_mut_chain_or_exit();
+                        let ledger_address =
+                            chain_ctx.get(&args.tx.ledger_address);
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(&ledger_address)
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada = ctx.to_sdk(client, io);
+                        tx::submit_change_representative(&namada, args).await?;
+                    }
                     Sub::TxRevealPk(TxRevealPk(args)) => {
                         let chain_ctx = ctx.borrow_mut_chain_or_exit();
                         let ledger_address =
@@ -318,6 +333,23 @@ impl CliApi {
                         tx::submit_validator_metadata_change(&namada, args)
                             .await?;
                     }
+                    Sub::TxDelegationPolicyChange(
+                        TxDelegationPolicyChange(args),
+                    ) => {
+                        let chain_ctx = ctx.borrow_mut_chain_or_exit();
+                        let ledger_address =
+                            chain_ctx.get(&args.tx.ledger_address);
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(&ledger_address)
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada = ctx.to_sdk(client, io);
+                        tx::submit_validator_delegation_policy_change(
+                            &namada, args,
+                        )
+                        .await?;
+                    }
                     Sub::ShieldedSync(ShieldedSync(args)) => {
                         let client = client.unwrap_or_else(|| {
                             C::from_tendermint_address(&args.ledger_address)
@@ -325,21 +357,42 @@ impl CliApi {
                         client.wait_until_node_is_synced(&io).await?;
                         let args = args.to_sdk(&mut ctx);
                         let chain_ctx = ctx.take_chain_or_exit();
-                        let vks = chain_ctx
-                            .wallet
-                            .get_viewing_keys()
-                            .values()
-                            .copied()
+                        let wallet_vks = chain_ctx.wallet.get_viewing_keys();
+                        let mut birthdays = BTreeMap::new();
+                        for (alias, vk) in &wallet_vks {
+                            if let Some(birthday) =
+                                chain_ctx.wallet.find_birthday(alias)
+                            {
+                                let vk =
+                                    ExtendedFullViewingKey::from(*vk).fvk.vk;
+                                birthdays.insert(vk, birthday);
+                            }
+                        }
+                        let arg_vks = args
+                            .viewing_keys
+                            .into_iter()
+                            .map(|vk| ExtendedFullViewingKey::from(vk).fvk.vk)
+                            .collect::<Vec<_>>();
+                        let vks = wallet_vks
+                            .into_values()
                             .map(|vk| ExtendedFullViewingKey::from(vk).fvk.vk)
-                            .chain(args.viewing_keys.into_iter().map(|vk| {
-                                ExtendedFullViewingKey::from(vk).fvk.vk
-                            }))
+                            .chain(arg_vks.iter().copied())
                             .collect::<Vec<_>>();
                         let sks = args
                             .spending_keys
                             .into_iter()
-                            .map(|sk| sk.into())
+                            .map(ExtendedSpendingKey::from)
                             .collect::<Vec<_>>();
+                        // The birthday given on the command line applies to
+                        // the keys given on the command line
+                        if let Some(birthday) = args.birthday {
+                            let sk_vks = sks.iter().map(|sk| {
+                                ExtendedFullViewingKey::from(sk).fvk.vk
+                            });
+                            for vk in arg_vks.iter().copied().chain(sk_vks) {
+                                birthdays.insert(vk, birthday);
+                            }
+                        }
                         crate::client::masp::syncing(
                             chain_ctx.shielded,
                             &client,
@@ -349,6 +402,7 @@ impl CliApi {
                             args.last_query_height,
                             &sks,
                             &vks,
+                            &birthdays,
                         )
                         .await?;
                     }
@@ -429,6 +483,31 @@ impl CliApi {
                         let namada = ctx.to_sdk(client, io);
                         tx::submit_resign_steward(&namada, args).await?;
                     }
+                    Sub::TxRegisterPgfProject(TxRegisterPgfProject(args)) => {
+                        let chain_ctx = ctx.borrow_mut_chain_or_exit();
+                        let ledger_address =
+                            chain_ctx.get(&args.tx.ledger_address);
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(&ledger_address)
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada = ctx.to_sdk(client, io);
+                        tx::submit_register_pgf_project(&namada, args).await?;
+                    }
+                    Sub::TxVotePgfFundingRound(TxVotePgfFundingRound(args)) => {
+                        let chain_ctx = ctx.borrow_mut_chain_or_exit();
+                        let ledger_address =
+                            chain_ctx.get(&args.tx.ledger_address);
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(&ledger_address)
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada = ctx.to_sdk(client, io);
+                        tx::submit_vote_pgf_funding_round(&namada, args)
+                            .await?;
+                    }
                     // Ledger queries
                     Sub::QueryEpoch(QueryEpoch(args)) => {
                         let chain_ctx = ctx.borrow_mut_chain_or_exit();
@@ -475,6 +554,21 @@ impl CliApi {
                         rpc::query_and_print_validator_state(&namada, args)
                             .await;
                     }
+                    Sub::QueryValidatorLiveness(QueryValidatorLiveness(
+                        args,
+                    )) => {
+                        let chain_ctx = ctx.borrow_mut_chain_or_exit();
+                        let ledger_address =
+                            chain_ctx.get(&args.query.ledger_address);
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(&ledger_address)
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada = ctx.to_sdk(client, io);
+                        rpc::query_and_print_validator_liveness(&namada, args)
+                            .await;
+                    }
                     Sub::QueryTransfers(QueryTransfers(args)) => {
                         let chain_ctx = ctx.borrow_mut_chain_or_exit();
                         let ledger_address =
@@ -487,6 +581,42 @@ impl CliApi {
                         let namada = ctx.to_sdk(client, io);
                         rpc::query_transfers(&namada, args).await;
                     }
+                    Sub::QueryShieldedHistory(QueryShieldedHistory(args)) => {
+                        let chain_ctx = ctx.borrow_mut_chain_or_exit();
+                        let ledger_address =
+                            chain_ctx.get(&args.query.ledger_address);
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(&ledger_address)
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada = ctx.to_sdk(client, io);
+                        rpc::query_shielded_history(&namada, args).await;
+                    }
+                    Sub::DiscloseNotes(DiscloseNotes(args)) => {
+                        let chain_ctx = ctx.borrow_mut_chain_or_exit();
+                        let ledger_address =
+                            chain_ctx.get(&args.query.ledger_address);
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(&ledger_address)
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada = ctx.to_sdk(client, io);
+                        rpc::disclose_notes(&namada, args).await;
+                    }
+                    Sub::VerifyNoteDisclosure(VerifyNoteDisclosure(args)) => {
+                        let chain_ctx = ctx.borrow_mut_chain_or_exit();
+                        let ledger_address =
+                            chain_ctx.get(&args.query.ledger_address);
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(&ledger_address)
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada = ctx.to_sdk(client, io);
+                        rpc::verify_note_disclosure(&namada, args).await;
+                    }
                     Sub::QueryConversions(QueryConversions(args)) => {
                         let chain_ctx = ctx.borrow_mut_chain_or_exit();
                         let ledger_address =
@@ -510,6 +640,30 @@ impl CliApi {
                         let namada = ctx.to_sdk(client, io);
                         rpc::query_masp_reward_tokens(&namada).await;
                     }
+                    Sub::QueryMaspTokenLimits(QueryMaspTokenLimits(args)) => {
+                        let chain_ctx = ctx.borrow_mut_chain_or_exit();
+                        let ledger_address =
+                            chain_ctx.get(&args.query.ledger_address);
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(&ledger_address)
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada = ctx.to_sdk(client, io);
+                        rpc::query_masp_token_limits(&namada, args).await;
+                    }
+                    Sub::QueryShieldedRewards(QueryShieldedRewards(args)) => {
+                        let chain_ctx = ctx.borrow_mut_chain_or_exit();
+                        let ledger_address =
+                            chain_ctx.get(&args.query.ledger_address);
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(&ledger_address)
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada = ctx.to_sdk(client, io);
+                        rpc::query_shielded_rewards(&namada, args).await;
+                    }
                     Sub::QueryBlock(QueryBlock(args)) => {
                         let chain_ctx = ctx.borrow_mut_chain_or_exit();
                         let ledger_address =
@@ -692,6 +846,18 @@ impl CliApi {
                         let namada = ctx.to_sdk(client, io);
                         rpc::query_proposal_votes(&namada, args).await;
                     }
+                    Sub::QueryRepresentatives(QueryRepresentatives(args)) => {
+                        let chain_ctx = ctx.borrow_mut_chain_or_exit();
+                        let ledger_address =
+                            chain_ctx.get(&args.query.ledger_address);
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(&ledger_address)
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada = ctx.to_sdk(client, io);
+                        rpc::query_representatives(&namada, args).await;
+                    }
                     Sub::QueryProtocolParameters(QueryProtocolParameters(
                         args,
                     )) => {
@@ -742,6 +908,10 @@ impl CliApi {
                         let namada = ctx.to_sdk(client, io);
                         tx::sign_tx(&namada, args).await?;
                     }
+                    Sub::SignMaspSpends(SignMaspSpends(args)) => {
+                        let args = args.to_sdk(&mut ctx);
+                        utils::sign_masp_spends(args);
+                    }
                     Sub::GenIbcShieldedTransfer(GenIbcShieldedTransfer(
                         args,
                     )) => {
@@ -810,6 +980,15 @@ impl CliApi {
                 Utils::SignGenesisTxs(SignGenesisTxs(args)) => {
                     utils::sign_genesis_tx(global_args, args).await
                 }
+                Utils::ExportMaspBuilder(ExportMaspBuilder(args)) => {
+                    utils::export_masp_builder(args)
+                }
+                Utils::ImportMaspBuilder(ImportMaspBuilder(args)) => {
+                    utils::import_masp_builder(args)
+                }
+                Utils::AuthorizeMaspSpends(AuthorizeMaspSpends(args)) => {
+                    utils::authorize_masp_spends(args)
+                }
                 Utils::ParseMigrationJson(MigrationJson(args)) => {
                     #[cfg(feature = "migrations")]
                     {
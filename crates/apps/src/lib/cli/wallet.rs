@@ -14,7 +14,10 @@ use ledger_transport_hid::TransportNativeHID;
 use masp_primitives::zip32::ExtendedFullViewingKey;
 use namada::core::address::{Address, DecodeError};
 use namada::core::key::*;
-use namada::core::masp::{ExtendedSpendingKey, MaspValue, PaymentAddress};
+use namada::core::masp::{
+    ExtendedProofGenerationKey, ExtendedSpendingKey, MaspValue, PaymentAddress,
+};
+use namada::core::storage::BlockHeight;
 use namada::io::Io;
 use namada_sdk::masp::find_valid_diversifier;
 use namada_sdk::wallet::{
@@ -194,6 +197,7 @@ fn shielded_key_derive(
         allow_non_compliant,
         prompt_bip39_passphrase,
         use_device,
+        birthday,
         ..
     }: args::KeyDerive,
 ) {
@@ -233,6 +237,9 @@ fn shielded_key_derive(
         display_line!(io, "No changes are persisted. Exiting.");
         cli::safe_exit(1)
     };
+    if let Some(birthday) = birthday {
+        wallet.insert_birthday(alias.clone(), birthday);
+    }
     wallet
         .save()
         .unwrap_or_else(|err| edisplay_line!(io, "{}", err));
@@ -255,6 +262,7 @@ fn shielded_key_gen(
         derivation_path,
         allow_non_compliant,
         prompt_bip39_passphrase,
+        birthday,
         ..
     }: args::KeyGen,
 ) {
@@ -296,6 +304,9 @@ fn shielded_key_gen(
         println!("No changes are persisted. Exiting.");
         cli::safe_exit(1);
     });
+    if let Some(birthday) = birthday {
+        wallet.insert_birthday(alias.clone(), birthday);
+    }
 
     wallet
         .save()
@@ -350,6 +361,7 @@ fn shielded_key_address_add(
     alias_force: bool,
     masp_value: MaspValue,
     unsafe_dont_encrypt: bool,
+    birthday: Option<BlockHeight>,
 ) {
     let alias = alias.to_lowercase();
     let mut wallet = load_wallet(ctx);
@@ -390,6 +402,9 @@ fn shielded_key_address_add(
             (alias, "payment address")
         }
     };
+    if let Some(birthday) = birthday {
+        wallet.insert_birthday(alias.clone(), birthday);
+    }
     wallet.save().unwrap_or_else(|err| eprintln!("{}", err));
     display_line!(
         io,
@@ -799,6 +814,7 @@ fn add_key_or_address(
     alias_force: bool,
     value: KeyAddrAddValue,
     unsafe_dont_encrypt: bool,
+    birthday: Option<BlockHeight>,
 ) {
     match value {
         KeyAddrAddValue::TranspValue(TransparentValue::TranspSecretKey(sk)) => {
@@ -824,6 +840,7 @@ fn add_key_or_address(
             alias_force,
             masp_value,
             unsafe_dont_encrypt,
+            birthday,
         ),
     }
 }
@@ -837,7 +854,7 @@ fn key_address_add(
         alias_force,
         value,
         unsafe_dont_encrypt,
-        ..
+        birthday,
     }: args::KeyAddressAdd,
 ) {
     let value = KeyAddrAddValue::from_str(&value).unwrap_or_else(|err| {
@@ -845,7 +862,15 @@ fn key_address_add(
         display_line!(io, "No changes are persisted. Exiting.");
         cli::safe_exit(1)
     });
-    add_key_or_address(ctx, io, alias, alias_force, value, unsafe_dont_encrypt)
+    add_key_or_address(
+        ctx,
+        io,
+        alias,
+        alias_force,
+        value,
+        unsafe_dont_encrypt,
+        birthday,
+    )
 }
 
 /// Remove keys and addresses
@@ -1107,6 +1132,14 @@ fn shielded_key_address_find_by_alias(
                 // if requested.
                 match wallet.find_spending_key(&alias, None) {
                     Ok(spending_key) => {
+                        // The proof generation key lets other parties build
+                        // the spends that this spending key authorizes
+                        display_line!(io,
+                            &mut w_lock;
+                            "    Proof generation key: {}",
+                            ExtendedProofGenerationKey::from(spending_key),
+                        )
+                        .unwrap();
                         if unsafe_show_secret {
                             display_line!(io, &mut w_lock; "    Spending key: {}", spending_key).unwrap();
                         }
@@ -1304,6 +1337,7 @@ fn key_import(
             alias_force,
             masp_value,
             unsafe_dont_encrypt,
+            None,
         );
     } else {
         display_line!(io, "Could not parse the data.");
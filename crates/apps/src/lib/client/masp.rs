@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::fmt::Debug;
 
 use color_eyre::owo_colors::OwoColorize;
@@ -27,6 +28,7 @@ pub async fn syncing<
     last_query_height: Option<BlockHeight>,
     sks: &[ExtendedSpendingKey],
     fvks: &[ViewingKey],
+    birthdays: &BTreeMap<ViewingKey, BlockHeight>,
 ) -> Result<ShieldedContext<U>, Error> {
     let shutdown_signal = async {
         let (tx, rx) = tokio::sync::oneshot::channel();
@@ -47,6 +49,7 @@ pub async fn syncing<
                 batch_size,
                 sks,
                 fvks,
+                birthdays,
             )
             .await
             .map(|_| shielded)
@@ -7,8 +7,9 @@ use std::io;
 use std::str::FromStr;
 
 use borsh::BorshDeserialize;
+use borsh_ext::BorshSerializeExt;
 use data_encoding::HEXLOWER;
-use itertools::Either;
+use itertools::{Either, Itertools};
 use masp_primitives::asset_type::AssetType;
 use masp_primitives::merkle_tree::MerklePath;
 use masp_primitives::sapling::{Node, ViewingKey};
@@ -32,7 +33,7 @@ use namada::governance::pgf::parameters::PgfParameters;
 use namada::governance::pgf::storage::steward::StewardDetail;
 use namada::governance::storage::keys as governance_storage;
 use namada::governance::storage::proposal::{
-    StoragePgfFunding, StorageProposal,
+    StoragePgfFunding, StoragePgfFundingSchedule, StorageProposal,
 };
 use namada::governance::utils::{
     compute_proposal_result, ProposalVotes, TallyType, TallyVote, VotePower,
@@ -46,12 +47,17 @@ use namada::ledger::parameters::{storage as param_storage, EpochDuration};
 use namada::ledger::pos::types::{CommissionPair, Slash};
 use namada::ledger::pos::PosParams;
 use namada::ledger::queries::RPC;
-use namada::proof_of_stake::types::{ValidatorState, WeightedValidator};
+use namada::proof_of_stake::types::{
+    ValidatorLiveness, ValidatorState, WeightedValidator,
+};
 use namada::{state as storage, token};
 use namada_sdk::error::{
     is_pinned_error, Error, PinnedBalanceError, QueryError,
 };
-use namada_sdk::masp::{Conversions, MaspChange, MaspTokenRewardData};
+use namada_sdk::masp::{
+    export_shielded_history, Conversions, DefaultLogger, MaspChange,
+    MaspTokenRewardData, NoteDisclosure, TransferDirection,
+};
 use namada_sdk::proof_of_stake::types::ValidatorMetaData;
 use namada_sdk::queries::Client;
 use namada_sdk::rpc::{
@@ -317,6 +323,290 @@ pub async fn query_transfers(
     }
 }
 
+/// Export the history of the shielded balance of a viewing key
+pub async fn query_shielded_history(
+    context: &impl Namada,
+    args: args::QueryShieldedHistory,
+) {
+    let vk = ExtendedFullViewingKey::from(args.viewing_key).fvk.vk;
+    let mut shielded = context.shielded_mut().await;
+    let _ = shielded.load().await;
+    // Precompute asset types to increase chances of success in decoding
+    let token_map = query_tokens(context, None, None).await;
+    let tokens = token_map.values().collect();
+    let _ = shielded
+        .precompute_asset_types(context.client(), tokens)
+        .await;
+    let history = shielded
+        .query_shielded_history(
+            context.client(),
+            context.io(),
+            &vk,
+            args.token.as_ref(),
+        )
+        .await
+        .unwrap_or_else(|err| {
+            edisplay_line!(
+                context.io(),
+                "Failed to query the shielded history: {err}"
+            );
+            cli::safe_exit(1)
+        });
+    let export = export_shielded_history(&history, args.format);
+    match args.output {
+        Some(output_path) => {
+            fs::write(&output_path, export).unwrap_or_else(|err| {
+                edisplay_line!(
+                    context.io(),
+                    "Failed to write output to {} with {err}.",
+                    output_path.to_string_lossy()
+                );
+                cli::safe_exit(1)
+            });
+            display_line!(
+                context.io(),
+                "Exported {} shielded history entries to {}",
+                history.len(),
+                output_path.to_string_lossy()
+            );
+        }
+        None => display!(context.io(), "{}", export),
+    }
+}
+
+/// Query the claimable MASP rewards of a viewing key and project them for
+/// the next epoch
+pub async fn query_shielded_rewards(
+    context: &impl Namada,
+    args: args::QueryShieldedRewards,
+) {
+    let vk = ExtendedFullViewingKey::from(args.viewing_key).fvk.vk;
+    let mut shielded = context.shielded_mut().await;
+    let _ = shielded.load().await;
+    // Precompute asset types to increase chances of success in decoding
+    let token_map = query_tokens(context, None, None).await;
+    let tokens = token_map.values().collect();
+    let _ = shielded
+        .precompute_asset_types(context.client(), tokens)
+        .await;
+    let projection = shielded
+        .project_shielded_rewards(context.client(), context.io(), &vk)
+        .await
+        .unwrap_or_else(|err| {
+            edisplay_line!(
+                context.io(),
+                "Failed to project the shielded rewards: {err}"
+            );
+            cli::safe_exit(1)
+        });
+    let native_token = &projection.native_token;
+    display_line!(context.io(), "Epoch: {}", projection.epoch);
+    display_line!(
+        context.io(),
+        "Claimable rewards: {}",
+        context
+            .format_amount(native_token, projection.claimable_rewards())
+            .await
+    );
+    display_line!(
+        context.io(),
+        "Projected reward for the next epoch: {}",
+        context
+            .format_amount(native_token, projection.projected_reward())
+            .await
+    );
+    for (token, exchanged_balance) in &projection.exchanged_balances {
+        let balance =
+            projection.balances.get(token).copied().unwrap_or_default();
+        display_line!(context.io(), "Shielded token {}:", token);
+        display_line!(
+            context.io(),
+            "  Balance: {}",
+            context.format_amount(token, balance).await
+        );
+        display_line!(
+            context.io(),
+            "  Balance with conversions applied: {}",
+            context.format_amount(token, *exchanged_balance).await
+        );
+        let Some(rewards) = projection.rewards.get(token) else {
+            display_line!(context.io(), "  Does not earn MASP rewards");
+            continue;
+        };
+        display_line!(
+            context.io(),
+            "  Projected reward for the next epoch: {}",
+            context
+                .format_amount(native_token, rewards.projected_reward)
+                .await
+        );
+        display_line!(context.io(), "  Rewards of the shielded pool:");
+        let next_epoch = projection.epoch.next();
+        for (epoch, masp_rewards, projected) in rewards
+            .history
+            .iter()
+            .map(|(epoch, masp_rewards)| (*epoch, masp_rewards, false))
+            .chain([(next_epoch, &rewards.next_epoch, true)])
+        {
+            display_line!(
+                context.io(),
+                "    Epoch {}{}: {} for {} locked",
+                epoch,
+                if projected { " (projected)" } else { "" },
+                context
+                    .format_amount(native_token, masp_rewards.inflation)
+                    .await,
+                context
+                    .format_amount(token, masp_rewards.locked_amount)
+                    .await
+            );
+        }
+    }
+}
+
+/// Disclose the notes received or sent by a viewing key in a transaction
+pub async fn disclose_notes(context: &impl Namada, args: args::DiscloseNotes) {
+    let fvk = ExtendedFullViewingKey::from(args.viewing_key).fvk;
+    let indexed_tx = IndexedTx {
+        height: args.height,
+        index: args.tx_index,
+    };
+    let mut shielded = context.shielded_mut().await;
+    let _ = shielded.load().await;
+    let logger = DefaultLogger::new(context.io());
+    let masp_tx = shielded
+        .fetch_shielded_transfer(context.client(), &logger, indexed_tx)
+        .await
+        .unwrap_or_else(|err| {
+            edisplay_line!(
+                context.io(),
+                "Failed to fetch the transaction: {err}"
+            );
+            cli::safe_exit(1)
+        })
+        .unwrap_or_else(|| {
+            edisplay_line!(
+                context.io(),
+                "No shielded transaction was found at index {} of block {}.",
+                indexed_tx.index,
+                indexed_tx.height
+            );
+            cli::safe_exit(1)
+        });
+    let disclosures = shielded
+        .disclose_notes(&fvk, &indexed_tx, &masp_tx)
+        .unwrap_or_else(|err| {
+            edisplay_line!(context.io(), "Failed to disclose the notes: {err}");
+            cli::safe_exit(1)
+        });
+    if disclosures.is_empty() {
+        edisplay_line!(
+            context.io(),
+            "No notes received or sent by the viewing key were found in \
+             transaction {} of block {}. Make sure that the shielded context \
+             is synced.",
+            indexed_tx.index,
+            indexed_tx.height
+        );
+        cli::safe_exit(1)
+    }
+    let encoded = HEXLOWER.encode(&disclosures.serialize_to_vec());
+    match args.output {
+        Some(output_path) => {
+            fs::write(&output_path, encoded).unwrap_or_else(|err| {
+                edisplay_line!(
+                    context.io(),
+                    "Failed to write output to {} with {err}.",
+                    output_path.to_string_lossy()
+                );
+                cli::safe_exit(1)
+            });
+            display_line!(
+                context.io(),
+                "Disclosed {} notes in {}",
+                disclosures.len(),
+                output_path.to_string_lossy()
+            );
+        }
+        None => display_line!(context.io(), "{}", encoded),
+    }
+}
+
+/// Verify a disclosure of notes and display the disclosed notes
+pub async fn verify_note_disclosure(
+    context: &impl Namada,
+    args: args::VerifyNoteDisclosure,
+) {
+    let disclosures: Vec<NoteDisclosure> = fs::read_to_string(&args.input)
+        .ok()
+        .and_then(|encoded| HEXLOWER.decode(encoded.trim().as_bytes()).ok())
+        .and_then(|bytes| BorshDeserialize::try_from_slice(&bytes).ok())
+        .unwrap_or_else(|| {
+            edisplay_line!(
+                context.io(),
+                "Failed to read a disclosure from {}",
+                args.input.to_string_lossy()
+            );
+            cli::safe_exit(1)
+        });
+    let mut shielded = context.shielded_mut().await;
+    let _ = shielded.load().await;
+    let mut all_valid = true;
+    for disclosure in disclosures {
+        display_line!(
+            context.io(),
+            "Note {} paid to {}:",
+            disclosure.merkle_path.position,
+            PaymentAddress::from(disclosure.payment_address)
+        );
+        let direction = match disclosure.direction {
+            TransferDirection::Incoming => "received",
+            TransferDirection::Outgoing => "sent",
+        };
+        display_line!(context.io(), "  Disclosed as: {direction}");
+        let asset_data = shielded
+            .decode_asset_type(context.client(), disclosure.note.asset_type)
+            .await;
+        match asset_data {
+            Some(asset_data) => {
+                let amount = token::Amount::from_masp_denominated(
+                    disclosure.note.value,
+                    asset_data.position,
+                );
+                let token_alias =
+                    lookup_token_alias(context, &asset_data.token, &MASP).await;
+                display_line!(
+                    context.io(),
+                    "  Amount: {} {}",
+                    context.format_amount(&asset_data.token, amount).await,
+                    token_alias
+                );
+            }
+            None => display_line!(
+                context.io(),
+                "  Amount: {} of asset type {}",
+                disclosure.note.value,
+                disclosure.note.asset_type
+            ),
+        }
+        let verified = namada_sdk::masp::verify_note_disclosure(
+            context.client(),
+            &disclosure,
+        )
+        .await;
+        match verified {
+            Ok(()) => display_line!(context.io(), "  Valid"),
+            Err(err) => {
+                all_valid = false;
+                display_line!(context.io(), "  Invalid: {err}");
+            }
+        }
+    }
+    if !all_valid {
+        cli::safe_exit(1)
+    }
+}
+
 /// Query the raw bytes of given storage key
 pub async fn query_raw_bytes<N: Namada>(
     context: &N,
@@ -852,6 +1142,89 @@ pub async fn query_proposal_votes(
             for vote in result {
                 display_line!(context.io(), "{}\n", vote);
             }
+            let representative_votes =
+                namada_sdk::rpc::query_proposal_representative_votes(
+                    context.client(),
+                    args.proposal_id,
+                )
+                .await
+                .unwrap();
+            if !representative_votes.is_empty() {
+                display_line!(context.io(), "Representative votes\n");
+                for (representative, vote) in representative_votes {
+                    display_line!(
+                        context.io(),
+                        "Representative: {}\nVote: {}\n",
+                        representative,
+                        vote
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Query the governance representatives of delegators
+pub async fn query_representatives(
+    context: &impl Namada,
+    args: args::QueryRepresentatives,
+) {
+    if let Some(delegator) = args.delegator {
+        let representative =
+            namada_sdk::rpc::query_representative(context.client(), &delegator)
+                .await
+                .unwrap();
+        match representative {
+            Some(representative) => display_line!(
+                context.io(),
+                "The representative of {} is {}",
+                delegator,
+                representative
+            ),
+            None => display_line!(
+                context.io(),
+                "The address {} has no representative",
+                delegator
+            ),
+        }
+    } else if let Some(representative) = args.representative {
+        let delegators = namada_sdk::rpc::query_represented_delegators(
+            context.client(),
+            &representative,
+        )
+        .await
+        .unwrap();
+        if delegators.is_empty() {
+            display_line!(
+                context.io(),
+                "The address {} does not represent any delegator",
+                representative
+            );
+        } else {
+            display_line!(
+                context.io(),
+                "Delegators represented by {}:",
+                representative
+            );
+            for delegator in delegators {
+                display_line!(context.io(), "  {}", delegator);
+            }
+        }
+    } else {
+        let representatives =
+            namada_sdk::rpc::query_representatives(context.client())
+                .await
+                .unwrap();
+        if representatives.is_empty() {
+            display_line!(context.io(), "No representatives found");
+        }
+        for (delegator, representative) in representatives {
+            display_line!(
+                context.io(),
+                "Delegator: {}, representative: {}",
+                delegator,
+                representative
+            );
         }
     }
 }
@@ -1397,9 +1770,17 @@ pub async fn query_account(context: &impl Namada, args: args::QueryAccount) {
     }
 }
 
-pub async fn query_pgf(context: &impl Namada, _args: args::QueryPgf) {
+pub async fn query_pgf(context: &impl Namada, args: args::QueryPgf) {
+    if args.history {
+        return query_pgf_history(context, args.epoch).await;
+    }
+    if args.rounds {
+        return query_pgf_funding_rounds(context).await;
+    }
+
     let stewards = query_pgf_stewards(context.client()).await;
     let fundings = query_pgf_fundings(context.client()).await;
+    let schedules = query_pgf_fundings_schedules(context.client()).await;
 
     match stewards.is_empty() {
         true => {
@@ -1436,18 +1817,173 @@ pub async fn query_pgf(context: &impl Namada, _args: args::QueryPgf) {
         false => {
             display_line!(context.io(), "Pgf fundings:");
             for funding in fundings {
+                let target = funding.detail.target();
                 display_line!(
                     context.io(),
                     "{:4}- {} for {}",
                     "",
-                    funding.detail.target(),
+                    target,
                     funding.detail.amount().to_string_native()
                 );
+                let Some(schedule) = schedules.get(&target) else {
+                    continue;
+                };
+                if let Some(start_epoch) = schedule.schedule.start_epoch {
+                    display_line!(
+                        context.io(),
+                        "{:4}  Start epoch: {}",
+                        "",
+                        start_epoch
+                    );
+                }
+                if let Some(end_epoch) = schedule.schedule.end_epoch {
+                    display_line!(
+                        context.io(),
+                        "{:4}  End epoch: {}",
+                        "",
+                        end_epoch
+                    );
+                }
+                display_line!(
+                    context.io(),
+                    "{:4}  Paid: {}",
+                    "",
+                    schedule.paid.to_string_native()
+                );
+                if let Some(remaining) = schedule.remaining_budget() {
+                    display_line!(
+                        context.io(),
+                        "{:4}  Remaining budget: {}",
+                        "",
+                        remaining.to_string_native()
+                    );
+                }
             }
         }
     }
 }
 
+async fn query_pgf_history(context: &impl Namada, epoch: Option<Epoch>) {
+    let reports = unwrap_client_response::<_, _>(
+        RPC.vp().pgf().history(context.client(), &epoch).await,
+    );
+
+    if reports.is_empty() {
+        display_line!(context.io(), "Pgf history: no records found.");
+        return;
+    }
+
+    display_line!(context.io(), "Pgf history:");
+    for report in reports {
+        display_line!(context.io(), "{:4}Epoch {}:", "", report.epoch);
+        display_line!(
+            context.io(),
+            "{:6}Inflation received: {}",
+            "",
+            report.inflation.to_string_native()
+        );
+        display_line!(
+            context.io(),
+            "{:6}Total paid: {}",
+            "",
+            report.total_paid().to_string_native()
+        );
+        for payment in report.payments {
+            display_line!(
+                context.io(),
+                "{:6}- Paid {} to {} for {}",
+                "",
+                payment.amount.to_string_native(),
+                payment.target,
+                payment.kind
+            );
+        }
+        for payment in report.failed_payments {
+            display_line!(
+                context.io(),
+                "{:6}- Failed to pay {} to {} for {}",
+                "",
+                payment.amount.to_string_native(),
+                payment.target,
+                payment.kind
+            );
+        }
+    }
+}
+
+async fn query_pgf_funding_rounds(context: &impl Namada) {
+    let rounds = unwrap_client_response::<_, _>(
+        RPC.vp().pgf().funding_rounds(context.client()).await,
+    );
+
+    if rounds.is_empty() {
+        display_line!(context.io(), "Pgf funding rounds: no rounds found.");
+        return;
+    }
+
+    display_line!(context.io(), "Pgf funding rounds:");
+    for round in rounds {
+        let projects = unwrap_client_response::<_, _>(
+            RPC.vp()
+                .pgf()
+                .funding_round_projects(context.client(), &round.id)
+                .await,
+        );
+        let tally = unwrap_client_response::<_, _>(
+            RPC.vp()
+                .pgf()
+                .funding_round_tally(context.client(), &round.id)
+                .await,
+        );
+
+        display_line!(
+            context.io(),
+            "{:4}Round {} (proposal {}){}:",
+            "",
+            round.id,
+            round.proposal_id,
+            if round.finalized { ", finalized" } else { "" }
+        );
+        display_line!(
+            context.io(),
+            "{:6}Budget: {}",
+            "",
+            round.round.budget.to_string_native()
+        );
+        display_line!(
+            context.io(),
+            "{:6}Voting epochs: {} to {}",
+            "",
+            round.round.start_epoch,
+            round.round.end_epoch
+        );
+        display_line!(
+            context.io(),
+            "{:6}Voice credits: {}",
+            "",
+            round.round.voice_credits
+        );
+        display_line!(
+            context.io(),
+            "{:6}Min. bonded stake: {}",
+            "",
+            round.round.min_bonded_stake.to_string_native()
+        );
+        if projects.is_empty() {
+            display_line!(context.io(), "{:6}No projects registered.", "");
+        }
+        for project in projects {
+            display_line!(
+                context.io(),
+                "{:6}- {} with {} votes",
+                "",
+                project,
+                tally.get(&project).copied().unwrap_or_default()
+            );
+        }
+    }
+}
+
 pub async fn query_protocol_parameters(
     context: &impl Namada,
     _args: args::QueryProtocolParameters,
@@ -1491,6 +2027,38 @@ pub async fn query_protocol_parameters(
         "",
         governance_parameters.min_proposal_grace_epochs
     );
+    display_line!(
+        context.io(),
+        "{:4}Proposal veto threshold: {}",
+        "",
+        governance_parameters.proposal_veto_threshold
+    );
+    display_line!(
+        context.io(),
+        "{:4}Expedited min. proposal fund: {}",
+        "",
+        governance_parameters
+            .expedited_min_proposal_fund
+            .to_string_native()
+    );
+    display_line!(
+        context.io(),
+        "{:4}Expedited proposal voting period: {}",
+        "",
+        governance_parameters.expedited_proposal_voting_period
+    );
+    display_line!(
+        context.io(),
+        "{:4}Expedited min. proposal grace epochs: {}",
+        "",
+        governance_parameters.expedited_min_proposal_grace_epochs
+    );
+    display_line!(
+        context.io(),
+        "{:4}Expedited proposal quorum: {}",
+        "",
+        governance_parameters.expedited_proposal_quorum
+    );
 
     let pgf_parameters = query_pgf_parameters(context.client()).await;
     display_line!(context.io(), "\nPublic Goods Funding Parameters");
@@ -1626,6 +2194,21 @@ pub async fn query_protocol_parameters(
         "",
         pos_params.validator_stake_threshold
     );
+    display_line!(
+        context.io(),
+        "{:4}Min self-bond ratio: {}",
+        "",
+        pos_params.min_self_bond_ratio
+    );
+    display_line!(
+        context.io(),
+        "{:4}Max delegated stake: {}",
+        "",
+        pos_params
+            .max_delegated_stake
+            .map(|amount| amount.to_string_native())
+            .unwrap_or_else(|| "none".to_string())
+    );
     display_line!(
         context.io(),
         "{:4}Duplicate vote minimum slash rate: {}",
@@ -1638,6 +2221,12 @@ pub async fn query_protocol_parameters(
         "",
         pos_params.light_client_attack_min_slash_rate
     );
+    display_line!(
+        context.io(),
+        "{:4}Ethereum bridge equivocation minimum slash rate: {}",
+        "",
+        pos_params.eth_bridge_equivocation_min_slash_rate
+    );
     display_line!(
         context.io(),
         "{:4}Liveness window: {} blocks",
@@ -1748,6 +2337,16 @@ pub async fn query_pgf_fundings<C: namada::ledger::queries::Client + Sync>(
     unwrap_client_response::<C, _>(RPC.vp().pgf().funding(client).await)
 }
 
+pub async fn query_pgf_fundings_schedules<
+    C: namada::ledger::queries::Client + Sync,
+>(
+    client: &C,
+) -> BTreeMap<String, StoragePgfFundingSchedule> {
+    unwrap_client_response::<C, _>(
+        RPC.vp().pgf().funding_schedules(client).await,
+    )
+}
+
 pub async fn query_pgf_parameters<C: namada::ledger::queries::Client + Sync>(
     client: &C,
 ) -> PgfParameters {
@@ -2140,6 +2739,53 @@ pub async fn query_and_print_validator_state(
     }
 }
 
+/// Query a validator's liveness record
+pub async fn query_and_print_validator_liveness(
+    context: &impl Namada,
+    args: args::QueryValidatorLiveness,
+) {
+    let validator = args.validator;
+    let liveness: Option<ValidatorLiveness> =
+        unwrap_client_response::<_, Option<ValidatorLiveness>>(
+            RPC.vp()
+                .pos()
+                .validator_liveness(context.client(), &validator)
+                .await,
+        );
+
+    match liveness {
+        Some(liveness) => {
+            display_line!(
+                context.io(),
+                "Validator {validator} missed {} votes out of the last {} \
+                 blocks (jailed at {} missed votes)",
+                liveness.sum_missed_votes,
+                liveness.liveness_window_check,
+                liveness.missed_votes_threshold,
+            );
+            if !liveness.missed_votes.is_empty() {
+                display_line!(
+                    context.io(),
+                    "Missed votes at heights: {}",
+                    liveness.missed_votes.iter().join(", ")
+                );
+            }
+            if liveness.jailing_imminent {
+                display_line!(
+                    context.io(),
+                    "Warning: validator {validator} is close to being jailed \
+                     for liveness"
+                );
+            }
+        }
+        None => display_line!(
+            context.io(),
+            "No liveness data found for {validator}. Liveness is only tracked \
+             for validators in the consensus set."
+        ),
+    }
+}
+
 /// Query PoS validator's commission rate information
 pub async fn query_and_print_commission_rate(
     context: &impl Namada,
@@ -2677,6 +3323,72 @@ pub async fn query_masp_reward_tokens(context: &impl Namada) {
     }
 }
 
+/// Query the MASP limits of a token and its remaining capacity
+pub async fn query_masp_token_limits(
+    context: &impl Namada,
+    args: args::QueryMaspTokenLimits,
+) {
+    let token = args.token;
+    let limits =
+        namada_sdk::rpc::query_masp_token_limits(context.client(), &token)
+            .await;
+    let (limits, capacity) = match limits {
+        Ok(Some(limits)) => limits,
+        Ok(None) => {
+            display_line!(
+                context.io(),
+                "No MASP limits are set for token {}",
+                token
+            );
+            return;
+        }
+        Err(err) => {
+            edisplay_line!(
+                context.io(),
+                "Failed to query the MASP limits of token {}: {}",
+                token,
+                err
+            );
+            cli::safe_exit(1)
+        }
+    };
+    let balance = get_token_balance(context.client(), &token, &MASP).await;
+    display_line!(context.io(), "MASP limits of token {}:", token);
+    display_line!(
+        context.io(),
+        "  Shielded balance: {}",
+        context.format_amount(&token, balance).await
+    );
+    for (label, limit, remaining) in [
+        ("Shielded cap", limits.shielded_cap, capacity.shielded),
+        (
+            "Deposit limit per epoch",
+            limits.deposit_limit,
+            capacity.deposit,
+        ),
+        (
+            "Withdraw limit per epoch",
+            limits.withdraw_limit,
+            capacity.withdraw,
+        ),
+    ] {
+        match limit.zip(remaining) {
+            Some((limit, remaining)) => {
+                let limit = context.format_amount(&token, limit).await;
+                let remaining = context.format_amount(&token, remaining).await;
+                display_line!(
+                    context.io(),
+                    "  {}: {} ({} remaining)",
+                    label,
+                    limit,
+                    remaining
+                );
+            }
+            None => display_line!(context.io(), "  {}: none", label),
+        }
+    }
+}
+
 /// Query a wasm code hash
 pub async fn query_wasm_code_hash(
     context: &impl Namada,
@@ -2955,6 +3667,7 @@ pub async fn compute_offline_proposal_votes(
         validator_voting_power,
         delegators_vote,
         delegator_voting_power,
+        ..ProposalVotes::default()
     }
 }
 
@@ -3009,10 +3722,20 @@ pub async fn compute_proposal_votes<
         }
     }
 
-    ProposalVotes {
+    let mut proposal_votes = ProposalVotes {
         validators_vote,
         validator_voting_power,
         delegators_vote,
         delegator_voting_power,
-    }
+        ..ProposalVotes::default()
+    };
+    namada_sdk::rpc::add_represented_votes(
+        client,
+        proposal_id,
+        epoch,
+        &mut proposal_votes,
+    )
+    .await
+    .unwrap();
+    proposal_votes
 }
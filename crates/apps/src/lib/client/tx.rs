@@ -14,7 +14,8 @@ use namada::governance::cli::offline::{
     OfflineProposal, OfflineSignedProposal, OfflineVote,
 };
 use namada::governance::cli::onchain::{
-    DefaultProposal, PgfFundingProposal, PgfStewardProposal,
+    DefaultProposal, ParameterChangeProposal, PgfFundingProposal,
+    PgfStewardProposal, UpgradeProposal,
 };
 use namada::governance::ProposalVote;
 use namada::ibc::apps::transfer::types::Memo;
@@ -22,6 +23,7 @@ use namada::io::Io;
 use namada::state::EPOCH_SWITCH_BLOCKS_DELAY;
 use namada::tx::data::pos::{BecomeValidator, ConsensusKeyChange};
 use namada::tx::{CompressedSignature, Section, Signer, Tx};
+use namada_sdk::masp::SpendRandomizer;
 use namada_sdk::rpc::{InnerTxResult, TxBroadcastData, TxResponse};
 use namada_sdk::signing::validate_fee_and_gen_unshield;
 use namada_sdk::wallet::alias::validator_consensus_key;
@@ -888,6 +890,7 @@ pub async fn submit_init_validator(
             tx_code_path: tx_init_account_code_path,
             public_keys: account_keys,
             threshold,
+            vesting: None,
         },
     )
     .await?;
@@ -949,8 +952,24 @@ pub async fn submit_transfer(
             args.clone().build(namada).await?;
 
         if args.tx.dump_tx {
+            if !signing_data.spend_randomizers.is_empty() {
+                dump_spend_randomizers(
+                    namada.io(),
+                    &args.tx,
+                    &tx,
+                    &signing_data.spend_randomizers,
+                );
+            }
             tx::dump_tx(namada.io(), &args.tx, tx);
             break;
+        } else if !signing_data.spend_randomizers.is_empty() {
+            edisplay_line!(
+                namada.io(),
+                "The shielded spends of a transfer from a proof generation \
+                 key must be signed separately. Use --dump-tx to serialize \
+                 the transaction along with the randomizers of its spends."
+            );
+            safe_exit(1)
         } else {
             sign(namada, &mut tx, &args.tx, signing_data).await?;
 
@@ -984,6 +1003,44 @@ pub async fn submit_transfer(
     Ok(())
 }
 
+/// Save the randomizers of the shielded spends of a dumped transaction, for
+/// the holders of the spending keys to sign the spends separately
+fn dump_spend_randomizers(
+    io: &impl Io,
+    args: &args::Tx,
+    tx: &Tx,
+    spend_randomizers: &[SpendRandomizer],
+) {
+    match &args.output_folder {
+        Some(path) => {
+            let filename = format!("{}.spend_randomizers", tx.header_hash());
+            let path = path.join(filename);
+            let out = File::create(&path).unwrap();
+            serde_json::to_writer_pretty(out, spend_randomizers)
+                .expect("Should be able to write to file.");
+            display_line!(
+                io,
+                "Spend randomizers serialized to {}. Only share them with the \
+                 holders of the spending keys.",
+                path.to_string_lossy()
+            );
+        }
+        None => {
+            display_line!(
+                io,
+                "Below the spend randomizers, only to be shared with the \
+                 holders of the spending keys: \n"
+            );
+            display_line!(
+                io,
+                "{}",
+                serde_json::to_string_pretty(spend_randomizers)
+                    .expect("Should be able to serialize spend randomizers.")
+            );
+        }
+    }
+}
+
 pub async fn submit_ibc_transfer<N: Namada>(
     namada: &N,
     args: args::TxIbcTransfer,
@@ -1114,6 +1171,63 @@ where
             .await?;
 
         tx::build_pgf_stewards_proposal(namada, &args, proposal).await?
+    } else if args.is_parameter_change {
+        let proposal =
+            ParameterChangeProposal::try_from(args.proposal_data.as_ref())
+                .map_err(|e| {
+                    error::TxSubmitError::FailedGovernaneProposalDeserialize(
+                        e.to_string(),
+                    )
+                })?;
+        let author_balance = rpc::get_token_balance(
+            namada.client(),
+            &namada.native_token(),
+            &proposal.proposal.author,
+        )
+        .await;
+        let proposal = proposal
+            .validate(
+                &governance_parameters,
+                current_epoch,
+                author_balance,
+                args.tx.force,
+            )
+            .map_err(|e| {
+                error::TxSubmitError::InvalidProposal(e.to_string())
+            })?;
+
+        submit_reveal_aux(namada, args.tx.clone(), &proposal.proposal.author)
+            .await?;
+
+        tx::build_parameter_change_proposal(namada, &args, proposal).await?
+    } else if args.is_upgrade {
+        let proposal = UpgradeProposal::try_from(args.proposal_data.as_ref())
+            .map_err(|e| {
+            error::TxSubmitError::FailedGovernaneProposalDeserialize(
+                e.to_string(),
+            )
+        })?;
+        let author_balance = rpc::get_token_balance(
+            namada.client(),
+            &namada.native_token(),
+            &proposal.proposal.author,
+        )
+        .await;
+        let proposal = proposal
+            .validate(
+                &governance_parameters,
+                current_epoch,
+                author_balance,
+                args.tx.force,
+            )
+            .map_err(|e| {
+                error::TxSubmitError::InvalidProposal(e.to_string())
+            })?;
+
+        submit_reveal_aux(namada, args.tx.clone(), &proposal.proposal.author)
+            .await?;
+
+        tx::build_upgrade_proposal(namada, &args, proposal).await?
     } else {
         let proposal = DefaultProposal::try_from(args.proposal_data.as_ref())
             .map_err(|e| {
@@ -1237,6 +1351,26 @@ where
     Ok(())
 }
 
+pub async fn submit_change_representative<N: Namada>(
+    namada: &N,
+    args: args::ChangeRepresentative,
+) -> Result<(), error::Error>
+where
+    <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
+{
+    let (mut tx, signing_data) = args.build(namada).await?;
+
+    if args.tx.dump_tx {
+        tx::dump_tx(namada.io(), &args.tx, tx);
+    } else {
+        sign(namada, &mut tx, &args.tx, signing_data).await?;
+
+        namada.submit(tx, &args.tx).await?;
+    }
+
+    Ok(())
+}
+
 pub async fn sign_tx<N: Namada>(
     namada: &N,
     args::SignTx {
@@ -1478,6 +1612,26 @@ where
     Ok(())
 }
 
+pub async fn submit_validator_delegation_policy_change<N: Namada>(
+    namada: &N,
+    args: args::DelegationPolicyChange,
+) -> Result<(), error::Error>
+where
+    <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
+{
+    let (mut tx, signing_data) = args.build(namada).await?;
+
+    if args.tx.dump_tx {
+        tx::dump_tx(namada.io(), &args.tx, tx);
+    } else {
+        sign(namada, &mut tx, &args.tx, signing_data).await?;
+
+        namada.submit(tx, &args.tx).await?;
+    }
+
+    Ok(())
+}
+
 pub async fn submit_unjail_validator<N: Namada>(
     namada: &N,
     args: args::TxUnjailValidator,
@@ -1578,6 +1732,46 @@ where
     Ok(())
 }
 
+pub async fn submit_register_pgf_project<N: Namada>(
+    namada: &N,
+    args: args::RegisterPgfProject,
+) -> Result<(), error::Error>
+where
+    <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
+{
+    let (mut tx, signing_data) = args.build(namada).await?;
+
+    if args.tx.dump_tx {
+        tx::dump_tx(namada.io(), &args.tx, tx);
+    } else {
+        sign(namada, &mut tx, &args.tx, signing_data).await?;
+
+        namada.submit(tx, &args.tx).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn submit_vote_pgf_funding_round<N: Namada>(
+    namada: &N,
+    args: args::VotePgfFundingRound,
+) -> Result<(), error::Error>
+where
+    <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
+{
+    let (mut tx, signing_data) = args.build(namada).await?;
+
+    if args.tx.dump_tx {
+        tx::dump_tx(namada.io(), &args.tx, tx);
+    } else {
+        sign(namada, &mut tx, &args.tx, signing_data).await?;
+
+        namada.submit(tx, &args.tx).await?;
+    }
+
+    Ok(())
+}
+
 /// Save accounts initialized from a tx into the wallet, if any.
 pub async fn save_initialized_accounts(
     namada: &impl Namada,
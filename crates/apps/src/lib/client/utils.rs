@@ -6,6 +6,7 @@ use std::str::FromStr;
 
 use borsh_ext::BorshSerializeExt;
 use color_eyre::owo_colors::OwoColorize;
+use data_encoding::HEXLOWER;
 use flate2::read::GzDecoder;
 use flate2::write::GzEncoder;
 use flate2::Compression;
@@ -16,9 +17,17 @@ use namada::core::key::*;
 use namada::core::string_encoding::StringEncoded;
 use namada::core::token;
 use namada::core::uint::Uint;
+use namada::tx::{MaspBuilder, Section, Tx};
 use namada::vm::validate_untrusted_wasm;
+use namada_sdk::masp::{
+    authorize_shielded_spends, sign_shielded_spends, SpendAuthorization,
+    SpendRandomizer,
+};
+use namada_sdk::masp_primitives::group::GroupEncoding;
+use namada_sdk::masp_primitives::zip32::ExtendedSpendingKey;
 use namada_sdk::wallet::{alias, Wallet};
 use prost::bytes::Bytes;
+use rand::rngs::OsRng;
 use serde_json::json;
 use sha2::{Digest, Sha256};
 use tokio::sync::RwLock;
@@ -1147,6 +1156,249 @@ pub async fn sign_genesis_tx(
     }
 }
 
+/// Read a transaction serialized with `--dump-tx` from the given file.
+fn read_dumped_tx(path: &Path) -> Tx {
+    let data = fs::read(path).unwrap_or_else(|err| {
+        eprintln!(
+            "Unable to read from file {}. Failed with {err}.",
+            path.to_string_lossy()
+        );
+        safe_exit(1)
+    });
+    Tx::deserialize(&data).unwrap_or_else(|err| {
+        eprintln!("Couldn't decode the transaction: {err}.");
+        safe_exit(1)
+    })
+}
+
+/// Export the masp builder sections of a dumped transaction, one file per
+/// masp transaction, so that the spends and outputs can be reviewed apart
+/// from the transaction.
+pub fn export_masp_builder(
+    args::ExportMaspBuilder {
+        tx_path,
+        output_folder,
+    }: args::ExportMaspBuilder,
+) {
+    let tx = read_dumped_tx(&tx_path);
+    let builders = tx.masp_builders();
+    if builders.is_empty() {
+        eprintln!("The transaction does not contain any masp builder.");
+        safe_exit(1)
+    }
+    for builder in builders {
+        let filename = format!("{}.masp_builder", builder.target);
+        let path = match &output_folder {
+            Some(folder) => folder.join(filename),
+            None => filename.into(),
+        };
+        let out = File::create(&path).unwrap_or_else(|err| {
+            eprintln!(
+                "Failed to create {} with {err}.",
+                path.to_string_lossy()
+            );
+            safe_exit(1)
+        });
+        serde_json::to_writer_pretty(out, builder)
+            .expect("Should be able to write to file.");
+        println!("Masp builder written to {}.", path.to_string_lossy());
+    }
+}
+
+/// Attach an exported masp builder back to the dumped transaction that
+/// carries its masp transaction.
+pub fn import_masp_builder(
+    args::ImportMaspBuilder {
+        tx_path,
+        masp_builder_path,
+        output_folder,
+    }: args::ImportMaspBuilder,
+) {
+    let mut tx = read_dumped_tx(&tx_path);
+    let data = fs::read(&masp_builder_path).unwrap_or_else(|err| {
+        eprintln!(
+            "Unable to read from file {}. Failed with {err}.",
+            masp_builder_path.to_string_lossy()
+        );
+        safe_exit(1)
+    });
+    let builder: MaspBuilder =
+        serde_json::from_slice(&data).unwrap_or_else(|err| {
+            eprintln!("Couldn't decode the masp builder: {err}.");
+            safe_exit(1)
+        });
+    tx.attach_masp_builder(builder).unwrap_or_else(|err| {
+        eprintln!("{err}.");
+        safe_exit(1)
+    });
+    let filename = format!("{}.tx", tx.header_hash());
+    let path = match output_folder {
+        Some(folder) => folder.join(filename),
+        None => filename.into(),
+    };
+    let out = File::create(&path).unwrap_or_else(|err| {
+        eprintln!("Failed to create {} with {err}.", path.to_string_lossy());
+        safe_exit(1)
+    });
+    serde_json::to_writer_pretty(out, &tx.serialize())
+        .expect("Should be able to write to file.");
+    println!("Transaction serialized to {}.", path.to_string_lossy());
+}
+
+/// Sign the shielded spends of a dumped transaction that were built with the
+/// proof generation key of the given spending key. The signatures are written
+/// to a file, to be applied to the transaction with `authorize-masp-spends`.
+pub fn sign_masp_spends(
+    args::SignMaspSpends {
+        tx_path,
+        spend_randomizers_path,
+        spending_key,
+        output_folder,
+    }: args::SignMaspSpends,
+) {
+    let tx = read_dumped_tx(&tx_path);
+    let data = fs::read(&spend_randomizers_path).unwrap_or_else(|err| {
+        eprintln!(
+            "Unable to read from file {}. Failed with {err}.",
+            spend_randomizers_path.to_string_lossy()
+        );
+        safe_exit(1)
+    });
+    let randomizers: Vec<SpendRandomizer> = serde_json::from_slice(&data)
+        .unwrap_or_else(|err| {
+            eprintln!("Couldn't decode the spend randomizers: {err}.");
+            safe_exit(1)
+        });
+    let spending_key = ExtendedSpendingKey::from(spending_key);
+    let mut authorizations = vec![];
+    for section in &tx.sections {
+        if let Section::MaspTx(masp_tx) = section {
+            let signed = sign_shielded_spends(
+                masp_tx,
+                &randomizers,
+                &spending_key,
+                &mut OsRng,
+            )
+            .unwrap_or_else(|err| {
+                eprintln!("{err}.");
+                safe_exit(1)
+            });
+            authorizations.extend(signed);
+        }
+    }
+    let Some(first) = authorizations.first() else {
+        eprintln!("The spending key does not authorize any shielded spend.");
+        safe_exit(1)
+    };
+    let filename = format!(
+        "spend_authorizations_{}_{}.json",
+        tx.header_hash(),
+        HEXLOWER.encode(&first.rk)
+    );
+    let path = match output_folder {
+        Some(folder) => folder.join(filename),
+        None => filename.into(),
+    };
+    let out = File::create(&path).unwrap_or_else(|err| {
+        eprintln!("Failed to create {} with {err}.", path.to_string_lossy());
+        safe_exit(1)
+    });
+    serde_json::to_writer_pretty(out, &authorizations)
+        .expect("Should be able to write to file.");
+    println!(
+        "{} spend signature(s) written to {}.",
+        authorizations.len(),
+        path.to_string_lossy()
+    );
+}
+
+/// Apply the spend signatures made with `sign-masp-spends` to the masp
+/// transactions of the dumped transaction that they authorize.
+pub fn authorize_masp_spends(
+    args::AuthorizeMaspSpends {
+        tx_path,
+        spend_authorizations,
+        output_folder,
+    }: args::AuthorizeMaspSpends,
+) {
+    let mut tx = read_dumped_tx(&tx_path);
+    let mut authorizations: Vec<SpendAuthorization> = vec![];
+    for path in &spend_authorizations {
+        let data = fs::read(path).unwrap_or_else(|err| {
+            eprintln!(
+                "Unable to read from file {}. Failed with {err}.",
+                path.to_string_lossy()
+            );
+            safe_exit(1)
+        });
+        let signed: Vec<SpendAuthorization> = serde_json::from_slice(&data)
+            .unwrap_or_else(|err| {
+                eprintln!("Couldn't decode the spend signatures: {err}.");
+                safe_exit(1)
+            });
+        authorizations.extend(signed);
+    }
+    let masp_txs: Vec<_> = tx
+        .sections
+        .iter()
+        .filter_map(|section| match section {
+            Section::MaspTx(masp_tx) => Some(masp_tx.clone()),
+            _ => None,
+        })
+        .collect();
+    let mut applied = 0;
+    for masp_tx in masp_txs {
+        // Only apply the signatures of the spends of this masp transaction
+        let rks: Vec<[u8; 32]> = masp_tx
+            .sapling_bundle()
+            .map(|bundle| {
+                bundle
+                    .shielded_spends
+                    .iter()
+                    .map(|spend| spend.rk.0.to_bytes())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let signed: Vec<_> = authorizations
+            .iter()
+            .filter(|authorization| rks.contains(&authorization.rk))
+            .cloned()
+            .collect();
+        if signed.is_empty() {
+            continue;
+        }
+        let masp_tx = authorize_shielded_spends(&masp_tx, &signed)
+            .unwrap_or_else(|err| {
+                eprintln!("{err}.");
+                safe_exit(1)
+            });
+        tx.update_masp_tx(masp_tx).unwrap_or_else(|err| {
+            eprintln!("{err}.");
+            safe_exit(1)
+        });
+        applied += signed.len();
+    }
+    if applied != authorizations.len() {
+        eprintln!(
+            "Some spend signatures do not belong to the transaction's masp \
+             transactions."
+        );
+        safe_exit(1)
+    }
+    let filename = format!("{}.tx", tx.header_hash());
+    let path = match output_folder {
+        Some(folder) => folder.join(filename),
+        None => filename.into(),
+    };
+    let out = File::create(&path).unwrap_or_else(|err| {
+        eprintln!("Failed to create {} with {err}.", path.to_string_lossy());
+        safe_exit(1)
+    });
+    serde_json::to_writer_pretty(out, &tx.serialize())
+        .expect("Should be able to write to file.");
+    println!("Transaction serialized to {}.", path.to_string_lossy());
+}
+
 /// Add a spinning wheel to a message for long running commands.
 /// Can be turned off for E2E tests by setting the `REDUCED_CLI_PRINTING`
 /// environment variable.
@@ -472,6 +472,7 @@ pub fn make_dev_genesis(
                     consensus_keypair.ref_to(),
                 )],
                 threshold: 1,
+                vesting: None,
             };
             let address = tx.derive_established_address();
             let established_account_tx = FinalizedEstablishedAccountTx {
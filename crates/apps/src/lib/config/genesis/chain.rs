@@ -365,12 +365,15 @@ impl Finalized {
             target_staked_ratio,
             duplicate_vote_min_slash_rate,
             light_client_attack_min_slash_rate,
+            eth_bridge_equivocation_min_slash_rate,
             cubic_slashing_window_length,
             validator_stake_threshold,
             liveness_window_check,
             liveness_threshold,
             rewards_gain_p,
             rewards_gain_d,
+            min_self_bond_ratio,
+            max_delegated_stake,
         } = self.parameters.pos_params.clone();
 
         namada::proof_of_stake::parameters::PosParams {
@@ -385,12 +388,15 @@ impl Finalized {
                 target_staked_ratio,
                 duplicate_vote_min_slash_rate,
                 light_client_attack_min_slash_rate,
+                eth_bridge_equivocation_min_slash_rate,
                 cubic_slashing_window_length,
                 validator_stake_threshold,
                 liveness_window_check,
                 liveness_threshold,
                 rewards_gain_p,
                 rewards_gain_d,
+                min_self_bond_ratio,
+                max_delegated_stake,
             },
             max_proposal_period: self.parameters.gov_params.max_proposal_period,
         }
@@ -406,6 +412,11 @@ impl Finalized {
             max_proposal_period,
             max_proposal_content_size,
             min_proposal_grace_epochs,
+            proposal_veto_threshold,
+            expedited_min_proposal_fund,
+            expedited_proposal_voting_period,
+            expedited_min_proposal_grace_epochs,
+            expedited_proposal_quorum,
         } = self.parameters.gov_params.clone();
         namada::governance::parameters::GovernanceParameters {
             min_proposal_fund: Amount::native_whole(min_proposal_fund),
@@ -414,6 +425,13 @@ impl Finalized {
             max_proposal_content_size,
             min_proposal_grace_epochs,
             min_proposal_voting_period,
+            proposal_veto_threshold,
+            expedited_min_proposal_fund: Amount::native_whole(
+                expedited_min_proposal_fund,
+            ),
+            expedited_proposal_voting_period,
+            expedited_min_proposal_grace_epochs,
+            expedited_proposal_quorum,
         }
     }
 
@@ -405,6 +405,9 @@ pub struct PosParams {
     /// Portion of a validator's stake that should be slashed on a
     /// light client attack.
     pub light_client_attack_min_slash_rate: Dec,
+    /// Portion of a validator's stake that should be slashed on an
+    /// equivocation with its Ethereum bridge hot key.
+    pub eth_bridge_equivocation_min_slash_rate: Dec,
     /// Number of epochs above and below (separately) the current epoch to
     /// consider when doing cubic slashing
     pub cubic_slashing_window_length: u64,
@@ -421,6 +424,11 @@ pub struct PosParams {
     pub rewards_gain_p: Dec,
     /// PoS gain d (read only)
     pub rewards_gain_d: Dec,
+    /// The minimum ratio of a validator's self-bond to its total stake
+    /// required to be in the consensus set and to receive new delegations
+    pub min_self_bond_ratio: Dec,
+    /// The maximum amount of delegated stake that a validator may receive
+    pub max_delegated_stake: Option<token::Amount>,
 }
 
 #[derive(
@@ -447,6 +455,19 @@ pub struct GovernanceParams {
     pub max_proposal_content_size: u64,
     /// Minimum number of epoch between end and grace epoch
     pub min_proposal_grace_epochs: u64,
+    /// Minimum fraction of the voted power that must veto a proposal to burn
+    /// its locked funds
+    pub proposal_veto_threshold: Dec,
+    /// Min funds to stake to submit an expedited proposal
+    pub expedited_min_proposal_fund: u64,
+    /// Voting period length in epochs of expedited proposals
+    pub expedited_proposal_voting_period: u64,
+    /// Minimum number of epoch between end and grace epoch of expedited
+    /// proposals
+    pub expedited_min_proposal_grace_epochs: u64,
+    /// Minimum fraction of the total voting power that must vote yay for an
+    /// expedited proposal to pass
+    pub expedited_proposal_quorum: Dec,
 }
 
 #[derive(
@@ -966,6 +987,7 @@ mod tests {
 
     use namada::core::key;
     use namada::core::key::RefTo;
+    use namada::core::storage::Epoch;
     use namada::core::string_encoding::StringEncoded;
     use tempfile::tempdir;
 
@@ -1024,4 +1046,49 @@ mod tests {
         let example_balance = balances.token.get(&token_alias).unwrap();
         assert_eq!(balance, example_balance.0.get(&address).unwrap().amount());
     }
+
+    #[test]
+    fn test_read_established_account_vesting() {
+        let pk = key::testing::keypair_1().ref_to();
+        let contents = format!(
+            r#"
+            [[established_account]]
+            vp = "vp_user"
+            threshold = 1
+            public_keys = ["{pk}"]
+
+            [established_account.vesting]
+            token = "NAM"
+            amount = "100.5"
+
+            [established_account.vesting.schedule.linear]
+            start_epoch = 0
+            cliff_epoch = 10
+            end_epoch = 20
+            "#
+        );
+
+        let txs = transactions::parse_unsigned(contents.as_bytes()).unwrap();
+        let tx = &txs.established_account.unwrap()[0];
+        let vesting = tx
+            .vesting
+            .as_ref()
+            .unwrap()
+            .to_vesting(namada::core::address::testing::nam(), 6.into())
+            .unwrap();
+        assert_eq!(vesting.amount, token::Amount::from(100_500_000));
+        assert_eq!(vesting.unlocked_amount(Epoch(9)), token::Amount::zero());
+        assert_eq!(
+            vesting.unlocked_amount(Epoch(15)),
+            token::Amount::from(75_375_000)
+        );
+        assert_eq!(vesting.unlocked_amount(Epoch(20)), vesting.amount);
+
+        // The vesting is part of the address derivation
+        let without_vesting = transactions::EstablishedAccountTx {
+            vesting: None,
+            ..tx.clone()
+        };
+        assert_ne!(tx.derive_address(), without_vesting.derive_address());
+    }
 }
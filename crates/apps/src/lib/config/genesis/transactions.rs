@@ -10,13 +10,16 @@ use itertools::{Either, Itertools};
 use ledger_namada_rs::NamadaApp;
 use ledger_transport_hid::hidapi::HidApi;
 use ledger_transport_hid::TransportNativeHID;
-use namada::account::AccountPublicKeysMap;
+use namada::account::{
+    AccountPublicKeysMap, Vesting, VestingSchedule, VestingUnlock,
+};
 use namada::core::address::{Address, EstablishedAddress};
 use namada::core::chain::ChainId;
 use namada::core::dec::Dec;
 use namada::core::key::{
     common, ed25519, RefTo, SerializeWithBorsh, SigScheme,
 };
+use namada::core::storage::Epoch;
 use namada::core::string_encoding::StringEncoded;
 use namada::core::time::DateTimeUtc;
 use namada::core::token;
@@ -37,6 +40,7 @@ use namada_sdk::wallet::alias::Alias;
 use namada_sdk::wallet::pre_genesis::ValidatorWallet;
 use namada_sdk::wallet::Wallet;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use tokio::sync::RwLock;
 
 use super::templates::{DenominatedBalances, Parameters, ValidityPredicates};
@@ -244,6 +248,7 @@ pub fn init_established_account(
         vp,
         threshold,
         public_keys,
+        vesting: None,
     };
     let address = unsigned_tx.derive_address();
     let txs = UnsignedTransactions {
@@ -684,8 +689,12 @@ pub struct EstablishedAccountTx {
     pub vp: String,
     #[serde(default = "default_threshold")]
     pub threshold: u8,
-    /// PKs have to come last in TOML to avoid `ValueAfterTable` error
+    /// PKs have to come after the other values in TOML to avoid
+    /// `ValueAfterTable` error
     pub public_keys: Vec<StringEncoded<common::PublicKey>>,
+    /// The vesting is a table so it has to come last in TOML
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vesting: Option<GenesisVesting>,
 }
 
 const fn default_threshold() -> u8 {
@@ -694,6 +703,146 @@ const fn default_threshold() -> u8 {
 
 impl DeriveEstablishedAddress for EstablishedAccountTx {
     const SALT: &'static str = "established-account-tx";
+
+    /// The vesting is only hashed when present to keep the addresses of the
+    /// accounts without vesting unchanged.
+    fn derive_established_address(&self) -> EstablishedAddress {
+        let mut hasher = Sha256::new();
+        hasher.update(Self::SALT.as_bytes());
+        hasher.update(
+            (&self.vp, &self.threshold, &self.public_keys).serialize_to_vec(),
+        );
+        if let Some(vesting) = &self.vesting {
+            hasher.update(vesting.serialize_to_vec());
+        }
+        let digest = hasher.finalize();
+        let digest_ref: &[u8; 32] = digest.as_ref();
+        EstablishedAddress::from(*digest_ref)
+    }
+}
+
+/// The vesting of an established account's tokens at genesis. The tokens
+/// must be credited to the account in the balances.
+#[derive(
+    Clone,
+    Debug,
+    Deserialize,
+    Serialize,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+)]
+pub struct GenesisVesting {
+    /// The vesting token
+    pub token: Alias,
+    /// The amount of tokens locked by the schedule
+    pub amount: DenominatedAmount,
+    /// The unlock schedule
+    pub schedule: GenesisVestingSchedule,
+}
+
+/// The unlock schedule of a [`GenesisVesting`]
+#[derive(
+    Clone,
+    Debug,
+    Deserialize,
+    Serialize,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum GenesisVestingSchedule {
+    /// Tokens are unlocked linearly every epoch from the start epoch until
+    /// the end epoch, but none of them before the cliff epoch
+    Linear {
+        start_epoch: Epoch,
+        cliff_epoch: Epoch,
+        end_epoch: Epoch,
+    },
+    /// Tokens are unlocked by the given steps
+    Custom { unlocks: Vec<GenesisVestingUnlock> },
+}
+
+/// A step of a [`GenesisVestingSchedule::Custom`] schedule
+#[derive(
+    Clone,
+    Debug,
+    Deserialize,
+    Serialize,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+)]
+pub struct GenesisVestingUnlock {
+    pub epoch: Epoch,
+    pub amount: DenominatedAmount,
+}
+
+impl GenesisVesting {
+    /// Convert into the on-chain vesting of the given token address, using
+    /// the token's denomination to interpret the amounts
+    pub fn to_vesting(
+        &self,
+        token: Address,
+        denom: token::Denomination,
+    ) -> eyre::Result<Vesting> {
+        let amount = |amount: DenominatedAmount| {
+            amount
+                .increase_precision(denom)
+                .map(|amount| amount.amount())
+                .map_err(|err| {
+                    eyre::eyre!(
+                        "Invalid vesting amount {amount} of token \"{}\": \
+                         {err}",
+                        self.token
+                    )
+                })
+        };
+        let schedule = match &self.schedule {
+            GenesisVestingSchedule::Linear {
+                start_epoch,
+                cliff_epoch,
+                end_epoch,
+            } => VestingSchedule::Linear {
+                start_epoch: *start_epoch,
+                cliff_epoch: *cliff_epoch,
+                end_epoch: *end_epoch,
+            },
+            GenesisVestingSchedule::Custom { unlocks } => {
+                VestingSchedule::Custom(
+                    unlocks
+                        .iter()
+                        .map(|unlock| {
+                            Ok(VestingUnlock {
+                                epoch: unlock.epoch,
+                                amount: amount(unlock.amount)?,
+                            })
+                        })
+                        .collect::<eyre::Result<_>>()?,
+                )
+            }
+        };
+        let vesting = Vesting {
+            token,
+            amount: amount(self.amount)?,
+            schedule,
+        };
+        vesting.validate().map_err(|err| eyre::eyre!(err))?;
+        Ok(vesting)
+    }
 }
 
 #[derive(
@@ -749,6 +898,7 @@ impl<T> Signed<T> {
             public_keys: pks.clone(),
             threshold,
             fee_payer: genesis_fee_payer_pk(),
+            spend_randomizers: vec![],
         };
 
         let mut tx = self.data.tx_to_sign();
@@ -1027,6 +1177,9 @@ pub fn validate(
             ) {
                 is_valid = false;
             }
+            if !validate_established_account_vesting(tx, balances) {
+                is_valid = false;
+            }
         }
     }
 
@@ -1249,6 +1402,53 @@ pub struct TokenBalancesForValidation {
     pub amounts: BTreeMap<GenesisAddress, DenominatedAmount>,
 }
 
+/// Check that the vesting of an established account, if any, is consistent
+/// and that the vested tokens are credited to the account in the balances.
+pub fn validate_established_account_vesting(
+    tx: &EstablishedAccountTx,
+    balances: Option<&DenominatedBalances>,
+) -> bool {
+    let Some(vesting) = &tx.vesting else {
+        return true;
+    };
+    let owner =
+        GenesisAddress::EstablishedAddress(tx.derive_established_address());
+    let Some(balance) = balances
+        .and_then(|balances| balances.token.get(&vesting.token))
+        .and_then(|balances| balances.0.get(&owner))
+    else {
+        eprintln!(
+            "Invalid vesting of the established account {owner}. The account \
+             has no balance of token \"{}\".",
+            vesting.token
+        );
+        return false;
+    };
+    // The address of the token is not known yet, but it doesn't matter to
+    // validate the schedule
+    let vesting = match vesting.to_vesting(
+        Address::Established(EstablishedAddress::from([0; 32])),
+        balance.denom(),
+    ) {
+        Ok(vesting) => vesting,
+        Err(err) => {
+            eprintln!(
+                "Invalid vesting of the established account {owner}: {err}."
+            );
+            return false;
+        }
+    };
+    if balance.amount() < vesting.amount {
+        eprintln!(
+            "Invalid vesting of the established account {owner}. The vesting \
+             amount {} is greater than the balance {balance}.",
+            DenominatedAmount::new(vesting.amount, balance.denom()),
+        );
+        return false;
+    }
+    true
+}
+
 pub fn validate_established_account(
     tx: &EstablishedAccountTx,
     vps: Option<&ValidityPredicates>,
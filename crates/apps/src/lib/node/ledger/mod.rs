@@ -33,7 +33,7 @@ use self::abortable::AbortableSpawner;
 use self::ethereum_oracle::last_processed_block;
 use self::shell::EthereumOracleChannels;
 use self::shims::abcipp_shim::AbciService;
-use crate::cli::args;
+use crate::cli::{self, args};
 use crate::config::utils::{convert_tm_addr_to_socket_addr, num_of_threads};
 use crate::config::{ethereum_bridge, TendermintMode};
 use crate::facade::tendermint::v0_37::abci::response;
@@ -317,6 +317,184 @@ pub fn update_db_keys(config: config::Ledger, updates: PathBuf, dry_run: bool) {
     }
 }
 
+/// Simulate the slashing of a hypothetical infraction on top of the last
+/// committed state, or the genesis state if requested. The changes are only
+/// applied to the write log, which is never committed.
+pub fn simulate_slash(
+    mut config: config::Ledger,
+    wasm_dir: PathBuf,
+    args: args::LedgerSimulateSlash,
+) {
+    use namada::core::hash::Sha256Hasher;
+    use namada::core::storage::Epoch;
+    use namada::proof_of_stake::simulation::{
+        simulate_slashes, SimulatedInfraction,
+    };
+    use namada::proof_of_stake::types::SlashType;
+    use namada::state::mockdb::MockDB;
+    use namada::state::FullAccessState;
+
+    /// An infraction read from the infractions file
+    #[derive(serde::Deserialize)]
+    struct InfractionSpec {
+        validator: namada::core::address::Address,
+        epoch: Option<Epoch>,
+        block_height: Option<u64>,
+        #[serde(default)]
+        light_client_attack: bool,
+    }
+
+    let slash_type = |light_client_attack| {
+        if light_client_attack {
+            SlashType::LightClientAttack
+        } else {
+            SlashType::DuplicateVote
+        }
+    };
+    let mut specs = vec![];
+    if let Some(validator) = args.validator.clone() {
+        specs.push(InfractionSpec {
+            validator,
+            epoch: args.epoch,
+            block_height: args.block_height.map(|height| height.0),
+            light_client_attack: args.light_client_attack,
+        });
+    }
+    if let Some(path) = &args.infractions {
+        let json = std::fs::read_to_string(path).unwrap_or_else(|err| {
+            tracing::error!(
+                "Unable to read the infractions file {}: {err}",
+                path.to_string_lossy()
+            );
+            cli::safe_exit(1)
+        });
+        let infractions: Vec<InfractionSpec> = serde_json::from_str(&json)
+            .unwrap_or_else(|err| {
+                tracing::error!("Unable to parse the infractions file: {err}");
+                cli::safe_exit(1)
+            });
+        specs.extend(infractions);
+    }
+    let infractions = |current_epoch| -> Vec<SimulatedInfraction> {
+        specs
+            .iter()
+            .map(|spec| SimulatedInfraction {
+                validator: spec.validator.clone(),
+                epoch: spec.epoch.unwrap_or(current_epoch),
+                block_height: spec.block_height.unwrap_or_default(),
+                slash_type: slash_type(spec.light_client_attack),
+            })
+            .collect()
+    };
+
+    let report = if args.from_genesis {
+        let chain_id = config.chain_id.clone();
+        let chain_dir = config.shell.base_dir.join(chain_id.as_str());
+        let genesis =
+            config::genesis::chain::Finalized::read_toml_files(&chain_dir)
+                .expect("Missing genesis files");
+        // Don't load the validator's wallet for the simulation
+        config.shell.tendermint_mode = TendermintMode::Full;
+        let (broadcast_sender, _broadcaster_receiver) =
+            mpsc::unbounded_channel();
+        let mut shell = Shell::<MockDB, Sha256Hasher>::new(
+            config,
+            wasm_dir,
+            broadcast_sender,
+            None,
+            None,
+            50 * 1024 * 1024,
+            50 * 1024 * 1024,
+        );
+        let mut initializer = shell::InitChainValidation::new(&mut shell, true);
+        initializer.run_validation(chain_id.to_string(), genesis);
+        initializer.report();
+
+        let current_epoch = shell.state.in_mem().block.epoch;
+        simulate_slashes(
+            &mut shell.state,
+            current_epoch,
+            &infractions(current_epoch),
+        )
+    } else {
+        let chain_id = config.chain_id;
+        let db_path = config.shell.db_dir(&chain_id);
+        let chain_dir = config.shell.base_dir.join(chain_id.as_str());
+        let genesis =
+            config::genesis::chain::Finalized::read_toml_files(&chain_dir)
+                .expect("Missing genesis files");
+        let native_token = genesis.get_native_token().clone();
+        let mut state = FullAccessState::<
+            storage::PersistentDB,
+            storage::PersistentStorageHasher,
+        >::open(
+            db_path,
+            None,
+            chain_id,
+            native_token,
+            config.shell.storage_read_past_height_limit,
+            shell::is_merklized_storage_key,
+        );
+
+        let current_epoch = state.in_mem().last_epoch;
+        simulate_slashes(&mut state, current_epoch, &infractions(current_epoch))
+    };
+
+    let report = match report {
+        Ok(report) => report,
+        Err(err) => {
+            tracing::error!("The slash simulation failed: {err}");
+            cli::safe_exit(1)
+        }
+    };
+
+    tracing::info!(
+        "Simulated slashes would be processed in epoch {}.",
+        report.processing_epoch
+    );
+    for (validator, slashes) in &report.slashes {
+        for slash in slashes {
+            tracing::info!(
+                "Validator {validator}: {} in epoch {} at height {}, slash \
+                 rate {}",
+                slash.r#type,
+                slash.epoch,
+                slash.block_height,
+                slash.rate
+            );
+        }
+    }
+    for (validator, stake) in &report.validator_stakes {
+        tracing::info!(
+            "Validator {validator} stake: {} -> {}",
+            stake.before.to_string_native(),
+            stake.after.to_string_native()
+        );
+    }
+    for (bond_id, impact) in &report.delegations {
+        tracing::info!(
+            "Bond {bond_id}: {} of {} bonded and {} of {} unbonded tokens \
+             slashed",
+            impact.bonds_slashed.to_string_native(),
+            impact.bonds_total.to_string_native(),
+            impact.unbonds_slashed.to_string_native(),
+            impact.unbonds_total.to_string_native(),
+        );
+    }
+    for (delegator, redelegations) in &report.redelegations {
+        for redelegation in redelegations {
+            tracing::info!(
+                "Redelegation of {delegator} from {} to {} in epoch {}: {} \
+                 tokens exposed to the slash",
+                redelegation.src_validator,
+                redelegation.dest_validator,
+                redelegation.redelegation_epoch,
+                redelegation.amount.to_string_native(),
+            );
+        }
+    }
+}
+
 /// Roll Namada state back to the previous height
 pub fn rollback(config: config::Ledger) -> Result<(), shell::Error> {
     shell::rollback(config)
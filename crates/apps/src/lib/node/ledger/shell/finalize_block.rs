@@ -3,8 +3,11 @@
 use data_encoding::HEXUPPER;
 use masp_primitives::merkle_tree::CommitmentTree;
 use masp_primitives::sapling::Node;
+use namada::core::ibc::{get_shielded_transfer, MsgShieldedTransfer};
+use namada::core::masp::{CompactBlock, CompactTx};
 use namada::core::storage::{BlockHash, BlockResults, Epoch, Header};
 use namada::governance::pgf::inflation as pgf_inflation;
+use namada::governance::pgf::rounds as pgf_rounds;
 use namada::ledger::events::EventType;
 use namada::ledger::gas::GasMetering;
 use namada::ledger::pos::namada_proof_of_stake;
@@ -55,9 +58,17 @@ where
     ) -> Result<shim::response::FinalizeBlock> {
         let mut response = shim::response::FinalizeBlock::default();
 
+        // Find the chain upgrade scheduled by governance at this height
+        let scheduled_upgrade = self.check_scheduled_upgrade()?;
+
         // Begin the new block and check if a new epoch has begun
         let (height, new_epoch) = self.update_state(req.header, req.hash);
 
+        // Migrate the state before executing the first block of the upgrade
+        if let Some((upgrade, handler)) = scheduled_upgrade {
+            self.apply_upgrade(upgrade, handler)?;
+        }
+
         let (current_epoch, _gas) = self.state.in_mem().get_current_epoch();
         let update_for_tendermint = matches!(
             self.state.in_mem().update_epoch_blocks_delay,
@@ -145,6 +156,7 @@ where
         // Tracks the accepted transactions
         self.state.in_mem_mut().block.results = BlockResults::default();
         let mut changed_keys = BTreeSet::new();
+        let mut masp_compact_txs = vec![];
         for (tx_index, processed_tx) in req.txs.iter().enumerate() {
             let tx = if let Ok(tx) = Tx::try_from(processed_tx.tx.as_ref()) {
                 tx
@@ -291,7 +303,8 @@ where
                     ProtocolTxType::BridgePoolVext
                     | ProtocolTxType::BridgePool
                     | ProtocolTxType::ValSetUpdateVext
-                    | ProtocolTxType::ValidatorSetUpdate => (
+                    | ProtocolTxType::ValidatorSetUpdate
+                    | ProtocolTxType::EthBridgeEquivocation => (
                         new_tx_event(&tx, height.0),
                         None,
                         TxGasMeter::new_from_sub_limit(0.into()),
@@ -355,6 +368,13 @@ where
                 },
             };
             let tx_gas_meter = RefCell::new(tx_gas_meter);
+            // The tx is consumed by its application, so keep its MASP
+            // transaction around if it has to be indexed
+            let mut masp_tx = if self.masp_compact_index {
+                get_masp_tx(&tx)
+            } else {
+                None
+            };
             let tx_result = protocol::check_tx_allowed(&tx, &self.state)
                 .and_then(|()| {
                     protocol::dispatch_tx(
@@ -408,6 +428,20 @@ where
                             ) {
                                 tx_event["is_valid_masp_tx"] =
                                     format!("{}", tx_index);
+                                // Shielded transfers over IBC are only found
+                                // in the events
+                                if self.masp_compact_index && masp_tx.is_none()
+                                {
+                                    masp_tx = result
+                                        .ibc_events
+                                        .iter()
+                                        .find_map(|event| {
+                                            get_shielded_transfer(event)
+                                                .ok()
+                                                .flatten()
+                                        })
+                                        .map(|shielded| shielded.masp_tx);
+                                }
                             }
                             changed_keys
                                 .extend(result.changed_keys.iter().cloned());
@@ -417,6 +451,11 @@ where
                             }
                         }
                         self.state.commit_tx();
+                        // Record the pgf payments over IBC that were refunded
+                        namada::ibc::settle_pgf_ibc_payments(
+                            &mut self.state,
+                            &result.ibc_events,
+                        )?;
                         if !tx_event.contains_key("code") {
                             tx_event["code"] = ResultCode::Ok.into();
                             self.state
@@ -518,7 +557,7 @@ where
                     if let EventType::Accepted = tx_event.event_type {
                         // If wrapper, invalid tx error code
                         tx_event["code"] = ResultCode::InvalidTx.into();
-                        // The fee unshield operation could still have been
+                        // The masp fee payment could still have been
                         // committed
                         if wrapper_args
                             .expect("Missing required wrapper arguments")
@@ -532,9 +571,33 @@ where
                     }
                 }
             }
+            if tx_event.contains_key("is_valid_masp_tx") {
+                if let Some(masp_tx) = masp_tx {
+                    masp_compact_txs.push(CompactTx::new(
+                        TxIndex(
+                            tx_index
+                                .try_into()
+                                .expect("transaction index out of bounds"),
+                        ),
+                        &masp_tx,
+                    ));
+                }
+            }
             response.events.push(tx_event);
         }
 
+        // Index the compact MASP data of the block for light clients
+        if self.masp_compact_index {
+            self.state
+                .db_mut()
+                .write_masp_compact_block(&CompactBlock {
+                    height,
+                    epoch: current_epoch,
+                    txs: masp_compact_txs,
+                })
+                .into_storage_result()?;
+        }
+
         stats.set_tx_cache_size(
             self.tx_wasm_cache.get_size(),
             self.tx_wasm_cache.get_cache_size(),
@@ -655,6 +718,11 @@ where
             namada::ibc::transfer_over_ibc,
         )?;
 
+        // Pgf funding rounds that have ended
+        pgf_rounds::finalize_funding_rounds(
+            self.state.restrict_writes_to_write_log(),
+        )?;
+
         Ok(())
     }
 
@@ -673,6 +741,24 @@ where
     }
 }
 
+/// Extract the MASP transaction that a transaction may carry, either in the
+/// fee payment section of a wrapper or in the data of a shielded transfer.
+/// The MASP transactions of shielded transfers over IBC can only be found in
+/// the events emitted by their application.
+fn get_masp_tx(tx: &Tx) -> Option<Transaction> {
+    if let TxType::Wrapper(wrapper) = tx.header().tx_type {
+        let hash = wrapper.unshield_section_hash?;
+        return tx.get_section(&hash)?.masp_tx();
+    }
+    let data = tx.data()?;
+    if let Ok(transfer) = token::Transfer::try_from_slice(&data) {
+        return tx.get_section(&transfer.shielded?)?.masp_tx();
+    }
+    MsgShieldedTransfer::try_from_slice(&data)
+        .ok()
+        .map(|message| message.shielded_transfer.masp_tx)
+}
+
 /// Convert ABCI vote info to PoS vote info. Any info which fails the conversion
 /// will be skipped and errors logged.
 ///
@@ -756,7 +842,9 @@ mod test_finalize_block {
     use namada::eth_bridge::storage::min_confirmations_key;
     use namada::ethereum_bridge::storage::wrapped_erc20s;
     use namada::governance::storage::keys::get_proposal_execution_key;
-    use namada::governance::storage::proposal::ProposalType;
+    use namada::governance::storage::proposal::{
+        ProposalType, ScheduledUpgrade,
+    };
     use namada::governance::{InitProposalData, VoteProposalData};
     use namada::ledger::gas::VpGasMeter;
     use namada::ledger::native_vp::parameters::ParametersVp;
@@ -797,6 +885,7 @@ mod test_finalize_block {
     use crate::facade::tendermint::abci::types::Validator;
     use crate::node::ledger::oracle::control::Command;
     use crate::node::ledger::shell::test_utils::*;
+    use crate::node::ledger::shell::upgrade::NO_OP_UPGRADE;
     use crate::node::ledger::shims::abcipp_shim_types::shim::request::{
         FinalizeBlock, ProcessedTx,
     };
@@ -1521,6 +1610,7 @@ mod test_finalize_block {
                 voting_start_epoch: Epoch::default(),
                 voting_end_epoch: Epoch::default().next(),
                 grace_epoch: Epoch::default().next(),
+                expedited: false,
                 r#type: ProposalType::Default(None),
             };
 
@@ -4975,4 +5065,100 @@ mod test_finalize_block {
             control_receiver.recv().await.expect("Test failed");
         assert_eq!(u64::from(cmd.min_confirmations), 42);
     }
+
+    /// Test that the node is told to halt at the height of a scheduled
+    /// upgrade which is not implemented by the binary, and that the last block
+    /// before the upgrade is committed
+    #[test]
+    fn test_unimplemented_upgrade_halt_height() {
+        let (mut shell, _, _, _) = setup();
+        assert!(shell.unimplemented_upgrade().is_none());
+        let last_height = shell.state.in_mem().get_last_block_height();
+        let upgrade = ScheduledUpgrade {
+            proposal_id: 0,
+            name: "unknown-upgrade".to_string(),
+            height: last_height + 2,
+        };
+        let scheduled = namada::governance::storage::schedule_upgrade(
+            &mut shell.state,
+            upgrade.clone(),
+        )
+        .expect("Test failed");
+        assert!(scheduled);
+        shell.commit();
+        assert_eq!(shell.unimplemented_upgrade(), Some(upgrade.clone()));
+
+        // The block before the upgrade is finalized and committed as usual
+        shell.finalize_and_commit(None);
+        assert_eq!(
+            shell.state.in_mem().get_last_block_height(),
+            last_height + 1
+        );
+        assert_eq!(shell.unimplemented_upgrade(), Some(upgrade));
+    }
+
+    /// Test that a scheduled upgrade implemented by the binary is applied at
+    /// its height without halting the node
+    #[test]
+    fn test_implemented_upgrade_applied() {
+        let (mut shell, _, _, _) = setup();
+        let last_height = shell.state.in_mem().get_last_block_height();
+        let scheduled = namada::governance::storage::schedule_upgrade(
+            &mut shell.state,
+            ScheduledUpgrade {
+                proposal_id: 0,
+                name: NO_OP_UPGRADE.to_string(),
+                height: last_height + 1,
+            },
+        )
+        .expect("Test failed");
+        assert!(scheduled);
+        shell.commit();
+        assert!(shell.unimplemented_upgrade().is_none());
+
+        shell.finalize_and_commit(None);
+        assert_eq!(
+            shell.state.in_mem().get_last_block_height(),
+            last_height + 1
+        );
+        assert!(
+            namada::governance::storage::get_scheduled_upgrade(&shell.state)
+                .expect("Test failed")
+                .is_none()
+        );
+    }
+
+    /// Test that scheduling an upgrade keeps the upgrade that is already
+    /// scheduled
+    #[test]
+    fn test_scheduled_upgrade_not_replaced() {
+        let (mut shell, _, _, _) = setup();
+        let earlier = ScheduledUpgrade {
+            proposal_id: 0,
+            name: "earlier-upgrade".to_string(),
+            height: BlockHeight(100),
+        };
+        let scheduled = namada::governance::storage::schedule_upgrade(
+            &mut shell.state,
+            earlier.clone(),
+        )
+        .expect("Test failed");
+        assert!(scheduled);
+
+        let scheduled = namada::governance::storage::schedule_upgrade(
+            &mut shell.state,
+            ScheduledUpgrade {
+                proposal_id: 1,
+                name: "later-upgrade".to_string(),
+                height: BlockHeight(50),
+            },
+        )
+        .expect("Test failed");
+        assert!(!scheduled);
+        assert_eq!(
+            namada::governance::storage::get_scheduled_upgrade(&shell.state)
+                .expect("Test failed"),
+            Some(earlier)
+        );
+    }
 }
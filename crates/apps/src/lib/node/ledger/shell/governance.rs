@@ -3,12 +3,15 @@ use std::collections::HashMap;
 use namada::core::encode;
 use namada::core::event::EmitEvents;
 use namada::core::storage::Epoch;
+use namada::governance::pgf::rounds::storage as pgf_rounds;
+use namada::governance::pgf::storage::history::{PgfPayment, PgfPaymentKind};
 use namada::governance::pgf::storage::keys as pgf_storage;
 use namada::governance::pgf::storage::steward::StewardDetail;
 use namada::governance::pgf::{storage as pgf, ADDRESS};
 use namada::governance::storage::keys as gov_storage;
 use namada::governance::storage::proposal::{
-    AddRemove, PGFAction, PGFTarget, ProposalType, StoragePgfFunding,
+    AddRemove, PGFAction, PGFTarget, ProposalType, ScheduledUpgrade,
+    StoragePgfFunding, StoragePgfFundingSchedule,
 };
 use namada::governance::utils::{
     compute_proposal_result, ProposalVotes, TallyResult, TallyType, TallyVote,
@@ -16,11 +19,13 @@ use namada::governance::utils::{
 };
 use namada::governance::{storage as gov_api, ADDRESS as gov_address};
 use namada::ibc;
+use namada::ledger::governance::parameter_change::apply_parameter_changes;
 use namada::ledger::governance::utils::ProposalEvent;
 use namada::ledger::pos::BondId;
-use namada::proof_of_stake::bond_amount;
 use namada::proof_of_stake::parameters::PosParams;
+use namada::proof_of_stake::queries::find_delegations;
 use namada::proof_of_stake::storage::read_total_stake;
+use namada::proof_of_stake::{bond_amount, is_validator};
 use namada::state::StorageWrite;
 use namada::tx::{Code, Data};
 use namada_sdk::proof_of_stake::storage::read_validator_stake;
@@ -87,11 +92,51 @@ where
             id,
             proposal_end_epoch,
         )?;
-        let proposal_result =
+        let mut proposal_result =
             compute_proposal_result(votes, total_voting_power, tally_type);
+
+        // An expedited proposal that doesn't reach the expedited tally is
+        // converted to a regular proposal and tallied again later
+        if gov_api::is_proposal_expedited(&shell.state, id)? {
+            let expedited_quorum =
+                gov_api::get_expedited_proposal_quorum(&shell.state)?;
+            if !proposal_result.is_expedited_passed(expedited_quorum) {
+                let current_epoch = shell.state.in_mem().block.epoch;
+                let (voting_end_epoch, grace_epoch) =
+                    gov_api::convert_expedited_proposal(
+                        &mut shell.state,
+                        id,
+                        current_epoch,
+                    )?;
+                tracing::info!(
+                    "Expedited governance proposal {} didn't pass the \
+                     expedited tally and has been converted to a regular \
+                     proposal with voting end epoch {} and grace epoch {}.",
+                    id,
+                    voting_end_epoch,
+                    grace_epoch
+                );
+                events.emit(
+                    ProposalEvent::expedited_proposal_converted_event(
+                        id,
+                        voting_end_epoch,
+                        grace_epoch,
+                    )
+                    .into(),
+                );
+                continue;
+            }
+        }
+
+        // A proposal vetoed by enough of the voting power is rejected,
+        // whatever the outcome of its tally
+        let veto_threshold =
+            gov_api::get_proposal_veto_threshold(&shell.state)?;
+        let is_vetoed = proposal_result.apply_veto(veto_threshold);
+
         gov_api::write_proposal_result(&mut shell.state, id, proposal_result)?;
 
-        let transfer_address = match proposal_result.result {
+        match proposal_result.result {
             TallyResult::Passed => {
                 let proposal_event = match proposal_type {
                     ProposalType::Default(_) => {
@@ -164,11 +209,89 @@ where
                         ProposalEvent::pgf_payments_proposal_event(id, result)
                             .into()
                     }
+                    ProposalType::ParameterChange(changes) => {
+                        let result = match apply_parameter_changes(
+                            &mut shell.state,
+                            &changes,
+                        ) {
+                            Ok(()) => true,
+                            Err(err) => {
+                                tracing::error!(
+                                    "Governance proposal (parameter change) \
+                                     {} could not be applied: {}",
+                                    id,
+                                    err
+                                );
+                                false
+                            }
+                        };
+                        tracing::info!(
+                            "Governance proposal (parameter change) {} has \
+                             been executed ({}) and passed.",
+                            id,
+                            result
+                        );
+
+                        ProposalEvent::parameter_change_proposal_event(
+                            id, result,
+                        )
+                        .into()
+                    }
+                    ProposalType::Upgrade(upgrade) => {
+                        let current_height = shell.state.in_mem().block.height;
+                        let result = if upgrade.height > current_height {
+                            let scheduled = gov_api::schedule_upgrade(
+                                &mut shell.state,
+                                ScheduledUpgrade {
+                                    proposal_id: id,
+                                    name: upgrade.name.clone(),
+                                    height: upgrade.height,
+                                },
+                            )?;
+                            if !scheduled {
+                                // The earlier scheduled upgrade is kept
+                                let earlier = gov_api::get_scheduled_upgrade(
+                                    &shell.state,
+                                )?
+                                .expect("The scheduled upgrade should exist");
+                                tracing::warn!(
+                                    "Governance proposal (upgrade) {} could \
+                                     not be scheduled: the upgrade {} from \
+                                     proposal {} is already scheduled at \
+                                     height {}",
+                                    id,
+                                    earlier.name,
+                                    earlier.proposal_id,
+                                    earlier.height
+                                );
+                            }
+                            scheduled
+                        } else {
+                            tracing::error!(
+                                "Governance proposal (upgrade) {} could not \
+                                 be scheduled: the upgrade height {} is not \
+                                 above the current height {}",
+                                id,
+                                upgrade.height,
+                                current_height
+                            );
+                            false
+                        };
+                        tracing::info!(
+                            "Governance proposal (upgrade) {} has been \
+                             executed ({}) and passed. Upgrade {} at height \
+                             {}.",
+                            id,
+                            result,
+                            upgrade.name,
+                            upgrade.height
+                        );
+
+                        ProposalEvent::upgrade_proposal_event(id, result).into()
+                    }
                 };
                 events.emit(proposal_event);
                 proposals_result.passed.push(id);
-
-                gov_api::get_proposal_author(&shell.state, id)?
             }
             TallyResult::Rejected => {
                 if let ProposalType::PGFPayment(_) = proposal_type {
@@ -197,28 +320,36 @@ where
                     "Governance proposal {} has been executed and rejected.",
                     id
                 );
-
-                None
             }
-        };
+        }
 
+        // The locked funds are refunded to the author, unless enough of the
+        // voting power vetoed the proposal
         let native_token = shell.state.get_native_token()?;
-        if let Some(address) = transfer_address {
-            token::transfer(
+        if is_vetoed {
+            token::burn_tokens(
                 &mut shell.state,
                 &native_token,
                 &gov_address,
-                &address,
                 funds,
             )?;
+            tracing::info!(
+                "Governance proposal {} has been vetoed, its locked funds \
+                 have been burned.",
+                id
+            );
         } else {
-            token::burn_tokens(
+            token::transfer(
                 &mut shell.state,
                 &native_token,
                 &gov_address,
+                &proposal_author,
                 funds,
             )?;
         }
+        events.emit(
+            ProposalEvent::proposal_funds_event(id, funds, is_vetoed).into(),
+        );
     }
 
     Ok(proposals_result)
@@ -278,11 +409,46 @@ where
         }
     }
 
+    let representatives_vote: HashMap<Address, TallyVote> =
+        gov_api::get_proposal_representative_votes(storage, proposal_id)?
+            .into_iter()
+            .map(|(representative, vote)| (representative, vote.into()))
+            .collect();
+    let mut delegator_representative: HashMap<Address, Address> =
+        HashMap::default();
+
+    // Delegators that didn't vote directly are represented by their
+    // representative, if it voted
+    for (delegator, representative) in gov_api::get_representatives(storage)? {
+        if delegators_vote.contains_key(&delegator)
+            || !representatives_vote.contains_key(&representative)
+            || is_validator(storage, &delegator)?
+        {
+            continue;
+        }
+        for validator in find_delegations(storage, &delegator, &epoch)?.keys() {
+            let bond_id = BondId {
+                source: delegator.clone(),
+                validator: validator.clone(),
+            };
+            if let Ok(stake) = bond_amount(storage, &bond_id, epoch) {
+                delegator_voting_power
+                    .entry(delegator.clone())
+                    .or_default()
+                    .insert(validator.clone(), stake);
+                delegator_representative
+                    .insert(delegator.clone(), representative.clone());
+            }
+        }
+    }
+
     Ok(ProposalVotes {
         validators_vote,
         validator_voting_power,
         delegators_vote,
         delegator_voting_power,
+        representatives_vote,
+        delegator_representative,
     })
 }
 
@@ -382,10 +548,10 @@ where
         match funding {
             PGFAction::Continuous(action) => match action {
                 AddRemove::Add(target) => {
-                    pgf_storage::fundings_handle().insert(
+                    pgf::add_payment(
                         state,
-                        target.target().clone(),
                         StoragePgfFunding::new(target.clone(), proposal_id),
+                        None,
                     )?;
                     tracing::info!(
                         "Added/Updated ContinousPgf from proposal id {}: set \
@@ -396,8 +562,7 @@ where
                     );
                 }
                 AddRemove::Remove(target) => {
-                    pgf_storage::fundings_handle()
-                        .remove(state, &target.target())?;
+                    pgf::remove_payment(state, &target.target())?;
                     tracing::info!(
                         "Removed ContinousPgf from proposal id {}: set {} to \
                          {}.",
@@ -407,7 +572,30 @@ where
                     );
                 }
             },
+            PGFAction::ScheduledContinuous(funding) => {
+                pgf::add_payment(
+                    state,
+                    StoragePgfFunding::new(funding.target.clone(), proposal_id),
+                    Some(StoragePgfFundingSchedule::new(
+                        funding.schedule.clone(),
+                    )),
+                )?;
+                tracing::info!(
+                    "Added/Updated ContinousPgf from proposal id {}: set {} \
+                     to {}{}.",
+                    proposal_id,
+                    funding.amount().to_string_native(),
+                    funding.target(),
+                    funding.schedule
+                );
+            }
             PGFAction::Retro(target) => {
+                let current_epoch = state.in_mem().block.epoch;
+                let record = PgfPayment {
+                    kind: PgfPaymentKind::Retro(proposal_id),
+                    target: target.target(),
+                    amount: target.amount(),
+                };
                 let result = match &target {
                     PGFTarget::Internal(target) => token::transfer(
                         state,
@@ -415,28 +603,68 @@ where
                         &ADDRESS,
                         &target.target,
                         target.amount,
-                    ),
+                    )
+                    .map(|()| None),
                     PGFTarget::Ibc(target) => {
                         ibc::transfer_over_ibc(state, token, &ADDRESS, target)
+                            .map(|sequence| {
+                                Some(pgf::ibc_packet_id(
+                                    &target.port_id,
+                                    &target.channel_id,
+                                    sequence,
+                                ))
+                            })
                     }
                 };
                 match result {
-                    Ok(()) => tracing::info!(
-                        "Execute RetroPgf from proposal id {}: sent {} to {}.",
-                        proposal_id,
-                        target.amount().to_string_native(),
-                        target.target()
-                    ),
-                    Err(e) => tracing::warn!(
-                        "Error in RetroPgf transfer from proposal id {}, \
-                         amount {} to {}: {}",
-                        proposal_id,
-                        target.amount().to_string_native(),
-                        target.target(),
-                        e
-                    ),
+                    Ok(packet_id) => {
+                        tracing::info!(
+                            "Execute RetroPgf from proposal id {}: sent {} to \
+                             {}.",
+                            proposal_id,
+                            target.amount().to_string_native(),
+                            target.target()
+                        );
+                        // the payments over IBC can still be refunded
+                        if let Some(packet_id) = packet_id {
+                            pgf::record_ibc_payment(
+                                state,
+                                packet_id,
+                                record.clone(),
+                            )?;
+                        }
+                        pgf::record_payment(state, current_epoch, record)?;
+                    }
+                    Err(e) => {
+                        tracing::warn!(
+                            "Error in RetroPgf transfer from proposal id {}, \
+                             amount {} to {}: {}",
+                            proposal_id,
+                            target.amount().to_string_native(),
+                            target.target(),
+                            e
+                        );
+                        pgf::record_failed_payment(
+                            state,
+                            current_epoch,
+                            record,
+                        )?;
+                    }
                 }
             }
+            PGFAction::FundingRound(round) => {
+                let round_id = pgf_rounds::open_funding_round(
+                    state,
+                    proposal_id,
+                    round.clone(),
+                )?;
+                tracing::info!(
+                    "Opened PGF funding round {} from proposal id {}: {}.",
+                    round_id,
+                    proposal_id,
+                    round
+                );
+            }
         }
     }
 
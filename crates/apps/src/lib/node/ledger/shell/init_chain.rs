@@ -54,6 +54,8 @@ enum Panic {
     MissingTokenConfig(String),
     #[error("Failed to read wasm {0} with reason: {1}")]
     ReadingWasm(String, String),
+    #[error("Invalid vesting of the established account {0}: {1}")]
+    InvalidVesting(Address, String),
 }
 
 /// Warnings generated by problems in genesis files.
@@ -556,6 +558,7 @@ where
                         vp,
                         threshold,
                         public_keys,
+                        vesting,
                     },
             } in txs
             {
@@ -578,6 +581,44 @@ where
                     *threshold,
                 )
                 .unwrap();
+
+                if let Some(vesting) = vesting {
+                    let Some(vesting) = self
+                        .validate(
+                            genesis
+                                .tokens
+                                .token
+                                .get(&vesting.token)
+                                .ok_or_else(|| {
+                                    Panic::MissingTokenConfig(
+                                        vesting.token.to_string(),
+                                    )
+                                })
+                                .and_then(|conf| {
+                                    vesting
+                                        .to_vesting(
+                                            conf.address.clone(),
+                                            conf.config.denom,
+                                        )
+                                        .map_err(|err| {
+                                            Panic::InvalidVesting(
+                                                address.clone(),
+                                                err.to_string(),
+                                            )
+                                        })
+                                }),
+                        )
+                        .or_placeholder(None)?
+                    else {
+                        continue;
+                    };
+                    namada::account::write_vesting(
+                        &mut self.state,
+                        address,
+                        vesting,
+                    )
+                    .unwrap();
+                }
             }
         }
         self.proceed_with(())
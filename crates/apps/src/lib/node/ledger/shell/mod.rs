@@ -11,7 +11,6 @@ mod governance;
 mod init_chain;
 pub use init_chain::InitChainValidation;
 use namada_sdk::state::StateRead;
-use namada_sdk::tx::data::GasLimit;
 pub mod prepare_proposal;
 use namada::state::State;
 pub mod process_proposal;
@@ -20,6 +19,7 @@ mod stats;
 #[cfg(any(test, feature = "testing"))]
 #[allow(dead_code)]
 pub mod testing;
+pub mod upgrade;
 pub mod utils;
 mod vote_extensions;
 
@@ -40,18 +40,20 @@ use namada::core::storage::{BlockHeight, Key, TxIndex};
 use namada::core::time::DateTimeUtc;
 use namada::core::{address, hints};
 use namada::ethereum_bridge::protocol::validation::bridge_pool_roots::validate_bp_roots_vext;
+use namada::ethereum_bridge::protocol::validation::equivocation::{
+    find_bp_root_equivocation, find_valset_upd_equivocation,
+    validate_eth_bridge_equivocation,
+};
 use namada::ethereum_bridge::protocol::validation::ethereum_events::validate_eth_events_vext;
 use namada::ethereum_bridge::protocol::validation::validator_set_update::validate_valset_upd_vext;
+use namada::ethereum_bridge::protocol::validation::VoteExtensionError;
 use namada::ledger::events::log::EventLog;
 use namada::ledger::events::Event;
 use namada::ledger::gas::{Gas, TxGasMeter};
 use namada::ledger::pos::namada_proof_of_stake::types::{
     ConsensusValidator, ValidatorSetUpdate,
 };
-use namada::ledger::protocol::{
-    apply_wasm_tx, get_fee_unshielding_transaction,
-    get_transfer_hash_from_storage, ShellParams,
-};
+use namada::ledger::protocol::get_fee_unshielding_transaction;
 use namada::ledger::{parameters, protocol};
 use namada::parameters::validate_tx_bytes;
 use namada::proof_of_stake::storage::read_pos_params;
@@ -66,6 +68,7 @@ use namada::tx::data::{DecryptedTx, TxType, WrapperTx, WrapperTxErr};
 use namada::tx::{Section, Tx};
 use namada::vm::wasm::{TxCache, VpCache};
 use namada::vm::{WasmCacheAccess, WasmCacheRwAccess};
+use namada::vote_ext::equivocation::EthBridgeEquivocation;
 use namada::vote_ext::EthereumTxData;
 use namada_sdk::eth_bridge::{EthBridgeQueries, EthereumOracleConfig};
 use namada_sdk::tendermint::AppHash;
@@ -124,6 +127,8 @@ pub enum Error {
     Storage(#[from] namada::state::StorageError),
     #[error("Transaction replay attempt: {0}")]
     ReplayAttempt(String),
+    #[error("Error applying the upgrade {0}: {1}")]
+    Upgrade(String, String),
 }
 
 impl From<Error> for TxResult {
@@ -349,6 +354,9 @@ where
     /// limit the how many block heights in the past can the storage be
     /// queried for reading values.
     storage_read_past_height_limit: Option<u64>,
+    /// Taken from config `masp_compact_index`. When set, the compact MASP
+    /// data of every block is indexed in the DB.
+    masp_compact_index: bool,
     /// Proposal execution tracking
     pub proposal_data: BTreeSet<u64>,
     /// Log of events emitted by `FinalizeBlock` ABCI calls.
@@ -410,6 +418,7 @@ where
         let mode = config.shell.tendermint_mode;
         let storage_read_past_height_limit =
             config.shell.storage_read_past_height_limit;
+        let masp_compact_index = config.shell.masp_compact_index;
         if !Path::new(&base_dir).is_dir() {
             std::fs::create_dir(&base_dir)
                 .expect("Creating directory for Namada should not fail");
@@ -526,6 +535,7 @@ where
                 tx_wasm_compilation_cache as usize,
             ),
             storage_read_past_height_limit,
+            masp_compact_index,
             proposal_data: BTreeSet::new(),
             // TODO: config event log params
             event_log: EventLog::default(),
@@ -769,6 +779,53 @@ where
         }
     }
 
+    /// If the given invalid validator set update vote extension conflicts
+    /// with one already signed by the same validator, broadcast the
+    /// evidence of the equivocation.
+    fn broadcast_valset_upd_equivocation(
+        &self,
+        ext: &namada::vote_ext::validator_set_update::SignedVext,
+    ) {
+        self.broadcast_eth_bridge_equivocation(|| {
+            find_valset_upd_equivocation(&self.state, ext)
+        })
+    }
+
+    /// If the given invalid Bridge pool root vote extension signs over a
+    /// root that conflicts with the one at its block height, broadcast the
+    /// evidence of the equivocation.
+    fn broadcast_bp_root_equivocation(
+        &self,
+        ext: &namada::vote_ext::bridge_pool_roots::SignedVext,
+    ) {
+        self.broadcast_eth_bridge_equivocation(|| {
+            find_bp_root_equivocation(&self.state, ext)
+        })
+    }
+
+    /// Broadcast the evidence of an equivocation with an Ethereum bridge
+    /// hot key, if this node is a validator and some evidence was found.
+    fn broadcast_eth_bridge_equivocation<F>(&self, find_evidence: F)
+    where
+        F: FnOnce() -> Option<EthBridgeEquivocation>,
+    {
+        let Some(protocol_key) = self.mode.get_protocol_key() else {
+            return;
+        };
+        let Some(evidence) = find_evidence() else {
+            return;
+        };
+        tracing::info!(
+            validator = %evidence.validator(),
+            "Found an equivocation with an Ethereum bridge hot key, \
+             broadcasting its evidence"
+        );
+        let tx = EthereumTxData::EthBridgeEquivocation(evidence)
+            .sign(protocol_key, self.chain_id.clone())
+            .to_bytes();
+        self.mode.broadcast(tx);
+    }
+
     /// If a handle to an Ethereum oracle was provided to the [`Shell`], attempt
     /// to send it an updated configuration, using a configuration
     /// based on Ethereum bridge parameters in blockchain storage.
@@ -1009,6 +1066,9 @@ where
                             "{INVALID_MSG}: Invalid Bridge pool roots vote \
                              extension: {err}",
                         );
+                        if matches!(err, VoteExtensionError::InvalidBPRootSig) {
+                            self.broadcast_bp_root_equivocation(&ext);
+                        }
                     } else {
                         response.log = String::from(VALID_MSG);
                     }
@@ -1039,6 +1099,7 @@ where
                             "{INVALID_MSG}: Invalid validator set update vote \
                              extension: {err}",
                         );
+                        self.broadcast_valset_upd_equivocation(&ext);
                     } else {
                         response.log = String::from(VALID_MSG);
                         // validator set update votes should be decided
@@ -1046,6 +1107,26 @@ where
                         response.priority = i64::MAX;
                     }
                 }
+                ProtocolTxType::EthBridgeEquivocation => {
+                    let evidence = try_vote_extension!(
+                        "Ethereum bridge equivocation",
+                        response,
+                        ethereum_tx_data_variants::EthBridgeEquivocation::try_from(
+                            &tx
+                        ),
+                    );
+                    if let Err(err) =
+                        validate_eth_bridge_equivocation(&self.state, &evidence)
+                    {
+                        response.code = ResultCode::InvalidVoteExtension.into();
+                        response.log = format!(
+                            "{INVALID_MSG}: Invalid Ethereum bridge \
+                             equivocation evidence: {err}",
+                        );
+                    } else {
+                        response.log = String::from(VALID_MSG);
+                    }
+                }
                 _ => {
                     response.code = ResultCode::InvalidTx.into();
                     response.log = format!(
@@ -1110,11 +1191,10 @@ where
 
                 // Validate wrapper fees
                 if let Err(e) = mempool_fee_check(
+                    &tx,
                     &wrapper,
-                    get_fee_unshielding_transaction(&tx, &wrapper),
                     &mut self.state.with_temp_write_log(),
                     &mut self.vp_wasm_cache.clone(),
-                    &mut self.tx_wasm_cache.clone(),
                 ) {
                     response.code = ResultCode::FeeError.into();
                     response.log = format!("{INVALID_MSG}: {e}");
@@ -1245,11 +1325,10 @@ where
 
 // Perform the fee check in mempool
 fn mempool_fee_check<D, H, CA>(
+    tx: &Tx,
     wrapper: &WrapperTx,
-    masp_transaction: Option<Transaction>,
     temp_state: &mut TempWlState<D, H>,
     vp_wasm_cache: &mut VpCache<CA>,
-    tx_wasm_cache: &mut TxCache<CA>,
 ) -> Result<()>
 where
     D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
@@ -1267,25 +1346,23 @@ where
     ))))?;
 
     wrapper_fee_check(
+        tx,
         wrapper,
-        masp_transaction,
         minimum_gas_price,
         temp_state,
         vp_wasm_cache,
-        tx_wasm_cache,
     )?;
     protocol::check_fees(temp_state, wrapper).map_err(Error::TxApply)
 }
 
 /// Check the validity of the fee payment, including the minimum amounts
-/// required and the optional unshield
+/// required and the optional masp fee payment
 pub fn wrapper_fee_check<D, H, CA>(
+    tx: &Tx,
     wrapper: &WrapperTx,
-    masp_transaction: Option<Transaction>,
     minimum_gas_price: token::Amount,
     temp_state: &mut TempWlState<D, H>,
     vp_wasm_cache: &mut VpCache<CA>,
-    tx_wasm_cache: &mut TxCache<CA>,
 ) -> Result<()>
 where
     D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
@@ -1317,13 +1394,13 @@ where
         }
     }
 
-    if let Some(transaction) = masp_transaction {
-        fee_unshielding_validation(
+    if let Some(transaction) = get_fee_unshielding_transaction(tx, wrapper) {
+        masp_fee_payment_validation(
+            tx,
             wrapper,
             transaction,
             temp_state,
             vp_wasm_cache,
-            tx_wasm_cache,
         )?;
     }
 
@@ -1331,12 +1408,12 @@ where
 }
 
 // Verifies the correctness of the masp transaction for fee payment
-fn fee_unshielding_validation<D, H, CA>(
+fn masp_fee_payment_validation<D, H, CA>(
+    tx: &Tx,
     wrapper: &WrapperTx,
     masp_transaction: Transaction,
     temp_state: &mut TempWlState<D, H>,
     vp_wasm_cache: &mut VpCache<CA>,
-    tx_wasm_cache: &mut TxCache<CA>,
 ) -> Result<()>
 where
     D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
@@ -1347,9 +1424,6 @@ where
     // checking the aggregated signature of the wrapper, no need for
     // further validation
 
-    // Validate data and generate unshielding tx
-    let transfer_code_hash = get_transfer_hash_from_storage(temp_state);
-
     let descriptions_limit = temp_state
         .read(
             &parameters::storage::get_fee_unshielding_descriptions_limit_key(),
@@ -1357,56 +1431,34 @@ where
         .expect("Error reading the storage")
         .expect("Missing fee unshielding descriptions limit param in storage");
 
-    let unshield = wrapper
-        .check_and_generate_fee_unshielding(
-            transfer_code_hash,
-            Some(namada_sdk::tx::TX_TRANSFER_WASM.to_string()),
-            descriptions_limit,
-            masp_transaction,
-        )
-        .map_err(|e| Error::TxApply(protocol::Error::FeeUnshieldingError(e)))?;
-
-    let fee_unshielding_gas_limit: GasLimit = temp_state
-        .read(&parameters::storage::get_fee_unshielding_gas_limit_key())
-        .expect("Error reading from storage")
-        .expect("Missing fee unshielding gas limit in storage");
+    wrapper
+        .check_masp_fee_payment(descriptions_limit, &masp_transaction)
+        .map_err(|e| Error::TxApply(protocol::Error::MaspFeePaymentError(e)))?;
 
     // Runtime check
     // NOTE: A clean tx write log must be provided to this call for a
     // correct vp validation. Block write log, instead, should contain
     // any prior changes (if any). This is to simulate the
-    // unshielding tx (to prevent the already written keys
+    // fee payment (to prevent the already written keys
     // from being passed/triggering VPs) but we cannot
     // commit the tx write log yet cause the tx could still
-    // be invalid.
+    // be invalid. The fee is credited to the fee payer to let the
+    // subsequent checks on its balance go through.
     temp_state.write_log_mut().precommit_tx();
 
-    let result = apply_wasm_tx(
-        unshield,
-        &TxIndex::default(),
-        ShellParams::new(
-            &RefCell::new(TxGasMeter::new(fee_unshielding_gas_limit)),
-            temp_state,
-            vp_wasm_cache,
-            tx_wasm_cache,
-        ),
+    protocol::pay_masp_fee(
+        tx,
+        wrapper,
+        &masp_transaction,
+        &wrapper.fee_payer(),
+        temp_state,
+        vp_wasm_cache,
     )
     .map_err(|e| {
-        Error::TxApply(protocol::Error::FeeUnshieldingError(
-            WrapperTxErr::InvalidUnshield(format!("Wasm run failed: {}", e)),
+        Error::TxApply(protocol::Error::MaspFeePaymentError(
+            WrapperTxErr::InvalidMaspFeePayment(e.to_string()),
         ))
-    })?;
-
-    if result.is_accepted() {
-        Ok(())
-    } else {
-        Err(Error::TxApply(protocol::Error::FeeUnshieldingError(
-            WrapperTxErr::InvalidUnshield(format!(
-                "Some VPs rejected fee unshielding: {:#?}",
-                result.vps_result.rejected_vps
-            )),
-        )))
-    }
+    })
 }
 
 /// for the shell
@@ -2308,6 +2360,108 @@ mod shell_tests {
         }
     }
 
+    /// Test that signing over two conflicting Bridge pool roots at the
+    /// same block height and nonce gets the evidence of the equivocation
+    /// broadcast by `CheckTx`, and the validator slashed once the evidence
+    /// is finalized.
+    #[test]
+    fn test_mempool_broadcasts_bp_root_equivocation() {
+        use namada::core::ethereum_events::Uint;
+        use namada::core::keccak::{keccak_hash, KeccakHash};
+        use namada::ethereum_bridge::storage::bridge_pool::get_key_from_hash;
+        use namada::proof_of_stake::storage::enqueued_slashes_handle;
+        use namada::proof_of_stake::types::SlashType;
+        use namada::state::StorageWrite;
+
+        use crate::node::ledger::shims::abcipp_shim_types::shim::request::{
+            FinalizeBlock, ProcessedTx,
+        };
+
+        let (mut shell, mut broadcaster_rx, _, _) =
+            test_utils::setup_at_height(1u64);
+        let address = shell.mode.get_validator_address().unwrap().clone();
+
+        // commit two different Bridge pool roots with the same nonce
+        shell.state.in_mem_mut().block.height = 2.into();
+        let key = get_key_from_hash(&KeccakHash([1; 32]));
+        shell.state.write(&key, BlockHeight(2)).unwrap();
+        shell.commit();
+        shell.state.in_mem_mut().block.height = 3.into();
+        shell.state.delete(&key).unwrap();
+        let key = get_key_from_hash(&KeccakHash([2; 32]));
+        shell.state.write(&key, BlockHeight(3)).unwrap();
+        shell.commit();
+
+        let sign_bp_root = |root: [u8; 32]| {
+            let to_sign =
+                keccak_hash([root, Uint::from(0).to_bytes()].concat());
+            let sig = Signed::<_, SignableEthMessage>::new(
+                shell.mode.get_eth_bridge_keypair().unwrap(),
+                to_sign,
+            )
+            .sig;
+            let protocol_key = shell.mode.get_protocol_key().unwrap();
+            EthereumTxData::BridgePoolVext(
+                bridge_pool_roots::Vext {
+                    block_height: 3.into(),
+                    validator_addr: address.clone(),
+                    sig,
+                }
+                .sign(protocol_key),
+            )
+            .sign(protocol_key, shell.chain_id.clone())
+            .to_bytes()
+        };
+
+        // the signature over the root at the height of the vext is valid
+        let rsp =
+            shell.mempool_validate(&sign_bp_root([2; 32]), Default::default());
+        assert_eq!(rsp.code, ResultCode::Ok.into());
+        assert!(broadcaster_rx.try_recv().is_err());
+
+        // the conflicting signature over the root of another height is not
+        let rsp =
+            shell.mempool_validate(&sign_bp_root([1; 32]), Default::default());
+        assert_eq!(rsp.code, ResultCode::InvalidVoteExtension.into());
+        let evidence_tx = broadcaster_rx
+            .try_recv()
+            .expect("The evidence of the equivocation should be broadcast");
+        let evidence =
+            ethereum_tx_data_variants::EthBridgeEquivocation::try_from(
+                &Tx::try_from(&evidence_tx[..]).unwrap(),
+            )
+            .unwrap();
+        assert_matches::assert_matches!(
+            evidence,
+            EthBridgeEquivocation::BridgePoolRoot { root, nonce, .. }
+                if root == KeccakHash([1; 32]) && nonce == Uint::from(0)
+        );
+
+        // finalizing the evidence slashes the validator
+        shell.finalize_and_commit(Some(FinalizeBlock {
+            txs: vec![ProcessedTx {
+                tx: evidence_tx.into(),
+                result: TxResult {
+                    code: ResultCode::Ok.into(),
+                    info: Default::default(),
+                },
+            }],
+            ..Default::default()
+        }));
+        let params = read_pos_params(&shell.state).unwrap();
+        let processing_epoch =
+            Epoch::default() + params.slash_processing_epoch_offset();
+        let slash = enqueued_slashes_handle()
+            .get_data_handler()
+            .at(&processing_epoch)
+            .at(&address)
+            .get(&shell.state, 0)
+            .unwrap()
+            .expect("The validator should have been slashed");
+        assert_eq!(slash.r#type, SlashType::EthBridgeEquivocation);
+        assert_eq!(slash.block_height, 3);
+    }
+
     /// Test if Ethereum events validation behaves as expected,
     /// considering honest validators.
     #[test]
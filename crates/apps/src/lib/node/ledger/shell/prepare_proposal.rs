@@ -1,6 +1,5 @@
 //! Implementation of the [`RequestPrepareProposal`] ABCI++ method for the Shell
 
-use masp_primitives::transaction::Transaction;
 use namada::core::address::Address;
 use namada::core::hints;
 use namada::core::key::tm_raw_hash_to_string;
@@ -11,7 +10,7 @@ use namada::proof_of_stake::storage::find_validator_by_raw_hash;
 use namada::state::{DBIter, StorageHasher, TempWlState, DB};
 use namada::tx::data::{DecryptedTx, TxType, WrapperTx};
 use namada::tx::Tx;
-use namada::vm::wasm::{TxCache, VpCache};
+use namada::vm::wasm::VpCache;
 use namada::vm::WasmCacheAccess;
 
 use super::super::*;
@@ -138,12 +137,11 @@ where
         });
         let mut temp_state = self.state.with_temp_write_log();
         let mut vp_wasm_cache = self.vp_wasm_cache.clone();
-        let mut tx_wasm_cache = self.tx_wasm_cache.clone();
 
         let txs = txs
             .iter()
             .filter_map(|tx_bytes| {
-                match validate_wrapper_bytes(tx_bytes, block_time, block_proposer, proposer_local_config, &mut temp_state, &mut vp_wasm_cache, &mut tx_wasm_cache, ) {
+                match validate_wrapper_bytes(tx_bytes, block_time, block_proposer, proposer_local_config, &mut temp_state, &mut vp_wasm_cache, ) {
                     Ok(gas) => {
                         temp_state.write_log_mut().commit_tx();
                         Some((tx_bytes.to_owned(), gas))
@@ -320,7 +318,6 @@ fn validate_wrapper_bytes<D, H, CA>(
     proposer_local_config: Option<&ValidatorLocalConfig>,
     temp_state: &mut TempWlState<D, H>,
     vp_wasm_cache: &mut VpCache<CA>,
-    tx_wasm_cache: &mut TxCache<CA>,
 ) -> Result<u64, ()>
 where
     D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
@@ -350,13 +347,12 @@ where
 
         // Check fees and extract the gas limit of this transaction
         match prepare_proposal_fee_check(
+            &tx,
             &wrapper,
-            protocol::get_fee_unshielding_transaction(&tx, &wrapper),
             block_proposer,
             proposer_local_config,
             temp_state,
             vp_wasm_cache,
-            tx_wasm_cache,
         ) {
             Ok(()) => Ok(u64::from(wrapper.gas_limit)),
             Err(_) => Err(()),
@@ -367,13 +363,12 @@ where
 }
 
 fn prepare_proposal_fee_check<D, H, CA>(
+    tx: &Tx,
     wrapper: &WrapperTx,
-    masp_transaction: Option<Transaction>,
     proposer: &Address,
     proposer_local_config: Option<&ValidatorLocalConfig>,
     temp_state: &mut TempWlState<D, H>,
     vp_wasm_cache: &mut VpCache<CA>,
-    tx_wasm_cache: &mut TxCache<CA>,
 ) -> Result<(), Error>
 where
     D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
@@ -406,12 +401,11 @@ where
     };
 
     super::wrapper_fee_check(
+        tx,
         wrapper,
-        masp_transaction,
         minimum_gas_price,
         temp_state,
         vp_wasm_cache,
-        tx_wasm_cache,
     )?;
 
     protocol::transfer_fee(temp_state, proposer, wrapper)
@@ -162,7 +162,6 @@ where
         let mut temp_state = self.state.with_temp_write_log();
         let mut metadata = ValidationMeta::from(self.state.read_only());
         let mut vp_wasm_cache = self.vp_wasm_cache.clone();
-        let mut tx_wasm_cache = self.tx_wasm_cache.clone();
 
         let tx_results: Vec<_> = txs
             .iter()
@@ -174,7 +173,6 @@ where
                     &mut temp_state,
                     block_time,
                     &mut vp_wasm_cache,
-                    &mut tx_wasm_cache,
                     block_proposer,
                 );
                 let error_code = ResultCode::from_u32(result.code).unwrap();
@@ -229,7 +227,6 @@ where
         temp_state: &mut TempWlState<D, H>,
         block_time: DateTimeUtc,
         vp_wasm_cache: &mut VpCache<CA>,
-        tx_wasm_cache: &mut TxCache<CA>,
         block_proposer: &Address,
     ) -> TxResult
     where
@@ -425,6 +422,33 @@ where
                             }
                         })
                     }
+                    ProtocolTxType::EthBridgeEquivocation => {
+                        ethereum_tx_data_variants::EthBridgeEquivocation::try_from(
+                            &tx,
+                        )
+                        .map_err(|err| err.to_string())
+                        .and_then(|evidence| {
+                            validate_eth_bridge_equivocation(
+                                &self.state,
+                                &evidence,
+                            )
+                            .map(|_| TxResult {
+                                code: ResultCode::Ok.into(),
+                                info: "Process Proposal accepted this \
+                                       transaction"
+                                    .into(),
+                            })
+                            .map_err(|err| err.to_string())
+                        })
+                        .unwrap_or_else(|err| TxResult {
+                            code: ResultCode::InvalidVoteExtension.into(),
+                            info: format!(
+                                "Process proposal rejected this proposal \
+                                 because one of the included Ethereum bridge \
+                                 equivocation evidences was invalid: {err}"
+                            ),
+                        })
+                    }
                     ProtocolTxType::EthereumEvents
                     | ProtocolTxType::BridgePool
                     | ProtocolTxType::ValidatorSetUpdate => TxResult {
@@ -577,12 +601,11 @@ where
 
                 // Check that the fee payer has sufficient balance.
                 match process_proposal_fee_check(
+                    &tx,
                     &wrapper,
-                    get_fee_unshielding_transaction(&tx, &wrapper),
                     block_proposer,
                     temp_state,
                     vp_wasm_cache,
-                    tx_wasm_cache,
                 ) {
                     Ok(()) => TxResult {
                         code: ResultCode::Ok.into(),
@@ -615,12 +638,11 @@ where
 }
 
 fn process_proposal_fee_check<D, H, CA>(
+    tx: &Tx,
     wrapper: &WrapperTx,
-    masp_transaction: Option<Transaction>,
     proposer: &Address,
     temp_state: &mut TempWlState<D, H>,
     vp_wasm_cache: &mut VpCache<CA>,
-    tx_wasm_cache: &mut TxCache<CA>,
 ) -> Result<()>
 where
     D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
@@ -638,12 +660,11 @@ where
     ))))?;
 
     wrapper_fee_check(
+        tx,
         wrapper,
-        masp_transaction,
         minimum_gas_price,
         temp_state,
         vp_wasm_cache,
-        tx_wasm_cache,
     )?;
 
     protocol::transfer_fee(temp_state, proposer, wrapper)
@@ -0,0 +1,224 @@
+//! Chain upgrades scheduled by governance.
+//!
+//! A passed upgrade proposal records the name and the height of the upgrade
+//! in storage. When the chain reaches that height, the node only proceeds if
+//! its binary registers a handler for the upgrade, in which case the state
+//! migration of the handler is applied before the block is executed.
+//! Otherwise, the ABCI service halts the node once the last block before the
+//! upgrade has been committed, so that it can be restarted with an upgraded
+//! binary.
+
+use namada::core::storage::DbColFam;
+use namada::governance::storage as gov_api;
+use namada::governance::storage::proposal::ScheduledUpgrade;
+use namada::state::StorageWrite;
+use namada_sdk::migrations::{DbChanges, DbUpdateType};
+
+use super::*;
+
+/// The state migration of a chain upgrade
+pub struct UpgradeHandler {
+    /// The name of the upgrade, as given in the upgrade proposal
+    pub name: &'static str,
+    /// The changes to apply to the state at the upgrade height
+    pub migration: fn() -> DbChanges,
+}
+
+/// The chain upgrades implemented by this binary. A binary implementing an
+/// upgrade scheduled by governance must register its handler here.
+pub const UPGRADE_HANDLERS: &[UpgradeHandler] = &[UpgradeHandler {
+    name: NO_OP_UPGRADE,
+    migration: no_op_migration,
+}];
+
+/// The name of an upgrade which leaves the state unchanged. It can be used to
+/// check that the validators are able to coordinate a chain upgrade.
+pub const NO_OP_UPGRADE: &str = "no-op";
+
+/// The state migration of the [`NO_OP_UPGRADE`]
+fn no_op_migration() -> DbChanges {
+    DbChanges { changes: vec![] }
+}
+
+/// Find the handler of the upgrade with the given name
+fn find_upgrade_handler(name: &str) -> Option<&'static UpgradeHandler> {
+    UPGRADE_HANDLERS.iter().find(|handler| handler.name == name)
+}
+
+impl<D, H> Shell<D, H>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    /// Find the chain upgrade scheduled at the height of the block about to be
+    /// finalized, if this binary implements it. The ABCI service halts the
+    /// node before reaching the height of an upgrade that isn't implemented,
+    /// see [`Shell::unimplemented_upgrade`].
+    pub(super) fn check_scheduled_upgrade(
+        &self,
+    ) -> Result<Option<(ScheduledUpgrade, &'static UpgradeHandler)>> {
+        let Some(upgrade) = gov_api::get_scheduled_upgrade(&self.state)? else {
+            return Ok(None);
+        };
+        let height = self.state.in_mem().get_last_block_height() + 1;
+        if upgrade.height != height {
+            return Ok(None);
+        }
+        Ok(find_upgrade_handler(&upgrade.name)
+            .map(|handler| (upgrade, handler)))
+    }
+
+    /// Get the chain upgrade scheduled by governance at a height that hasn't
+    /// been reached yet, if this binary doesn't implement it. The node must
+    /// halt at the height of this upgrade.
+    pub fn unimplemented_upgrade(&self) -> Option<ScheduledUpgrade> {
+        let upgrade = gov_api::get_scheduled_upgrade(&self.state)
+            .expect("Reading the scheduled upgrade shouldn't fail")?;
+        let last_height = self.state.in_mem().get_last_block_height();
+        (upgrade.height > last_height
+            && find_upgrade_handler(&upgrade.name).is_none())
+        .then_some(upgrade)
+    }
+
+    /// Apply the state migration of a scheduled chain upgrade and clear it
+    /// from storage
+    pub(super) fn apply_upgrade(
+        &mut self,
+        upgrade: ScheduledUpgrade,
+        handler: &UpgradeHandler,
+    ) -> Result<()> {
+        tracing::info!(
+            "Applying the upgrade {} scheduled by governance proposal {} at \
+             height {}.",
+            upgrade.name,
+            upgrade.proposal_id,
+            upgrade.height
+        );
+        let changes = (handler.migration)();
+        apply_migration(&mut self.state, &changes)
+            .map_err(|err| Error::Upgrade(upgrade.name.clone(), err))?;
+        gov_api::clear_scheduled_upgrade(&mut self.state)?;
+        tracing::info!("The upgrade {} has been applied.", upgrade.name);
+        Ok(())
+    }
+}
+
+/// Apply the changes of a state migration through the write log, so that
+/// they are committed together with the upgrade block
+#[cfg(feature = "migrations")]
+fn apply_migration<D, H>(
+    state: &mut FullAccessState<D, H>,
+    changes: &DbChanges,
+) -> std::result::Result<(), String>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    validate_column_families(changes)?;
+    let mut visitor = StateUpdateVisitor { state };
+    for change in &changes.changes {
+        let status = change.update(&mut visitor).map_err(|err| {
+            format!(
+                "Attempt to write to key/pattern <{}> failed: {}",
+                change.pattern(),
+                err
+            )
+        })?;
+        tracing::info!("{}", status);
+    }
+    Ok(())
+}
+
+/// Without the `migrations` feature, only upgrades that don't change the
+/// state can be applied
+#[cfg(not(feature = "migrations"))]
+fn apply_migration<D, H>(
+    _state: &mut FullAccessState<D, H>,
+    changes: &DbChanges,
+) -> std::result::Result<(), String>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    validate_column_families(changes)?;
+    if changes.changes.is_empty() {
+        Ok(())
+    } else {
+        Err("State migrations require the \"migrations\" feature".to_string())
+    }
+}
+
+/// Only the subspace can be changed by a state migration applied at an
+/// upgrade height, as the other column families are not written through the
+/// write log
+fn validate_column_families(
+    changes: &DbChanges,
+) -> std::result::Result<(), String> {
+    for change in &changes.changes {
+        let cf = match change {
+            DbUpdateType::Add { cf, .. }
+            | DbUpdateType::Delete(_, cf)
+            | DbUpdateType::RepeatAdd { cf, .. }
+            | DbUpdateType::RepeatDelete(_, cf) => cf,
+        };
+        if *cf != DbColFam::SUBSPACE {
+            return Err(format!(
+                "State migrations can only change the subspace, found a \
+                 change to the {:?} column family",
+                cf
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// A visitor applying the changes of a state migration to the write log
+#[cfg(feature = "migrations")]
+struct StateUpdateVisitor<'a, D, H>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    state: &'a mut FullAccessState<D, H>,
+}
+
+#[cfg(feature = "migrations")]
+impl<'a, D, H> namada_sdk::migrations::DBUpdateVisitor
+    for StateUpdateVisitor<'a, D, H>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    fn read(&self, key: &Key, _cf: &DbColFam) -> Option<Vec<u8>> {
+        self.state
+            .read_bytes(key)
+            .expect("Reading from storage shouldn't fail")
+    }
+
+    fn write(&mut self, key: &Key, _cf: &DbColFam, value: impl AsRef<[u8]>) {
+        self.state
+            .write_bytes(key, value)
+            .expect("Writing to storage shouldn't fail")
+    }
+
+    fn delete(&mut self, key: &Key, _cf: &DbColFam) {
+        self.state
+            .delete(key)
+            .expect("Deleting from storage shouldn't fail")
+    }
+
+    fn get_pattern(&self, pattern: regex::Regex) -> Vec<(String, Vec<u8>)> {
+        // Match the keys in the DB, but read their values through the write
+        // log to account for the changes already applied by the migration
+        self.state
+            .db()
+            .iter_pattern(None, pattern)
+            .filter_map(|(key, _, _)| {
+                let storage_key =
+                    Key::parse(&key).expect("Storage keys should be valid");
+                self.read(&storage_key, &DbColFam::SUBSPACE)
+                    .map(|value| (key, value))
+            })
+            .collect()
+    }
+}
@@ -160,6 +160,13 @@ where
                         .valset_upd_seen(ext.data.signing_epoch.next()))
                     .then(|| tx_bytes.clone())
                 }
+                EthereumTxData::EthBridgeEquivocation(evidence) => {
+                    // only propose evidence of infractions which
+                    // have not been slashed yet
+                    validate_eth_bridge_equivocation(&self.state, &evidence)
+                        .is_ok()
+                        .then(|| tx_bytes.clone())
+                }
                 _ => None,
             }
         })
@@ -39,6 +39,11 @@ pub struct AbcippShim {
         Req,
         tokio::sync::oneshot::Sender<Result<Resp, BoxError>>,
     )>,
+    /// A channel for notifying the [`AbciService`] of the height at which
+    /// to halt for a chain upgrade not implemented by this binary
+    upgrade_halt_send: std::sync::mpsc::Sender<BlockHeight>,
+    /// The last upgrade halt height sent to the [`AbciService`]
+    upgrade_halt_height: Option<BlockHeight>,
 }
 
 impl AbcippShim {
@@ -58,33 +63,66 @@ impl AbcippShim {
         // the number of requests that can come in
 
         let (shell_send, shell_recv) = std::sync::mpsc::channel();
+        let (upgrade_halt_send, upgrade_halt_recv) = std::sync::mpsc::channel();
         let (server_shutdown, _) = broadcast::channel::<()>(1);
         let action_at_height = config.shell.action_at_height.clone();
+        let mut shim = Self {
+            service: Shell::new(
+                config,
+                wasm_dir,
+                broadcast_sender,
+                eth_oracle,
+                Some(db_cache),
+                vp_wasm_compilation_cache,
+                tx_wasm_compilation_cache,
+            ),
+            begin_block_request: None,
+            delivered_txs: vec![],
+            shell_recv,
+            upgrade_halt_send,
+            upgrade_halt_height: None,
+        };
+        // The node may be restarted before the height of an upgrade that
+        // was already scheduled
+        shim.update_upgrade_halt_height();
         (
-            Self {
-                service: Shell::new(
-                    config,
-                    wasm_dir,
-                    broadcast_sender,
-                    eth_oracle,
-                    Some(db_cache),
-                    vp_wasm_compilation_cache,
-                    tx_wasm_compilation_cache,
-                ),
-                begin_block_request: None,
-                delivered_txs: vec![],
-                shell_recv,
-            },
+            shim,
             AbciService {
                 shell_send,
                 shutdown: server_shutdown.clone(),
                 action_at_height,
+                upgrade_halt_recv,
                 suspended: false,
             },
             server_shutdown,
         )
     }
 
+    /// If governance scheduled a chain upgrade that this binary doesn't
+    /// implement, notify the [`AbciService`] to halt the node at the height
+    /// of the upgrade, i.e. after the last block before the upgrade has been
+    /// committed.
+    fn update_upgrade_halt_height(&mut self) {
+        let Some(upgrade) = self.service.unimplemented_upgrade() else {
+            return;
+        };
+        if self.upgrade_halt_height == Some(upgrade.height) {
+            return;
+        }
+        tracing::warn!(
+            "The upgrade {} scheduled by governance proposal {} is not \
+             implemented by this binary. The node will halt at block height \
+             {}, restart it with a binary implementing this upgrade.",
+            upgrade.name,
+            upgrade.proposal_id,
+            upgrade.height
+        );
+        self.upgrade_halt_height = Some(upgrade.height);
+        if self.upgrade_halt_send.send(upgrade.height).is_err() {
+            tracing::info!("The ABCI service has shut down");
+        }
+    }
+
     /// Get the hash of the txs in the block
     pub fn get_hash(&self) -> Hash {
         let bytes: Vec<u8> =
@@ -96,6 +134,7 @@ impl AbcippShim {
     /// [`AbciService`].
     pub fn run(mut self) {
         while let Ok((req, resp_sender)) = self.shell_recv.recv() {
+            let is_commit = matches!(req, Req::Commit);
             let resp = match req {
                 Req::ProcessProposal(proposal) => self
                     .service
@@ -183,6 +222,12 @@ impl AbcippShim {
                 },
             };
 
+            // Check for a newly scheduled upgrade before responding, so that
+            // the ABCI service halts before the next block is processed
+            if is_commit {
+                self.update_upgrade_halt_height();
+            }
+
             let resp = resp.map_err(|e| e.into());
             if resp_sender.send(resp).is_err() {
                 tracing::info!("ABCI response channel is closed")
@@ -218,6 +263,9 @@ pub struct AbciService {
     shutdown: broadcast::Sender<()>,
     /// An action to be taken at a specified block height.
     action_at_height: Option<ActionAtHeight>,
+    /// Receives the height at which to halt for a chain upgrade not
+    /// implemented by this binary
+    upgrade_halt_recv: std::sync::mpsc::Receiver<BlockHeight>,
 }
 
 impl AbciService {
@@ -333,6 +381,23 @@ impl AbciService {
         .boxed()
     }
 
+    /// Halt at the height of a chain upgrade not implemented by this binary,
+    /// unless an action was configured at an earlier height
+    fn update_upgrade_halt(&mut self) {
+        for upgrade_height in self.upgrade_halt_recv.try_iter() {
+            let earlier_action = matches!(
+                self.action_at_height,
+                Some(ActionAtHeight { height, .. }) if height < upgrade_height
+            );
+            if !earlier_action {
+                self.action_at_height = Some(ActionAtHeight {
+                    height: upgrade_height,
+                    action: Action::Halt,
+                });
+            }
+        }
+    }
+
     /// Given the type of request, determine if we need to check
     /// to possibly take an action.
     fn get_action(&self, req: &Req) -> Option<CheckAction> {
@@ -377,6 +442,7 @@ impl Service<Req> for AbciService {
     }
 
     fn call(&mut self, req: Req) -> Self::Future {
+        self.update_upgrade_halt();
         let action = self.get_action(&req);
         if let Some(action) = action {
             let (suspended, fut) = Self::maybe_take_action(
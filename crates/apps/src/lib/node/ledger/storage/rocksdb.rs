@@ -27,6 +27,8 @@
 //!   - `old/{dyn}`: value from predecessor block height
 //! - `block`: block state
 //!   - `results/{h}`: block results at height `h`
+//!   - `masp_compact/{h}`: compact MASP data of the block at height `h`, if
+//!     indexed
 //!   - `h`: for each block at height `h`:
 //!     - `tree`: merkle tree
 //!       - `root`: root hash
@@ -50,6 +52,7 @@ use borsh::BorshDeserialize;
 use borsh_ext::BorshSerializeExt;
 use data_encoding::HEXLOWER;
 use itertools::Either;
+use namada::core::masp::CompactBlock;
 use namada::core::storage::{
     BlockHeight, BlockResults, Epoch, EthEventsQueue, Header, Key, KeySeg,
     KEY_SEGMENT_SEPARATOR,
@@ -550,6 +553,8 @@ impl RocksDB {
         let block_cf = self.get_column_family(BLOCK_CF)?;
         tracing::info!("Removing last block results");
         batch.delete_cf(block_cf, format!("results/{}", last_block.height));
+        batch
+            .delete_cf(block_cf, format!("masp_compact/{}", last_block.height));
 
         // Restore the state of replay protection to the last block
         let reprot_cf = self.get_column_family(REPLAY_PROTECTION_CF)?;
@@ -1148,6 +1153,21 @@ impl DB for RocksDB {
         }
     }
 
+    fn read_masp_compact_block(
+        &self,
+        height: BlockHeight,
+    ) -> Result<Option<CompactBlock>> {
+        let block_cf = self.get_column_family(BLOCK_CF)?;
+        let value = self
+            .0
+            .get_cf(block_cf, format!("masp_compact/{}", height.raw()))
+            .map_err(|e| Error::DBError(e.into_string()))?;
+        match value {
+            Some(bytes) => Ok(Some(decode(bytes).map_err(Error::CodingError)?)),
+            None => Ok(None),
+        }
+    }
+
     fn read_merkle_tree_stores(
         &self,
         epoch: Epoch,
@@ -1399,6 +1419,17 @@ impl DB for RocksDB {
         Ok(prev_len)
     }
 
+    fn write_masp_compact_block(&mut self, block: &CompactBlock) -> Result<()> {
+        let block_cf = self.get_column_family(BLOCK_CF)?;
+        self.0
+            .put_cf(
+                block_cf,
+                format!("masp_compact/{}", block.height.raw()),
+                encode(block),
+            )
+            .map_err(|e| Error::DBError(e.into_string()))
+    }
+
     fn batch() -> Self::WriteBatch {
         RocksDBWriteBatch::default()
     }
@@ -2185,6 +2216,24 @@ mod test {
         assert_eq!(latest_value, None);
     }
 
+    #[test]
+    fn test_masp_compact_block() {
+        let dir = tempdir().unwrap();
+        let mut db = open(dir.path(), None).unwrap();
+
+        let block = CompactBlock {
+            height: BlockHeight(10),
+            epoch: Epoch(1),
+            txs: vec![],
+        };
+        db.write_masp_compact_block(&block).unwrap();
+
+        let read = db.read_masp_compact_block(BlockHeight(10)).unwrap();
+        assert_eq!(read, Some(block));
+        let missing = db.read_masp_compact_block(BlockHeight(11)).unwrap();
+        assert_eq!(missing, None);
+    }
+
     #[test]
     fn test_prefix_iter() {
         let dir = tempdir().unwrap();
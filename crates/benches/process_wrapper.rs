@@ -41,7 +41,7 @@ fn process_tx(c: &mut Criterion) {
             defaults::albert_keypair().ref_to(),
             0.into(),
             1_000_000.into(),
-            // NOTE: The unshield operation has to be gas-free so don't include
+            // NOTE: The masp fee payment has to be gas-free so don't include
             // it here
             None,
         ),
@@ -64,7 +64,6 @@ fn process_tx(c: &mut Criterion) {
                     shell.state.with_temp_write_log(),
                     ValidationMeta::from(shell.state.read_only()),
                     shell.vp_wasm_cache.clone(),
-                    shell.tx_wasm_cache.clone(),
                     defaults::daewon_address(),
                 )
             },
@@ -73,7 +72,6 @@ fn process_tx(c: &mut Criterion) {
                 mut temp_state,
                 mut validation_meta,
                 mut vp_wasm_cache,
-                mut tx_wasm_cache,
                 block_proposer,
             )| {
                 assert_eq!(
@@ -86,7 +84,6 @@ fn process_tx(c: &mut Criterion) {
                             &mut temp_state,
                             datetime,
                             &mut vp_wasm_cache,
-                            &mut tx_wasm_cache,
                             &block_proposer
                         )
                         .code,
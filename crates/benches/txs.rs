@@ -425,6 +425,7 @@ fn init_account(c: &mut Criterion) {
         public_keys: vec![new_account.to_public()],
         vp_code_hash: extra_hash,
         threshold: 1,
+        vesting: None,
     };
     let tx = shell.generate_tx(
         TX_INIT_ACCOUNT_WASM,
@@ -466,6 +467,7 @@ fn init_proposal(c: &mut Criterion) {
                                     voting_start_epoch: 12.into(),
                                     voting_end_epoch: 15.into(),
                                     grace_epoch: 18.into(),
+                                    expedited: false,
                                 },
                                 None,
                                 Some(vec![content_section]),
@@ -518,6 +520,7 @@ fn init_proposal(c: &mut Criterion) {
                                     voting_start_epoch: 12.into(),
                                     voting_end_epoch: 15.into(),
                                     grace_epoch: 18.into(),
+                                    expedited: false,
                                 },
                                 None,
                                 Some(vec![content_section, wasm_code_section]),
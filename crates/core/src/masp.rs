@@ -7,6 +7,8 @@ use std::str::FromStr;
 use borsh::{BorshDeserialize, BorshSerialize};
 use borsh_ext::BorshSerializeExt;
 use masp_primitives::asset_type::AssetType;
+use masp_primitives::ff::PrimeField;
+use masp_primitives::transaction::Transaction;
 use namada_macros::BorshDeserializer;
 #[cfg(feature = "migrations")]
 use namada_migrations::*;
@@ -15,10 +17,10 @@ use sha2::{Digest, Sha256};
 
 use crate::address::{Address, DecodeError, HASH_HEX_LEN, MASP};
 use crate::impl_display_and_from_str_via_format;
-use crate::storage::Epoch;
+use crate::storage::{BlockHeight, Epoch, TxIndex};
 use crate::string_encoding::{
-    self, MASP_EXT_FULL_VIEWING_KEY_HRP, MASP_EXT_SPENDING_KEY_HRP,
-    MASP_PAYMENT_ADDRESS_HRP,
+    self, MASP_EXT_FULL_VIEWING_KEY_HRP, MASP_EXT_PROOF_GENERATION_KEY_HRP,
+    MASP_EXT_SPENDING_KEY_HRP, MASP_PAYMENT_ADDRESS_HRP,
 };
 use crate::token::{Denomination, MaspDigitPos};
 
@@ -381,6 +383,115 @@ impl<'de> serde::Deserialize<'de> for ExtendedSpendingKey {
     }
 }
 
+/// An extended full viewing key along with the nullifier deriving key of its
+/// spending key. Together they allow the proofs of shielded spends to be
+/// constructed, while the spend authorization signatures still require the
+/// spending key.
+#[derive(Clone, Debug, Copy)]
+pub struct ExtendedProofGenerationKey {
+    viewing_key: masp_primitives::zip32::ExtendedFullViewingKey,
+    nsk: masp_primitives::jubjub::Fr,
+}
+
+impl ExtendedProofGenerationKey {
+    /// Get the extended full viewing key of this proof generation key
+    pub fn viewing_key(&self) -> ExtendedViewingKey {
+        ExtendedViewingKey(self.viewing_key)
+    }
+
+    /// Get the proof generation key of the underlying spending key
+    pub fn proof_generation_key(
+        &self,
+    ) -> masp_primitives::sapling::ProofGenerationKey {
+        masp_primitives::sapling::ProofGenerationKey {
+            ak: self.viewing_key.fvk.vk.ak,
+            nsk: self.nsk,
+        }
+    }
+}
+
+impl string_encoding::Format for ExtendedProofGenerationKey {
+    type EncodedBytes<'a> = Vec<u8>;
+
+    const HRP: &'static str = MASP_EXT_PROOF_GENERATION_KEY_HRP;
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = ExtendedViewingKey(self.viewing_key).to_bytes();
+        bytes.extend_from_slice(&self.nsk.to_repr());
+        bytes
+    }
+
+    fn decode_bytes(
+        bytes: &[u8],
+    ) -> Result<Self, string_encoding::DecodeError> {
+        if bytes.len() != 169 + 32 {
+            return Err(DecodeError::InvalidInnerEncoding(format!(
+                "Expected 201 bytes, got {}",
+                bytes.len()
+            )));
+        }
+        let viewing_key = ExtendedViewingKey::decode_bytes(&bytes[..169])
+            .map_err(DecodeError::InvalidBytes)?
+            .0;
+        let nsk: Option<masp_primitives::jubjub::Fr> =
+            masp_primitives::jubjub::Fr::from_repr(
+                bytes[169..]
+                    .try_into()
+                    .expect("the length is checked above"),
+            )
+            .into();
+        let nsk = nsk.ok_or_else(|| {
+            DecodeError::InvalidInnerEncoding(
+                "Invalid nullifier deriving key".to_string(),
+            )
+        })?;
+        let key = Self { viewing_key, nsk };
+        // The nullifier deriving key must be the one of the viewing key
+        if key.proof_generation_key().to_viewing_key() != viewing_key.fvk.vk {
+            return Err(DecodeError::InvalidInnerEncoding(
+                "The nullifier deriving key does not match the viewing key"
+                    .to_string(),
+            ));
+        }
+        Ok(key)
+    }
+}
+
+impl_display_and_from_str_via_format!(ExtendedProofGenerationKey);
+
+impl From<ExtendedSpendingKey> for ExtendedProofGenerationKey {
+    fn from(key: ExtendedSpendingKey) -> Self {
+        Self {
+            viewing_key: (&key.0).into(),
+            nsk: key.0.expsk.nsk,
+        }
+    }
+}
+
+impl serde::Serialize for ExtendedProofGenerationKey {
+    fn serialize<S>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let encoded = self.to_string();
+        serde::Serialize::serialize(&encoded, serializer)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for ExtendedProofGenerationKey {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error;
+        let encoded: String = serde::Deserialize::deserialize(deserializer)?;
+        Self::from_str(&encoded).map_err(D::Error::custom)
+    }
+}
+
 /// Represents a source of funds for a transfer
 #[derive(Debug, Clone)]
 pub enum TransferSource {
@@ -388,6 +499,9 @@ pub enum TransferSource {
     Address(Address),
     /// A transfer coming from a shielded address
     ExtendedSpendingKey(ExtendedSpendingKey),
+    /// A transfer coming from a shielded address whose spends are to be
+    /// authorized separately by the holder of the spending key
+    ProofGenerationKey(ExtendedProofGenerationKey),
 }
 
 impl TransferSource {
@@ -397,7 +511,7 @@ impl TransferSource {
             Self::Address(x) => x.clone(),
             // An ExtendedSpendingKey for a source effectively means that
             // assets will be drawn from the MASP
-            Self::ExtendedSpendingKey(_) => MASP,
+            Self::ExtendedSpendingKey(_) | Self::ProofGenerationKey(_) => MASP,
         }
     }
 
@@ -409,6 +523,14 @@ impl TransferSource {
         }
     }
 
+    /// Get the contained ExtendedProofGenerationKey, if any
+    pub fn proof_generation_key(&self) -> Option<ExtendedProofGenerationKey> {
+        match self {
+            Self::ProofGenerationKey(x) => Some(*x),
+            _ => None,
+        }
+    }
+
     /// Get the contained Address, if any
     pub fn address(&self) -> Option<Address> {
         match self {
@@ -423,6 +545,7 @@ impl Display for TransferSource {
         match self {
             Self::Address(x) => x.fmt(f),
             Self::ExtendedSpendingKey(x) => x.fmt(f),
+            Self::ProofGenerationKey(x) => x.fmt(f),
         }
     }
 }
@@ -560,3 +683,99 @@ impl FromStr for MaspValue {
             })
     }
 }
+
+/// The size of the prefix of an encrypted note plaintext that is enough to
+/// trial decrypt the note: the lead byte, the diversifier, the value, the
+/// rseed and the asset type, but not the memo
+pub const COMPACT_NOTE_SIZE: usize = 1 + 11 + 8 + 32 + 32;
+
+/// The data of a shielded output that a light client needs to trial decrypt
+/// it and to update its note commitment tree
+#[derive(
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+)]
+pub struct CompactOutput {
+    /// The note commitment
+    pub cmu: [u8; 32],
+    /// The ephemeral public key
+    pub epk: [u8; 32],
+    /// The first [`COMPACT_NOTE_SIZE`] bytes of the encrypted note
+    pub enc_ciphertext: Vec<u8>,
+}
+
+/// The shielded outputs and spent nullifiers of a MASP transaction
+#[derive(
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+)]
+pub struct CompactTx {
+    /// The index of the transaction in its block
+    pub index: TxIndex,
+    /// The shielded outputs of the transaction, in order
+    pub outputs: Vec<CompactOutput>,
+    /// The nullifiers revealed by the transaction
+    pub nullifiers: Vec<[u8; 32]>,
+}
+
+impl CompactTx {
+    /// Extract the compact data of the given MASP transaction
+    pub fn new(index: TxIndex, shielded: &Transaction) -> Self {
+        let (outputs, nullifiers) = match shielded.sapling_bundle() {
+            Some(bundle) => (
+                bundle
+                    .shielded_outputs
+                    .iter()
+                    .map(|so| CompactOutput {
+                        cmu: so.cmu.to_repr(),
+                        epk: so.ephemeral_key.0,
+                        enc_ciphertext: so.enc_ciphertext[..COMPACT_NOTE_SIZE]
+                            .to_vec(),
+                    })
+                    .collect(),
+                bundle
+                    .shielded_spends
+                    .iter()
+                    .map(|ss| ss.nullifier.0)
+                    .collect(),
+            ),
+            None => Default::default(),
+        };
+        Self {
+            index,
+            outputs,
+            nullifiers,
+        }
+    }
+}
+
+/// The compact data of the MASP transactions of a block, as indexed by the
+/// nodes that enable it
+#[derive(
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+)]
+pub struct CompactBlock {
+    /// The height of the block
+    pub height: BlockHeight,
+    /// The epoch of the block
+    pub epoch: Epoch,
+    /// The MASP transactions of the block, in order
+    pub txs: Vec<CompactTx>,
+}
@@ -31,6 +31,8 @@ pub const MASP_EXT_FULL_VIEWING_KEY_HRP: &str = "zvknam";
 pub const MASP_PAYMENT_ADDRESS_HRP: &str = "znam";
 /// MASP extended spending key human-readable part
 pub const MASP_EXT_SPENDING_KEY_HRP: &str = "zsknam";
+/// MASP extended proof generation key human-readable part
+pub const MASP_EXT_PROOF_GENERATION_KEY_HRP: &str = "zpgknam";
 /// `common::PublicKey` human-readable part
 pub const COMMON_PK_HRP: &str = "tpknam";
 /// `common::Signature` human-readable part
@@ -0,0 +1,265 @@
+//! Code for handling Ethereum bridge hot key equivocation protocol txs.
+
+use eyre::Result;
+use namada_proof_of_stake::slashing::slash;
+use namada_proof_of_stake::storage::read_pos_params;
+use namada_proof_of_stake::storage_key::enqueued_slashes_key;
+use namada_proof_of_stake::types::SlashType;
+use namada_state::{DBIter, StorageHasher, WlState, DB};
+use namada_tx::data::TxResult;
+use namada_vote_ext::equivocation::EthBridgeEquivocation;
+
+use crate::protocol::validation::equivocation::validate_eth_bridge_equivocation;
+
+/// Slash the validator of some valid evidence of an Ethereum bridge
+/// hot key equivocation.
+///
+/// Invalid evidence, or evidence of an infraction that has already
+/// been slashed, is ignored.
+pub fn apply_derived_tx<D, H>(
+    state: &mut WlState<D, H>,
+    evidence: EthBridgeEquivocation,
+) -> Result<TxResult>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let infraction = match validate_eth_bridge_equivocation(state, &evidence) {
+        Ok(infraction) => infraction,
+        Err(err) => {
+            tracing::debug!(
+                %err,
+                validator = %evidence.validator(),
+                "Ignoring invalid Ethereum bridge equivocation"
+            );
+            return Ok(TxResult::default());
+        }
+    };
+    tracing::info!(
+        validator = %infraction.validator,
+        epoch = ?infraction.epoch,
+        block_height = ?infraction.block_height,
+        "Slashing validator for an Ethereum bridge key equivocation"
+    );
+
+    let params = read_pos_params(state)?;
+    let current_epoch = state.in_mem().get_current_epoch().0;
+    slash(
+        state,
+        &params,
+        current_epoch,
+        infraction.epoch,
+        infraction.block_height,
+        SlashType::EthBridgeEquivocation,
+        &infraction.validator,
+        current_epoch.next(),
+    )?;
+
+    Ok(TxResult {
+        changed_keys: [enqueued_slashes_key()].into(),
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod test_apply_equivocation {
+    use std::collections::HashMap;
+
+    use namada_core::address::{self, Address};
+    use namada_core::ethereum_events::{EthAddress, Uint};
+    use namada_core::keccak::{keccak_hash, KeccakHash};
+    use namada_core::key::SignableEthMessage;
+    use namada_core::storage::BlockHeight;
+    use namada_core::token::Amount;
+    use namada_proof_of_stake::storage::enqueued_slashes_handle;
+    use namada_state::testing::TestState;
+    use namada_storage::StorageWrite;
+    use namada_tx::Signed;
+    use namada_vote_ext::validator_set_update::{EthAddrBook, VotingPowersMap};
+    use namada_vote_ext::{bridge_pool_roots, validator_set_update};
+
+    use super::*;
+    use crate::storage::bridge_pool::get_nonce_key;
+    use crate::storage::eth_bridge_queries::EthBridgeQueries;
+    use crate::storage::vp;
+    use crate::test_utils;
+
+    /// Setup storage with a single validator, and a Bridge pool
+    /// root committed at height 100.
+    fn setup() -> (TestState, Address, test_utils::TestValidatorKeys) {
+        let validator = address::testing::established_address_2();
+        let (mut state, mut keys) =
+            test_utils::setup_storage_with_validators(HashMap::from_iter([(
+                validator.clone(),
+                Amount::native_whole(100),
+            )]));
+        state.in_mem_mut().block.height = 1.into();
+        state.commit_block().unwrap();
+
+        vp::bridge_pool::init_storage(&mut state);
+        state.write(&get_nonce_key(), Uint::from(42)).unwrap();
+        test_utils::commit_bridge_pool_root_at_height(
+            &mut state,
+            &KeccakHash([1; 32]),
+            100.into(),
+        );
+        let keys = keys.remove(&validator).unwrap();
+        (state, validator, keys)
+    }
+
+    /// Sign a Bridge pool root vote extension at height 100, over
+    /// the given root and nonce.
+    fn bp_root_evidence(
+        validator: &Address,
+        keys: &test_utils::TestValidatorKeys,
+        root: KeccakHash,
+        nonce: Uint,
+    ) -> EthBridgeEquivocation {
+        let to_sign = keccak_hash([root.0, nonce.to_bytes()].concat());
+        let vext = bridge_pool_roots::Vext {
+            validator_addr: validator.clone(),
+            block_height: 100.into(),
+            sig: Signed::<_, SignableEthMessage>::new(
+                &keys.eth_bridge,
+                to_sign,
+            )
+            .sig,
+        }
+        .sign(&keys.protocol);
+        EthBridgeEquivocation::BridgePoolRoot { vext, root, nonce }
+    }
+
+    /// Voting powers of two Ethereum validators.
+    fn voting_powers(first: u64, second: u64) -> VotingPowersMap {
+        let first_validator = EthAddrBook {
+            hot_key_addr: EthAddress([1; 20]),
+            cold_key_addr: EthAddress([2; 20]),
+        };
+        let second_validator = EthAddrBook {
+            hot_key_addr: EthAddress([3; 20]),
+            cold_key_addr: EthAddress([4; 20]),
+        };
+        HashMap::from_iter([
+            (first_validator, first.into()),
+            (second_validator, second.into()),
+        ])
+    }
+
+    /// Sign a validator set update vote extension in the current
+    /// epoch, over the given voting powers.
+    fn valset_upd_vext(
+        validator: &Address,
+        keys: &test_utils::TestValidatorKeys,
+        voting_powers: VotingPowersMap,
+    ) -> validator_set_update::SignedVext {
+        validator_set_update::Vext {
+            voting_powers,
+            validator_addr: validator.clone(),
+            signing_epoch: 0.into(),
+        }
+        .sign(&keys.eth_bridge)
+    }
+
+    /// Get the number of slashes enqueued for the given validator.
+    fn num_enqueued_slashes(state: &TestState, validator: &Address) -> u64 {
+        let params = read_pos_params(state).unwrap();
+        let processing_epoch = state.in_mem().get_current_epoch().0
+            + params.slash_processing_epoch_offset();
+        enqueued_slashes_handle()
+            .get_data_handler()
+            .at(&processing_epoch)
+            .at(validator)
+            .len(state)
+            .unwrap()
+    }
+
+    /// Test that signing over a Bridge pool root other than the
+    /// one in storage gets the validator slashed only once.
+    #[test]
+    fn test_bp_root_equivocation_is_slashed() {
+        let (mut state, validator, keys) = setup();
+        let evidence = bp_root_evidence(
+            &validator,
+            &keys,
+            KeccakHash([2; 32]),
+            Uint::from(42),
+        );
+
+        let TxResult { changed_keys, .. } =
+            apply_derived_tx(&mut state, evidence.clone()).unwrap();
+        assert_eq!(changed_keys, [enqueued_slashes_key()].into());
+        assert_eq!(num_enqueued_slashes(&state, &validator), 1);
+        let slash = enqueued_slashes_handle()
+            .get_data_handler()
+            .at(&(state.in_mem().get_current_epoch().0
+                + read_pos_params(&state)
+                    .unwrap()
+                    .slash_processing_epoch_offset()))
+            .at(&validator)
+            .get(&state, 0)
+            .unwrap()
+            .unwrap();
+        assert_eq!(slash.r#type, SlashType::EthBridgeEquivocation);
+        assert_eq!(slash.block_height, 100);
+
+        // the same evidence must not be slashed twice
+        let TxResult { changed_keys, .. } =
+            apply_derived_tx(&mut state, evidence).unwrap();
+        assert!(changed_keys.is_empty());
+        assert_eq!(num_enqueued_slashes(&state, &validator), 1);
+    }
+
+    /// Test that signing over the Bridge pool root and nonce in
+    /// storage is not slashed.
+    #[test]
+    fn test_bp_root_without_equivocation_is_not_slashed() {
+        let (mut state, validator, keys) = setup();
+        let height = BlockHeight(100);
+        let root = state
+            .ethbridge_queries()
+            .get_bridge_pool_root_at_height(height)
+            .unwrap();
+        let nonce = state
+            .ethbridge_queries()
+            .get_bridge_pool_nonce_at_height(height);
+        let evidence = bp_root_evidence(&validator, &keys, root, nonce);
+
+        let TxResult { changed_keys, .. } =
+            apply_derived_tx(&mut state, evidence).unwrap();
+        assert!(changed_keys.is_empty());
+        assert_eq!(num_enqueued_slashes(&state, &validator), 0);
+    }
+
+    /// Test that signing two validator set updates with different
+    /// voting powers in the same epoch gets the validator slashed.
+    #[test]
+    fn test_valset_upd_equivocation_is_slashed() {
+        let (mut state, validator, keys) = setup();
+        let evidence = EthBridgeEquivocation::ValidatorSetUpdate {
+            first: valset_upd_vext(&validator, &keys, voting_powers(100, 200)),
+            second: valset_upd_vext(&validator, &keys, voting_powers(200, 100)),
+        };
+
+        let TxResult { changed_keys, .. } =
+            apply_derived_tx(&mut state, evidence).unwrap();
+        assert_eq!(changed_keys, [enqueued_slashes_key()].into());
+        assert_eq!(num_enqueued_slashes(&state, &validator), 1);
+    }
+
+    /// Test that two validator set updates with proportional voting
+    /// powers are not slashed, since the hot key signs over the same
+    /// normalized voting powers for both of them.
+    #[test]
+    fn test_valset_upd_with_scaled_voting_powers_is_not_slashed() {
+        let (mut state, validator, keys) = setup();
+        let evidence = EthBridgeEquivocation::ValidatorSetUpdate {
+            first: valset_upd_vext(&validator, &keys, voting_powers(100, 200)),
+            second: valset_upd_vext(&validator, &keys, voting_powers(200, 400)),
+        };
+
+        let TxResult { changed_keys, .. } =
+            apply_derived_tx(&mut state, evidence).unwrap();
+        assert!(changed_keys.is_empty());
+        assert_eq!(num_enqueued_slashes(&state, &validator), 0);
+    }
+}
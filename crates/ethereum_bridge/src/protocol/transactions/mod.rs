@@ -5,6 +5,7 @@
 //! natively rather than via the wasm environment as happens with regular
 //! transactions.
 pub mod bridge_pool_roots;
+pub mod equivocation;
 pub mod ethereum_events;
 mod read;
 mod update;
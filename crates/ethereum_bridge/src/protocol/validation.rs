@@ -1,6 +1,7 @@
 //! Validation logic for Ethereum bridge protocol actions.
 
 pub mod bridge_pool_roots;
+pub mod equivocation;
 pub mod ethereum_events;
 pub mod validator_set_update;
 
@@ -52,4 +53,14 @@ pub enum VoteExtensionError {
          not active"
     )]
     EthereumBridgeInactive,
+    #[error(
+        "The evidence does not prove an equivocation with an Ethereum bridge \
+         key"
+    )]
+    NoEquivocation,
+    #[error(
+        "The Ethereum bridge key equivocation is outdated or has already been \
+         slashed"
+    )]
+    EquivocationNotSlashable,
 }
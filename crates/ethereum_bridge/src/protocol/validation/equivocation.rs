@@ -0,0 +1,401 @@
+//! Validation of evidence of Ethereum bridge hot key equivocations.
+
+use namada_core::address::Address;
+use namada_core::borsh::BorshDeserialize;
+use namada_core::ethereum_events::Uint;
+use namada_core::keccak::{keccak_hash, KeccakHash};
+use namada_core::key::{common, Signable};
+use namada_core::storage::{BlockHeight, Epoch};
+use namada_proof_of_stake::pos_queries::PosQueries;
+use namada_proof_of_stake::slashing::is_infraction_slashable;
+use namada_proof_of_stake::storage::read_pos_params;
+use namada_proof_of_stake::types::SlashType;
+use namada_state::{DBIter, StorageHasher, StorageRead, WlState, DB};
+use namada_tx::{SignableEthMessage, Signed};
+use namada_vote_ext::equivocation::EthBridgeEquivocation;
+use namada_vote_ext::validator_set_update::SerializeWithAbiEncode;
+use namada_vote_ext::{bridge_pool_roots, validator_set_update};
+
+use super::VoteExtensionError;
+use crate::storage::bridge_pool::get_nonce_key;
+use crate::storage::eth_bridge_queries::EthBridgeQueries;
+use crate::storage::proof::EthereumProof;
+use crate::storage::vote_tallies;
+
+/// The number of block heights, before and after the block height of an
+/// invalid Bridge pool root vote extension, whose roots are checked against
+/// the signature of the vote extension.
+const BP_ROOT_EQUIVOCATION_SEARCH_DEPTH: u64 = 5;
+
+/// A slashable infraction, proven by some [`EthBridgeEquivocation`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EquivocationInfraction {
+    /// The validator that equivocated.
+    pub validator: Address,
+    /// The epoch of the infraction.
+    pub epoch: Epoch,
+    /// The block height of the infraction.
+    pub block_height: BlockHeight,
+}
+
+/// Validates evidence of an Ethereum bridge hot key equivocation,
+/// returning the infraction it proves.
+///
+/// Checks that:
+///  * The evidence is made up of messages signed with the hot key of the
+///    validator, that conflict with each other or with the state of the chain.
+///  * The infraction can still be slashed, i.e. it is not outdated, and the
+///    validator has not been slashed for it yet.
+pub fn validate_eth_bridge_equivocation<D, H>(
+    state: &WlState<D, H>,
+    evidence: &EthBridgeEquivocation,
+) -> Result<EquivocationInfraction, VoteExtensionError>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+{
+    let infraction = match evidence {
+        EthBridgeEquivocation::BridgePoolRoot { vext, root, nonce } => {
+            validate_bp_root_equivocation(state, vext, root, nonce)?
+        }
+        EthBridgeEquivocation::ValidatorSetUpdate { first, second } => {
+            validate_valset_upd_equivocation(state, first, second)?
+        }
+    };
+
+    let is_slashable = read_pos_params(state)
+        .and_then(|params| {
+            is_infraction_slashable(
+                state,
+                &params,
+                state.in_mem().get_current_epoch().0,
+                infraction.epoch,
+                infraction.block_height,
+                SlashType::EthBridgeEquivocation,
+                &infraction.validator,
+            )
+        })
+        .map_err(|err| {
+            tracing::debug!(
+                ?err,
+                "Failed to check if an Ethereum bridge equivocation is \
+                 slashable"
+            );
+            VoteExtensionError::EquivocationNotSlashable
+        })?;
+    if !is_slashable {
+        tracing::debug!(
+            validator = %infraction.validator,
+            epoch = ?infraction.epoch,
+            block_height = ?infraction.block_height,
+            "Ethereum bridge equivocation is outdated or has already been \
+             slashed"
+        );
+        return Err(VoteExtensionError::EquivocationNotSlashable);
+    }
+    Ok(infraction)
+}
+
+/// Look for a validator set update, already signed by the validator of
+/// the given vote extension in the same signing epoch, that conflicts
+/// with it. The vote extension should have failed validation.
+///
+/// Returns the evidence of the equivocation, if one was found.
+pub fn find_valset_upd_equivocation<D, H>(
+    state: &WlState<D, H>,
+    ext: &validator_set_update::SignedVext,
+) -> Option<EthBridgeEquivocation>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+{
+    let validator = &ext.data.validator_addr;
+    let signing_epoch = ext.data.signing_epoch;
+    let keys = vote_tallies::Keys::from(&signing_epoch.next());
+    let proof = state
+        .read::<EthereumProof<validator_set_update::VotingPowersMap>>(
+            &keys.body(),
+        )
+        .ok()
+        .flatten()?;
+    let addr_book = state
+        .ethbridge_queries()
+        .get_eth_addr_book(validator, Some(signing_epoch))?;
+    let sig = proof.signatures.get(&addr_book)?.clone();
+
+    let first = validator_set_update::SignedVext(Signed::new_from(
+        validator_set_update::Vext {
+            voting_powers: proof.data,
+            validator_addr: validator.clone(),
+            signing_epoch,
+        },
+        common::Signature::Secp256k1(sig),
+    ));
+    let evidence = EthBridgeEquivocation::ValidatorSetUpdate {
+        first,
+        second: ext.clone(),
+    };
+    validate_eth_bridge_equivocation(state, &evidence)
+        .ok()
+        .map(|_| evidence)
+}
+
+/// Look for a Bridge pool root, other than the one at the block height of
+/// the given vote extension but with the same nonce, that the validator
+/// signed over in the vote extension. The vote extension should have
+/// failed validation because of its inner signature.
+///
+/// Only the roots at the heights close to the one of the vote extension
+/// are looked at, since these are the ones that a validator may sign
+/// over in place of the root at the height of its vote extension.
+///
+/// Returns the evidence of the equivocation, if one was found.
+pub fn find_bp_root_equivocation<D, H>(
+    state: &WlState<D, H>,
+    ext: &bridge_pool_roots::SignedVext,
+) -> Option<EthBridgeEquivocation>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+{
+    let block_height = ext.data.block_height;
+    let last_height = state.in_mem().get_last_block_height();
+    if block_height.0 == 0 || block_height > last_height {
+        return None;
+    }
+    let nonce = read_bp_nonce_at_height(state, block_height)?;
+    let root = state
+        .ethbridge_queries()
+        .get_bridge_pool_root_at_height(block_height)?;
+
+    let first_search_height = block_height
+        .0
+        .saturating_sub(BP_ROOT_EQUIVOCATION_SEARCH_DEPTH)
+        .max(1);
+    let last_search_height = block_height
+        .0
+        .saturating_add(BP_ROOT_EQUIVOCATION_SEARCH_DEPTH)
+        .min(last_height.0);
+    (first_search_height..=last_search_height)
+        .map(BlockHeight)
+        .filter(|height| {
+            *height != block_height
+                && read_bp_nonce_at_height(state, *height) == Some(nonce)
+        })
+        .filter_map(|height| {
+            state
+                .ethbridge_queries()
+                .get_bridge_pool_root_at_height(height)
+        })
+        .filter(|other_root| other_root != &root)
+        .map(|other_root| EthBridgeEquivocation::BridgePoolRoot {
+            vext: ext.clone(),
+            root: other_root,
+            nonce,
+        })
+        .find(|evidence| {
+            validate_eth_bridge_equivocation(state, evidence).is_ok()
+        })
+}
+
+/// Read the Bridge pool nonce at the given block height, if it was
+/// already in storage at that height.
+fn read_bp_nonce_at_height<D, H>(
+    state: &WlState<D, H>,
+    height: BlockHeight,
+) -> Option<Uint>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+{
+    let bytes = state
+        .db()
+        .read_subspace_val_with_height(
+            &get_nonce_key(),
+            height,
+            state.in_mem().get_last_block_height(),
+        )
+        .ok()
+        .flatten()?;
+    Uint::try_from_slice(&bytes).ok()
+}
+
+/// Validates a Bridge pool root vote extension, whose inner signature
+/// is over a root and nonce other than the ones found in storage at
+/// the block height of the vote extension.
+fn validate_bp_root_equivocation<D, H>(
+    state: &WlState<D, H>,
+    ext: &bridge_pool_roots::SignedVext,
+    root: &KeccakHash,
+    nonce: &Uint,
+) -> Result<EquivocationInfraction, VoteExtensionError>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+{
+    let block_height = ext.data.block_height;
+    let last_height = state.in_mem().get_last_block_height();
+    if block_height.0 == 0 || block_height > last_height {
+        tracing::debug!(
+            ?block_height,
+            ?last_height,
+            "Bridge pool root equivocation issued for an invalid block height"
+        );
+        return Err(VoteExtensionError::UnexpectedBlockHeight);
+    }
+    let epoch =
+        state.pos_queries().get_epoch(block_height).ok_or_else(|| {
+            tracing::debug!(
+                ?block_height,
+                "The epoch of a Bridge pool root equivocation is unknown"
+            );
+            VoteExtensionError::UnexpectedEpoch
+        })?;
+
+    // get the public key associated with this validator
+    let validator = &ext.data.validator_addr;
+    let (_, pk) = state
+        .pos_queries()
+        .get_validator_from_address(validator, Some(epoch))
+        .map_err(|err| {
+            tracing::debug!(
+                ?err,
+                %validator,
+                "Could not get public key from Storage for some validator, \
+                 while validating a Bridge pool root equivocation"
+            );
+            VoteExtensionError::PubKeyNotInStorage
+        })?;
+    // verify the signature of the vote extension
+    ext.verify(&pk).map_err(|err| {
+        tracing::debug!(
+            ?err,
+            %validator,
+            "Failed to verify the signature of a Bridge pool root \
+             equivocation's vote extension"
+        );
+        VoteExtensionError::VerifySigFailed
+    })?;
+
+    // verify that the validator signed over the given root and nonce
+    let signed = Signed::<_, SignableEthMessage>::new_from(
+        keccak_hash([root.0, nonce.to_bytes()].concat()),
+        ext.data.sig.clone(),
+    );
+    let hot_key = state
+        .pos_queries()
+        .read_validator_eth_hot_key(validator, Some(epoch))
+        .ok_or(VoteExtensionError::PubKeyNotInStorage)?;
+    signed.verify(&hot_key).map_err(|err| {
+        tracing::debug!(
+            ?err,
+            %validator,
+            "Failed to verify the signature of the Bridge pool root of an \
+             equivocation"
+        );
+        VoteExtensionError::InvalidBPRootSig
+    })?;
+
+    // the root and nonce must diverge from the ones in storage
+    let bp_root = state
+        .ethbridge_queries()
+        .get_bridge_pool_root_at_height(block_height)
+        .ok_or(VoteExtensionError::UnexpectedBlockHeight)?;
+    let bp_nonce = state
+        .ethbridge_queries()
+        .get_bridge_pool_nonce_at_height(block_height);
+    if &bp_root == root && &bp_nonce == nonce {
+        tracing::debug!(
+            %validator,
+            ?block_height,
+            "The Bridge pool root and nonce of an alleged equivocation match \
+             the ones in storage"
+        );
+        return Err(VoteExtensionError::NoEquivocation);
+    }
+
+    Ok(EquivocationInfraction {
+        validator: validator.clone(),
+        epoch,
+        block_height,
+    })
+}
+
+/// Validates two validator set update vote extensions, signed by the
+/// same validator for the same signing epoch, over different voting
+/// powers.
+///
+/// The voting powers are compared through the messages that the hot key
+/// signs over, since these normalize the voting powers. Proportional
+/// voting powers, which sign over the same message, do not conflict.
+fn validate_valset_upd_equivocation<D, H>(
+    state: &WlState<D, H>,
+    first: &validator_set_update::SignedVext,
+    second: &validator_set_update::SignedVext,
+) -> Result<EquivocationInfraction, VoteExtensionError>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+{
+    let validator = &first.data.validator_addr;
+    let signing_epoch = first.data.signing_epoch;
+    if validator != &second.data.validator_addr
+        || signing_epoch != second.data.signing_epoch
+        || SerializeWithAbiEncode::as_signable(&first.data)
+            == SerializeWithAbiEncode::as_signable(&second.data)
+    {
+        tracing::debug!(
+            %validator,
+            ?signing_epoch,
+            "The validator set updates of an alleged equivocation do not \
+             conflict with each other"
+        );
+        return Err(VoteExtensionError::NoEquivocation);
+    }
+    if signing_epoch > state.in_mem().get_current_epoch().0 {
+        tracing::debug!(
+            ?signing_epoch,
+            "Validator set update equivocation issued for a future epoch"
+        );
+        return Err(VoteExtensionError::UnexpectedEpoch);
+    }
+
+    // get the public key associated with this validator
+    let hot_key = state
+        .pos_queries()
+        .read_validator_eth_hot_key(validator, Some(signing_epoch))
+        .ok_or_else(|| {
+            tracing::debug!(
+                %validator,
+                "Could not get Ethereum hot key from Storage for some \
+                 validator, while validating a valset upd equivocation"
+            );
+            VoteExtensionError::PubKeyNotInStorage
+        })?;
+    for ext in [first, second] {
+        ext.verify(&hot_key).map_err(|err| {
+            tracing::debug!(
+                ?err,
+                %validator,
+                "Failed to verify the signature of a valset upd equivocation"
+            );
+            VoteExtensionError::VerifySigFailed
+        })?;
+    }
+
+    // validator set updates are voted on at the 2nd block height
+    // of their signing epoch
+    let block_height = state
+        .in_mem()
+        .block
+        .pred_epochs
+        .get_start_height_of_epoch(signing_epoch)
+        .ok_or(VoteExtensionError::UnexpectedEpoch)?
+        + 1;
+
+    Ok(EquivocationInfraction {
+        validator: validator.clone(),
+        epoch: signing_epoch,
+        block_height,
+    })
+}
@@ -271,6 +271,11 @@ impl OfflineVote {
         self.vote.is_abstain()
     }
 
+    /// Check if the vote is a veto
+    pub fn is_veto(&self) -> bool {
+        self.vote.is_veto()
+    }
+
     /// compute the hash of a proposal
     pub fn compute_hash(&self) -> Hash {
         let proposal_hash_data = self.proposal_hash.serialize_to_vec();
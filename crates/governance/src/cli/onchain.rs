@@ -12,12 +12,16 @@ use serde::{Deserialize, Serialize};
 
 use super::validation::{
     is_valid_author_balance, is_valid_content, is_valid_default_proposal_data,
-    is_valid_end_epoch, is_valid_grace_epoch, is_valid_pgf_funding_data,
+    is_valid_end_epoch, is_valid_expedited_voting_period, is_valid_grace_epoch,
+    is_valid_parameter_change_data, is_valid_pgf_funding_data,
     is_valid_pgf_stewards_data, is_valid_proposal_period, is_valid_start_epoch,
-    ProposalValidation,
+    is_valid_upgrade_data, ProposalValidation,
 };
 use crate::parameters::GovernanceParameters;
-use crate::storage::proposal::PGFTarget;
+use crate::pgf::rounds::FundingRound;
+use crate::storage::proposal::{
+    PGFContinuousFunding, PGFTarget, ParameterChange, UpgradeData,
+};
 
 #[derive(
     Debug,
@@ -42,6 +46,70 @@ pub struct OnChainProposal {
     pub voting_end_epoch: Epoch,
     /// The epoch from which this changes are executed
     pub grace_epoch: Epoch,
+    /// Flag if the proposal is expedited, in which case it has a shorter
+    /// voting period but must reach a stricter tally
+    #[serde(default)]
+    pub expedited: bool,
+}
+
+impl OnChainProposal {
+    /// Validate the voting epochs of the proposal, which depend on whether
+    /// the proposal is expedited
+    pub fn validate_epochs(
+        &self,
+        governance_parameters: &GovernanceParameters,
+        current_epoch: Epoch,
+    ) -> Result<(), ProposalValidation> {
+        if self.expedited {
+            is_valid_start_epoch(self.voting_start_epoch, current_epoch, 1)?;
+            is_valid_expedited_voting_period(
+                self.voting_start_epoch,
+                self.voting_end_epoch,
+                governance_parameters.expedited_proposal_voting_period,
+            )?;
+            is_valid_grace_epoch(
+                self.grace_epoch,
+                self.voting_end_epoch,
+                governance_parameters.expedited_min_proposal_grace_epochs,
+            )?;
+        } else {
+            is_valid_start_epoch(
+                self.voting_start_epoch,
+                current_epoch,
+                governance_parameters.min_proposal_voting_period,
+            )?;
+            is_valid_end_epoch(
+                self.voting_start_epoch,
+                self.voting_end_epoch,
+                current_epoch,
+                governance_parameters.min_proposal_voting_period,
+                governance_parameters.min_proposal_voting_period,
+                governance_parameters.max_proposal_period,
+            )?;
+            is_valid_grace_epoch(
+                self.grace_epoch,
+                self.voting_end_epoch,
+                governance_parameters.min_proposal_grace_epochs,
+            )?;
+        }
+        is_valid_proposal_period(
+            self.voting_start_epoch,
+            self.grace_epoch,
+            governance_parameters.max_proposal_period,
+        )
+    }
+
+    /// The minimum amount of funds locked by the proposal
+    pub fn min_proposal_fund(
+        &self,
+        governance_parameters: &GovernanceParameters,
+    ) -> token::Amount {
+        if self.expedited {
+            governance_parameters.expedited_min_proposal_fund
+        } else {
+            governance_parameters.min_proposal_fund
+        }
+    }
 }
 
 /// Pgf default proposal
@@ -73,32 +141,11 @@ impl DefaultProposal {
         if force {
             return Ok(self);
         }
-        is_valid_start_epoch(
-            self.proposal.voting_start_epoch,
-            current_epoch,
-            governance_parameters.min_proposal_voting_period,
-        )?;
-        is_valid_end_epoch(
-            self.proposal.voting_start_epoch,
-            self.proposal.voting_end_epoch,
-            current_epoch,
-            governance_parameters.min_proposal_voting_period,
-            governance_parameters.min_proposal_voting_period,
-            governance_parameters.max_proposal_period,
-        )?;
-        is_valid_grace_epoch(
-            self.proposal.grace_epoch,
-            self.proposal.voting_end_epoch,
-            governance_parameters.min_proposal_grace_epochs,
-        )?;
-        is_valid_proposal_period(
-            self.proposal.voting_start_epoch,
-            self.proposal.grace_epoch,
-            governance_parameters.max_proposal_period,
-        )?;
+        self.proposal
+            .validate_epochs(governance_parameters, current_epoch)?;
         is_valid_author_balance(
             balance,
-            governance_parameters.min_proposal_fund,
+            self.proposal.min_proposal_fund(governance_parameters),
         )?;
         is_valid_content(
             &self.proposal.content,
@@ -121,6 +168,112 @@ impl TryFrom<&[u8]> for DefaultProposal {
     }
 }
 
+/// Protocol parameters change proposal
+#[derive(
+    Debug,
+    Clone,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    Serialize,
+    Deserialize,
+)]
+pub struct ParameterChangeProposal {
+    /// The proposal data
+    pub proposal: OnChainProposal,
+    /// The parameter changes to apply if the proposal passes
+    pub data: Vec<ParameterChange>,
+}
+
+impl ParameterChangeProposal {
+    /// Validate a parameter change proposal
+    pub fn validate(
+        self,
+        governance_parameters: &GovernanceParameters,
+        current_epoch: Epoch,
+        balance: token::Amount,
+        force: bool,
+    ) -> Result<Self, ProposalValidation> {
+        if force {
+            return Ok(self);
+        }
+        self.proposal
+            .validate_epochs(governance_parameters, current_epoch)?;
+        is_valid_author_balance(
+            balance,
+            self.proposal.min_proposal_fund(governance_parameters),
+        )?;
+        is_valid_content(
+            &self.proposal.content,
+            governance_parameters.max_proposal_content_size,
+        )?;
+        is_valid_parameter_change_data(&self.data)?;
+
+        Ok(self)
+    }
+}
+
+impl TryFrom<&[u8]> for ParameterChangeProposal {
+    type Error = serde_json::Error;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        serde_json::from_slice(value)
+    }
+}
+
+/// Chain upgrade proposal
+#[derive(
+    Debug,
+    Clone,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    Serialize,
+    Deserialize,
+)]
+pub struct UpgradeProposal {
+    /// The proposal data
+    pub proposal: OnChainProposal,
+    /// The upgrade to schedule if the proposal passes
+    pub data: UpgradeData,
+}
+
+impl UpgradeProposal {
+    /// Validate a chain upgrade proposal
+    pub fn validate(
+        self,
+        governance_parameters: &GovernanceParameters,
+        current_epoch: Epoch,
+        balance: token::Amount,
+        force: bool,
+    ) -> Result<Self, ProposalValidation> {
+        if force {
+            return Ok(self);
+        }
+        self.proposal
+            .validate_epochs(governance_parameters, current_epoch)?;
+        is_valid_author_balance(
+            balance,
+            self.proposal.min_proposal_fund(governance_parameters),
+        )?;
+        is_valid_content(
+            &self.proposal.content,
+            governance_parameters.max_proposal_content_size,
+        )?;
+        is_valid_upgrade_data(&self.data)?;
+
+        Ok(self)
+    }
+}
+
+impl TryFrom<&[u8]> for UpgradeProposal {
+    type Error = serde_json::Error;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        serde_json::from_slice(value)
+    }
+}
+
 /// Pgf stewards proposal
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PgfStewardProposal {
@@ -151,32 +304,11 @@ impl PgfStewardProposal {
         if force {
             return Ok(self);
         }
-        is_valid_start_epoch(
-            self.proposal.voting_start_epoch,
-            current_epoch,
-            governance_parameters.min_proposal_voting_period,
-        )?;
-        is_valid_end_epoch(
-            self.proposal.voting_start_epoch,
-            self.proposal.voting_end_epoch,
-            current_epoch,
-            governance_parameters.min_proposal_voting_period,
-            governance_parameters.min_proposal_voting_period,
-            governance_parameters.max_proposal_period,
-        )?;
-        is_valid_grace_epoch(
-            self.proposal.grace_epoch,
-            self.proposal.voting_end_epoch,
-            governance_parameters.min_proposal_grace_epochs,
-        )?;
-        is_valid_proposal_period(
-            self.proposal.voting_start_epoch,
-            self.proposal.grace_epoch,
-            governance_parameters.max_proposal_period,
-        )?;
+        self.proposal
+            .validate_epochs(governance_parameters, current_epoch)?;
         is_valid_author_balance(
             balance,
-            governance_parameters.min_proposal_fund,
+            self.proposal.min_proposal_fund(governance_parameters),
         )?;
         is_valid_content(
             &self.proposal.content,
@@ -224,34 +356,13 @@ impl PgfFundingProposal {
         if force {
             return Ok(self);
         }
-        is_valid_start_epoch(
-            self.proposal.voting_start_epoch,
-            current_epoch,
-            governance_parameters.min_proposal_voting_period,
-        )?;
-        is_valid_end_epoch(
-            self.proposal.voting_start_epoch,
-            self.proposal.voting_end_epoch,
-            current_epoch,
-            governance_parameters.min_proposal_voting_period,
-            governance_parameters.min_proposal_voting_period,
-            governance_parameters.max_proposal_period,
-        )?;
-        is_valid_grace_epoch(
-            self.proposal.grace_epoch,
-            self.proposal.voting_end_epoch,
-            governance_parameters.min_proposal_grace_epochs,
-        )?;
-        is_valid_proposal_period(
-            self.proposal.voting_start_epoch,
-            self.proposal.grace_epoch,
-            governance_parameters.max_proposal_period,
-        )?;
+        self.proposal
+            .validate_epochs(governance_parameters, current_epoch)?;
         is_valid_content(
             &self.proposal.content,
             governance_parameters.max_proposal_content_size,
         )?;
-        is_valid_pgf_funding_data(&self.data)?;
+        is_valid_pgf_funding_data(&self.data, self.proposal.grace_epoch)?;
 
         Ok(self)
     }
@@ -318,17 +429,20 @@ impl PgfAction {
 )]
 pub struct PgfFunding {
     /// Pgf continuous funding
-    pub continuous: Vec<PGFTarget>,
+    pub continuous: Vec<PGFContinuousFunding>,
     /// pgf retro fundings
     pub retro: Vec<PGFTarget>,
+    /// pgf funding rounds
+    #[serde(default)]
+    pub rounds: Vec<FundingRound>,
 }
 
 impl Display for PgfFunding {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if !self.continuous.is_empty() {
             write!(f, "Continuous: ")?;
-            for target in &self.continuous {
-                write!(f, "  {}", &target)?;
+            for funding in &self.continuous {
+                write!(f, "  {}", &funding)?;
             }
         }
         if !self.retro.is_empty() {
@@ -337,6 +451,12 @@ impl Display for PgfFunding {
                 write!(f, "  {}", &target)?;
             }
         }
+        if !self.rounds.is_empty() {
+            write!(f, "Funding rounds: ")?;
+            for round in &self.rounds {
+                write!(f, "  {}", &round)?;
+            }
+        }
         Ok(())
     }
 }
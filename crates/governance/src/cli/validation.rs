@@ -1,4 +1,4 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use namada_core::address::Address;
 use namada_core::storage::Epoch;
@@ -6,6 +6,9 @@ use namada_core::token;
 use thiserror::Error;
 
 use super::onchain::{PgfFunding, StewardsUpdate};
+use crate::storage::proposal::{
+    ParameterChange, UpgradeData, MAX_UPGRADE_NAME_LENGTH,
+};
 
 /// This enum raprresent a proposal data
 #[derive(Clone, Debug, PartialEq, Error)]
@@ -26,6 +29,12 @@ pub enum ProposalValidation {
          a multiple of {0}"
     )]
     InvalidStartEndDifference(u64, u64),
+    /// The voting period of an expedited proposal is invalid
+    #[error(
+        "Invalid expedited proposal end epoch: difference between proposal \
+         start and end epoch must be {0}, but found {1}"
+    )]
+    InvalidExpeditedVotingPeriod(u64, u64),
     /// The proposal difference between end and grace epoch is invalid
     #[error(
         "Invalid proposal grace epoch: difference between proposal grace and \
@@ -69,6 +78,26 @@ pub enum ProposalValidation {
     /// The pgf funding data is not valid
     #[error("invalid proposal extra data: cannot be empty.")]
     InvalidPgfFundingExtraData,
+    /// The schedule of a pgf continuous funding is not valid
+    #[error(
+        "Invalid pgf funding schedule for {0}: the end epoch must not be \
+         before the start epoch or the activation epoch {1}, and the cap must \
+         be positive"
+    )]
+    InvalidPgfFundingSchedule(String, Epoch),
+    /// A pgf funding round is not valid
+    #[error(
+        "Invalid pgf funding round ({0}): the budget and voice credits must \
+         be positive and the voting period must not be empty nor start before \
+         the activation epoch {1}"
+    )]
+    InvalidPgfFundingRound(String, Epoch),
+    /// The parameter change data is not valid
+    #[error("Invalid parameter change proposal data: {0}")]
+    InvalidParameterChangeExtraData(String),
+    /// The upgrade data is not valid
+    #[error("Invalid upgrade proposal data: {0}")]
+    InvalidUpgradeExtraData(String),
 }
 
 pub fn is_valid_author_balance(
@@ -130,6 +159,24 @@ pub fn is_valid_end_epoch(
     }
 }
 
+pub fn is_valid_expedited_voting_period(
+    proposal_start_epoch: Epoch,
+    proposal_end_epoch: Epoch,
+    expedited_proposal_voting_period: u64,
+) -> Result<(), ProposalValidation> {
+    let voting_period =
+        proposal_end_epoch.0.saturating_sub(proposal_start_epoch.0);
+
+    if voting_period > 0 && voting_period == expedited_proposal_voting_period {
+        Ok(())
+    } else {
+        Err(ProposalValidation::InvalidExpeditedVotingPeriod(
+            expedited_proposal_voting_period,
+            voting_period,
+        ))
+    }
+}
+
 pub fn is_valid_grace_epoch(
     proposal_grace_epoch: Epoch,
     proposal_end_epoch: Epoch,
@@ -243,10 +290,71 @@ pub fn is_valid_pgf_stewards_data(
 
 pub fn is_valid_pgf_funding_data(
     data: &PgfFunding,
+    activation_epoch: Epoch,
 ) -> Result<(), ProposalValidation> {
-    if !data.continuous.is_empty() || !data.retro.is_empty() {
-        Ok(())
-    } else {
-        Err(ProposalValidation::InvalidPgfFundingExtraData)
+    if data.continuous.is_empty()
+        && data.retro.is_empty()
+        && data.rounds.is_empty()
+    {
+        return Err(ProposalValidation::InvalidPgfFundingExtraData);
+    }
+    for funding in &data.continuous {
+        if !funding.schedule.is_valid(activation_epoch) {
+            return Err(ProposalValidation::InvalidPgfFundingSchedule(
+                funding.target(),
+                activation_epoch,
+            ));
+        }
+    }
+    for round in &data.rounds {
+        if !round.is_valid(activation_epoch) {
+            return Err(ProposalValidation::InvalidPgfFundingRound(
+                round.to_string(),
+                activation_epoch,
+            ));
+        }
+    }
+    Ok(())
+}
+
+pub fn is_valid_parameter_change_data(
+    data: &[ParameterChange],
+) -> Result<(), ProposalValidation> {
+    if data.is_empty() {
+        return Err(ProposalValidation::InvalidParameterChangeExtraData(
+            "cannot be empty".to_string(),
+        ));
+    }
+    let mut changed = BTreeSet::new();
+    for change in data {
+        if !changed.insert((change.module, change.parameter.as_str())) {
+            return Err(ProposalValidation::InvalidParameterChangeExtraData(
+                format!(
+                    "duplicate change of {}.{}",
+                    change.module, change.parameter
+                ),
+            ));
+        }
+        change.parse_value().map_err(|err| {
+            ProposalValidation::InvalidParameterChangeExtraData(err.to_string())
+        })?;
+    }
+    Ok(())
+}
+
+pub fn is_valid_upgrade_data(
+    data: &UpgradeData,
+) -> Result<(), ProposalValidation> {
+    if data.name.is_empty() || data.name.len() > MAX_UPGRADE_NAME_LENGTH {
+        return Err(ProposalValidation::InvalidUpgradeExtraData(format!(
+            "the upgrade name must be between 1 and {} characters long",
+            MAX_UPGRADE_NAME_LENGTH
+        )));
+    }
+    if data.height.0 == 0 {
+        return Err(ProposalValidation::InvalidUpgradeExtraData(
+            "the upgrade height must be positive".to_string(),
+        ));
     }
+    Ok(())
 }
@@ -12,9 +12,13 @@ pub mod storage;
 /// Governance utility functions/structs
 pub mod utils;
 
-pub use storage::proposal::{InitProposalData, ProposalType, VoteProposalData};
-pub use storage::vote::ProposalVote;
-pub use storage::{init_proposal, is_proposal_accepted, vote_proposal};
+pub use storage::proposal::{
+    ChangeRepresentativeData, InitProposalData, ProposalType, VoteProposalData,
+};
+pub use storage::vote::{ProposalVote, WeightedVote};
+pub use storage::{
+    change_representative, init_proposal, is_proposal_accepted, vote_proposal,
+};
 
 /// The governance internal address
 pub const ADDRESS: Address = address::GOV;
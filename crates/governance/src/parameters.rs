@@ -1,4 +1,5 @@
 use namada_core::borsh::{BorshDeserialize, BorshSerialize};
+use namada_core::dec::Dec;
 use namada_core::token;
 use namada_macros::BorshDeserializer;
 #[cfg(feature = "migrations")]
@@ -33,6 +34,19 @@ pub struct GovernanceParameters {
     pub max_proposal_content_size: u64,
     /// Minimum epochs between end and grace epochs
     pub min_proposal_grace_epochs: u64,
+    /// Minimum fraction of the voting power taking part in a vote that must
+    /// veto a proposal for its locked funds to be burned rather than
+    /// refunded
+    pub proposal_veto_threshold: Dec,
+    /// Minimum amount of locked funds for expedited proposals
+    pub expedited_min_proposal_fund: token::Amount,
+    /// Voting period in epochs of expedited proposals
+    pub expedited_proposal_voting_period: u64,
+    /// Minimum epochs between end and grace epochs of expedited proposals
+    pub expedited_min_proposal_grace_epochs: u64,
+    /// Minimum fraction of the total voting power that must vote yay for an
+    /// expedited proposal to pass before the end of its short voting period
+    pub expedited_proposal_quorum: Dec,
 }
 
 impl Default for GovernanceParameters {
@@ -44,6 +58,11 @@ impl Default for GovernanceParameters {
             max_proposal_period: 27,
             max_proposal_content_size: 10_000,
             min_proposal_grace_epochs: 6,
+            proposal_veto_threshold: Dec::one() / 3,
+            expedited_min_proposal_fund: token::Amount::native_whole(2_000),
+            expedited_proposal_voting_period: 1,
+            expedited_min_proposal_grace_epochs: 1,
+            expedited_proposal_quorum: Dec::two() / 3,
         }
     }
 }
@@ -61,6 +80,11 @@ impl GovernanceParameters {
             max_proposal_period,
             max_proposal_content_size,
             min_proposal_grace_epochs,
+            proposal_veto_threshold,
+            expedited_min_proposal_fund,
+            expedited_proposal_voting_period,
+            expedited_min_proposal_grace_epochs,
+            expedited_proposal_quorum,
         } = self;
 
         let min_proposal_fund_key =
@@ -92,6 +116,36 @@ impl GovernanceParameters {
         storage
             .write(&min_proposal_grace_epoch_key, min_proposal_grace_epochs)?;
 
+        let proposal_veto_threshold_key =
+            goverance_storage::get_proposal_veto_threshold_key();
+        storage.write(&proposal_veto_threshold_key, proposal_veto_threshold)?;
+
+        let expedited_min_proposal_fund_key =
+            goverance_storage::get_expedited_min_proposal_fund_key();
+        storage.write(
+            &expedited_min_proposal_fund_key,
+            expedited_min_proposal_fund,
+        )?;
+
+        let expedited_proposal_voting_period_key =
+            goverance_storage::get_expedited_proposal_voting_period_key();
+        storage.write(
+            &expedited_proposal_voting_period_key,
+            expedited_proposal_voting_period,
+        )?;
+
+        let expedited_min_proposal_grace_epoch_key =
+            goverance_storage::get_expedited_min_proposal_grace_epoch_key();
+        storage.write(
+            &expedited_min_proposal_grace_epoch_key,
+            expedited_min_proposal_grace_epochs,
+        )?;
+
+        let expedited_proposal_quorum_key =
+            goverance_storage::get_expedited_proposal_quorum_key();
+        storage
+            .write(&expedited_proposal_quorum_key, expedited_proposal_quorum)?;
+
         let counter_key = goverance_storage::get_counter_key();
         storage.write(&counter_key, u64::MIN)
     }
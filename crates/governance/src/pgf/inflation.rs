@@ -3,21 +3,28 @@
 use namada_core::address::Address;
 use namada_core::token;
 use namada_parameters::storage as params_storage;
-use namada_storage::{Result, StorageRead, StorageWrite};
+use namada_storage::{Error, Result, StorageRead, StorageWrite};
 use namada_trans_token::credit_tokens;
 use namada_trans_token::storage_key::minted_balance_key;
 
-use crate::pgf::storage::{get_parameters, get_payments, get_stewards};
+use crate::pgf::storage::history::{PgfPayment, PgfPaymentKind};
+use crate::pgf::storage::keys::funding_schedules_handle;
+use crate::pgf::storage::{
+    get_parameters, get_payments, get_stewards, ibc_packet_id,
+    record_failed_payment, record_ibc_payment, record_inflation,
+    record_payment, remove_payment,
+};
 use crate::storage::proposal::{PGFIbcTarget, PGFTarget};
 
-/// Apply the PGF inflation.
+/// Apply the PGF inflation. The given IBC transfer function must return the
+/// sequence of the sent packet.
 pub fn apply_inflation<S, F>(
     storage: &mut S,
     transfer_over_ibc: F,
 ) -> Result<()>
 where
     S: StorageWrite + StorageRead,
-    F: Fn(&mut S, &Address, &Address, &PGFIbcTarget) -> Result<()>,
+    F: Fn(&mut S, &Address, &Address, &PGFIbcTarget) -> Result<u64>,
 {
     let pgf_parameters = get_parameters(storage)?;
     let staking_token = storage.get_native_token()?;
@@ -32,12 +39,15 @@ where
     let pgf_inflation_amount =
         (pgf_parameters.pgf_inflation_rate * total_supply) / epochs_per_year;
 
+    let current_epoch = storage.get_block_epoch()?;
+
     credit_tokens(
         storage,
         &staking_token,
         &super::ADDRESS,
         pgf_inflation_amount,
     )?;
+    record_inflation(storage, current_epoch, pgf_inflation_amount)?;
 
     tracing::info!(
         "Minting {} tokens for PGF rewards distribution into the PGF account \
@@ -51,35 +61,99 @@ where
     pgf_fundings.sort_by(|a, b| a.id.cmp(&b.id));
 
     for funding in pgf_fundings {
-        let result = match &funding.detail {
+        let key = funding.detail.target();
+        let mut schedule = funding_schedules_handle().get(storage, &key)?;
+        if let Some(schedule) = &schedule {
+            if schedule.schedule.has_ended(current_epoch) {
+                remove_payment(storage, &key)?;
+                tracing::info!(
+                    "Removing ended continuous funding for {} project.",
+                    &key
+                );
+                continue;
+            }
+            if !schedule.schedule.is_active(current_epoch) {
+                continue;
+            }
+        }
+
+        let payment = match &schedule {
+            Some(schedule) => funding
+                .detail
+                .with_amount(schedule.next_payment(funding.detail.amount())),
+            None => funding.detail.clone(),
+        };
+        let result = match &payment {
             PGFTarget::Internal(target) => namada_trans_token::transfer(
                 storage,
                 &staking_token,
                 &super::ADDRESS,
                 &target.target,
                 target.amount,
-            ),
+            )
+            .map(|()| None),
             PGFTarget::Ibc(target) => transfer_over_ibc(
                 storage,
                 &staking_token,
                 &super::ADDRESS,
                 target,
-            ),
+            )
+            .map(|sequence| {
+                Some(ibc_packet_id(
+                    &target.port_id,
+                    &target.channel_id,
+                    sequence,
+                ))
+            }),
+        };
+        let record = PgfPayment {
+            kind: PgfPaymentKind::Continuous(funding.id),
+            target: key.clone(),
+            amount: payment.amount(),
         };
         match result {
-            Ok(()) => {
+            Ok(packet_id) => {
                 tracing::info!(
                     "Paying {} tokens for {} project.",
-                    funding.detail.amount().to_string_native(),
-                    &funding.detail.target(),
+                    payment.amount().to_string_native(),
+                    &key,
                 );
+                // the payments over IBC can still be refunded
+                if let Some(packet_id) = packet_id {
+                    record_ibc_payment(storage, packet_id, record.clone())?;
+                }
+                record_payment(storage, current_epoch, record)?;
+                if let Some(schedule) = &mut schedule {
+                    schedule.paid = schedule
+                        .paid
+                        .checked_add(payment.amount())
+                        .ok_or(Error::new_const(
+                            "PGF funding paid amount overflow",
+                        ))?;
+                }
             }
             Err(_) => {
                 tracing::warn!(
                     "Failed to pay {} tokens for {} project.",
-                    funding.detail.amount().to_string_native(),
-                    &funding.detail.target(),
+                    payment.amount().to_string_native(),
+                    &key,
                 );
+                record_failed_payment(storage, current_epoch, record)?;
+            }
+        }
+
+        // only the fundings with a schedule have to track their payments
+        if let Some(schedule) = schedule {
+            if schedule.is_completed(current_epoch) {
+                remove_payment(storage, &key)?;
+                tracing::info!(
+                    "Removing completed continuous funding for {} project \
+                     (total paid {}).",
+                    &key,
+                    schedule.paid.to_string_native()
+                );
+            } else {
+                funding_schedules_handle().insert(storage, key, schedule)?;
             }
         }
     }
@@ -94,6 +168,12 @@ where
         for (address, percentage) in steward.reward_distribution {
             let pgf_steward_reward = percentage * pgf_steward_inflation;
 
+            let record = PgfPayment {
+                kind: PgfPaymentKind::StewardReward(steward.address.clone()),
+                target: address.to_string(),
+                amount: pgf_steward_reward,
+            };
+
             if credit_tokens(
                 storage,
                 &staking_token,
@@ -108,6 +188,7 @@ where
                     address,
                     total_supply.to_string_native()
                 );
+                record_payment(storage, current_epoch, record)?;
             } else {
                 tracing::warn!(
                     "Failed minting {} tokens for steward {} (total supply \
@@ -116,6 +197,7 @@ where
                     address,
                     total_supply.to_string_native()
                 );
+                record_failed_payment(storage, current_epoch, record)?;
             }
         }
     }
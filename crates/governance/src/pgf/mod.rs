@@ -8,6 +8,8 @@ pub mod cli;
 pub mod inflation;
 /// Pgf parameters
 pub mod parameters;
+/// Pgf funding rounds
+pub mod rounds;
 /// Pgf storage
 pub mod storage;
 
@@ -0,0 +1,405 @@
+//! Pgf funding rounds.
+//!
+//! A funding round is opened by a PGF funding proposal with a budget and a
+//! voting period. Projects register themselves in the round and accounts
+//! with enough bonded stake cast quadratic votes for them: each account
+//! receives the same amount of voice credits and giving `n` votes to a
+//! project costs `n²` credits. When the round ends, its budget is
+//! distributed among the projects in proportion to the votes they received.
+
+/// Pgf funding rounds storage
+pub mod storage;
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use namada_core::address::Address;
+use namada_core::storage::Epoch;
+use namada_core::token;
+use namada_macros::BorshDeserializer;
+#[cfg(feature = "migrations")]
+use namada_migrations::*;
+use namada_storage::{Error, Result, StorageRead, StorageWrite};
+use serde::{Deserialize, Serialize};
+
+use crate::pgf::storage::history::{PgfPayment, PgfPaymentKind};
+use crate::pgf::storage::{record_failed_payment, record_payment};
+
+/// The votes given by a voter to the projects of a funding round
+pub type FundingRoundBallot = BTreeMap<Address, u64>;
+
+/// A PGF funding round
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    Serialize,
+    Deserialize,
+)]
+pub struct FundingRound {
+    /// The native token amount distributed among the projects at the end of
+    /// the round
+    pub budget: token::Amount,
+    /// The first epoch in which votes can be cast
+    pub start_epoch: Epoch,
+    /// The epoch at which the round ends and its budget is distributed
+    pub end_epoch: Epoch,
+    /// The voice credits available to each voter
+    pub voice_credits: u64,
+    /// The minimum stake that a voter must have bonded at the start of the
+    /// round
+    pub min_bonded_stake: token::Amount,
+}
+
+impl FundingRound {
+    /// Check that a round opened at the given epoch is consistent: it must
+    /// have a budget and voice credits, and its voting period must not be
+    /// empty nor start before the opening
+    pub fn is_valid(&self, activation_epoch: Epoch) -> bool {
+        !self.budget.is_zero()
+            && self.voice_credits > 0
+            && activation_epoch <= self.start_epoch
+            && self.start_epoch < self.end_epoch
+    }
+
+    /// Check if projects can register in the round at the given epoch
+    pub fn is_registration_open(&self, epoch: Epoch) -> bool {
+        epoch < self.end_epoch
+    }
+
+    /// Check if votes can be cast in the round at the given epoch
+    pub fn is_voting_open(&self, epoch: Epoch) -> bool {
+        self.start_epoch <= epoch && epoch < self.end_epoch
+    }
+}
+
+impl Display for FundingRound {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "budget={}, voting epochs={}..{}, voice credits={}, min bonded \
+             stake={}",
+            self.budget.to_string_native(),
+            self.start_epoch,
+            self.end_epoch,
+            self.voice_credits,
+            self.min_bonded_stake.to_string_native()
+        )
+    }
+}
+
+/// Storage structure for pgf funding rounds
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    Serialize,
+    Deserialize,
+)]
+pub struct StorageFundingRound {
+    /// The round id
+    pub id: u64,
+    /// The id of the proposal that opened the round
+    pub proposal_id: u64,
+    /// The round details
+    pub round: FundingRound,
+    /// Whether the budget of the round has been distributed
+    pub finalized: bool,
+}
+
+/// Compute the voice credits spent by a ballot, or `None` on overflow
+pub fn ballot_cost(ballot: &FundingRoundBallot) -> Option<u64> {
+    ballot.values().try_fold(0u64, |acc, votes| {
+        votes
+            .checked_mul(*votes)
+            .and_then(|cost| acc.checked_add(cost))
+    })
+}
+
+/// Sum the votes received by each project across the given ballots
+pub fn tally<'a>(
+    ballots: impl IntoIterator<Item = &'a FundingRoundBallot>,
+) -> Result<BTreeMap<Address, u64>> {
+    let mut tally = BTreeMap::<Address, u64>::new();
+    for ballot in ballots {
+        for (project, votes) in ballot {
+            let project_votes = tally.entry(project.clone()).or_default();
+            *project_votes = project_votes
+                .checked_add(*votes)
+                .ok_or(Error::new_const("PGF funding round tally overflow"))?;
+        }
+    }
+    Ok(tally)
+}
+
+/// Split a budget among projects in proportion to their votes. Projects
+/// without votes are left out and the rounding remainder is not
+/// distributed.
+pub fn distribute(
+    budget: token::Amount,
+    tally: &BTreeMap<Address, u64>,
+) -> Result<BTreeMap<Address, token::Amount>> {
+    let total_votes = tally
+        .values()
+        .try_fold(0u64, |acc, votes| acc.checked_add(*votes))
+        .ok_or(Error::new_const("PGF funding round tally overflow"))?;
+    if total_votes == 0 {
+        return Ok(BTreeMap::new());
+    }
+
+    tally
+        .iter()
+        .filter(|(_, votes)| **votes > 0)
+        .map(|(project, votes)| {
+            budget
+                .checked_mul(token::Amount::from(*votes))
+                .and_then(|amount| {
+                    amount.checked_div(token::Amount::from(total_votes))
+                })
+                .map(|amount| (project.clone(), amount))
+                .ok_or(Error::new_const(
+                    "PGF funding round distribution overflow",
+                ))
+        })
+        .collect()
+}
+
+/// Distribute the budget of the funding rounds that have ended and move them
+/// to the finalized rounds. The budget is capped by the native token balance of
+/// the pgf account.
+pub fn finalize_funding_rounds<S>(storage: &mut S) -> Result<()>
+where
+    S: StorageWrite + StorageRead,
+{
+    let current_epoch = storage.get_block_epoch()?;
+    let staking_token = storage.get_native_token()?;
+
+    let rounds = storage::get_active_funding_rounds(storage)?;
+    for round in rounds {
+        if current_epoch < round.round.end_epoch {
+            continue;
+        }
+
+        let balance = namada_trans_token::read_balance(
+            storage,
+            &staking_token,
+            &super::ADDRESS,
+        )?;
+        let budget = std::cmp::min(round.round.budget, balance);
+        let tally = storage::get_tally(storage, round.id)?;
+        let payments = distribute(budget, &tally)?;
+
+        for (project, amount) in payments {
+            let record = PgfPayment {
+                kind: PgfPaymentKind::FundingRound(round.id),
+                target: project.to_string(),
+                amount,
+            };
+            match namada_trans_token::transfer(
+                storage,
+                &staking_token,
+                &super::ADDRESS,
+                &project,
+                amount,
+            ) {
+                Ok(()) => {
+                    tracing::info!(
+                        "Paying {} tokens for {} project of funding round {}.",
+                        amount.to_string_native(),
+                        &project,
+                        round.id,
+                    );
+                    record_payment(storage, current_epoch, record)?;
+                }
+                Err(_) => {
+                    tracing::warn!(
+                        "Failed to pay {} tokens for {} project of funding \
+                         round {}.",
+                        amount.to_string_native(),
+                        &project,
+                        round.id,
+                    );
+                    record_failed_payment(storage, current_epoch, record)?;
+                }
+            }
+        }
+
+        storage::finalize_funding_round(storage, round)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(any(test, feature = "testing"))]
+/// Testing helpers and strategies for funding rounds
+pub mod testing {
+    use namada_core::storage::testing::arb_epoch;
+    use namada_core::token::testing::arb_amount;
+    use proptest::prop_compose;
+
+    use super::*;
+
+    prop_compose! {
+        /// Generate an arbitrary PGF funding round
+        pub fn arb_funding_round()(
+            budget in arb_amount(),
+            start_epoch in arb_epoch(),
+            duration in 1..100u64,
+            voice_credits in 1..1_000u64,
+            min_bonded_stake in arb_amount(),
+        ) -> FundingRound {
+            FundingRound {
+                budget,
+                start_epoch,
+                end_epoch: start_epoch + duration,
+                voice_credits,
+                min_bonded_stake,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use namada_core::address::testing::{
+        established_address_1, established_address_2, established_address_3,
+    };
+    use namada_storage::testing::TestStorage;
+
+    use super::*;
+
+    #[test]
+    fn test_ballot_cost() {
+        let ballot = FundingRoundBallot::from([
+            (established_address_1(), 3),
+            (established_address_2(), 4),
+        ]);
+        assert_eq!(ballot_cost(&ballot), Some(25));
+        assert_eq!(ballot_cost(&FundingRoundBallot::new()), Some(0));
+
+        let ballot =
+            FundingRoundBallot::from([(established_address_1(), u64::MAX)]);
+        assert_eq!(ballot_cost(&ballot), None);
+    }
+
+    #[test]
+    fn test_funding_round_validity() {
+        let round = FundingRound {
+            budget: token::Amount::native_whole(1_000),
+            start_epoch: Epoch(10),
+            end_epoch: Epoch(20),
+            voice_credits: 100,
+            min_bonded_stake: token::Amount::zero(),
+        };
+        assert!(round.is_valid(Epoch(10)));
+        assert!(!round.is_valid(Epoch(11)));
+        assert!(!FundingRound {
+            end_epoch: Epoch(10),
+            ..round.clone()
+        }
+        .is_valid(Epoch(5)));
+        assert!(!FundingRound {
+            voice_credits: 0,
+            ..round.clone()
+        }
+        .is_valid(Epoch(5)));
+        assert!(!FundingRound {
+            budget: token::Amount::zero(),
+            ..round.clone()
+        }
+        .is_valid(Epoch(5)));
+
+        assert!(round.is_registration_open(Epoch(5)));
+        assert!(!round.is_registration_open(Epoch(20)));
+        assert!(!round.is_voting_open(Epoch(9)));
+        assert!(round.is_voting_open(Epoch(19)));
+        assert!(!round.is_voting_open(Epoch(20)));
+    }
+
+    #[test]
+    fn test_tally_and_distribute() {
+        let ballots = [
+            FundingRoundBallot::from([
+                (established_address_1(), 3),
+                (established_address_2(), 1),
+            ]),
+            FundingRoundBallot::from([
+                (established_address_1(), 1),
+                (established_address_3(), 0),
+            ]),
+        ];
+        let tally = tally(&ballots).unwrap();
+        assert_eq!(tally[&established_address_1()], 4);
+        assert_eq!(tally[&established_address_2()], 1);
+        assert_eq!(tally[&established_address_3()], 0);
+
+        let payments = distribute(token::Amount::from(1_001), &tally).unwrap();
+        assert_eq!(payments.len(), 2);
+        assert_eq!(
+            payments[&established_address_1()],
+            token::Amount::from(800)
+        );
+        assert_eq!(
+            payments[&established_address_2()],
+            token::Amount::from(200)
+        );
+
+        let payments =
+            distribute(token::Amount::from(1_000), &BTreeMap::new()).unwrap();
+        assert!(payments.is_empty());
+    }
+
+    #[test]
+    fn test_finalized_rounds_are_moved() {
+        let mut storage = TestStorage::default();
+        let ended = FundingRound {
+            budget: token::Amount::native_whole(1_000),
+            start_epoch: Epoch(0),
+            end_epoch: Epoch(0),
+            voice_credits: 100,
+            min_bonded_stake: token::Amount::zero(),
+        };
+        let ongoing = FundingRound {
+            end_epoch: Epoch(10),
+            ..ended.clone()
+        };
+        let ended_id =
+            storage::open_funding_round(&mut storage, 0, ended).unwrap();
+        let ongoing_id =
+            storage::open_funding_round(&mut storage, 1, ongoing).unwrap();
+
+        finalize_funding_rounds(&mut storage).unwrap();
+
+        let active = storage::get_active_funding_rounds(&storage).unwrap();
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].id, ongoing_id);
+        assert!(!active[0].finalized);
+
+        let finalized = storage::finalized_funding_rounds_handle()
+            .get(&storage, &ended_id)
+            .unwrap()
+            .unwrap();
+        assert!(finalized.finalized);
+        assert_eq!(
+            storage::get_funding_round(&storage, ended_id).unwrap(),
+            Some(finalized)
+        );
+
+        let rounds = storage::get_funding_rounds(&storage).unwrap();
+        assert_eq!(
+            rounds.iter().map(|round| round.id).collect::<Vec<_>>(),
+            vec![ended_id, ongoing_id]
+        );
+    }
+}
@@ -0,0 +1,356 @@
+//! Pgf funding rounds storage
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use namada_core::address::Address;
+use namada_core::storage::{DbKeySeg, Epoch, Key};
+use namada_macros::StorageKeys;
+use namada_storage::collections::lazy_map::{NestedMap, NestedSubKey};
+use namada_storage::collections::{lazy_map, LazyCollection, LazyMap};
+use namada_storage::{Error, Result, StorageRead, StorageWrite};
+
+use super::{FundingRound, FundingRoundBallot, StorageFundingRound};
+use crate::pgf::ADDRESS;
+
+/// Storage keys for pgf funding rounds.
+#[derive(StorageKeys)]
+struct Keys {
+    funding_round_counter: &'static str,
+    funding_rounds: &'static str,
+    finalized_funding_rounds: &'static str,
+    funding_round_projects: &'static str,
+    funding_round_ballots: &'static str,
+}
+
+/// Obtain the storage key of the funding rounds counter
+pub fn funding_round_counter_key() -> Key {
+    Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(ADDRESS.to_owned()),
+            DbKeySeg::StringSeg(Keys::VALUES.funding_round_counter.to_string()),
+        ],
+    }
+}
+
+/// Obtain a storage key for the funding rounds
+pub fn funding_rounds_key_prefix() -> Key {
+    Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(ADDRESS.to_owned()),
+            DbKeySeg::StringSeg(Keys::VALUES.funding_rounds.to_string()),
+        ],
+    }
+}
+
+/// LazyMap handler for the funding rounds that are not finalized yet by
+/// their id
+pub fn funding_rounds_handle() -> LazyMap<u64, StorageFundingRound> {
+    LazyMap::open(funding_rounds_key_prefix())
+}
+
+/// Obtain a storage key for the finalized funding rounds
+pub fn finalized_funding_rounds_key_prefix() -> Key {
+    Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(ADDRESS.to_owned()),
+            DbKeySeg::StringSeg(
+                Keys::VALUES.finalized_funding_rounds.to_string(),
+            ),
+        ],
+    }
+}
+
+/// LazyMap handler for the finalized funding rounds by their id. They are
+/// kept apart from the other rounds so that finalizing the rounds at every
+/// epoch doesn't iterate over all the past rounds.
+pub fn finalized_funding_rounds_handle() -> LazyMap<u64, StorageFundingRound> {
+    LazyMap::open(finalized_funding_rounds_key_prefix())
+}
+
+/// Obtain a storage key for the projects registered in funding rounds
+pub fn funding_round_projects_key_prefix() -> Key {
+    Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(ADDRESS.to_owned()),
+            DbKeySeg::StringSeg(
+                Keys::VALUES.funding_round_projects.to_string(),
+            ),
+        ],
+    }
+}
+
+/// NestedMap handler for the projects registered in each funding round,
+/// with the epoch of their registration
+pub fn funding_round_projects_handle() -> NestedMap<u64, LazyMap<Address, Epoch>>
+{
+    NestedMap::open(funding_round_projects_key_prefix())
+}
+
+/// Obtain a storage key for the ballots cast in funding rounds
+pub fn funding_round_ballots_key_prefix() -> Key {
+    Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(ADDRESS.to_owned()),
+            DbKeySeg::StringSeg(Keys::VALUES.funding_round_ballots.to_string()),
+        ],
+    }
+}
+
+/// NestedMap handler for the ballots cast in each funding round by voter
+pub fn funding_round_ballots_handle(
+) -> NestedMap<u64, LazyMap<Address, FundingRoundBallot>> {
+    NestedMap::open(funding_round_ballots_key_prefix())
+}
+
+/// Check if the given storage key is a funding round key, or the funding
+/// rounds counter key
+pub fn is_funding_round_key(key: &Key) -> bool {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(pgf), DbKeySeg::StringSeg(prefix), ..]
+            if pgf == &ADDRESS =>
+        {
+            prefix.as_str() == Keys::VALUES.funding_rounds
+                || prefix.as_str() == Keys::VALUES.finalized_funding_rounds
+                || prefix.as_str() == Keys::VALUES.funding_round_counter
+        }
+        _ => false,
+    }
+}
+
+/// Check if the given storage key is the registration of a project in a
+/// funding round. If it is, returns the round id and the project address.
+pub fn is_funding_round_project_key(key: &Key) -> Option<(u64, &Address)> {
+    is_funding_round_nested_key(key, Keys::VALUES.funding_round_projects)
+}
+
+/// Check if the given storage key is the ballot of a voter in a funding
+/// round. If it is, returns the round id and the voter address.
+pub fn is_funding_round_ballot_key(key: &Key) -> Option<(u64, &Address)> {
+    is_funding_round_nested_key(key, Keys::VALUES.funding_round_ballots)
+}
+
+fn is_funding_round_nested_key<'a>(
+    key: &'a Key,
+    collection: &str,
+) -> Option<(u64, &'a Address)> {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(pgf), DbKeySeg::StringSeg(prefix), DbKeySeg::StringSeg(data), DbKeySeg::StringSeg(id), DbKeySeg::StringSeg(nested_data), DbKeySeg::AddressSeg(address)]
+            if pgf == &ADDRESS
+                && prefix.as_str() == collection
+                && data.as_str() == lazy_map::DATA_SUBKEY
+                && nested_data.as_str() == lazy_map::DATA_SUBKEY =>
+        {
+            id.parse::<u64>().ok().map(|id| (id, address))
+        }
+        _ => None,
+    }
+}
+
+/// Open a new funding round approved by the given proposal, returning its id
+pub fn open_funding_round<S>(
+    storage: &mut S,
+    proposal_id: u64,
+    round: FundingRound,
+) -> Result<u64>
+where
+    S: StorageRead + StorageWrite,
+{
+    let counter_key = funding_round_counter_key();
+    let id: u64 = storage.read(&counter_key)?.unwrap_or_default();
+    let next_id = id
+        .checked_add(1)
+        .ok_or(Error::new_const("PGF funding round counter overflow"))?;
+    storage.write(&counter_key, next_id)?;
+
+    funding_rounds_handle().insert(
+        storage,
+        id,
+        StorageFundingRound {
+            id,
+            proposal_id,
+            round,
+            finalized: false,
+        },
+    )?;
+
+    Ok(id)
+}
+
+/// Query a funding round, finalized or not
+pub fn get_funding_round<S>(
+    storage: &S,
+    id: u64,
+) -> Result<Option<StorageFundingRound>>
+where
+    S: StorageRead,
+{
+    match funding_rounds_handle().get(storage, &id)? {
+        Some(round) => Ok(Some(round)),
+        None => finalized_funding_rounds_handle().get(storage, &id),
+    }
+}
+
+/// Query the funding rounds that are not finalized yet
+pub fn get_active_funding_rounds<S>(
+    storage: &S,
+) -> Result<Vec<StorageFundingRound>>
+where
+    S: StorageRead,
+{
+    funding_rounds_handle()
+        .iter(storage)?
+        .map(|data| data.map(|(_id, round)| round))
+        .collect()
+}
+
+/// Query all the funding rounds, finalized or not, ordered by id
+pub fn get_funding_rounds<S>(storage: &S) -> Result<Vec<StorageFundingRound>>
+where
+    S: StorageRead,
+{
+    let mut rounds = get_active_funding_rounds(storage)?;
+    for data in finalized_funding_rounds_handle().iter(storage)? {
+        let (_id, round) = data?;
+        rounds.push(round);
+    }
+    rounds.sort_by_key(|round| round.id);
+    Ok(rounds)
+}
+
+/// Mark a funding round as finalized and move it to the finalized rounds
+pub fn finalize_funding_round<S>(
+    storage: &mut S,
+    mut round: StorageFundingRound,
+) -> Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    funding_rounds_handle().remove(storage, &round.id)?;
+    round.finalized = true;
+    finalized_funding_rounds_handle().insert(storage, round.id, round)?;
+
+    Ok(())
+}
+
+/// Register a project in a funding round in the given epoch
+pub fn register_project<S>(
+    storage: &mut S,
+    round_id: u64,
+    project: Address,
+    epoch: Epoch,
+) -> Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    funding_round_projects_handle()
+        .at(&round_id)
+        .insert(storage, project, epoch)?;
+
+    Ok(())
+}
+
+/// Check if a project is registered in a funding round
+pub fn is_registered_project<S>(
+    storage: &S,
+    round_id: u64,
+    project: &Address,
+) -> Result<bool>
+where
+    S: StorageRead,
+{
+    funding_round_projects_handle()
+        .at(&round_id)
+        .contains(storage, project)
+}
+
+/// Query the projects registered in a funding round
+pub fn get_projects<S>(storage: &S, round_id: u64) -> Result<BTreeSet<Address>>
+where
+    S: StorageRead,
+{
+    funding_round_projects_handle()
+        .at(&round_id)
+        .iter(storage)?
+        .map(|data| data.map(|(project, _epoch)| project))
+        .collect()
+}
+
+/// Write the ballot of a voter in a funding round, replacing any previous
+/// one
+pub fn write_ballot<S>(
+    storage: &mut S,
+    round_id: u64,
+    voter: Address,
+    ballot: FundingRoundBallot,
+) -> Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    funding_round_ballots_handle()
+        .at(&round_id)
+        .insert(storage, voter, ballot)?;
+
+    Ok(())
+}
+
+/// Query the ballot of a voter in a funding round
+pub fn get_ballot<S>(
+    storage: &S,
+    round_id: u64,
+    voter: &Address,
+) -> Result<Option<FundingRoundBallot>>
+where
+    S: StorageRead,
+{
+    funding_round_ballots_handle()
+        .at(&round_id)
+        .get(storage, voter)
+}
+
+/// Query the ballots cast in a funding round by voter
+pub fn get_ballots<S>(
+    storage: &S,
+    round_id: u64,
+) -> Result<BTreeMap<Address, FundingRoundBallot>>
+where
+    S: StorageRead,
+{
+    funding_round_ballots_handle()
+        .at(&round_id)
+        .iter(storage)?
+        .collect()
+}
+
+/// Query the number of votes received by each project of a funding round
+pub fn get_tally<S>(
+    storage: &S,
+    round_id: u64,
+) -> Result<BTreeMap<Address, u64>>
+where
+    S: StorageRead,
+{
+    let ballots = get_ballots(storage, round_id)?;
+    super::tally(ballots.values())
+}
+
+/// Query the ids of the funding rounds in which the given voter has cast a
+/// ballot
+pub fn get_voter_rounds<S>(storage: &S, voter: &Address) -> Result<Vec<u64>>
+where
+    S: StorageRead,
+{
+    let mut rounds = BTreeSet::new();
+    for data in funding_round_ballots_handle().iter(storage)? {
+        let (
+            NestedSubKey::Data {
+                key: round_id,
+                nested_sub_key: lazy_map::SubKey::Data(ballot_voter),
+            },
+            _,
+        ) = data?;
+        if &ballot_voter == voter {
+            rounds.insert(round_id);
+        }
+    }
+    Ok(rounds.into_iter().collect())
+}
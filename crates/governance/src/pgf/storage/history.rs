@@ -0,0 +1,107 @@
+use std::fmt::Display;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use namada_core::address::Address;
+use namada_core::storage::Epoch;
+use namada_core::token;
+use namada_macros::BorshDeserializer;
+#[cfg(feature = "migrations")]
+use namada_migrations::*;
+
+/// The reason of a payment made by the pgf account
+#[derive(
+    Clone,
+    Debug,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    PartialEq,
+    Eq,
+)]
+pub enum PgfPaymentKind {
+    /// A continuous funding added by the given proposal
+    Continuous(u64),
+    /// A retro funding of the given proposal
+    Retro(u64),
+    /// A reward of the given steward
+    StewardReward(Address),
+    /// A payment of the given funding round
+    FundingRound(u64),
+}
+
+impl Display for PgfPaymentKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PgfPaymentKind::Continuous(id) => {
+                write!(f, "continuous funding (proposal {})", id)
+            }
+            PgfPaymentKind::Retro(id) => {
+                write!(f, "retro funding (proposal {})", id)
+            }
+            PgfPaymentKind::StewardReward(steward) => {
+                write!(f, "steward reward ({})", steward)
+            }
+            PgfPaymentKind::FundingRound(id) => {
+                write!(f, "funding round {}", id)
+            }
+        }
+    }
+}
+
+/// A payment made by the pgf account
+#[derive(
+    Clone,
+    Debug,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    PartialEq,
+    Eq,
+)]
+pub struct PgfPayment {
+    /// The reason of the payment
+    pub kind: PgfPaymentKind,
+    /// The target of the payment, either an address on this chain or on the
+    /// counterparty chain of an IBC transfer
+    pub target: String,
+    /// The amount of the payment
+    pub amount: token::Amount,
+}
+
+/// The pgf inflows and outflows of an epoch
+#[derive(
+    Clone,
+    Debug,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    PartialEq,
+    Eq,
+)]
+pub struct PgfEpochReport {
+    /// The epoch of the report
+    pub epoch: Epoch,
+    /// The inflation received by the pgf account
+    pub inflation: token::Amount,
+    /// The payments made by the pgf account
+    pub payments: Vec<PgfPayment>,
+    /// The payments that couldn't be made, e.g. failed IBC transfers
+    pub failed_payments: Vec<PgfPayment>,
+}
+
+impl PgfEpochReport {
+    /// Create an empty report for the given epoch
+    pub fn empty(epoch: Epoch) -> Self {
+        Self {
+            epoch,
+            inflation: token::Amount::zero(),
+            payments: vec![],
+            failed_payments: vec![],
+        }
+    }
+
+    /// The total amount paid in the epoch
+    pub fn total_paid(&self) -> token::Amount {
+        self.payments.iter().map(|payment| payment.amount).sum()
+    }
+}
@@ -1,19 +1,27 @@
 use namada_core::address::Address;
-use namada_core::storage::{DbKeySeg, Key, KeySeg};
+use namada_core::storage::{DbKeySeg, Epoch, Key, KeySeg};
+use namada_core::token;
 use namada_macros::StorageKeys;
-use namada_storage::collections::{lazy_map, LazyCollection, LazyMap};
+use namada_storage::collections::lazy_map::NestedMap;
+use namada_storage::collections::{lazy_map, LazyCollection, LazyMap, LazyVec};
 
+use crate::pgf::storage::history::PgfPayment;
 use crate::pgf::storage::steward::StewardDetail;
 use crate::pgf::ADDRESS;
-use crate::storage::proposal::StoragePgfFunding;
+use crate::storage::proposal::{StoragePgfFunding, StoragePgfFundingSchedule};
 
 /// Storage keys for pgf internal address.
 #[derive(StorageKeys)]
 struct Keys {
     stewards: &'static str,
     fundings: &'static str,
+    funding_schedules: &'static str,
     pgf_inflation_rate: &'static str,
     steward_inflation_rate: &'static str,
+    inflation_history: &'static str,
+    payments_history: &'static str,
+    failed_payments_history: &'static str,
+    ibc_payments: &'static str,
 }
 
 /// Obtain a storage key for stewards key
@@ -65,6 +73,23 @@ pub fn fundings_handle() -> LazyMap<String, StoragePgfFunding> {
     LazyMap::open(fundings_key_prefix())
 }
 
+/// Obtain a storage key for the schedules of pgf fundings.
+pub fn funding_schedules_key_prefix() -> Key {
+    Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(ADDRESS.to_owned()),
+            DbKeySeg::StringSeg(Keys::VALUES.funding_schedules.to_string()),
+        ],
+    }
+}
+
+/// LazyMap handler for the schedules of the pgf fundings, indexed by the
+/// target of the funding
+pub fn funding_schedules_handle() -> LazyMap<String, StoragePgfFundingSchedule>
+{
+    LazyMap::open(funding_schedules_key_prefix())
+}
+
 /// Check if the given storage key is a pgf funding key.
 pub fn is_fundings_key(key: &Key) -> bool {
     matches!(&key.segments[..], [DbKeySeg::AddressSeg(pgf), DbKeySeg::StringSeg(prefix), DbKeySeg::StringSeg(data), DbKeySeg::AddressSeg(_)] if pgf.eq(&ADDRESS)
@@ -72,6 +97,70 @@ pub fn is_fundings_key(key: &Key) -> bool {
                 && data.as_str() == lazy_map::DATA_SUBKEY)
 }
 
+/// Obtain a storage key for the pgf inflation history.
+pub fn inflation_history_key_prefix() -> Key {
+    Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(ADDRESS.to_owned()),
+            DbKeySeg::StringSeg(Keys::VALUES.inflation_history.to_string()),
+        ],
+    }
+}
+
+/// LazyMap handler for the inflation received by pgf in each epoch
+pub fn inflation_history_handle() -> LazyMap<Epoch, token::Amount> {
+    LazyMap::open(inflation_history_key_prefix())
+}
+
+/// Obtain a storage key for the pgf payments history.
+pub fn payments_history_key_prefix() -> Key {
+    Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(ADDRESS.to_owned()),
+            DbKeySeg::StringSeg(Keys::VALUES.payments_history.to_string()),
+        ],
+    }
+}
+
+/// NestedMap handler for the payments made by pgf in each epoch
+pub fn payments_history_handle() -> NestedMap<Epoch, LazyVec<PgfPayment>> {
+    NestedMap::open(payments_history_key_prefix())
+}
+
+/// Obtain a storage key for the pgf failed payments history.
+pub fn failed_payments_history_key_prefix() -> Key {
+    Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(ADDRESS.to_owned()),
+            DbKeySeg::StringSeg(
+                Keys::VALUES.failed_payments_history.to_string(),
+            ),
+        ],
+    }
+}
+
+/// NestedMap handler for the payments that pgf failed to make in each epoch
+pub fn failed_payments_history_handle() -> NestedMap<Epoch, LazyVec<PgfPayment>>
+{
+    NestedMap::open(failed_payments_history_key_prefix())
+}
+
+/// Obtain a storage key for the pgf payments sent over IBC.
+pub fn ibc_payments_key_prefix() -> Key {
+    Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(ADDRESS.to_owned()),
+            DbKeySeg::StringSeg(Keys::VALUES.ibc_payments.to_string()),
+        ],
+    }
+}
+
+/// LazyMap handler for the payments sent by pgf over IBC whose packets are
+/// neither acknowledged nor timed out yet, indexed by packet
+pub fn ibc_payments_handle() -> LazyMap<String, PgfPayment> {
+    LazyMap::open(ibc_payments_key_prefix())
+}
+
 /// Check if key is inside governance address space
 pub fn is_pgf_key(key: &Key) -> bool {
     matches!(&key.segments[0], DbKeySeg::AddressSeg(addr) if addr == &ADDRESS)
@@ -1,20 +1,27 @@
 //! Pgf
 
+/// Pgf treasury history structures
+pub mod history;
 /// Pgf storage keys
 pub mod keys;
 /// Pgf steward structures
 pub mod steward;
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::Display;
 
 use namada_core::address::Address;
 use namada_core::dec::Dec;
-use namada_storage::{Result, StorageRead, StorageWrite};
+use namada_core::storage::Epoch;
+use namada_core::token;
+use namada_storage::collections::lazy_map::NestedSubKey;
+use namada_storage::{Error, Result, StorageRead, StorageWrite};
 
 use crate::pgf::parameters::PgfParameters;
+use crate::pgf::storage::history::{PgfEpochReport, PgfPayment};
 use crate::pgf::storage::keys as pgf_keys;
 use crate::pgf::storage::steward::StewardDetail;
-use crate::storage::proposal::StoragePgfFunding;
+use crate::storage::proposal::{StoragePgfFunding, StoragePgfFundingSchedule};
 
 /// Query the current pgf steward set
 pub fn get_stewards<S>(storage: &S) -> Result<Vec<StewardDetail>>
@@ -77,6 +84,234 @@ where
     Ok(fundings)
 }
 
+/// Query the schedules of the current pgf continuous payments, indexed by their
+/// target. Payments without a schedule are not included.
+pub fn get_payments_schedules<S>(
+    storage: &S,
+) -> Result<BTreeMap<String, StoragePgfFundingSchedule>>
+where
+    S: StorageRead,
+{
+    pgf_keys::funding_schedules_handle()
+        .iter(storage)?
+        .collect()
+}
+
+/// Query the remaining budget of the current pgf continuous payments, indexed
+/// by their target. Payments without a cap have no remaining budget.
+pub fn get_payments_remaining_budget<S>(
+    storage: &S,
+) -> Result<BTreeMap<String, Option<token::Amount>>>
+where
+    S: StorageRead,
+{
+    let schedules = get_payments_schedules(storage)?;
+    let budgets = get_payments(storage)?
+        .into_iter()
+        .map(|funding| {
+            let target = funding.detail.target();
+            let budget = schedules
+                .get(&target)
+                .and_then(StoragePgfFundingSchedule::remaining_budget);
+            (target, budget)
+        })
+        .collect();
+
+    Ok(budgets)
+}
+
+/// Add or update a pgf continuous payment with an optional schedule,
+/// replacing the schedule of a previous payment to the same target
+pub fn add_payment<S>(
+    storage: &mut S,
+    funding: StoragePgfFunding,
+    schedule: Option<StoragePgfFundingSchedule>,
+) -> Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let target = funding.detail.target();
+    match schedule {
+        Some(schedule) => {
+            pgf_keys::funding_schedules_handle().insert(
+                storage,
+                target.clone(),
+                schedule,
+            )?;
+        }
+        None => {
+            pgf_keys::funding_schedules_handle().remove(storage, &target)?;
+        }
+    }
+    pgf_keys::fundings_handle().insert(storage, target, funding)?;
+
+    Ok(())
+}
+
+/// Remove a pgf continuous payment together with its schedule
+pub fn remove_payment<S>(storage: &mut S, target: &str) -> Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let target = target.to_owned();
+    pgf_keys::fundings_handle().remove(storage, &target)?;
+    pgf_keys::funding_schedules_handle().remove(storage, &target)?;
+
+    Ok(())
+}
+
+/// Record the inflation received by pgf in the given epoch
+pub fn record_inflation<S>(
+    storage: &mut S,
+    epoch: Epoch,
+    amount: token::Amount,
+) -> Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let handle = pgf_keys::inflation_history_handle();
+    let received = handle.get(storage, &epoch)?.unwrap_or_default();
+    let received = received
+        .checked_add(amount)
+        .ok_or(Error::new_const("PGF inflation history overflow"))?;
+    handle.insert(storage, epoch, received)?;
+
+    Ok(())
+}
+
+/// Record a payment made by pgf in the given epoch
+pub fn record_payment<S>(
+    storage: &mut S,
+    epoch: Epoch,
+    payment: PgfPayment,
+) -> Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    pgf_keys::payments_history_handle()
+        .at(&epoch)
+        .push(storage, payment)
+}
+
+/// Record a payment that pgf failed to make in the given epoch
+pub fn record_failed_payment<S>(
+    storage: &mut S,
+    epoch: Epoch,
+    payment: PgfPayment,
+) -> Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    pgf_keys::failed_payments_history_handle()
+        .at(&epoch)
+        .push(storage, payment)
+}
+
+/// The identifier of an IBC packet, used to track the pgf payments sent over
+/// IBC
+pub fn ibc_packet_id(
+    port_id: impl Display,
+    channel_id: impl Display,
+    sequence: u64,
+) -> String {
+    format!("{port_id}/{channel_id}/{sequence}")
+}
+
+/// Track a payment sent by pgf over IBC until its packet is acknowledged or
+/// times out
+pub fn record_ibc_payment<S>(
+    storage: &mut S,
+    packet_id: String,
+    payment: PgfPayment,
+) -> Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    pgf_keys::ibc_payments_handle().insert(storage, packet_id, payment)?;
+    Ok(())
+}
+
+/// Stop tracking a payment sent by pgf over IBC once its packet is
+/// acknowledged or timed out. If the tokens were refunded to pgf, the payment
+/// is recorded as failed in the given epoch.
+pub fn settle_ibc_payment<S>(
+    storage: &mut S,
+    epoch: Epoch,
+    packet_id: &String,
+    is_refunded: bool,
+) -> Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let payment = pgf_keys::ibc_payments_handle().remove(storage, packet_id)?;
+    match payment {
+        Some(payment) if is_refunded => {
+            tracing::info!(
+                "PGF payment of {} tokens to {} over IBC was refunded.",
+                payment.amount.to_string_native(),
+                payment.target
+            );
+            record_failed_payment(storage, epoch, payment)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Query the pgf inflows and outflows of every recorded epoch, or only of the
+/// given epoch
+pub fn get_history<S>(
+    storage: &S,
+    epoch: Option<Epoch>,
+) -> Result<Vec<PgfEpochReport>>
+where
+    S: StorageRead,
+{
+    let inflation_history = pgf_keys::inflation_history_handle();
+    let payments_history = pgf_keys::payments_history_handle();
+    let failed_payments_history = pgf_keys::failed_payments_history_handle();
+
+    let epochs = match epoch {
+        Some(epoch) => BTreeSet::from([epoch]),
+        None => {
+            let mut epochs = BTreeSet::new();
+            for data in inflation_history.iter(storage)? {
+                let (epoch, _) = data?;
+                epochs.insert(epoch);
+            }
+            for data in payments_history
+                .iter(storage)?
+                .chain(failed_payments_history.iter(storage)?)
+            {
+                let (NestedSubKey::Data { key: epoch, .. }, _) = data?;
+                epochs.insert(epoch);
+            }
+            epochs
+        }
+    };
+
+    let mut reports = Vec::with_capacity(epochs.len());
+    for epoch in epochs {
+        let inflation =
+            inflation_history.get(storage, &epoch)?.unwrap_or_default();
+        let payments = payments_history
+            .at(&epoch)
+            .iter(storage)?
+            .collect::<Result<Vec<_>>>()?;
+        let failed_payments = failed_payments_history
+            .at(&epoch)
+            .iter(storage)?
+            .collect::<Result<Vec<_>>>()?;
+        reports.push(PgfEpochReport {
+            epoch,
+            inflation,
+            payments,
+            failed_payments,
+        });
+    }
+
+    Ok(reports)
+}
+
 /// Query the pgf parameters
 pub fn get_parameters<S>(storage: &S) -> Result<PgfParameters>
 where
@@ -24,9 +24,20 @@ struct Keys {
     max_period: &'static str,
     max_content: &'static str,
     min_grace_epoch: &'static str,
+    veto_threshold: &'static str,
+    expedited_min_fund: &'static str,
+    expedited_period: &'static str,
+    expedited_grace_epoch: &'static str,
+    expedited_quorum: &'static str,
+    expedited: &'static str,
     counter: &'static str,
     pending: &'static str,
     result: &'static str,
+    result_veto_power: &'static str,
+    representative: &'static str,
+    represented: &'static str,
+    representative_vote: &'static str,
+    scheduled_upgrade: &'static str,
 }
 
 /// Check if key is inside governance address space
@@ -54,6 +65,57 @@ pub fn is_vote_key(key: &Key) -> bool {
     }
 }
 
+/// Check if a key is a representative vote key
+pub fn is_representative_vote_key(key: &Key) -> bool {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::StringSeg(id),
+            DbKeySeg::StringSeg(vote),
+            DbKeySeg::AddressSeg(_representative),
+        ] if addr == &ADDRESS
+            && prefix == Keys::VALUES.proposal
+            && vote == Keys::VALUES.representative_vote =>
+        {
+            id.parse::<u64>().is_ok()
+        }
+        _ => false,
+    }
+}
+
+/// Check if a key is the key of the representative of a delegator. Returns
+/// the delegator's address.
+pub fn is_representative_key(key: &Key) -> Option<&Address> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::AddressSeg(delegator),
+        ] if addr == &ADDRESS && prefix == Keys::VALUES.representative => {
+            Some(delegator)
+        }
+        _ => None,
+    }
+}
+
+/// Check if a key is the key of a delegator represented by a
+/// representative. Returns the representative's and the delegator's
+/// addresses.
+pub fn is_represented_key(key: &Key) -> Option<(&Address, &Address)> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::AddressSeg(representative),
+            DbKeySeg::AddressSeg(delegator),
+        ] if addr == &ADDRESS && prefix == Keys::VALUES.represented => {
+            Some((representative, delegator))
+        }
+        _ => None,
+    }
+}
+
 /// Check if key is author key
 pub fn is_author_key(key: &Key) -> bool {
     match &key.segments[..] {
@@ -270,6 +332,27 @@ pub fn is_min_grace_epoch_key(key: &Key) -> bool {
                     && min_grace_epoch_param == Keys::VALUES.min_grace_epoch)
 }
 
+/// Check if key is the proposal veto threshold parameter key
+pub fn is_veto_threshold_key(key: &Key) -> bool {
+    matches!(&key.segments[..], [
+                    DbKeySeg::AddressSeg(addr),
+                    DbKeySeg::StringSeg(veto_threshold_param),
+                ] if addr == &ADDRESS
+                    && veto_threshold_param == Keys::VALUES.veto_threshold)
+}
+
+/// Check if key is one of the expedited proposals parameter keys
+pub fn is_expedited_parameter_key(key: &Key) -> bool {
+    matches!(&key.segments[..], [
+                    DbKeySeg::AddressSeg(addr),
+                    DbKeySeg::StringSeg(param),
+                ] if addr == &ADDRESS
+                    && (param == Keys::VALUES.expedited_min_fund
+                        || param == Keys::VALUES.expedited_period
+                        || param == Keys::VALUES.expedited_grace_epoch
+                        || param == Keys::VALUES.expedited_quorum))
+}
+
 /// Check if key is parameter key
 pub fn is_parameter_key(key: &Key) -> bool {
     is_min_proposal_fund_key(key)
@@ -278,6 +361,26 @@ pub fn is_parameter_key(key: &Key) -> bool {
         || is_min_proposal_voting_period_key(key)
         || is_max_proposal_period_key(key)
         || is_min_grace_epoch_key(key)
+        || is_veto_threshold_key(key)
+        || is_expedited_parameter_key(key)
+}
+
+/// Check if key is the key flagging a proposal as expedited
+pub fn is_proposal_expedited_key(key: &Key) -> bool {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::StringSeg(id),
+            DbKeySeg::StringSeg(expedited),
+        ] if addr == &ADDRESS
+            && prefix == Keys::VALUES.proposal
+            && expedited == Keys::VALUES.expedited =>
+        {
+            id.parse::<u64>().is_ok()
+        }
+        _ => false,
+    }
 }
 
 /// Check if key is start epoch or end epoch key
@@ -334,6 +437,48 @@ pub fn get_min_proposal_grace_epoch_key() -> Key {
         .expect("Cannot obtain a storage key")
 }
 
+/// Get proposal veto threshold key
+pub fn get_proposal_veto_threshold_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.veto_threshold.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get expedited proposals minimum fund key
+pub fn get_expedited_min_proposal_fund_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.expedited_min_fund.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get expedited proposals voting period key
+pub fn get_expedited_proposal_voting_period_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.expedited_period.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get expedited proposals min grace epoch key
+pub fn get_expedited_min_proposal_grace_epoch_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.expedited_grace_epoch.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get expedited proposals quorum key
+pub fn get_expedited_proposal_quorum_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.expedited_quorum.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the key of the chain upgrade scheduled by governance
+pub fn get_scheduled_upgrade_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.scheduled_upgrade.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
 /// Get key of proposal ids counter
 pub fn get_counter_key() -> Key {
     Key::from(ADDRESS.to_db_key())
@@ -395,6 +540,15 @@ pub fn get_funds_key(id: u64) -> Key {
         .expect("Cannot obtain a storage key")
 }
 
+/// Get key flagging a proposal as expedited
+pub fn get_proposal_expedited_key(id: u64) -> Key {
+    proposal_prefix()
+        .push(&id.to_string())
+        .expect("Cannot obtain a storage key")
+        .push(&Keys::VALUES.expedited.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
 /// Get proposal grace epoch key
 pub fn get_grace_epoch_key(id: u64) -> Key {
     proposal_prefix()
@@ -451,6 +605,56 @@ pub fn get_vote_proposal_key(
         .expect("Cannot obtain a storage key")
 }
 
+/// Get proposal representative vote prefix key
+pub fn get_proposal_representative_vote_prefix_key(id: u64) -> Key {
+    proposal_prefix()
+        .push(&id.to_string())
+        .expect("Cannot obtain a storage key")
+        .push(&Keys::VALUES.representative_vote.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the key of the vote of a representative for a specific proposal id
+pub fn get_representative_vote_key(id: u64, representative: &Address) -> Key {
+    get_proposal_representative_vote_prefix_key(id)
+        .push(representative)
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the prefix of the keys of the representatives of all delegators
+pub fn get_representative_prefix_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.representative.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the key of the representative a delegator assigned its voting power to
+pub fn get_representative_key(delegator: &Address) -> Key {
+    get_representative_prefix_key()
+        .push(delegator)
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the prefix of the keys of the delegators represented by a
+/// representative
+pub fn get_represented_prefix_key(representative: &Address) -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.represented.to_owned())
+        .expect("Cannot obtain a storage key")
+        .push(representative)
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the key of a delegator represented by a representative
+pub fn get_represented_key(
+    representative: &Address,
+    delegator: &Address,
+) -> Key {
+    get_represented_prefix_key(representative)
+        .push(delegator)
+        .expect("Cannot obtain a storage key")
+}
+
 /// Get the proposal execution key
 pub fn get_proposal_execution_key(id: u64) -> Key {
     Key::from(ADDRESS.to_db_key())
@@ -469,6 +673,15 @@ pub fn get_proposal_result_key(id: u64) -> Key {
         .expect("Cannot obtain a storage key")
 }
 
+/// Get the proposal result veto power key
+pub fn get_proposal_result_veto_power_key(id: u64) -> Key {
+    proposal_prefix()
+        .push(&id.to_string())
+        .expect("Cannot obtain a storage key")
+        .push(&Keys::VALUES.result_veto_power.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
 /// Get proposal id from key
 pub fn get_proposal_id(key: &Key) -> Option<u64> {
     match key.get_at(2) {
@@ -512,6 +725,17 @@ pub fn get_vote_delegation_address(key: &Key) -> Option<&Address> {
     }
 }
 
+/// Get the representative address from a representative vote key
+pub fn get_representative_vote_address(key: &Key) -> Option<&Address> {
+    if !is_representative_vote_key(key) {
+        return None;
+    }
+    match key.get_at(4) {
+        Some(DbKeySeg::AddressSeg(res)) => Some(res),
+        _ => None,
+    }
+}
+
 /// Get voter address from vote key
 pub fn get_voter_address(key: &Key) -> Option<&Address> {
     match key.get_at(5) {
@@ -11,6 +11,7 @@ use std::collections::BTreeMap;
 
 use namada_core::address::Address;
 use namada_core::borsh::BorshDeserialize;
+use namada_core::dec::Dec;
 use namada_core::storage::Epoch;
 use namada_storage::{iter_prefix, Error, Result, StorageRead, StorageWrite};
 use namada_trans_token as token;
@@ -18,7 +19,8 @@ use namada_trans_token as token;
 use crate::parameters::GovernanceParameters;
 use crate::storage::keys as governance_keys;
 use crate::storage::proposal::{
-    InitProposalData, ProposalType, StorageProposal, VoteProposalData,
+    ChangeRepresentativeData, InitProposalData, ProposalType, ScheduledUpgrade,
+    StorageProposal, VoteProposalData,
 };
 use crate::storage::vote::ProposalVote;
 use crate::utils::{ProposalResult, Vote};
@@ -82,9 +84,17 @@ where
 
     storage.write(&counter_key, proposal_id + 1)?;
 
-    let min_proposal_funds_key = governance_keys::get_min_proposal_fund_key();
-    let min_proposal_funds: token::Amount =
-        storage.read(&min_proposal_funds_key)?.unwrap();
+    let min_proposal_funds = if data.expedited {
+        let expedited_key =
+            governance_keys::get_proposal_expedited_key(proposal_id);
+        storage.write(&expedited_key, true)?;
+
+        get_expedited_min_proposal_fund(storage)?
+    } else {
+        let min_proposal_funds_key =
+            governance_keys::get_min_proposal_fund_key();
+        storage.read(&min_proposal_funds_key)?.unwrap()
+    };
 
     let funds_key = governance_keys::get_funds_key(proposal_id);
     storage.write(&funds_key, min_proposal_funds)?;
@@ -111,6 +121,11 @@ pub fn vote_proposal<S>(storage: &mut S, data: VoteProposalData) -> Result<()>
 where
     S: StorageRead + StorageWrite,
 {
+    if !data.vote.is_valid() {
+        return Err(Error::new_const(
+            "Vote weights must be non-negative and sum up to 1",
+        ));
+    }
     for delegation in data.delegations {
         let vote_key = governance_keys::get_vote_proposal_key(
             data.id,
@@ -119,9 +134,128 @@ where
         );
         storage.write(&vote_key, data.vote.clone())?;
     }
+    if is_representative(storage, &data.voter)? {
+        let representative_vote_key =
+            governance_keys::get_representative_vote_key(data.id, &data.voter);
+        storage.write(&representative_vote_key, data.vote)?;
+    }
     Ok(())
 }
 
+/// A transaction to assign the voting power of a delegator to a
+/// representative, or to revoke it.
+pub fn change_representative<S>(
+    storage: &mut S,
+    data: ChangeRepresentativeData,
+) -> Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    if data.representative.as_ref() == Some(&data.delegator) {
+        return Err(Error::new_const(
+            "A delegator cannot be its own representative",
+        ));
+    }
+    let representative_key =
+        governance_keys::get_representative_key(&data.delegator);
+    if let Some(current) = storage.read::<Address>(&representative_key)? {
+        storage.delete(&governance_keys::get_represented_key(
+            &current,
+            &data.delegator,
+        ))?;
+    }
+    match data.representative {
+        Some(representative) => {
+            storage.write(
+                &governance_keys::get_represented_key(
+                    &representative,
+                    &data.delegator,
+                ),
+                (),
+            )?;
+            storage.write(&representative_key, representative)
+        }
+        None => storage.delete(&representative_key),
+    }
+}
+
+/// Read the representative a delegator assigned its voting power to
+pub fn get_representative<S>(
+    storage: &S,
+    delegator: &Address,
+) -> Result<Option<Address>>
+where
+    S: StorageRead,
+{
+    storage.read(&governance_keys::get_representative_key(delegator))
+}
+
+/// Read the delegators represented by a representative
+pub fn get_represented_delegators<S>(
+    storage: &S,
+    representative: &Address,
+) -> Result<Vec<Address>>
+where
+    S: StorageRead,
+{
+    let prefix = governance_keys::get_represented_prefix_key(representative);
+    iter_prefix::<()>(storage, &prefix)?
+        .filter_map(|result| match result {
+            Ok((key, _)) => governance_keys::is_represented_key(&key)
+                .map(|(_, delegator)| Ok(delegator.clone())),
+            Err(err) => Some(Err(err)),
+        })
+        .collect()
+}
+
+/// Check if an address represents at least one delegator
+pub fn is_representative<S>(storage: &S, address: &Address) -> Result<bool>
+where
+    S: StorageRead,
+{
+    let prefix = governance_keys::get_represented_prefix_key(address);
+    Ok(iter_prefix::<()>(storage, &prefix)?.next().is_some())
+}
+
+/// Read the representatives of all the delegators, keyed by delegator
+pub fn get_representatives<S>(storage: &S) -> Result<BTreeMap<Address, Address>>
+where
+    S: StorageRead,
+{
+    let prefix = governance_keys::get_representative_prefix_key();
+    iter_prefix::<Address>(storage, &prefix)?
+        .filter_map(|result| match result {
+            Ok((key, representative)) => {
+                governance_keys::is_representative_key(&key)
+                    .map(|delegator| Ok((delegator.clone(), representative)))
+            }
+            Err(err) => Some(Err(err)),
+        })
+        .collect()
+}
+
+/// Query all the votes cast by representatives for a proposal_id
+pub fn get_proposal_representative_votes<S>(
+    storage: &S,
+    proposal_id: u64,
+) -> Result<BTreeMap<Address, ProposalVote>>
+where
+    S: StorageRead,
+{
+    let prefix = governance_keys::get_proposal_representative_vote_prefix_key(
+        proposal_id,
+    );
+    iter_prefix::<ProposalVote>(storage, &prefix)?
+        .filter_map(|result| match result {
+            Ok((key, vote)) => {
+                governance_keys::get_representative_vote_address(&key)
+                    .map(|representative| Ok((representative.clone(), vote)))
+            }
+            Err(err) => Some(Err(err)),
+        })
+        .collect()
+}
+
 /// Write the proposal result to storage.
 pub fn write_proposal_result<S>(
     storage: &mut S,
@@ -133,7 +267,11 @@ where
 {
     let proposal_result_key =
         governance_keys::get_proposal_result_key(proposal_id);
-    storage.write(&proposal_result_key, proposal_result)
+    storage.write(&proposal_result_key, proposal_result)?;
+
+    let veto_power_key =
+        governance_keys::get_proposal_result_veto_power_key(proposal_id);
+    storage.write(&veto_power_key, proposal_result.total_veto_power)
 }
 
 /// Read a proposal by id from storage
@@ -150,6 +288,7 @@ where
     let end_epoch_key = governance_keys::get_voting_end_epoch_key(id);
     let grace_epoch_key = governance_keys::get_grace_epoch_key(id);
     let proposal_type_key = governance_keys::get_proposal_type_key(id);
+    let expedited = is_proposal_expedited(storage, id)?;
 
     let author: Option<Address> = storage.read(&author_key)?;
     let content: Option<BTreeMap<String, String>> = storage.read(&content)?;
@@ -167,6 +306,7 @@ where
         voting_start_epoch: voting_start_epoch.unwrap(),
         voting_end_epoch: voting_end_epoch.unwrap(),
         grace_epoch: grace_epoch.unwrap(),
+        expedited,
     });
 
     Ok(proposal)
@@ -273,6 +413,18 @@ where
 
     let max_proposal_period: u64 = get_max_proposal_period(storage)?;
 
+    let proposal_veto_threshold = get_proposal_veto_threshold(storage)?;
+
+    let expedited_min_proposal_fund = get_expedited_min_proposal_fund(storage)?;
+
+    let expedited_proposal_voting_period =
+        get_expedited_proposal_voting_period(storage)?;
+
+    let expedited_min_proposal_grace_epochs =
+        get_expedited_min_proposal_grace_epochs(storage)?;
+
+    let expedited_proposal_quorum = get_expedited_proposal_quorum(storage)?;
+
     Ok(GovernanceParameters {
         min_proposal_fund,
         max_proposal_code_size,
@@ -280,9 +432,141 @@ where
         max_proposal_period,
         max_proposal_content_size,
         min_proposal_grace_epochs,
+        proposal_veto_threshold,
+        expedited_min_proposal_fund,
+        expedited_proposal_voting_period,
+        expedited_min_proposal_grace_epochs,
+        expedited_proposal_quorum,
     })
 }
 
+/// Get governance "expedited_min_proposal_fund" parameter
+pub fn get_expedited_min_proposal_fund<S>(storage: &S) -> Result<token::Amount>
+where
+    S: StorageRead,
+{
+    let key = governance_keys::get_expedited_min_proposal_fund_key();
+    let expedited_min_proposal_fund: token::Amount =
+        storage.read(&key)?.unwrap_or_else(|| {
+            GovernanceParameters::default().expedited_min_proposal_fund
+        });
+    Ok(expedited_min_proposal_fund)
+}
+
+/// Get governance "expedited_proposal_voting_period" parameter
+pub fn get_expedited_proposal_voting_period<S>(storage: &S) -> Result<u64>
+where
+    S: StorageRead,
+{
+    let key = governance_keys::get_expedited_proposal_voting_period_key();
+    let expedited_proposal_voting_period: u64 =
+        storage.read(&key)?.unwrap_or_else(|| {
+            GovernanceParameters::default().expedited_proposal_voting_period
+        });
+    Ok(expedited_proposal_voting_period)
+}
+
+/// Get governance "expedited_min_proposal_grace_epochs" parameter
+pub fn get_expedited_min_proposal_grace_epochs<S>(storage: &S) -> Result<u64>
+where
+    S: StorageRead,
+{
+    let key = governance_keys::get_expedited_min_proposal_grace_epoch_key();
+    let expedited_min_proposal_grace_epochs: u64 =
+        storage.read(&key)?.unwrap_or_else(|| {
+            GovernanceParameters::default().expedited_min_proposal_grace_epochs
+        });
+    Ok(expedited_min_proposal_grace_epochs)
+}
+
+/// Get governance "expedited_proposal_quorum" parameter
+pub fn get_expedited_proposal_quorum<S>(storage: &S) -> Result<Dec>
+where
+    S: StorageRead,
+{
+    let key = governance_keys::get_expedited_proposal_quorum_key();
+    let expedited_proposal_quorum: Dec =
+        storage.read(&key)?.unwrap_or_else(|| {
+            GovernanceParameters::default().expedited_proposal_quorum
+        });
+    Ok(expedited_proposal_quorum)
+}
+
+/// Check if a proposal is expedited
+pub fn is_proposal_expedited<S>(storage: &S, proposal_id: u64) -> Result<bool>
+where
+    S: StorageRead,
+{
+    let key = governance_keys::get_proposal_expedited_key(proposal_id);
+    Ok(storage.read(&key)?.unwrap_or_default())
+}
+
+/// Convert an expedited proposal that failed the expedited tally into a
+/// regular proposal. The votes already cast are kept, the voting period is
+/// extended to the regular one (and at least until the next epoch) and the
+/// proposal is rescheduled to be tallied again at its new grace epoch. Returns
+/// the new voting end and grace epochs.
+pub fn convert_expedited_proposal<S>(
+    storage: &mut S,
+    proposal_id: u64,
+    current_epoch: Epoch,
+) -> Result<(Epoch, Epoch)>
+where
+    S: StorageRead + StorageWrite,
+{
+    let params = get_parameters(storage)?;
+
+    let start_epoch_key =
+        governance_keys::get_voting_start_epoch_key(proposal_id);
+    let end_epoch_key = governance_keys::get_voting_end_epoch_key(proposal_id);
+    let grace_epoch_key = governance_keys::get_grace_epoch_key(proposal_id);
+
+    let start_epoch: Epoch = storage
+        .read(&start_epoch_key)?
+        .ok_or(Error::new_const("Missing proposal voting start epoch"))?;
+    let grace_epoch: Epoch = storage
+        .read(&grace_epoch_key)?
+        .ok_or(Error::new_const("Missing proposal grace epoch"))?;
+
+    let new_end_epoch = std::cmp::max(
+        start_epoch + params.min_proposal_voting_period,
+        current_epoch.next(),
+    );
+    let new_grace_epoch = new_end_epoch + params.min_proposal_grace_epochs;
+
+    storage.write(&end_epoch_key, new_end_epoch)?;
+    storage.write(&grace_epoch_key, new_grace_epoch)?;
+    storage.delete(&governance_keys::get_committing_proposals_key(
+        proposal_id,
+        grace_epoch.0,
+    ))?;
+    storage.write(
+        &governance_keys::get_committing_proposals_key(
+            proposal_id,
+            new_grace_epoch.0,
+        ),
+        (),
+    )?;
+    storage
+        .delete(&governance_keys::get_proposal_expedited_key(proposal_id))?;
+
+    Ok((new_end_epoch, new_grace_epoch))
+}
+
+/// Get governance "proposal_veto_threshold" parameter. Chains started before
+/// the parameter was introduced use its default value.
+pub fn get_proposal_veto_threshold<S>(storage: &S) -> Result<Dec>
+where
+    S: StorageRead,
+{
+    let key = governance_keys::get_proposal_veto_threshold_key();
+    let proposal_veto_threshold: Dec =
+        storage.read(&key)?.unwrap_or_else(|| {
+            GovernanceParameters::default().proposal_veto_threshold
+        });
+    Ok(proposal_veto_threshold)
+}
+
 /// Get governance "max_proposal_period" parameter
 pub fn get_max_proposal_period<S>(storage: &S) -> Result<u64>
 where
@@ -304,5 +588,49 @@ where
 {
     let key = governance_keys::get_proposal_result_key(proposal_id);
     let proposal_result: Option<ProposalResult> = storage.read(&key)?;
-    Ok(proposal_result)
+    let Some(mut proposal_result) = proposal_result else {
+        return Ok(None);
+    };
+
+    // Missing for the results stored before veto votes existed
+    let key = governance_keys::get_proposal_result_veto_power_key(proposal_id);
+    if let Some(total_veto_power) = storage.read(&key)? {
+        proposal_result.total_veto_power = total_veto_power;
+    }
+    Ok(Some(proposal_result))
+}
+
+/// Get the chain upgrade scheduled by governance, if any
+pub fn get_scheduled_upgrade<S>(storage: &S) -> Result<Option<ScheduledUpgrade>>
+where
+    S: StorageRead,
+{
+    let key = governance_keys::get_scheduled_upgrade_key();
+    storage.read(&key)
+}
+
+/// Schedule a chain upgrade. An already scheduled upgrade is never replaced:
+/// in that case, the given upgrade is not scheduled and `false` is returned.
+pub fn schedule_upgrade<S>(
+    storage: &mut S,
+    upgrade: ScheduledUpgrade,
+) -> Result<bool>
+where
+    S: StorageRead + StorageWrite,
+{
+    let key = governance_keys::get_scheduled_upgrade_key();
+    if storage.has_key(&key)? {
+        return Ok(false);
+    }
+    storage.write(&key, upgrade)?;
+    Ok(true)
+}
+
+/// Clear the scheduled chain upgrade once it has been applied
+pub fn clear_scheduled_upgrade<S>(storage: &mut S) -> Result<()>
+where
+    S: StorageWrite,
+{
+    let key = governance_keys::get_scheduled_upgrade_key();
+    storage.delete(&key)
 }
@@ -1,24 +1,28 @@
 use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::Display;
+use std::str::FromStr;
 
 use borsh::{BorshDeserialize, BorshSerialize};
 use itertools::Itertools;
 use namada_core::address::Address;
+use namada_core::dec::Dec;
 use namada_core::hash::Hash;
 use namada_core::ibc::core::host::types::identifiers::{ChannelId, PortId};
-use namada_core::storage::Epoch;
+use namada_core::storage::{BlockHeight, Epoch};
 use namada_macros::BorshDeserializer;
 #[cfg(feature = "migrations")]
 use namada_migrations::*;
-use namada_trans_token::Amount;
+use namada_trans_token::{Amount, NATIVE_MAX_DECIMAL_PLACES};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use super::vote::ProposalVote;
 use crate::cli::onchain::{
-    DefaultProposal, PgfAction, PgfContinuous, PgfFundingProposal, PgfRetro,
-    PgfSteward, PgfStewardProposal, StewardsUpdate,
+    DefaultProposal, ParameterChangeProposal, PgfAction, PgfContinuous,
+    PgfFundingProposal, PgfRetro, PgfSteward, PgfStewardProposal,
+    StewardsUpdate, UpgradeProposal,
 };
+use crate::pgf::rounds::FundingRound;
 use crate::utils::{ProposalStatus, TallyType};
 
 #[allow(missing_docs)]
@@ -54,6 +58,8 @@ pub struct InitProposalData {
     pub voting_end_epoch: Epoch,
     /// The epoch from which this changes are executed
     pub grace_epoch: Epoch,
+    /// Flag if the proposal is expedited
+    pub expedited: bool,
 }
 
 impl InitProposalData {
@@ -88,6 +94,26 @@ pub struct VoteProposalData {
     pub delegations: Vec<Address>,
 }
 
+/// A tx data type to assign the governance voting power of a delegator to a
+/// representative
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    Serialize,
+    Deserialize,
+)]
+pub struct ChangeRepresentativeData {
+    /// The delegator address
+    pub delegator: Address,
+    /// The representative voting on behalf of the delegator, or `None` to
+    /// revoke the current representative
+    pub representative: Option<Address>,
+}
+
 impl TryFrom<DefaultProposal> for InitProposalData {
     type Error = ProposalError;
 
@@ -100,6 +126,41 @@ impl TryFrom<DefaultProposal> for InitProposalData {
             voting_start_epoch: value.proposal.voting_start_epoch,
             voting_end_epoch: value.proposal.voting_end_epoch,
             grace_epoch: value.proposal.grace_epoch,
+            expedited: value.proposal.expedited,
+        })
+    }
+}
+
+impl TryFrom<ParameterChangeProposal> for InitProposalData {
+    type Error = ProposalError;
+
+    fn try_from(value: ParameterChangeProposal) -> Result<Self, Self::Error> {
+        Ok(InitProposalData {
+            id: value.proposal.id,
+            content: Hash::default(),
+            author: value.proposal.author,
+            r#type: ProposalType::ParameterChange(value.data),
+            voting_start_epoch: value.proposal.voting_start_epoch,
+            voting_end_epoch: value.proposal.voting_end_epoch,
+            grace_epoch: value.proposal.grace_epoch,
+            expedited: value.proposal.expedited,
+        })
+    }
+}
+
+impl TryFrom<UpgradeProposal> for InitProposalData {
+    type Error = ProposalError;
+
+    fn try_from(value: UpgradeProposal) -> Result<Self, Self::Error> {
+        Ok(InitProposalData {
+            id: value.proposal.id,
+            content: Hash::default(),
+            author: value.proposal.author,
+            r#type: ProposalType::Upgrade(value.data),
+            voting_start_epoch: value.proposal.voting_start_epoch,
+            voting_end_epoch: value.proposal.voting_end_epoch,
+            grace_epoch: value.proposal.grace_epoch,
+            expedited: value.proposal.expedited,
         })
     }
 }
@@ -119,6 +180,7 @@ impl TryFrom<PgfStewardProposal> for InitProposalData {
             voting_start_epoch: value.proposal.voting_start_epoch,
             voting_end_epoch: value.proposal.voting_end_epoch,
             grace_epoch: value.proposal.grace_epoch,
+            expedited: value.proposal.expedited,
         })
     }
 }
@@ -132,11 +194,13 @@ impl TryFrom<PgfFundingProposal> for InitProposalData {
             .continuous
             .iter()
             .cloned()
-            .map(|target| {
-                if target.amount().is_zero() {
-                    PGFAction::Continuous(AddRemove::Remove(target))
+            .map(|funding| {
+                if funding.amount().is_zero() {
+                    PGFAction::Continuous(AddRemove::Remove(funding.target))
+                } else if funding.schedule == PGFFundingSchedule::default() {
+                    PGFAction::Continuous(AddRemove::Add(funding.target))
                 } else {
-                    PGFAction::Continuous(AddRemove::Add(target))
+                    PGFAction::ScheduledContinuous(funding)
                 }
             })
             .collect::<BTreeSet<PGFAction>>();
@@ -149,16 +213,26 @@ impl TryFrom<PgfFundingProposal> for InitProposalData {
             .map(PGFAction::Retro)
             .collect::<BTreeSet<PGFAction>>();
 
+        let funding_rounds = value
+            .data
+            .rounds
+            .iter()
+            .cloned()
+            .map(PGFAction::FundingRound)
+            .collect::<BTreeSet<PGFAction>>();
+
         continuous_fundings.extend(retro_fundings);
+        continuous_fundings.extend(funding_rounds);
 
         Ok(InitProposalData {
             id: value.proposal.id,
             content: Hash::default(),
             author: value.proposal.author,
-            r#type: ProposalType::PGFPayment(continuous_fundings), /* here continuous_fundings also contains the retro funding */
+            r#type: ProposalType::PGFPayment(continuous_fundings), /* here continuous_fundings also contains the retro funding and the funding rounds */
             voting_start_epoch: value.proposal.voting_start_epoch,
             voting_end_epoch: value.proposal.voting_end_epoch,
             grace_epoch: value.proposal.grace_epoch,
+            expedited: value.proposal.expedited,
         })
     }
 }
@@ -190,6 +264,65 @@ impl StoragePgfFunding {
     }
 }
 
+/// Storage structure for the schedule of a pgf funding, kept apart from the
+/// funding itself. Fundings without a schedule are paid every epoch until
+/// removed.
+#[derive(
+    Debug,
+    Clone,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    Serialize,
+    Deserialize,
+)]
+pub struct StoragePgfFundingSchedule {
+    /// The schedule of the funding
+    pub schedule: PGFFundingSchedule,
+    /// The total amount paid so far
+    pub paid: Amount,
+}
+
+impl StoragePgfFundingSchedule {
+    /// Init a new pgf funding schedule struct
+    pub fn new(schedule: PGFFundingSchedule) -> Self {
+        Self {
+            schedule,
+            paid: Amount::zero(),
+        }
+    }
+
+    /// The amount left to pay before the funding reaches its cap, if it has
+    /// one
+    pub fn remaining_budget(&self) -> Option<Amount> {
+        self.schedule
+            .cap
+            .map(|cap| cap.checked_sub(self.paid).unwrap_or_default())
+    }
+
+    /// The amount to pay in the next epoch for a funding of the given amount,
+    /// which is limited by the remaining budget of the funding
+    pub fn next_payment(&self, amount: Amount) -> Amount {
+        match self.remaining_budget() {
+            Some(remaining) => std::cmp::min(amount, remaining),
+            None => amount,
+        }
+    }
+
+    /// Check if the funding is completed after the payment of the given
+    /// epoch, either because its budget is exhausted or because it has
+    /// reached its end epoch
+    pub fn is_completed(&self, epoch: Epoch) -> bool {
+        self.remaining_budget()
+            .map_or(false, |remaining| remaining.is_zero())
+            || self.schedule.end_epoch.map_or(false, |end| end <= epoch)
+    }
+}
+
 /// The type of a Proposal
 #[derive(
     Debug,
@@ -208,6 +341,268 @@ pub enum ProposalType {
     PGFSteward(BTreeSet<AddRemove<Address>>),
     /// PGF funding proposal
     PGFPayment(BTreeSet<PGFAction>),
+    /// Protocol parameters change proposal
+    ParameterChange(Vec<ParameterChange>),
+    /// Chain upgrade proposal
+    Upgrade(UpgradeData),
+}
+
+/// A module whose parameters can be changed by a governance proposal
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ParameterModule {
+    /// Protocol parameters
+    Protocol,
+    /// Proof-of-stake parameters
+    Pos,
+    /// Governance parameters
+    Governance,
+    /// Public goods funding parameters
+    Pgf,
+    /// Ethereum bridge parameters
+    EthBridge,
+}
+
+impl ParameterModule {
+    /// The parameters of the module that can be changed by a governance
+    /// proposal, together with the kind of value they hold
+    pub fn parameters(&self) -> &'static [(&'static str, ParameterValueKind)] {
+        use ParameterValueKind::*;
+
+        match self {
+            ParameterModule::Protocol => &[
+                ("max_tx_bytes", Integer),
+                ("max_block_gas", Integer),
+                ("epochs_per_year", Integer),
+                ("max_signatures_per_transaction", Integer),
+                ("fee_unshielding_gas_limit", Integer),
+                ("fee_unshielding_descriptions_limit", Integer),
+            ],
+            // The `cubic_slashing_window_length` and `liveness_window_check`
+            // are left out as the enqueued slashes and the recorded liveness
+            // data depend on their current values
+            ParameterModule::Pos => &[
+                ("max_validator_slots", Integer),
+                ("block_proposer_reward", Decimal),
+                ("block_vote_reward", Decimal),
+                ("max_inflation_rate", Decimal),
+                ("target_staked_ratio", Decimal),
+                ("duplicate_vote_min_slash_rate", Decimal),
+                ("light_client_attack_min_slash_rate", Decimal),
+                ("eth_bridge_equivocation_min_slash_rate", Decimal),
+                ("validator_stake_threshold", Amount),
+                ("liveness_threshold", Decimal),
+                ("min_self_bond_ratio", Decimal),
+                ("max_delegated_stake", OptionalAmount),
+            ],
+            ParameterModule::Governance => &[
+                ("min_proposal_fund", Amount),
+                ("max_proposal_code_size", Integer),
+                ("min_proposal_voting_period", Integer),
+                ("max_proposal_period", Integer),
+                ("max_proposal_content_size", Integer),
+                ("min_proposal_grace_epochs", Integer),
+                ("proposal_veto_threshold", Decimal),
+                ("expedited_min_proposal_fund", Amount),
+                ("expedited_proposal_voting_period", Integer),
+                ("expedited_min_proposal_grace_epochs", Integer),
+                ("expedited_proposal_quorum", Decimal),
+            ],
+            ParameterModule::Pgf => &[
+                ("pgf_inflation_rate", Decimal),
+                ("stewards_inflation_rate", Decimal),
+            ],
+            ParameterModule::EthBridge => &[("min_confirmations", Integer)],
+        }
+    }
+}
+
+impl Display for ParameterModule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParameterModule::Protocol => write!(f, "protocol"),
+            ParameterModule::Pos => write!(f, "pos"),
+            ParameterModule::Governance => write!(f, "governance"),
+            ParameterModule::Pgf => write!(f, "pgf"),
+            ParameterModule::EthBridge => write!(f, "eth_bridge"),
+        }
+    }
+}
+
+/// The kind of value held by a parameter that can be changed by governance
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParameterValueKind {
+    /// An unsigned integer
+    Integer,
+    /// A decimal number
+    Decimal,
+    /// An amount of the native token
+    Amount,
+    /// An optional amount of the native token, `none` to unset it
+    OptionalAmount,
+}
+
+/// The parsed new value of a parameter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParameterValue {
+    /// An unsigned integer
+    Integer(u64),
+    /// A decimal number
+    Decimal(Dec),
+    /// An amount of the native token
+    Amount(Amount),
+    /// An optional amount of the native token
+    OptionalAmount(Option<Amount>),
+}
+
+#[allow(missing_docs)]
+#[derive(Debug, Error)]
+pub enum ParameterChangeError {
+    #[error("Unknown {0} parameter: {1}")]
+    UnknownParameter(ParameterModule, String),
+    #[error("Invalid value for {0}.{1}: {2}")]
+    InvalidValue(ParameterModule, String, String),
+}
+
+/// A change of a protocol parameter proposed by governance
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    Serialize,
+    Deserialize,
+)]
+pub struct ParameterChange {
+    /// The module owning the parameter
+    pub module: ParameterModule,
+    /// The name of the parameter
+    pub parameter: String,
+    /// The new value of the parameter
+    pub value: String,
+}
+
+impl ParameterChange {
+    /// Parse the new value of the parameter according to its kind, failing
+    /// if the parameter cannot be changed by governance
+    pub fn parse_value(&self) -> Result<ParameterValue, ParameterChangeError> {
+        let kind = self
+            .module
+            .parameters()
+            .iter()
+            .find_map(|(name, kind)| (*name == self.parameter).then_some(*kind))
+            .ok_or_else(|| {
+                ParameterChangeError::UnknownParameter(
+                    self.module,
+                    self.parameter.clone(),
+                )
+            })?;
+        let invalid_value = |err: String| {
+            ParameterChangeError::InvalidValue(
+                self.module,
+                self.parameter.clone(),
+                err,
+            )
+        };
+        let value = self.value.trim();
+        match kind {
+            ParameterValueKind::Integer => u64::from_str(value)
+                .map(ParameterValue::Integer)
+                .map_err(|err| invalid_value(err.to_string())),
+            ParameterValueKind::Decimal => Dec::from_str(value)
+                .map(ParameterValue::Decimal)
+                .map_err(|err| invalid_value(err.to_string())),
+            ParameterValueKind::Amount => {
+                Amount::from_str(value, NATIVE_MAX_DECIMAL_PLACES)
+                    .map(ParameterValue::Amount)
+                    .map_err(|err| invalid_value(err.to_string()))
+            }
+            ParameterValueKind::OptionalAmount => {
+                if value.eq_ignore_ascii_case("none") {
+                    Ok(ParameterValue::OptionalAmount(None))
+                } else {
+                    Amount::from_str(value, NATIVE_MAX_DECIMAL_PLACES)
+                        .map(|amount| {
+                            ParameterValue::OptionalAmount(Some(amount))
+                        })
+                        .map_err(|err| invalid_value(err.to_string()))
+                }
+            }
+        }
+    }
+}
+
+impl Display for ParameterChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{} = {}", self.module, self.parameter, self.value)
+    }
+}
+
+/// The maximum length of the name of a chain upgrade
+pub const MAX_UPGRADE_NAME_LENGTH: usize = 64;
+
+/// A chain upgrade proposed by governance
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    Serialize,
+    Deserialize,
+)]
+pub struct UpgradeData {
+    /// The name of the upgrade, which must match the name of the migration
+    /// handler registered in the upgraded node binary
+    pub name: String,
+    /// The block height at which the chain halts to be upgraded
+    pub height: BlockHeight,
+}
+
+impl Display for UpgradeData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at height {}", self.name, self.height)
+    }
+}
+
+/// A chain upgrade scheduled by a passed governance proposal
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    Serialize,
+    Deserialize,
+)]
+pub struct ScheduledUpgrade {
+    /// The id of the proposal that scheduled the upgrade
+    pub proposal_id: u64,
+    /// The name of the upgrade
+    pub name: String,
+    /// The block height at which the upgrade takes place
+    pub height: BlockHeight,
 }
 
 /// An add or remove action for PGF
@@ -280,6 +675,20 @@ impl PGFTarget {
             PGFTarget::Ibc(t) => t.amount,
         }
     }
+
+    /// Returns the funding target with the given amount
+    pub fn with_amount(&self, amount: Amount) -> Self {
+        match self {
+            PGFTarget::Internal(t) => PGFTarget::Internal(PGFInternalTarget {
+                amount,
+                ..t.clone()
+            }),
+            PGFTarget::Ibc(t) => PGFTarget::Ibc(PGFIbcTarget {
+                amount,
+                ..t.clone()
+            }),
+        }
+    }
 }
 
 impl Display for PGFTarget {
@@ -403,6 +812,127 @@ impl borsh::BorshSchema for PGFIbcTarget {
     }
 }
 
+/// The schedule of a continuous PGF funding
+#[derive(
+    Debug,
+    Clone,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    Serialize,
+    Deserialize,
+)]
+pub struct PGFFundingSchedule {
+    /// The first epoch in which the funding is paid, if it doesn't start as
+    /// soon as the proposal is executed
+    #[serde(default)]
+    pub start_epoch: Option<Epoch>,
+    /// The last epoch in which the funding is paid, if it doesn't run until
+    /// removed by another proposal
+    #[serde(default)]
+    pub end_epoch: Option<Epoch>,
+    /// The maximum total amount paid by the funding
+    #[serde(default)]
+    pub cap: Option<Amount>,
+}
+
+impl PGFFundingSchedule {
+    /// Check if the funding is paid in the given epoch
+    pub fn is_active(&self, epoch: Epoch) -> bool {
+        self.start_epoch.map_or(true, |start| start <= epoch)
+            && !self.has_ended(epoch)
+    }
+
+    /// Check if the last epoch of the funding is before the given epoch
+    pub fn has_ended(&self, epoch: Epoch) -> bool {
+        self.end_epoch.map_or(false, |end| end < epoch)
+    }
+
+    /// Check that the schedule of a funding activated at the given epoch is
+    /// consistent: the funding must end after it starts and after its
+    /// activation, and a cap must allow for some payment
+    pub fn is_valid(&self, activation_epoch: Epoch) -> bool {
+        let is_valid_end = match (self.start_epoch, self.end_epoch) {
+            (Some(start), Some(end)) => start <= end && activation_epoch <= end,
+            (None, Some(end)) => activation_epoch <= end,
+            (_, None) => true,
+        };
+        let is_valid_cap = self.cap.map_or(true, |cap| !cap.is_zero());
+
+        is_valid_end && is_valid_cap
+    }
+}
+
+impl Display for PGFFundingSchedule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(start_epoch) = self.start_epoch {
+            write!(f, ", start epoch={}", start_epoch)?;
+        }
+        if let Some(end_epoch) = self.end_epoch {
+            write!(f, ", end epoch={}", end_epoch)?;
+        }
+        if let Some(cap) = self.cap {
+            write!(f, ", cap={}", cap)?;
+        }
+        Ok(())
+    }
+}
+
+/// A continuous PGF funding
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    Serialize,
+    Deserialize,
+)]
+pub struct PGFContinuousFunding {
+    /// The target of the funding
+    #[serde(flatten)]
+    pub target: PGFTarget,
+    /// The schedule of the funding
+    #[serde(flatten)]
+    pub schedule: PGFFundingSchedule,
+}
+
+impl PGFContinuousFunding {
+    /// Returns the funding target as String
+    pub fn target(&self) -> String {
+        self.target.target()
+    }
+
+    /// Returns the funding amount paid every epoch
+    pub fn amount(&self) -> Amount {
+        self.target.amount()
+    }
+}
+
+impl From<PGFTarget> for PGFContinuousFunding {
+    fn from(target: PGFTarget) -> Self {
+        Self {
+            target,
+            schedule: PGFFundingSchedule::default(),
+        }
+    }
+}
+
+impl Display for PGFContinuousFunding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.target, self.schedule)
+    }
+}
+
 /// The actions that a PGF Steward can propose to execute
 #[derive(
     Debug,
@@ -422,6 +952,12 @@ pub enum PGFAction {
     Continuous(AddRemove<PGFTarget>),
     /// A retro payment
     Retro(PGFTarget),
+    /// A funding round distributing a budget among projects by quadratic
+    /// voting
+    FundingRound(FundingRound),
+    /// A continuous payment that is only paid within a schedule or up to a
+    /// cap
+    ScheduledContinuous(PGFContinuousFunding),
 }
 
 impl ProposalType {
@@ -448,6 +984,16 @@ impl ProposalType {
                     .map(|action| format!("\n  {}", &action))
                     .join("")
             ),
+            ProposalType::ParameterChange(changes) => format!(
+                "Changes:{}",
+                changes
+                    .iter()
+                    .map(|change| format!("\n  {}", &change))
+                    .join("")
+            ),
+            ProposalType::Upgrade(upgrade) => {
+                format!("Upgrade: {}\nHeight: {}", upgrade.name, upgrade.height)
+            }
         }
     }
 }
@@ -458,6 +1004,8 @@ impl Display for ProposalType {
             ProposalType::Default(_) => write!(f, "Default"),
             ProposalType::PGFSteward(_) => write!(f, "PGF steward"),
             ProposalType::PGFPayment(_) => write!(f, "PGF funding"),
+            ProposalType::ParameterChange(_) => write!(f, "Parameter change"),
+            ProposalType::Upgrade(_) => write!(f, "Upgrade"),
         }
     }
 }
@@ -522,6 +1070,12 @@ impl Display for PGFAction {
                 write!(f, "Continuous: {}", &add_remove)
             }
             PGFAction::Retro(target) => write!(f, "Retroactive: {}", &target),
+            PGFAction::FundingRound(round) => {
+                write!(f, "Funding round: {}", &round)
+            }
+            PGFAction::ScheduledContinuous(funding) => {
+                write!(f, "Continuous: Add({})", &funding)
+            }
         }
     }
 }
@@ -543,6 +1097,8 @@ pub struct StorageProposal {
     pub voting_end_epoch: Epoch,
     /// The epoch from which this changes are executed
     pub grace_epoch: Epoch,
+    /// Flag if the proposal is still expedited
+    pub expedited: bool,
 }
 
 impl StorageProposal {
@@ -593,6 +1149,7 @@ Content: {:?}
 Start Epoch: {}
 End Epoch: {}
 Grace Epoch: {}
+Expedited: {}
 Status: {}
 Data: {}",
             self.id,
@@ -602,6 +1159,7 @@ Data: {}",
             self.voting_start_epoch,
             self.voting_end_epoch,
             self.grace_epoch,
+            self.expedited,
             self.get_status(current_epoch),
             self.r#type.format_data()
         )
@@ -645,6 +1203,7 @@ pub mod testing {
     use proptest::{collection, option, prop_compose};
 
     use super::*;
+    use crate::pgf::rounds::testing::arb_funding_round;
     use crate::storage::vote::testing::arb_proposal_vote;
 
     /// Generate an arbitrary add or removal of what's generated by the supplied
@@ -715,11 +1274,39 @@ pub mod testing {
         ]
     }
 
+    prop_compose! {
+        /// Generate an arbitrary PGF funding schedule
+        pub fn arb_pgf_funding_schedule()(
+            start_epoch in option::of(arb_epoch()),
+            end_epoch in option::of(arb_epoch()),
+            cap in option::of(arb_amount()),
+        ) -> PGFFundingSchedule {
+            PGFFundingSchedule {
+                start_epoch,
+                end_epoch,
+                cap,
+            }
+        }
+    }
+
+    prop_compose! {
+        /// Generate an arbitrary PGF continuous funding
+        pub fn arb_pgf_continuous_funding()(
+            target in arb_pgf_target(),
+            schedule in arb_pgf_funding_schedule(),
+        ) -> PGFContinuousFunding {
+            PGFContinuousFunding { target, schedule }
+        }
+    }
+
     /// Generate an arbitrary PGF action
     pub fn arb_pgf_action() -> impl Strategy<Value = PGFAction> {
         prop_oneof![
             arb_add_remove(arb_pgf_target()).prop_map(PGFAction::Continuous),
             arb_pgf_target().prop_map(PGFAction::Retro),
+            arb_funding_round().prop_map(PGFAction::FundingRound),
+            arb_pgf_continuous_funding()
+                .prop_map(PGFAction::ScheduledContinuous),
         ]
     }
 
@@ -747,6 +1334,7 @@ pub mod testing {
             voting_start_epoch in arb_epoch(),
             voting_end_epoch in arb_epoch(),
             grace_epoch in arb_epoch(),
+            expedited in any::<bool>(),
         ) -> InitProposalData {
             InitProposalData {
                 id,
@@ -756,6 +1344,7 @@ pub mod testing {
                 voting_start_epoch,
                 voting_end_epoch,
                 grace_epoch,
+                expedited,
             }
         }
     }
@@ -777,3 +1366,121 @@ pub mod testing {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn funding_schedule(
+        schedule: PGFFundingSchedule,
+        paid: u64,
+    ) -> StoragePgfFundingSchedule {
+        StoragePgfFundingSchedule {
+            paid: Amount::native_whole(paid),
+            ..StoragePgfFundingSchedule::new(schedule)
+        }
+    }
+
+    #[test]
+    fn test_pgf_funding_schedule_is_active() {
+        let schedule = PGFFundingSchedule {
+            start_epoch: Some(Epoch(5)),
+            end_epoch: Some(Epoch(10)),
+            cap: None,
+        };
+        assert!(!schedule.is_active(Epoch(4)));
+        assert!(schedule.is_active(Epoch(5)));
+        assert!(schedule.is_active(Epoch(10)));
+        assert!(!schedule.is_active(Epoch(11)));
+        assert!(schedule.has_ended(Epoch(11)));
+
+        let schedule = PGFFundingSchedule::default();
+        assert!(schedule.is_active(Epoch(0)));
+        assert!(!schedule.has_ended(Epoch(u64::MAX)));
+    }
+
+    #[test]
+    fn test_pgf_funding_schedule_is_valid() {
+        let activation_epoch = Epoch(10);
+        assert!(PGFFundingSchedule::default().is_valid(activation_epoch));
+
+        let schedule = PGFFundingSchedule {
+            start_epoch: Some(Epoch(12)),
+            end_epoch: Some(Epoch(11)),
+            cap: None,
+        };
+        assert!(!schedule.is_valid(activation_epoch));
+
+        let schedule = PGFFundingSchedule {
+            start_epoch: None,
+            end_epoch: Some(Epoch(9)),
+            cap: None,
+        };
+        assert!(!schedule.is_valid(activation_epoch));
+
+        let schedule = PGFFundingSchedule {
+            start_epoch: None,
+            end_epoch: None,
+            cap: Some(Amount::zero()),
+        };
+        assert!(!schedule.is_valid(activation_epoch));
+
+        let schedule = PGFFundingSchedule {
+            start_epoch: Some(Epoch(12)),
+            end_epoch: Some(Epoch(20)),
+            cap: Some(Amount::native_whole(100)),
+        };
+        assert!(schedule.is_valid(activation_epoch));
+    }
+
+    #[test]
+    fn test_pgf_funding_payments_are_capped() {
+        let schedule = PGFFundingSchedule {
+            start_epoch: None,
+            end_epoch: None,
+            cap: Some(Amount::native_whole(25)),
+        };
+
+        let amount = Amount::native_whole(10);
+
+        let uncapped = funding_schedule(PGFFundingSchedule::default(), 100);
+        assert_eq!(uncapped.remaining_budget(), None);
+        assert_eq!(uncapped.next_payment(amount), amount);
+        assert!(!uncapped.is_completed(Epoch(100)));
+
+        let capped = funding_schedule(schedule.clone(), 10);
+        assert_eq!(capped.remaining_budget(), Some(Amount::native_whole(15)));
+        assert_eq!(capped.next_payment(amount), amount);
+        assert!(!capped.is_completed(Epoch(0)));
+
+        let capped = funding_schedule(schedule.clone(), 20);
+        assert_eq!(capped.next_payment(amount), Amount::native_whole(5));
+
+        let capped = funding_schedule(schedule, 25);
+        assert_eq!(capped.remaining_budget(), Some(Amount::zero()));
+        assert_eq!(capped.next_payment(amount), Amount::zero());
+        assert!(capped.is_completed(Epoch(0)));
+    }
+
+    #[test]
+    fn test_unscheduled_continuous_funding_encoding() {
+        use namada_core::borsh::BorshSerializeExt;
+
+        // Fundings without a schedule must keep the encoding of the fundings
+        // stored and proposed before schedules were introduced
+        let target = PGFTarget::Internal(PGFInternalTarget {
+            target: namada_core::address::testing::established_address_1(),
+            amount: Amount::native_whole(10),
+        });
+
+        let action = PGFAction::Continuous(AddRemove::Add(target.clone()));
+        let mut expected = vec![0, 0];
+        expected.extend(target.serialize_to_vec());
+        assert_eq!(action.serialize_to_vec(), expected);
+
+        let funding = StoragePgfFunding::new(target.clone(), 3);
+        let mut expected = target.serialize_to_vec();
+        expected.extend(3_u64.serialize_to_vec());
+        assert_eq!(funding.serialize_to_vec(), expected);
+    }
+}
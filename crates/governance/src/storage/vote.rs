@@ -1,6 +1,9 @@
 use std::fmt::Display;
+use std::str::FromStr;
 
 use borsh::{BorshDeserialize, BorshSerialize};
+use namada_core::dec::Dec;
+use namada_core::token;
 use namada_macros::BorshDeserializer;
 #[cfg(feature = "migrations")]
 use namada_migrations::*;
@@ -25,6 +28,13 @@ pub enum ProposalVote {
     Nay,
     /// Abstain
     Abstain,
+    /// No, with a veto. Counts as a nay vote and, if enough of the voting
+    /// power vetoes a proposal, its locked funds are burned instead of
+    /// being refunded to the author.
+    Veto,
+    /// A vote split between yay, nay and abstain, e.g. cast by a custodian
+    /// on behalf of its clients
+    Weighted(WeightedVote),
 }
 
 impl ProposalVote {
@@ -42,6 +52,108 @@ impl ProposalVote {
     pub fn is_abstain(&self) -> bool {
         matches!(self, ProposalVote::Abstain)
     }
+
+    /// Check if a vote is a veto
+    pub fn is_veto(&self) -> bool {
+        matches!(self, ProposalVote::Veto)
+    }
+
+    /// Check if a vote is split between multiple options
+    pub fn is_weighted(&self) -> bool {
+        matches!(self, ProposalVote::Weighted(_))
+    }
+
+    /// Get the fractions of the voting power assigned to each option
+    pub fn weights(&self) -> WeightedVote {
+        match self {
+            ProposalVote::Yay => WeightedVote {
+                yay: Dec::one(),
+                nay: Dec::zero(),
+                abstain: Dec::zero(),
+            },
+            ProposalVote::Nay | ProposalVote::Veto => WeightedVote {
+                yay: Dec::zero(),
+                nay: Dec::one(),
+                abstain: Dec::zero(),
+            },
+            ProposalVote::Abstain => WeightedVote {
+                yay: Dec::zero(),
+                nay: Dec::zero(),
+                abstain: Dec::one(),
+            },
+            ProposalVote::Weighted(weights) => *weights,
+        }
+    }
+
+    /// Check that the weights of a vote are non-negative and sum up to 1
+    pub fn is_valid(&self) -> bool {
+        match self {
+            ProposalVote::Weighted(weights) => weights.is_valid(),
+            _ => true,
+        }
+    }
+}
+
+/// The fractions of the voting power of a weighted vote
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    Eq,
+    Serialize,
+    Deserialize,
+)]
+pub struct WeightedVote {
+    /// Fraction of the voting power voting yay
+    pub yay: Dec,
+    /// Fraction of the voting power voting nay
+    pub nay: Dec,
+    /// Fraction of the voting power abstaining
+    pub abstain: Dec,
+}
+
+impl WeightedVote {
+    /// Create a new weighted vote, checking that the fractions are
+    /// non-negative and sum up to 1
+    pub fn new(yay: Dec, nay: Dec, abstain: Dec) -> Option<Self> {
+        let vote = Self { yay, nay, abstain };
+        vote.is_valid().then_some(vote)
+    }
+
+    /// Check that the fractions are non-negative and sum up to 1
+    pub fn is_valid(&self) -> bool {
+        [self.yay, self.nay, self.abstain]
+            .iter()
+            .all(|weight| !weight.is_negative() && *weight <= Dec::one())
+            && self.yay + self.nay + self.abstain == Dec::one()
+    }
+
+    /// Split the given voting power between yay, nay and abstain. Any
+    /// rounding remainder is attributed to abstain, so that the parts always
+    /// sum up to the given voting power.
+    pub fn split(
+        &self,
+        voting_power: token::Amount,
+    ) -> (token::Amount, token::Amount, token::Amount) {
+        let yay = self.yay * voting_power;
+        let nay = self.nay * voting_power;
+        let abstain = voting_power - yay - nay;
+        (yay, nay, abstain)
+    }
+}
+
+impl Display for WeightedVote {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "yay={},nay={},abstain={}",
+            self.yay, self.nay, self.abstain
+        )
+    }
 }
 
 impl Display for ProposalVote {
@@ -50,6 +162,8 @@ impl Display for ProposalVote {
             ProposalVote::Yay => write!(f, "yay"),
             ProposalVote::Nay => write!(f, "nay"),
             ProposalVote::Abstain => write!(f, "abstain"),
+            ProposalVote::Veto => write!(f, "veto"),
+            ProposalVote::Weighted(weights) => write!(f, "{weights}"),
         }
     }
 }
@@ -62,11 +176,51 @@ impl TryFrom<String> for ProposalVote {
             "yay" => Ok(ProposalVote::Yay),
             "nay" => Ok(ProposalVote::Nay),
             "abstain" => Ok(ProposalVote::Abstain),
+            "veto" => Ok(ProposalVote::Veto),
+            weighted if weighted.contains('=') => {
+                WeightedVote::from_str(weighted).map(ProposalVote::Weighted)
+            }
             _ => Err("invalid vote".to_string()),
         }
     }
 }
 
+impl FromStr for WeightedVote {
+    type Err = String;
+
+    /// Parse a weighted vote in the format `yay=0.6,nay=0.3,abstain=0.1`.
+    /// Omitted options get a weight of 0.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut yay = None;
+        let mut nay = None;
+        let mut abstain = None;
+        for part in s.split(',') {
+            let (option, weight) = part
+                .split_once('=')
+                .ok_or_else(|| format!("invalid weighted vote part: {part}"))?;
+            let weight = Dec::from_str(weight.trim())
+                .map_err(|e| format!("invalid vote weight {weight}: {e}"))?;
+            let slot = match option.trim().to_lowercase().as_str() {
+                "yay" => &mut yay,
+                "nay" => &mut nay,
+                "abstain" => &mut abstain,
+                _ => return Err(format!("invalid vote option: {option}")),
+            };
+            if slot.replace(weight).is_some() {
+                return Err(format!("duplicate vote option: {option}"));
+            }
+        }
+        WeightedVote::new(
+            yay.unwrap_or_default(),
+            nay.unwrap_or_default(),
+            abstain.unwrap_or_default(),
+        )
+        .ok_or_else(|| {
+            "vote weights must be non-negative and sum up to 1".to_string()
+        })
+    }
+}
+
 #[cfg(any(test, feature = "testing"))]
 /// Testing helpers and and strategies for governance proposals
 pub mod testing {
@@ -80,6 +234,19 @@ pub mod testing {
             Just(ProposalVote::Yay),
             Just(ProposalVote::Nay),
             Just(ProposalVote::Abstain),
+            Just(ProposalVote::Veto),
+            arb_weighted_vote().prop_map(ProposalVote::Weighted),
         ]
     }
+
+    /// Generate an arbitrary weighted vote whose weights sum up to 1
+    pub fn arb_weighted_vote() -> impl Strategy<Value = WeightedVote> {
+        (0..=100u64, 0..=100u64).prop_map(|(a, b)| {
+            let (low, high) = if a <= b { (a, b) } else { (b, a) };
+            let yay = Dec::new(low as i128, 2).unwrap();
+            let nay = Dec::new((high - low) as i128, 2).unwrap();
+            let abstain = Dec::one() - yay - nay;
+            WeightedVote { yay, nay, abstain }
+        })
+    }
 }
@@ -12,7 +12,7 @@ use namada_migrations::*;
 
 use super::cli::offline::OfflineVote;
 use super::storage::proposal::ProposalType;
-use super::storage::vote::ProposalVote;
+use super::storage::vote::{ProposalVote, WeightedVote};
 
 /// Proposal status
 pub enum ProposalStatus {
@@ -83,6 +83,8 @@ impl TallyType {
     pub fn from(proposal_type: ProposalType, is_steward: bool) -> Self {
         match (proposal_type, is_steward) {
             (ProposalType::Default(_), _) => TallyType::TwoThirds,
+            (ProposalType::ParameterChange(_), _) => TallyType::TwoThirds,
+            (ProposalType::Upgrade(_), _) => TallyType::TwoThirds,
             (ProposalType::PGFSteward(_), _) => TallyType::OneHalfOverOneThird,
             (ProposalType::PGFPayment(_), true) => {
                 TallyType::LessOneHalfOverOneThirdNay
@@ -196,6 +198,11 @@ pub struct ProposalResult {
     pub total_nay_power: VotePower,
     /// The total voting power from abstained votes
     pub total_abstain_power: VotePower,
+    /// The total voting power from veto votes, also counted in the nay votes.
+    /// It is stored under its own key to keep the layout of the results
+    /// stored before veto votes existed.
+    #[borsh(skip)]
+    pub total_veto_power: VotePower,
 }
 
 impl ProposalResult {
@@ -213,6 +220,38 @@ impl ProposalResult {
 
         at_least_two_third_voted && at_least_two_thirds_voted_nay
     }
+
+    /// Return true if the veto votes reached the given fraction of the voting
+    /// power that took part in the vote, in which case the proposal is
+    /// rejected and the funds it locked must be burned
+    pub fn is_vetoed(&self, veto_threshold: Dec) -> bool {
+        let total_voted_power = self.total_yay_power
+            + self.total_nay_power
+            + self.total_abstain_power;
+
+        !self.total_veto_power.is_zero()
+            && self.total_veto_power
+                >= total_voted_power.mul_ceil(veto_threshold)
+    }
+
+    /// Reject the proposal if it was vetoed with the given threshold, and
+    /// return whether it was
+    pub fn apply_veto(&mut self, veto_threshold: Dec) -> bool {
+        let is_vetoed = self.is_vetoed(veto_threshold);
+        if is_vetoed {
+            self.result = TallyResult::Rejected;
+        }
+        is_vetoed
+    }
+
+    /// Return true if an expedited proposal passed: on top of the regular
+    /// tally, the yay votes must reach the given fraction of the total voting
+    /// power
+    pub fn is_expedited_passed(&self, expedited_quorum: Dec) -> bool {
+        matches!(self.result, TallyResult::Passed)
+            && self.total_yay_power
+                >= self.total_voting_power.mul_ceil(expedited_quorum)
+    }
 }
 
 impl Display for ProposalResult {
@@ -229,12 +268,13 @@ impl Display for ProposalResult {
 
         write!(
             f,
-            "{} with {} yay votes, {} nay votes and {} abstain votes, total \
-             voting power: {}, threshold (fraction) of total voting power \
-             needed to tally: {} ({})",
+            "{} with {} yay votes, {} nay votes (of which {} veto votes) and \
+             {} abstain votes, total voting power: {}, threshold (fraction) \
+             of total voting power needed to tally: {} ({})",
             self.result,
             self.total_yay_power.to_string_native(),
             self.total_nay_power.to_string_native(),
+            self.total_veto_power.to_string_native(),
             self.total_abstain_power.to_string_native(),
             self.total_voting_power.to_string_native(),
             threshold.to_string_native(),
@@ -289,6 +329,30 @@ impl TallyVote {
         }
     }
 
+    /// Check if a vote is a veto
+    pub fn is_veto(&self) -> bool {
+        match self {
+            TallyVote::OnChain(vote) => vote.is_veto(),
+            TallyVote::Offline(vote) => vote.is_veto(),
+        }
+    }
+
+    /// Get the fractions of the voting power assigned to each option
+    pub fn weights(&self) -> WeightedVote {
+        match self {
+            TallyVote::OnChain(vote) => vote.weights(),
+            TallyVote::Offline(vote) => vote.vote.weights(),
+        }
+    }
+
+    /// Check that the weights of a vote are non-negative and sum up to 1
+    pub fn is_valid(&self) -> bool {
+        match self {
+            TallyVote::OnChain(vote) => vote.is_valid(),
+            TallyVote::Offline(vote) => vote.vote.is_valid(),
+        }
+    }
+
     /// Check if two votes are equal, returns an error if the variants of the
     /// two instances are different
     pub fn is_same_side(&self, other: &TallyVote) -> bool {
@@ -316,6 +380,11 @@ pub struct ProposalVotes {
     pub delegators_vote: HashMap<Address, TallyVote>,
     /// Map from delegator address to the corresponding validator voting power
     pub delegator_voting_power: HashMap<Address, HashMap<Address, VotePower>>,
+    /// Map from representative address to their vote
+    pub representatives_vote: HashMap<Address, TallyVote>,
+    /// Map from delegator address to the representative they assigned their
+    /// voting power to
+    pub delegator_representative: HashMap<Address, Address>,
 }
 
 impl ProposalVotes {
@@ -353,6 +422,39 @@ impl ProposalVotes {
             .insert(validator_address.clone(), voting_power);
         self.delegators_vote.insert(address.clone(), vote);
     }
+
+    /// Add vote corresponding to a representative
+    pub fn add_representative(&mut self, address: &Address, vote: TallyVote) {
+        self.representatives_vote.insert(address.clone(), vote);
+    }
+
+    /// Add the voting power of a delegator that assigned it to a
+    /// representative. The delegator's own vote, if any, takes precedence
+    /// over the representative's one.
+    pub fn add_represented_delegator(
+        &mut self,
+        address: &Address,
+        representative: &Address,
+        validator_address: &Address,
+        voting_power: VotePower,
+    ) {
+        self.delegator_voting_power
+            .entry(address.clone())
+            .or_default()
+            .insert(validator_address.clone(), voting_power);
+        self.delegator_representative
+            .insert(address.clone(), representative.clone());
+    }
+
+    /// Get the vote counting for a delegator: either its own vote or,
+    /// failing that, the vote of its representative
+    pub fn delegator_vote(&self, delegator: &Address) -> Option<&TallyVote> {
+        self.delegators_vote.get(delegator).or_else(|| {
+            self.delegator_representative.get(delegator).and_then(
+                |representative| self.representatives_vote.get(representative),
+            )
+        })
+    }
 }
 
 /// Compute the result of a proposal
@@ -364,62 +466,54 @@ pub fn compute_proposal_result(
     let mut yay_voting_power = VotePower::default();
     let mut nay_voting_power = VotePower::default();
     let mut abstain_voting_power = VotePower::default();
-
-    for (address, vote_power) in votes.validator_voting_power {
-        let vote_type = votes.validators_vote.get(&address);
-        if let Some(vote) = vote_type {
-            if vote.is_yay() {
-                yay_voting_power += vote_power;
-            } else if vote.is_nay() {
-                nay_voting_power += vote_power;
-            } else if vote.is_abstain() {
-                abstain_voting_power += vote_power;
-            }
+    let mut veto_voting_power = VotePower::default();
+
+    let mut add_votes = |vote: &TallyVote, voting_power: VotePower| {
+        let (yay, nay, abstain) = vote.weights().split(voting_power);
+        yay_voting_power += yay;
+        nay_voting_power += nay;
+        abstain_voting_power += abstain;
+        if vote.is_veto() {
+            veto_voting_power += voting_power;
         }
-    }
+    };
 
-    for (delegator, delegations) in votes.delegator_voting_power {
-        let delegator_vote = match votes.delegators_vote.get(&delegator) {
-            Some(vote) => vote,
-            None => continue,
+    // The voting power with which each validator votes, without the
+    // delegations whose delegators (or their representatives) voted
+    // differently
+    let mut validator_voting_power = votes.validator_voting_power.clone();
+
+    for (delegator, delegations) in &votes.delegator_voting_power {
+        let delegator_vote = match votes.delegator_vote(delegator) {
+            Some(vote) if vote.is_valid() => vote,
+            _ => continue,
         };
         for (validator, voting_power) in delegations {
-            let validator_vote = votes.validators_vote.get(&validator);
+            let voting_power = *voting_power;
+            let validator_vote = votes
+                .validators_vote
+                .get(validator)
+                .filter(|vote| vote.is_valid());
             if let Some(validator_vote) = validator_vote {
-                let validator_vote_is_same_side =
-                    validator_vote.is_same_side(delegator_vote);
-
-                if !validator_vote_is_same_side {
-                    if delegator_vote.is_yay() {
-                        yay_voting_power += voting_power;
-                        if validator_vote.is_nay() {
-                            nay_voting_power -= voting_power;
-                        } else if validator_vote.is_abstain() {
-                            abstain_voting_power -= voting_power;
-                        }
-                    } else if delegator_vote.is_nay() {
-                        nay_voting_power += voting_power;
-                        if validator_vote.is_yay() {
-                            yay_voting_power -= voting_power;
-                        } else if validator_vote.is_abstain() {
-                            abstain_voting_power -= voting_power;
-                        }
-                    } else if delegator_vote.is_abstain() {
-                        abstain_voting_power += voting_power;
-                        if validator_vote.is_yay() {
-                            yay_voting_power -= voting_power;
-                        } else if validator_vote.is_nay() {
-                            nay_voting_power -= voting_power;
-                        }
-                    }
+                if validator_vote.is_same_side(delegator_vote) {
+                    continue;
+                }
+                if let Some(validator_power) =
+                    validator_voting_power.get_mut(validator)
+                {
+                    *validator_power = validator_power
+                        .checked_sub(voting_power)
+                        .unwrap_or_default();
                 }
-            } else if delegator_vote.is_yay() {
-                yay_voting_power += voting_power;
-            } else if delegator_vote.is_nay() {
-                nay_voting_power += voting_power;
-            } else if delegator_vote.is_abstain() {
-                abstain_voting_power += voting_power;
             }
+            add_votes(delegator_vote, voting_power);
+        }
+    }
+
+    for (address, vote_power) in validator_voting_power {
+        match votes.validators_vote.get(&address) {
+            Some(vote) if vote.is_valid() => add_votes(vote, vote_power),
+            _ => continue,
         }
     }
 
@@ -438,6 +532,7 @@ pub fn compute_proposal_result(
         total_yay_power: yay_voting_power,
         total_nay_power: nay_voting_power,
         total_abstain_power: abstain_voting_power,
+        total_veto_power: veto_voting_power,
     }
 }
 
@@ -465,6 +560,7 @@ mod test {
     use std::ops::{Add, Sub};
 
     use namada_core::address;
+    use namada_core::borsh::BorshSerializeExt;
 
     use super::*;
 
@@ -1414,6 +1510,364 @@ mod test {
         assert!(!proposal_result.two_thirds_nay_over_two_thirds_total())
     }
 
+    #[test]
+    fn test_proposal_weighted_votes() {
+        let mut proposal_votes = ProposalVotes::default();
+
+        let weighted = |yay: i128, nay: i128, abstain: i128| {
+            ProposalVote::Weighted(
+                WeightedVote::new(
+                    Dec::new(yay, 1).unwrap(),
+                    Dec::new(nay, 1).unwrap(),
+                    Dec::new(abstain, 1).unwrap(),
+                )
+                .unwrap(),
+            )
+        };
+
+        let validator_address = address::testing::established_address_1();
+        proposal_votes.add_validator(
+            &validator_address,
+            token::Amount::from_u64(100),
+            weighted(6, 3, 1).into(),
+        );
+
+        let validator_address_two = address::testing::established_address_2();
+        proposal_votes.add_validator(
+            &validator_address_two,
+            token::Amount::from_u64(50),
+            ProposalVote::Yay.into(),
+        );
+
+        let delegator_address = address::testing::established_address_3();
+        proposal_votes.add_delegator(
+            &delegator_address,
+            &validator_address,
+            token::Amount::from_u64(20),
+            ProposalVote::Nay.into(),
+        );
+
+        let delegator_address_two = address::testing::established_address_4();
+        proposal_votes.add_delegator(
+            &delegator_address_two,
+            &validator_address_two,
+            token::Amount::from_u64(10),
+            weighted(5, 5, 0).into(),
+        );
+
+        for (tally_type, passed) in [
+            (TallyType::TwoThirds, false),
+            (TallyType::OneHalfOverOneThird, true),
+            (TallyType::LessOneHalfOverOneThirdNay, true),
+        ] {
+            let proposal_result = compute_proposal_result(
+                proposal_votes.clone(),
+                token::Amount::from(200),
+                tally_type,
+            );
+
+            assert_eq!(
+                matches!(proposal_result.result, TallyResult::Passed),
+                passed
+            );
+            // 80 * 0.6 from the first validator, 50 - 10 from the second
+            // validator and 10 * 0.5 from the second delegator
+            assert_eq!(
+                proposal_result.total_yay_power,
+                token::Amount::from(93),
+                "yay"
+            );
+            // 80 * 0.3 from the first validator, 20 from the first
+            // delegator and 10 * 0.5 from the second delegator
+            assert_eq!(
+                proposal_result.total_nay_power,
+                token::Amount::from(49),
+                "nay"
+            );
+            assert_eq!(
+                proposal_result.total_abstain_power,
+                token::Amount::from(8),
+                "abstain"
+            );
+        }
+    }
+
+    #[test]
+    fn test_proposal_weighted_votes_rounding_and_validity() {
+        let mut proposal_votes = ProposalVotes::default();
+
+        let third = Dec::one() / 3;
+        let validator_address = address::testing::established_address_1();
+        proposal_votes.add_validator(
+            &validator_address,
+            token::Amount::from_u64(100),
+            ProposalVote::Weighted(WeightedVote {
+                yay: third,
+                nay: third,
+                abstain: Dec::one() - third - third,
+            })
+            .into(),
+        );
+
+        // A vote whose weights do not sum up to 1 is ignored
+        let validator_address_two = address::testing::established_address_2();
+        proposal_votes.add_validator(
+            &validator_address_two,
+            token::Amount::from_u64(100),
+            ProposalVote::Weighted(WeightedVote {
+                yay: Dec::one(),
+                nay: Dec::one(),
+                abstain: Dec::zero(),
+            })
+            .into(),
+        );
+
+        let proposal_result = compute_proposal_result(
+            proposal_votes,
+            token::Amount::from(200),
+            TallyType::OneHalfOverOneThird,
+        );
+
+        assert!(matches!(proposal_result.result, TallyResult::Rejected));
+        assert_eq!(proposal_result.total_yay_power, token::Amount::from(33));
+        assert_eq!(proposal_result.total_nay_power, token::Amount::from(33));
+        // The rounding remainder goes to abstain
+        assert_eq!(
+            proposal_result.total_abstain_power,
+            token::Amount::from(34)
+        );
+    }
+
+    #[test]
+    fn test_proposal_representatives() {
+        let mut proposal_votes = ProposalVotes::default();
+
+        let validator_address = address::testing::established_address_1();
+        proposal_votes.add_validator(
+            &validator_address,
+            token::Amount::from_u64(100),
+            ProposalVote::Yay.into(),
+        );
+
+        let representative = address::testing::established_address_2();
+        proposal_votes
+            .add_representative(&representative, ProposalVote::Nay.into());
+        let idle_representative = address::testing::established_address_3();
+
+        // Represented delegator that didn't vote
+        let delegator_address = address::testing::established_address_4();
+        proposal_votes.add_represented_delegator(
+            &delegator_address,
+            &representative,
+            &validator_address,
+            token::Amount::from_u64(30),
+        );
+
+        // Represented delegator that voted directly
+        let delegator_address_two =
+            address::testing::address_from_simple_seed(5);
+        proposal_votes.add_delegator(
+            &delegator_address_two,
+            &validator_address,
+            token::Amount::from_u64(20),
+            ProposalVote::Abstain.into(),
+        );
+        proposal_votes.add_represented_delegator(
+            &delegator_address_two,
+            &representative,
+            &validator_address,
+            token::Amount::from_u64(20),
+        );
+
+        // Delegator whose representative didn't vote
+        let delegator_address_three =
+            address::testing::address_from_simple_seed(6);
+        proposal_votes.add_represented_delegator(
+            &delegator_address_three,
+            &idle_representative,
+            &validator_address,
+            token::Amount::from_u64(10),
+        );
+
+        let proposal_result = compute_proposal_result(
+            proposal_votes,
+            token::Amount::from(100),
+            TallyType::OneHalfOverOneThird,
+        );
+
+        assert!(matches!(proposal_result.result, TallyResult::Passed));
+        assert_eq!(
+            proposal_result.total_yay_power,
+            token::Amount::from(50),
+            "yay"
+        );
+        assert_eq!(
+            proposal_result.total_nay_power,
+            token::Amount::from(30),
+            "nay"
+        );
+        assert_eq!(
+            proposal_result.total_abstain_power,
+            token::Amount::from(20),
+            "abstain"
+        );
+    }
+
+    #[test]
+    fn test_proposal_veto() {
+        let mut proposal_votes = ProposalVotes::default();
+
+        let validator_address = address::testing::established_address_1();
+        let validator_voting_power = token::Amount::from_u64(100);
+        proposal_votes.add_validator(
+            &validator_address,
+            validator_voting_power,
+            ProposalVote::Yay.into(),
+        );
+
+        let delegator_address = address::testing::established_address_2();
+        let delegator_voting_power = token::Amount::from_u64(40);
+        proposal_votes.add_delegator(
+            &delegator_address,
+            &validator_address,
+            delegator_voting_power,
+            ProposalVote::Veto.into(),
+        );
+
+        let proposal_result = compute_proposal_result(
+            proposal_votes.clone(),
+            validator_voting_power,
+            TallyType::OneHalfOverOneThird,
+        );
+
+        // Veto votes are counted as nay votes
+        assert_eq!(
+            proposal_result.total_yay_power,
+            validator_voting_power.sub(delegator_voting_power)
+        );
+        assert_eq!(proposal_result.total_nay_power, delegator_voting_power);
+        assert_eq!(proposal_result.total_veto_power, delegator_voting_power);
+
+        assert!(proposal_result.is_vetoed(Dec::one() / 3));
+        assert!(proposal_result.is_vetoed(Dec::new(4, 1).unwrap()));
+        assert!(!proposal_result.is_vetoed(Dec::new(5, 1).unwrap()));
+
+        // The yay votes pass the tally, but reaching the veto threshold
+        // rejects the proposal
+        assert!(matches!(proposal_result.result, TallyResult::Passed));
+        let mut vetoed_result = proposal_result;
+        assert!(vetoed_result.apply_veto(Dec::one() / 3));
+        assert!(matches!(vetoed_result.result, TallyResult::Rejected));
+
+        // Below the veto threshold, the proposal still passes
+        let mut not_vetoed_result = proposal_result;
+        assert!(!not_vetoed_result.apply_veto(Dec::new(5, 1).unwrap()));
+        assert!(matches!(not_vetoed_result.result, TallyResult::Passed));
+
+        // Without veto votes, a proposal is never vetoed
+        proposal_votes.add_delegator(
+            &delegator_address,
+            &validator_address,
+            delegator_voting_power,
+            ProposalVote::Nay.into(),
+        );
+        let proposal_result = compute_proposal_result(
+            proposal_votes,
+            validator_voting_power,
+            TallyType::OneHalfOverOneThird,
+        );
+        assert_eq!(proposal_result.total_veto_power, token::Amount::zero());
+        assert!(!proposal_result.is_vetoed(Dec::zero()));
+    }
+
+    #[test]
+    fn test_proposal_result_storage_layout() {
+        let proposal_result = ProposalResult {
+            result: TallyResult::Rejected,
+            tally_type: TallyType::OneHalfOverOneThird,
+            total_voting_power: token::Amount::from_u64(100),
+            total_yay_power: token::Amount::from_u64(10),
+            total_nay_power: token::Amount::from_u64(60),
+            total_abstain_power: token::Amount::from_u64(5),
+            total_veto_power: token::Amount::from_u64(50),
+        };
+
+        // The veto power is not part of the encoding, so that the results
+        // stored before veto votes existed can still be decoded
+        let legacy_bytes = (
+            proposal_result.result,
+            proposal_result.tally_type,
+            proposal_result.total_voting_power,
+            proposal_result.total_yay_power,
+            proposal_result.total_nay_power,
+            proposal_result.total_abstain_power,
+        )
+            .serialize_to_vec();
+        assert_eq!(proposal_result.serialize_to_vec(), legacy_bytes);
+
+        let decoded = ProposalResult::try_from_slice(&legacy_bytes).unwrap();
+        assert_eq!(decoded.total_nay_power, proposal_result.total_nay_power);
+        assert_eq!(decoded.total_veto_power, token::Amount::zero());
+    }
+
+    #[test]
+    fn test_proposal_expedited_tally() {
+        let mut proposal_votes = ProposalVotes::default();
+
+        let validator_address = address::testing::established_address_1();
+        let validator_voting_power = token::Amount::from_u64(60);
+        proposal_votes.add_validator(
+            &validator_address,
+            validator_voting_power,
+            ProposalVote::Yay.into(),
+        );
+        let total_voting_power = token::Amount::from_u64(100);
+
+        // Passes the regular tally, but not the expedited one
+        let proposal_result = compute_proposal_result(
+            proposal_votes.clone(),
+            total_voting_power,
+            TallyType::OneHalfOverOneThird,
+        );
+        assert!(matches!(proposal_result.result, TallyResult::Passed));
+        assert!(!proposal_result.is_expedited_passed(Dec::two() / 3));
+        assert!(proposal_result.is_expedited_passed(Dec::new(6, 1).unwrap()));
+
+        // Reaches the expedited quorum
+        let other_validator_address = address::testing::established_address_2();
+        proposal_votes.add_validator(
+            &other_validator_address,
+            token::Amount::from_u64(7),
+            ProposalVote::Yay.into(),
+        );
+        let proposal_result = compute_proposal_result(
+            proposal_votes.clone(),
+            total_voting_power,
+            TallyType::OneHalfOverOneThird,
+        );
+        assert!(proposal_result.is_expedited_passed(Dec::two() / 3));
+
+        // Never passes if the regular tally fails
+        let mut proposal_votes = ProposalVotes::default();
+        proposal_votes.add_validator(
+            &validator_address,
+            token::Amount::from_u64(30),
+            ProposalVote::Yay.into(),
+        );
+        proposal_votes.add_validator(
+            &other_validator_address,
+            token::Amount::from_u64(40),
+            ProposalVote::Nay.into(),
+        );
+        let proposal_result = compute_proposal_result(
+            proposal_votes,
+            total_voting_power,
+            TallyType::OneHalfOverOneThird,
+        );
+        assert!(matches!(proposal_result.result, TallyResult::Rejected));
+        assert!(!proposal_result.is_expedited_passed(Dec::zero()));
+    }
+
     #[test]
     fn test_validator_voting_period() {
         assert!(!is_valid_validator_voting_period(
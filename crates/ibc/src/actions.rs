@@ -1,17 +1,20 @@
 //! Implementation of `IbcActions` with the protocol storage
 
 use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet};
 use std::rc::Rc;
 
 use namada_core::address::{Address, InternalAddress};
 use namada_core::ibc::apps::transfer::types::msgs::transfer::MsgTransfer;
 use namada_core::ibc::apps::transfer::types::packet::PacketData;
-use namada_core::ibc::apps::transfer::types::PrefixedCoin;
+use namada_core::ibc::apps::transfer::types::{ack_success_b64, PrefixedCoin};
+use namada_core::ibc::core::channel::types::acknowledgement::AcknowledgementStatus;
 use namada_core::ibc::core::channel::types::timeout::TimeoutHeight;
 use namada_core::ibc::primitives::Msg;
-use namada_core::ibc::IbcEvent;
+use namada_core::ibc::{IbcEvent, EVENT_TYPE_PACKET};
 use namada_core::tendermint::Time as TmTime;
 use namada_core::token::DenominatedAmount;
+use namada_governance::pgf::storage as pgf_storage;
 use namada_governance::storage::proposal::PGFIbcTarget;
 use namada_parameters::read_epoch_duration_parameter;
 use namada_state::write_log::WriteLog;
@@ -440,13 +443,13 @@ where
 
 impl<S> IbcCommonContext for IbcProtocolContext<'_, S> where S: State {}
 
-/// Transfer tokens over IBC
+/// Transfer tokens over IBC and return the sequence of the sent packet
 pub fn transfer_over_ibc<D, H>(
     state: &mut WlState<D, H>,
     token: &Address,
     source: &Address,
     target: &PGFIbcTarget,
-) -> StorageResult<()>
+) -> StorageResult<u64>
 where
     D: DB + for<'iter> DBIter<'iter> + 'static,
     H: StorageHasher + 'static,
@@ -481,7 +484,66 @@ where
     let mut data = vec![];
     prost::Message::encode(&any_msg, &mut data).into_storage_result()?;
 
-    let ctx = IbcProtocolContext { state };
-    let mut actions = IbcActions::new(Rc::new(RefCell::new(ctx)));
-    actions.execute(&data).into_storage_result()
+    let ctx = Rc::new(RefCell::new(IbcProtocolContext { state }));
+    let sequence = ctx
+        .borrow()
+        .get_next_sequence_send(&target.port_id, &target.channel_id)
+        .into_storage_result()?;
+    let mut actions = IbcActions::new(ctx);
+    actions.execute(&data).into_storage_result()?;
+    Ok(sequence.into())
+}
+
+/// Settle the pgf payments sent over IBC whose packets are acknowledged or
+/// timed out by the given IBC events. The payments refunded to pgf are
+/// recorded as failed.
+pub fn settle_pgf_ibc_payments<S>(
+    storage: &mut S,
+    events: &BTreeSet<IbcEvent>,
+) -> StorageResult<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let success_ack =
+        AcknowledgementStatus::success(ack_success_b64()).to_string();
+    // Whether each settled packet is refunded to the sender
+    let mut settled_packets = BTreeMap::new();
+    for event in events {
+        let is_refunded = match event.event_type.as_str() {
+            "timeout_packet" => true,
+            // The acknowledgement events of the transfer module are tagged
+            // with their packet, and an error acknowledgement is refunded
+            EVENT_TYPE_PACKET => {
+                event.attributes.contains_key("error")
+                    || event
+                        .attributes
+                        .get("acknowledgement")
+                        .is_some_and(|ack| *ack != success_ack)
+            }
+            _ => continue,
+        };
+        let (Some(port_id), Some(channel_id), Some(sequence)) = (
+            event.attributes.get("packet_src_port"),
+            event.attributes.get("packet_src_channel"),
+            event.attributes.get("packet_sequence"),
+        ) else {
+            continue;
+        };
+        let Ok(sequence) = sequence.parse::<u64>() else {
+            continue;
+        };
+        let packet_id =
+            pgf_storage::ibc_packet_id(port_id, channel_id, sequence);
+        *settled_packets.entry(packet_id).or_insert(false) |= is_refunded;
+    }
+    let epoch = storage.get_block_epoch()?;
+    for (packet_id, is_refunded) in settled_packets {
+        pgf_storage::settle_ibc_payment(
+            storage,
+            epoch,
+            &packet_id,
+            is_refunded,
+        )?;
+    }
+    Ok(())
 }
@@ -28,6 +28,7 @@ use namada_core::ibc::core::host::types::identifiers::{
     ChannelId, ConnectionId, PortId,
 };
 use namada_core::ibc::core::router::module::Module;
+use namada_core::ibc::core::router::types::event::ModuleEvent;
 use namada_core::ibc::core::router::types::module::{ModuleExtras, ModuleId};
 use namada_core::ibc::primitives::Signer;
 
@@ -290,12 +291,17 @@ where
         acknowledgement: &Acknowledgement,
         relayer: &Signer,
     ) -> (ModuleExtras, Result<(), PacketError>) {
-        let (extras, result) = on_acknowledgement_packet_execute(
+        let (mut extras, result) = on_acknowledgement_packet_execute(
             &mut self.ctx,
             packet,
             acknowledgement,
             relayer,
         );
+        // The acknowledgement events of the module don't identify the packet
+        // they settle
+        for event in &mut extras.events {
+            add_packet_attributes(event, packet);
+        }
         (extras, result.map_err(into_packet_error))
     }
 
@@ -319,6 +325,16 @@ where
     }
 }
 
+/// Add the identifiers of the given packet to the attributes of an event of
+/// the module, like the ones of the packet events of the core handlers
+pub fn add_packet_attributes(event: &mut ModuleEvent, packet: &Packet) {
+    event.attributes.extend([
+        ("packet_src_port", packet.port_id_on_a.to_string()).into(),
+        ("packet_src_channel", packet.chan_id_on_a.to_string()).into(),
+        ("packet_sequence", packet.seq_on_a.to_string()).into(),
+    ]);
+}
+
 fn into_channel_error(error: TokenTransferError) -> ChannelError {
     ChannelError::AppModule {
         description: error.to_string(),
@@ -9,7 +9,9 @@ use std::fmt::Debug;
 use std::rc::Rc;
 use std::str::FromStr;
 
-pub use actions::{transfer_over_ibc, CompatibleIbcTxHostEnvState};
+pub use actions::{
+    settle_pgf_ibc_payments, transfer_over_ibc, CompatibleIbcTxHostEnvState,
+};
 use borsh::BorshDeserialize;
 pub use context::common::IbcCommonContext;
 use context::router::IbcRouter;
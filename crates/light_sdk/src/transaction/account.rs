@@ -22,12 +22,14 @@ impl InitAccount {
         public_keys: Vec<common::PublicKey>,
         vp_code_hash: Hash,
         threshold: u8,
+        vesting: Option<namada_sdk::account::Vesting>,
         args: GlobalArgs,
     ) -> Self {
         let init_account = namada_sdk::account::InitAccount {
             public_keys,
             vp_code_hash,
             threshold,
+            vesting,
         };
 
         Self(transaction::build_tx(
@@ -1,5 +1,5 @@
 use namada_sdk::address::Address;
-use namada_sdk::governance::{ProposalType, ProposalVote};
+use namada_sdk::governance::{ProposalType, ProposalVote, WeightedVote};
 use namada_sdk::hash::Hash;
 use namada_sdk::key::common;
 use namada_sdk::storage::Epoch;
@@ -27,6 +27,7 @@ impl InitProposal {
         voting_start_epoch: Epoch,
         voting_end_epoch: Epoch,
         grace_epoch: Epoch,
+        expedited: bool,
         args: GlobalArgs,
     ) -> Self {
         let init_proposal = namada_sdk::governance::InitProposalData {
@@ -37,6 +38,7 @@ impl InitProposal {
             voting_start_epoch,
             voting_end_epoch,
             grace_epoch,
+            expedited,
         };
 
         Self(transaction::build_tx(
@@ -125,6 +127,24 @@ impl VoteProposal {
         ))
     }
 
+    /// Build a raw VoteProposal transaction splitting the voting power of the
+    /// voter between yay, nay and abstain according to the given weights
+    pub fn new_weighted(
+        id: u64,
+        weights: WeightedVote,
+        voter: Address,
+        delegations: Vec<Address>,
+        args: GlobalArgs,
+    ) -> Self {
+        Self::new(
+            id,
+            ProposalVote::Weighted(weights),
+            voter,
+            delegations,
+            args,
+        )
+    }
+
     /// Get the bytes to sign for the given transaction
     pub fn get_sign_bytes(&self) -> Vec<Hash> {
         transaction::get_sign_bytes(&self.0)
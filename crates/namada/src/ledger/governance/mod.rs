@@ -1,14 +1,19 @@
 //! Governance VP
 
+pub mod parameter_change;
 pub mod utils;
 
 use std::collections::BTreeSet;
 
 use borsh::BorshDeserialize;
 use namada_governance::storage::proposal::{
-    AddRemove, PGFAction, ProposalType,
+    AddRemove, PGFAction, ProposalType, MAX_UPGRADE_NAME_LENGTH,
+};
+use namada_governance::storage::{
+    get_expedited_min_proposal_fund, get_expedited_min_proposal_grace_epochs,
+    get_expedited_proposal_voting_period, is_proposal_accepted,
+    is_representative, keys as gov_storage,
 };
-use namada_governance::storage::{is_proposal_accepted, keys as gov_storage};
 use namada_governance::utils::is_valid_validator_voting_period;
 use namada_governance::ProposalVote;
 use namada_proof_of_stake::is_validator;
@@ -35,6 +40,9 @@ pub const ADDRESS: Address = Address::Internal(InternalAddress::Governance);
 /// The maximum number of item in a pgf proposal
 pub const MAX_PGF_ACTIONS: usize = 20;
 
+/// The maximum number of parameter changes in a parameter change proposal
+pub const MAX_PARAMETER_CHANGES: usize = 20;
+
 #[allow(missing_docs)]
 #[derive(Error, Debug)]
 pub enum Error {
@@ -86,6 +94,15 @@ where
                 (KeyType::VOTE, Some(proposal_id)) => {
                     self.is_valid_vote_key(proposal_id, key, verifiers)
                 }
+                (KeyType::REPRESENTATIVE_VOTE, Some(proposal_id)) => self
+                    .is_valid_representative_vote_key(
+                        proposal_id,
+                        key,
+                        verifiers,
+                    ),
+                (KeyType::REPRESENTATIVE, _) => {
+                    self.is_valid_representative_key(key, verifiers)
+                }
                 (KeyType::CONTENT, Some(proposal_id)) => {
                     self.is_valid_content_key(proposal_id)
                 }
@@ -110,6 +127,9 @@ where
                 (KeyType::AUTHOR, Some(proposal_id)) => {
                     self.is_valid_author(proposal_id, verifiers)
                 }
+                (KeyType::EXPEDITED, Some(proposal_id)) => {
+                    self.is_valid_expedited(proposal_id)
+                }
                 (KeyType::COUNTER, _) => self.is_valid_counter(set_count),
                 (KeyType::PROPOSAL_COMMIT, _) => {
                     self.is_valid_proposal_commit()
@@ -220,11 +240,16 @@ where
             delegation_address.clone(),
         );
 
-        if self
-            .force_read::<ProposalVote>(&vote_key, ReadType::Post)
-            .is_err()
-        {
-            return Err(Error::InvalidVoteKey(key.to_string()));
+        match self.force_read::<ProposalVote>(&vote_key, ReadType::Post) {
+            Ok(vote) if !vote.is_valid() => {
+                tracing::info!(
+                    "Invalid weighted vote {vote}: the weights must be \
+                     non-negative and sum up to 1."
+                );
+                return Ok(false);
+            }
+            Ok(_) => {}
+            Err(_) => return Err(Error::InvalidVoteKey(key.to_string())),
         }
 
         // TODO: We should refactor this by modifying the vote proposal tx
@@ -296,6 +321,100 @@ where
         Ok(is_delegator)
     }
 
+    fn is_valid_representative_vote_key(
+        &self,
+        proposal_id: u64,
+        key: &Key,
+        verifiers: &BTreeSet<Address>,
+    ) -> Result<bool> {
+        let representative = gov_storage::get_representative_vote_address(key)
+            .ok_or_else(|| Error::InvalidVoteKey(key.to_string()))?;
+
+        let counter_key = gov_storage::get_counter_key();
+        let pre_counter: u64 = self.force_read(&counter_key, ReadType::Pre)?;
+        if pre_counter <= proposal_id {
+            tracing::info!(
+                "Invalid proposal ID. Expected {pre_counter} or lower, got \
+                 {proposal_id}."
+            );
+            return Ok(false);
+        }
+
+        let vote: ProposalVote = self.force_read(key, ReadType::Post)?;
+        if !vote.is_valid() {
+            tracing::info!(
+                "Invalid weighted vote {vote}: the weights must be \
+                 non-negative and sum up to 1."
+            );
+            return Ok(false);
+        }
+
+        let current_epoch = self.ctx.get_block_epoch()?;
+        let pre_voting_start_epoch: Epoch = self.force_read(
+            &gov_storage::get_voting_start_epoch_key(proposal_id),
+            ReadType::Pre,
+        )?;
+        let pre_voting_end_epoch: Epoch = self.force_read(
+            &gov_storage::get_voting_end_epoch_key(proposal_id),
+            ReadType::Pre,
+        )?;
+        // A validator representative is restricted to the validator voting
+        // window
+        let is_validator = is_validator(&self.ctx.pre(), representative)?;
+        if !self.is_valid_voting_window(
+            current_epoch,
+            pre_voting_start_epoch,
+            pre_voting_end_epoch,
+            is_validator,
+        ) {
+            tracing::info!(
+                "Voted outside voting window. Current epoch: {current_epoch}, \
+                 start: {pre_voting_start_epoch}, end: {pre_voting_end_epoch}."
+            );
+            return Ok(false);
+        }
+
+        Ok(verifiers.contains(representative)
+            && is_representative(&self.ctx.pre(), representative)?)
+    }
+
+    /// Validate a change of the representative of a delegator. The
+    /// delegator must be a verifier and the representative key must be
+    /// consistent with the index of represented delegators.
+    fn is_valid_representative_key(
+        &self,
+        key: &Key,
+        verifiers: &BTreeSet<Address>,
+    ) -> Result<bool> {
+        if let Some(delegator) = gov_storage::is_representative_key(key) {
+            let post: Option<Address> = self.ctx.post().read(key)?;
+            let is_consistent = match &post {
+                Some(representative) if representative == delegator => false,
+                Some(representative) => self.ctx.post().has_key(
+                    &gov_storage::get_represented_key(
+                        representative,
+                        delegator,
+                    ),
+                )?,
+                None => true,
+            };
+            Ok(is_consistent && verifiers.contains(delegator))
+        } else if let Some((representative, delegator)) =
+            gov_storage::is_represented_key(key)
+        {
+            let post: Option<Address> = self
+                .ctx
+                .post()
+                .read(&gov_storage::get_representative_key(delegator))?;
+            let is_represented = self.ctx.post().has_key(key)?;
+            let is_consistent =
+                is_represented == (post.as_ref() == Some(representative));
+            Ok(is_consistent && verifiers.contains(delegator))
+        } else {
+            Ok(false)
+        }
+    }
+
     /// Validate a content key
     pub fn is_valid_content_key(&self, proposal_id: u64) -> Result<bool> {
         let content_key: Key = gov_storage::get_content_key(proposal_id);
@@ -382,6 +501,9 @@ where
                         PGFAction::Continuous(AddRemove::Add(target)) => {
                             Some(target.target().to_lowercase())
                         }
+                        PGFAction::ScheduledContinuous(funding) => {
+                            Some(funding.target().to_lowercase())
+                        }
                         _ => None,
                     })
                     .collect::<BTreeSet<String>>();
@@ -403,15 +525,23 @@ where
                     .filter(|funding| matches!(funding, PGFAction::Retro(_)))
                     .count();
 
+                let total_funding_rounds = fundings
+                    .iter()
+                    .filter(|funding| {
+                        matches!(funding, PGFAction::FundingRound(_))
+                    })
+                    .count();
+
                 let is_total_fundings_valid = fundings.len() < MAX_PGF_ACTIONS;
 
                 // check that they are unique by checking that the set of add
-                // plus the set of remove plus the set of retro is equal to the
-                // total fundings
+                // plus the set of remove plus the set of retro plus the funding
+                // rounds is equal to the total fundings
                 let are_continuous_fundings_unique =
                     are_continuous_add_targets_unique.len()
                         + are_continuous_remove_targets_unique.len()
                         + total_retro_targerts
+                        + total_funding_rounds
                         == fundings.len();
 
                 // can't remove and add the same target in the same proposal
@@ -420,9 +550,62 @@ where
                     .count() as u64
                     == 0;
 
+                // the schedule of the added fundings and funding rounds must be
+                // consistent with the epoch in which the proposal is executed
+                let grace_epoch_key =
+                    gov_storage::get_grace_epoch_key(proposal_id);
+                let grace_epoch: Epoch =
+                    self.force_read(&grace_epoch_key, ReadType::Post)?;
+                let are_schedules_valid =
+                    fundings.iter().all(|funding| match funding {
+                        PGFAction::ScheduledContinuous(funding) => {
+                            funding.schedule.is_valid(grace_epoch)
+                        }
+                        PGFAction::FundingRound(round) => {
+                            round.is_valid(grace_epoch)
+                        }
+                        _ => true,
+                    });
+
                 Ok(is_total_fundings_valid
                     && are_continuous_fundings_unique
-                    && are_targets_unique)
+                    && are_targets_unique
+                    && are_schedules_valid)
+            }
+            ProposalType::ParameterChange(changes) => {
+                let is_total_changes_valid = !changes.is_empty()
+                    && changes.len() < MAX_PARAMETER_CHANGES;
+
+                // the same parameter can't be changed twice
+                let are_changes_unique = changes
+                    .iter()
+                    .map(|change| (change.module, change.parameter.as_str()))
+                    .collect::<BTreeSet<_>>()
+                    .len()
+                    == changes.len();
+
+                // the resulting parameters must be valid with respect to the
+                // ones currently in storage
+                let are_changes_valid =
+                    parameter_change::validate_parameter_changes(
+                        &self.ctx.pre(),
+                        &changes,
+                    )
+                    .is_ok();
+
+                Ok(is_total_changes_valid
+                    && are_changes_unique
+                    && are_changes_valid)
+            }
+            ProposalType::Upgrade(upgrade) => {
+                let is_valid_name = !upgrade.name.is_empty()
+                    && upgrade.name.len() <= MAX_UPGRADE_NAME_LENGTH;
+
+                // the upgrade can't be scheduled in the past
+                let current_height = self.ctx.get_block_height()?;
+                let is_valid_height = upgrade.height > current_height;
+
+                Ok(is_valid_name && is_valid_height)
             }
             _ => Ok(true), // default proposal
         }
@@ -476,8 +659,11 @@ where
             self.force_read(&end_epoch_key, ReadType::Post)?;
         let grace_epoch: Epoch =
             self.force_read(&grace_epoch_key, ReadType::Post)?;
-        let min_grace_epoch: u64 =
-            self.force_read(&min_grace_epoch_key, ReadType::Pre)?;
+        let min_grace_epoch: u64 = if self.is_expedited(proposal_id)? {
+            get_expedited_min_proposal_grace_epochs(&self.ctx.pre())?
+        } else {
+            self.force_read(&min_grace_epoch_key, ReadType::Pre)?
+        };
         let max_proposal_period: u64 =
             self.force_read(&max_proposal_period, ReadType::Pre)?;
 
@@ -551,6 +737,11 @@ where
             return Ok(false);
         }
 
+        if self.is_expedited(proposal_id)? {
+            return self
+                .is_valid_expedited_voting_period(start_epoch, end_epoch);
+        }
+
         Ok((end_epoch - start_epoch) % min_period == 0
             && (end_epoch - start_epoch).0 >= min_period)
     }
@@ -591,11 +782,61 @@ where
             );
             return Ok(false);
         }
+
+        if self.is_expedited(proposal_id)? {
+            return self
+                .is_valid_expedited_voting_period(start_epoch, end_epoch);
+        }
+
         Ok((end_epoch - start_epoch) % min_period == 0
             && (end_epoch - start_epoch).0 >= min_period
             && (end_epoch - start_epoch).0 <= max_period)
     }
 
+    /// Validate the voting period of an expedited proposal
+    fn is_valid_expedited_voting_period(
+        &self,
+        start_epoch: Epoch,
+        end_epoch: Epoch,
+    ) -> Result<bool> {
+        let expedited_period =
+            get_expedited_proposal_voting_period(&self.ctx.pre())?;
+
+        let is_valid = (end_epoch - start_epoch).0 == expedited_period;
+        if !is_valid {
+            tracing::info!(
+                "Expected an expedited voting period of {expedited_period} \
+                 epochs, got start = {start_epoch}, end = {end_epoch}."
+            );
+        }
+        Ok(is_valid)
+    }
+
+    /// Validate the flag marking a proposal as expedited. It can only be set
+    /// when the proposal is created.
+    pub fn is_valid_expedited(&self, proposal_id: u64) -> Result<bool> {
+        let expedited_key =
+            gov_storage::get_proposal_expedited_key(proposal_id);
+        let author_key = gov_storage::get_author_key(proposal_id);
+
+        let has_pre_expedited = self.ctx.has_key_pre(&expedited_key)?;
+        let has_pre_author = self.ctx.has_key_pre(&author_key)?;
+        if has_pre_expedited || has_pre_author {
+            return Ok(false);
+        }
+
+        let is_expedited: bool =
+            self.force_read(&expedited_key, ReadType::Post)?;
+        Ok(is_expedited)
+    }
+
+    /// Check if a proposal is expedited
+    fn is_expedited(&self, proposal_id: u64) -> Result<bool> {
+        let expedited_key =
+            gov_storage::get_proposal_expedited_key(proposal_id);
+        Ok(self.ctx.post().read(&expedited_key)?.unwrap_or_default())
+    }
+
     /// Validate a funds key
     pub fn is_valid_funds(
         &self,
@@ -610,7 +851,11 @@ where
         let min_funds_parameter_key = gov_storage::get_min_proposal_fund_key();
 
         let min_funds_parameter: token::Amount =
-            self.force_read(&min_funds_parameter_key, ReadType::Pre)?;
+            if self.is_expedited(proposal_id)? {
+                get_expedited_min_proposal_fund(&self.ctx.pre())?
+            } else {
+                self.force_read(&min_funds_parameter_key, ReadType::Pre)?
+            };
         let pre_balance: Option<token::Amount> =
             self.ctx.pre().read(&balance_key)?;
         let post_balance: token::Amount =
@@ -787,6 +1032,10 @@ enum KeyType {
     #[allow(non_camel_case_types)]
     VOTE,
     #[allow(non_camel_case_types)]
+    REPRESENTATIVE_VOTE,
+    #[allow(non_camel_case_types)]
+    REPRESENTATIVE,
+    #[allow(non_camel_case_types)]
     CONTENT,
     #[allow(non_camel_case_types)]
     PROPOSAL_CODE,
@@ -807,6 +1056,8 @@ enum KeyType {
     #[allow(non_camel_case_types)]
     AUTHOR,
     #[allow(non_camel_case_types)]
+    EXPEDITED,
+    #[allow(non_camel_case_types)]
     PARAMETER,
     #[allow(non_camel_case_types)]
     UNKNOWN_GOVERNANCE,
@@ -818,6 +1069,12 @@ impl KeyType {
     fn from_key(key: &Key, native_token: &Address) -> Self {
         if gov_storage::is_vote_key(key) {
             Self::VOTE
+        } else if gov_storage::is_representative_vote_key(key) {
+            Self::REPRESENTATIVE_VOTE
+        } else if gov_storage::is_representative_key(key).is_some()
+            || gov_storage::is_represented_key(key).is_some()
+        {
+            Self::REPRESENTATIVE
         } else if gov_storage::is_content_key(key) {
             KeyType::CONTENT
         } else if gov_storage::is_proposal_type_key(key) {
@@ -836,6 +1093,8 @@ impl KeyType {
             KeyType::FUNDS
         } else if gov_storage::is_author_key(key) {
             KeyType::AUTHOR
+        } else if gov_storage::is_proposal_expedited_key(key) {
+            KeyType::EXPEDITED
         } else if gov_storage::is_counter_key(key) {
             KeyType::COUNTER
         } else if gov_storage::is_parameter_key(key) {
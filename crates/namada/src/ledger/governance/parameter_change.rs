@@ -0,0 +1,615 @@
+//! Protocol parameter changes proposed by governance
+
+use std::num::NonZeroU64;
+
+use namada_core::borsh::BorshSerializeExt;
+use namada_core::dec::Dec;
+use namada_ethereum_bridge::storage as bridge_storage;
+use namada_ethereum_bridge::storage::parameters::MinimumConfirmations;
+use namada_governance::pgf::storage::keys as pgf_storage;
+use namada_governance::storage::proposal::{
+    ParameterChange, ParameterChangeError, ParameterModule, ParameterValue,
+};
+use namada_governance::storage::{
+    get_parameters as read_gov_params, keys as gov_storage,
+};
+use namada_parameters::storage as params_storage;
+use namada_proof_of_stake::storage::{
+    get_num_consensus_validators, read_pos_params,
+};
+use namada_proof_of_stake::storage_key as pos_storage;
+use namada_state::{StorageRead, StorageWrite};
+use thiserror::Error;
+
+use crate::storage::{Epoch, Key};
+
+#[allow(missing_docs)]
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    InvalidChange(#[from] ParameterChangeError),
+    #[error("Invalid value for {0}.{1}: {2}")]
+    InvalidValue(ParameterModule, String, String),
+    #[error("The updated {0} parameters are invalid: {1}")]
+    InvalidParameters(ParameterModule, String),
+    #[error("Storage error: {0}")]
+    Storage(#[from] namada_state::StorageError),
+}
+
+/// Parameter change result
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Check that the given parameter changes can be applied on top of the
+/// parameters currently in storage
+pub fn validate_parameter_changes<S>(
+    storage: &S,
+    changes: &[ParameterChange],
+) -> Result<()>
+where
+    S: StorageRead,
+{
+    updated_parameters(storage, changes).map(|_| ())
+}
+
+/// Apply the given parameter changes to storage. Either all of the changes
+/// are applied or, if any of them is invalid, none of them.
+pub fn apply_parameter_changes<S>(
+    storage: &mut S,
+    changes: &[ParameterChange],
+) -> Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    for (key, value) in updated_parameters(storage, changes)? {
+        storage.write_bytes(&key, value)?;
+    }
+    Ok(())
+}
+
+/// Compute the storage writes implementing the given parameter changes.
+/// Parameters stored as a whole (e.g. PoS parameters) are updated and
+/// validated together.
+fn updated_parameters<S>(
+    storage: &S,
+    changes: &[ParameterChange],
+) -> Result<Vec<(Key, Vec<u8>)>>
+where
+    S: StorageRead,
+{
+    let mut writes = vec![];
+    let mut pos_params = None;
+    let mut gov_params = None;
+
+    for change in changes {
+        let value = change.parse_value()?;
+        let invalid_value = |err: &str| {
+            Error::InvalidValue(
+                change.module,
+                change.parameter.clone(),
+                err.to_string(),
+            )
+        };
+        let parameter = change.parameter.as_str();
+
+        match change.module {
+            ParameterModule::Protocol => {
+                let value = as_integer(value, invalid_value)?;
+                if value == 0 {
+                    return Err(invalid_value("must be positive"));
+                }
+                let (key, value) = match parameter {
+                    "max_tx_bytes" => (
+                        params_storage::get_max_tx_bytes_key(),
+                        u32::try_from(value)
+                            .map_err(|err| invalid_value(&err.to_string()))?
+                            .serialize_to_vec(),
+                    ),
+                    "max_block_gas" => (
+                        params_storage::get_max_block_gas_key(),
+                        value.serialize_to_vec(),
+                    ),
+                    "epochs_per_year" => (
+                        params_storage::get_epochs_per_year_key(),
+                        value.serialize_to_vec(),
+                    ),
+                    "max_signatures_per_transaction" => (
+                        params_storage::get_max_signatures_per_transaction_key(
+                        ),
+                        u8::try_from(value)
+                            .map_err(|err| invalid_value(&err.to_string()))?
+                            .serialize_to_vec(),
+                    ),
+                    "fee_unshielding_gas_limit" => (
+                        params_storage::get_fee_unshielding_gas_limit_key(),
+                        value.serialize_to_vec(),
+                    ),
+                    "fee_unshielding_descriptions_limit" => (
+                        params_storage::get_fee_unshielding_descriptions_limit_key(),
+                        value.serialize_to_vec(),
+                    ),
+                    _ => return Err(unknown_parameter(change)),
+                };
+                writes.push((key, value));
+            }
+            ParameterModule::Pos => {
+                let params = match pos_params.as_mut() {
+                    Some(params) => params,
+                    None => pos_params.insert(read_pos_params(storage)?.owned),
+                };
+                match (parameter, value) {
+                    ("max_validator_slots", ParameterValue::Integer(value)) => {
+                        // The validator sets are not resized, so the
+                        // consensus sets already populated must fit
+                        let num_consensus_validators =
+                            max_num_consensus_validators(
+                                storage,
+                                params.pipeline_len,
+                            )?;
+                        if value == 0 || value < num_consensus_validators {
+                            return Err(invalid_value(&format!(
+                                "must be positive and at least the number of \
+                                 consensus validators \
+                                 ({num_consensus_validators})"
+                            )));
+                        }
+                        params.max_validator_slots = value
+                    }
+                    (
+                        "block_proposer_reward",
+                        ParameterValue::Decimal(value),
+                    ) => params.block_proposer_reward = value,
+                    ("block_vote_reward", ParameterValue::Decimal(value)) => {
+                        params.block_vote_reward = value
+                    }
+                    ("max_inflation_rate", ParameterValue::Decimal(value)) => {
+                        params.max_inflation_rate = value
+                    }
+                    ("target_staked_ratio", ParameterValue::Decimal(value)) => {
+                        params.target_staked_ratio = value
+                    }
+                    (
+                        "duplicate_vote_min_slash_rate",
+                        ParameterValue::Decimal(value),
+                    ) => params.duplicate_vote_min_slash_rate = value,
+                    (
+                        "light_client_attack_min_slash_rate",
+                        ParameterValue::Decimal(value),
+                    ) => params.light_client_attack_min_slash_rate = value,
+                    (
+                        "eth_bridge_equivocation_min_slash_rate",
+                        ParameterValue::Decimal(value),
+                    ) => params.eth_bridge_equivocation_min_slash_rate = value,
+                    ("liveness_threshold", ParameterValue::Decimal(value)) => {
+                        params.liveness_threshold = value
+                    }
+                    ("min_self_bond_ratio", ParameterValue::Decimal(value)) => {
+                        params.min_self_bond_ratio = value
+                    }
+                    (
+                        "validator_stake_threshold",
+                        ParameterValue::Amount(value),
+                    ) => params.validator_stake_threshold = value,
+                    (
+                        "max_delegated_stake",
+                        ParameterValue::OptionalAmount(value),
+                    ) => params.max_delegated_stake = value,
+                    _ => return Err(unknown_parameter(change)),
+                }
+            }
+            ParameterModule::Governance => {
+                let params = match gov_params.as_mut() {
+                    Some(params) => params,
+                    None => gov_params.insert(read_gov_params(storage)?),
+                };
+                match (parameter, value) {
+                    ("min_proposal_fund", ParameterValue::Amount(value)) => {
+                        params.min_proposal_fund = value
+                    }
+                    (
+                        "max_proposal_code_size",
+                        ParameterValue::Integer(value),
+                    ) => params.max_proposal_code_size = value,
+                    (
+                        "min_proposal_voting_period",
+                        ParameterValue::Integer(value),
+                    ) => params.min_proposal_voting_period = value,
+                    ("max_proposal_period", ParameterValue::Integer(value)) => {
+                        params.max_proposal_period = value
+                    }
+                    (
+                        "max_proposal_content_size",
+                        ParameterValue::Integer(value),
+                    ) => params.max_proposal_content_size = value,
+                    (
+                        "min_proposal_grace_epochs",
+                        ParameterValue::Integer(value),
+                    ) => params.min_proposal_grace_epochs = value,
+                    (
+                        "proposal_veto_threshold",
+                        ParameterValue::Decimal(value),
+                    ) => params.proposal_veto_threshold = value,
+                    (
+                        "expedited_min_proposal_fund",
+                        ParameterValue::Amount(value),
+                    ) => params.expedited_min_proposal_fund = value,
+                    (
+                        "expedited_proposal_voting_period",
+                        ParameterValue::Integer(value),
+                    ) => params.expedited_proposal_voting_period = value,
+                    (
+                        "expedited_min_proposal_grace_epochs",
+                        ParameterValue::Integer(value),
+                    ) => params.expedited_min_proposal_grace_epochs = value,
+                    (
+                        "expedited_proposal_quorum",
+                        ParameterValue::Decimal(value),
+                    ) => params.expedited_proposal_quorum = value,
+                    _ => return Err(unknown_parameter(change)),
+                }
+            }
+            ParameterModule::Pgf => {
+                let value = match value {
+                    ParameterValue::Decimal(value) => value,
+                    _ => return Err(unknown_parameter(change)),
+                };
+                if value.is_negative() || value > Dec::one() {
+                    return Err(invalid_value("must be between 0 and 1"));
+                }
+                let key = match parameter {
+                    "pgf_inflation_rate" => {
+                        pgf_storage::get_pgf_inflation_rate_key()
+                    }
+                    "stewards_inflation_rate" => {
+                        pgf_storage::get_steward_inflation_rate_key()
+                    }
+                    _ => return Err(unknown_parameter(change)),
+                };
+                writes.push((key, value.serialize_to_vec()));
+            }
+            ParameterModule::EthBridge => match parameter {
+                "min_confirmations" => {
+                    let value = as_integer(value, invalid_value)?;
+                    let value = NonZeroU64::new(value)
+                        .ok_or_else(|| invalid_value("must be positive"))?;
+                    writes.push((
+                        bridge_storage::min_confirmations_key(),
+                        MinimumConfirmations::from(value).serialize_to_vec(),
+                    ));
+                }
+                _ => return Err(unknown_parameter(change)),
+            },
+        }
+    }
+
+    if let Some(params) = pos_params {
+        let errors = params.validate();
+        if !errors.is_empty() {
+            return Err(Error::InvalidParameters(
+                ParameterModule::Pos,
+                errors
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            ));
+        }
+        writes.extend([
+            (pos_storage::params_key(), params.serialize_to_vec()),
+            (
+                pos_storage::eth_bridge_equivocation_min_slash_rate_key(),
+                params
+                    .eth_bridge_equivocation_min_slash_rate
+                    .serialize_to_vec(),
+            ),
+            (
+                pos_storage::min_self_bond_ratio_key(),
+                params.min_self_bond_ratio.serialize_to_vec(),
+            ),
+            (
+                pos_storage::max_delegated_stake_key(),
+                params.max_delegated_stake.serialize_to_vec(),
+            ),
+        ]);
+    }
+
+    if let Some(params) = gov_params {
+        let invalid_params = |err: &str| {
+            Error::InvalidParameters(
+                ParameterModule::Governance,
+                err.to_string(),
+            )
+        };
+        if params.min_proposal_voting_period == 0
+            || params.min_proposal_voting_period > params.max_proposal_period
+        {
+            return Err(invalid_params(
+                "the minimum voting period must be positive and at most the \
+                 maximum proposal period",
+            ));
+        }
+        if params.proposal_veto_threshold <= Dec::zero()
+            || params.proposal_veto_threshold > Dec::one()
+        {
+            return Err(invalid_params(
+                "the proposal veto threshold must be in (0, 1]",
+            ));
+        }
+        if params.expedited_proposal_voting_period == 0
+            || params.expedited_proposal_voting_period
+                > params.max_proposal_period
+        {
+            return Err(invalid_params(
+                "the expedited voting period must be positive and at most the \
+                 maximum proposal period",
+            ));
+        }
+        if params.expedited_proposal_quorum <= Dec::zero()
+            || params.expedited_proposal_quorum > Dec::one()
+        {
+            return Err(invalid_params(
+                "the expedited proposal quorum must be in (0, 1]",
+            ));
+        }
+        writes.extend([
+            (
+                gov_storage::get_min_proposal_fund_key(),
+                params.min_proposal_fund.serialize_to_vec(),
+            ),
+            (
+                gov_storage::get_max_proposal_code_size_key(),
+                params.max_proposal_code_size.serialize_to_vec(),
+            ),
+            (
+                gov_storage::get_min_proposal_voting_period_key(),
+                params.min_proposal_voting_period.serialize_to_vec(),
+            ),
+            (
+                gov_storage::get_max_proposal_period_key(),
+                params.max_proposal_period.serialize_to_vec(),
+            ),
+            (
+                gov_storage::get_max_proposal_content_key(),
+                params.max_proposal_content_size.serialize_to_vec(),
+            ),
+            (
+                gov_storage::get_min_proposal_grace_epoch_key(),
+                params.min_proposal_grace_epochs.serialize_to_vec(),
+            ),
+            (
+                gov_storage::get_proposal_veto_threshold_key(),
+                params.proposal_veto_threshold.serialize_to_vec(),
+            ),
+            (
+                gov_storage::get_expedited_min_proposal_fund_key(),
+                params.expedited_min_proposal_fund.serialize_to_vec(),
+            ),
+            (
+                gov_storage::get_expedited_proposal_voting_period_key(),
+                params.expedited_proposal_voting_period.serialize_to_vec(),
+            ),
+            (
+                gov_storage::get_expedited_min_proposal_grace_epoch_key(),
+                params
+                    .expedited_min_proposal_grace_epochs
+                    .serialize_to_vec(),
+            ),
+            (
+                gov_storage::get_expedited_proposal_quorum_key(),
+                params.expedited_proposal_quorum.serialize_to_vec(),
+            ),
+        ]);
+    }
+
+    Ok(writes)
+}
+
+fn as_integer(
+    value: ParameterValue,
+    invalid_value: impl Fn(&str) -> Error,
+) -> Result<u64> {
+    match value {
+        ParameterValue::Integer(value) => Ok(value),
+        _ => Err(invalid_value("expected an integer")),
+    }
+}
+
+fn unknown_parameter(change: &ParameterChange) -> Error {
+    ParameterChangeError::UnknownParameter(
+        change.module,
+        change.parameter.clone(),
+    )
+    .into()
+}
+
+/// The largest number of consensus validators across the validator sets
+/// populated so far, from the current epoch up to the pipeline epoch
+fn max_num_consensus_validators<S>(
+    storage: &S,
+    pipeline_len: u64,
+) -> Result<u64>
+where
+    S: StorageRead,
+{
+    let current_epoch = storage.get_block_epoch()?;
+    let mut max_num_validators = 0;
+    for epoch in Epoch::iter_bounds_inclusive(
+        current_epoch,
+        current_epoch + pipeline_len,
+    ) {
+        max_num_validators = std::cmp::max(
+            max_num_validators,
+            get_num_consensus_validators(storage, epoch)?,
+        );
+    }
+    Ok(max_num_validators)
+}
+
+#[cfg(test)]
+mod tests {
+    use namada_core::address::testing::established_address_2;
+    use namada_core::key::testing::common_sk_from_simple_seed;
+    use namada_core::key::RefTo;
+    use namada_governance::storage::proposal::ParameterValueKind;
+    use namada_proof_of_stake::types::GenesisValidator;
+    use namada_state::testing::TestState;
+
+    use super::*;
+
+    fn change(
+        module: ParameterModule,
+        parameter: &str,
+        value: &str,
+    ) -> ParameterChange {
+        ParameterChange {
+            module,
+            parameter: parameter.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    fn init_storage() -> TestState {
+        let mut state = TestState::default();
+        // this also initializes the default governance parameters
+        namada_proof_of_stake::test_utils::test_init_genesis(
+            &mut state,
+            Default::default(),
+            [].into_iter(),
+            0.into(),
+        )
+        .unwrap();
+        namada_governance::pgf::parameters::PgfParameters::default()
+            .init_storage(&mut state)
+            .unwrap();
+        state
+    }
+
+    /// Every parameter advertised as governable must be handled
+    #[test]
+    fn test_all_parameters_are_handled() {
+        let state = init_storage();
+        for module in [
+            ParameterModule::Protocol,
+            ParameterModule::Pos,
+            ParameterModule::Governance,
+            ParameterModule::Pgf,
+        ] {
+            for (parameter, kind) in module.parameters() {
+                let value = match kind {
+                    ParameterValueKind::Integer => "10",
+                    ParameterValueKind::Decimal => "0.1",
+                    ParameterValueKind::Amount
+                    | ParameterValueKind::OptionalAmount => "1000",
+                };
+                let change = change(module, parameter, value);
+                let result = validate_parameter_changes(&state, &[change]);
+                assert!(
+                    !matches!(
+                        result,
+                        Err(Error::InvalidChange(
+                            ParameterChangeError::UnknownParameter(..)
+                        ))
+                    ),
+                    "{module}.{parameter} is not handled"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_apply_parameter_changes() {
+        let mut state = init_storage();
+
+        let changes = [
+            change(ParameterModule::Protocol, "max_block_gas", "12345"),
+            change(ParameterModule::Pos, "max_validator_slots", "42"),
+            change(ParameterModule::Pos, "max_delegated_stake", "100"),
+            change(ParameterModule::Governance, "max_proposal_period", "30"),
+            change(ParameterModule::Pgf, "pgf_inflation_rate", "0.05"),
+        ];
+        apply_parameter_changes(&mut state, &changes).unwrap();
+
+        let max_block_gas: u64 = state
+            .read(&params_storage::get_max_block_gas_key())
+            .unwrap()
+            .unwrap();
+        assert_eq!(max_block_gas, 12345);
+        let pos_params = read_pos_params(&state).unwrap();
+        assert_eq!(pos_params.max_validator_slots, 42);
+        assert_eq!(
+            pos_params.max_delegated_stake,
+            Some(namada_core::token::Amount::native_whole(100))
+        );
+        let gov_params = read_gov_params(&state).unwrap();
+        assert_eq!(gov_params.max_proposal_period, 30);
+        let pgf_inflation_rate: Dec = state
+            .read(&pgf_storage::get_pgf_inflation_rate_key())
+            .unwrap()
+            .unwrap();
+        assert_eq!(pgf_inflation_rate, Dec::new(5, 2).unwrap());
+    }
+
+    #[test]
+    fn test_invalid_parameter_changes() {
+        let state = init_storage();
+
+        // Unknown parameter
+        let changes = [change(ParameterModule::Pos, "pipeline_len", "5")];
+        assert!(validate_parameter_changes(&state, &changes).is_err());
+
+        // Value of the wrong type
+        let changes =
+            [change(ParameterModule::Pos, "max_validator_slots", "0.5")];
+        assert!(validate_parameter_changes(&state, &changes).is_err());
+
+        // Inconsistent parameters
+        let changes = [change(
+            ParameterModule::Governance,
+            "min_proposal_voting_period",
+            "100",
+        )];
+        assert!(validate_parameter_changes(&state, &changes).is_err());
+
+        // A single invalid change prevents all of them from being applied
+        let mut state = state;
+        let changes = [
+            change(ParameterModule::Protocol, "max_block_gas", "12345"),
+            change(ParameterModule::Pgf, "pgf_inflation_rate", "2"),
+        ];
+        assert!(apply_parameter_changes(&mut state, &changes).is_err());
+        let max_block_gas: u64 = state
+            .read(&params_storage::get_max_block_gas_key())
+            .unwrap()
+            .unwrap();
+        assert_ne!(max_block_gas, 12345);
+    }
+
+    /// The number of validator slots cannot be lowered below the size of
+    /// the consensus validator set
+    #[test]
+    fn test_max_validator_slots_fit_consensus_set() {
+        let mut state = TestState::default();
+        let validator =
+            crate::ledger::native_vp::ibc::get_dummy_genesis_validator();
+        let other_validator = GenesisValidator {
+            address: established_address_2(),
+            consensus_key: common_sk_from_simple_seed(2).ref_to(),
+            ..validator.clone()
+        };
+        namada_proof_of_stake::test_utils::test_init_genesis(
+            &mut state,
+            Default::default(),
+            [validator, other_validator].into_iter(),
+            0.into(),
+        )
+        .unwrap();
+
+        for slots in ["0", "1"] {
+            let changes =
+                [change(ParameterModule::Pos, "max_validator_slots", slots)];
+            assert!(validate_parameter_changes(&state, &changes).is_err());
+        }
+        let changes =
+            [change(ParameterModule::Pos, "max_validator_slots", "2")];
+        assert!(validate_parameter_changes(&state, &changes).is_ok());
+    }
+}
@@ -2,6 +2,8 @@
 
 use std::collections::HashMap;
 
+use namada_core::storage::Epoch;
+use namada_core::token;
 use namada_governance::utils::TallyResult;
 use namada_sdk::events::{Event, EventLevel};
 use thiserror::Error;
@@ -120,6 +122,71 @@ impl ProposalEvent {
         )
     }
 
+    /// Create a new proposal event for parameter change proposal
+    pub fn parameter_change_proposal_event(
+        proposal_id: u64,
+        result: bool,
+    ) -> Self {
+        ProposalEvent::new(
+            EventType::Proposal.to_string(),
+            TallyResult::Passed,
+            proposal_id,
+            false,
+            result,
+        )
+    }
+
+    /// Create a new proposal event for chain upgrade proposal
+    pub fn upgrade_proposal_event(proposal_id: u64, result: bool) -> Self {
+        ProposalEvent::new(
+            EventType::Proposal.to_string(),
+            TallyResult::Passed,
+            proposal_id,
+            false,
+            result,
+        )
+    }
+
+    /// Create a new proposal event for an expedited proposal that has been
+    /// converted to a regular proposal
+    pub fn expedited_proposal_converted_event(
+        proposal_id: u64,
+        voting_end_epoch: Epoch,
+        grace_epoch: Epoch,
+    ) -> Self {
+        let attributes = HashMap::from([
+            ("proposal_id".to_string(), proposal_id.to_string()),
+            ("expedited_converted".to_string(), "1".to_string()),
+            ("voting_end_epoch".to_string(), voting_end_epoch.to_string()),
+            ("grace_epoch".to_string(), grace_epoch.to_string()),
+        ]);
+        Self {
+            event_type: EventType::Proposal.to_string(),
+            attributes,
+        }
+    }
+
+    /// Create a new proposal event reporting whether the funds locked by a
+    /// proposal were refunded to its author or burned because of a veto
+    pub fn proposal_funds_event(
+        proposal_id: u64,
+        funds: token::Amount,
+        burned: bool,
+    ) -> Self {
+        let attributes = HashMap::from([
+            ("proposal_id".to_string(), proposal_id.to_string()),
+            ("proposal_funds".to_string(), funds.to_string_native()),
+            (
+                "proposal_funds_outcome".to_string(),
+                if burned { "burned" } else { "refunded" }.to_string(),
+            ),
+        ]);
+        Self {
+            event_type: EventType::Proposal.to_string(),
+            attributes,
+        }
+    }
+
     /// Create a new proposal event for eth proposal
     pub fn eth_proposal_event(proposal_id: u64, result: bool) -> Self {
         ProposalEvent::new(
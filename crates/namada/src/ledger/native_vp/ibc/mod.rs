@@ -337,6 +337,7 @@ mod tests {
     use crate::ibc::apps::transfer::types::{
         ack_success_b64, PrefixedCoin, TracePrefix, VERSION,
     };
+    use crate::ibc::context::transfer_mod::add_packet_attributes;
     use crate::ibc::core::channel::types::acknowledgement::{
         Acknowledgement, AcknowledgementStatus,
     };
@@ -2384,15 +2385,16 @@ mod tests {
         // event
         let data = serde_json::from_slice::<PacketData>(&packet.data)
             .expect("decoding packet data failed");
-        let ack_event = AckEvent {
+        let mut ack_event = ModuleEvent::from(AckEvent {
             sender: data.sender,
             receiver: data.receiver,
             denom: data.token.denom,
             amount: data.token.amount,
             memo: data.memo,
             acknowledgement: transfer_ack,
-        };
-        let event = RawIbcEvent::Module(ModuleEvent::from(ack_event));
+        });
+        add_packet_attributes(&mut ack_event, &packet);
+        let event = RawIbcEvent::Module(ack_event);
         state
             .write_log_mut()
             .emit_ibc_event(event.try_into().unwrap());
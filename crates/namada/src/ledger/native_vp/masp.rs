@@ -14,9 +14,10 @@ use namada_core::address::InternalAddress::Masp;
 use namada_core::masp::encode_asset_type;
 use namada_core::storage::{IndexedTx, Key};
 use namada_gas::MASP_VERIFY_SHIELDED_TX_GAS;
+use namada_governance::is_proposal_accepted;
 use namada_sdk::masp::verify_shielded_tx;
 use namada_state::{OptionExt, ResultExt, StateRead};
-use namada_token::read_denom;
+use namada_token::{read_denom, read_masp_token_capacity};
 use namada_tx::Tx;
 use namada_vp_env::VpEnv;
 use num_traits::ops::checked::{CheckedAdd, CheckedSub};
@@ -25,8 +26,8 @@ use sha2::Digest as Sha2Digest;
 use thiserror::Error;
 use token::storage_key::{
     balance_key, is_any_shielded_action_balance_key, is_masp_allowed_key,
-    is_masp_key, is_masp_nullifier_key, is_masp_tx_pin_key,
-    masp_commitment_anchor_key, masp_commitment_tree_key,
+    is_masp_key, is_masp_nullifier_key, is_masp_token_limits_key,
+    is_masp_tx_pin_key, masp_commitment_anchor_key, masp_commitment_tree_key,
     masp_convert_anchor_key, masp_nullifier_key,
 };
 use token::Amount;
@@ -379,6 +380,15 @@ where
             amount,
         })
     }
+
+    /// Return if the token limits change was done via a governance proposal
+    pub fn is_valid_parameter(&self, tx: &Tx) -> Result<bool> {
+        match tx.data() {
+            Some(data) => is_proposal_accepted(&self.ctx.pre(), data.as_ref())
+                .map_err(Error::NativeVpError),
+            None => Ok(false),
+        }
+    }
 }
 
 // Make a map to help recognize asset types lacking an epoch
@@ -408,9 +418,20 @@ where
         keys_changed: &BTreeSet<Key>,
         _verifiers: &BTreeSet<Address>,
     ) -> Result<bool> {
+        // The token limits can only be changed by governance
+        if keys_changed
+            .iter()
+            .any(|key| is_masp_token_limits_key(key).is_some())
+        {
+            return self.is_valid_parameter(tx_data);
+        }
+
         let epoch = self.ctx.get_block_epoch()?;
         let conversion_state = self.ctx.state.in_mem().get_conversion_state();
         let shielded_tx = self.ctx.get_shielded_action(tx_data)?;
+        // A MASP transaction attached to a wrapper pays its fee, credited to
+        // the block proposer, with its transparent outputs
+        let fee_payment = tx_data.header().wrapper();
 
         if u64::from(self.ctx.get_block_height()?)
             > u64::from(shielded_tx.expiry_height())
@@ -432,6 +453,46 @@ where
                 "No denomination found in storage for the given token",
             )?;
 
+        // Enforce the governance-set limits of the token, if any
+        if let Some(capacity) =
+            read_masp_token_capacity(&self.ctx.pre(), &transfer.token)?
+        {
+            let within_limits = if transfer.source != Address::Internal(Masp) {
+                capacity.can_deposit(transfer.amount)
+            } else if transfer.target != Address::Internal(Masp) {
+                capacity.can_withdraw(transfer.amount)
+            } else {
+                true
+            };
+            if !within_limits {
+                tracing::debug!(
+                    "MASP transfer exceeds the limits of token {}",
+                    transfer.token
+                );
+                return Ok(false);
+            }
+        }
+
+        if let Some(wrapper) = &fee_payment {
+            let fee = wrapper
+                .get_tx_fee()
+                .wrap_err("unable to compute the wrapper fee")?;
+            let fee = token::denom_to_amount(
+                fee,
+                &wrapper.fee.token,
+                &self.ctx.pre(),
+            )?;
+            if transfer.source != Address::Internal(Masp)
+                || transfer.token != wrapper.fee.token
+                || transfer.amount != fee
+            {
+                tracing::debug!(
+                    "The MASP fee payment does not match the wrapper fee"
+                );
+                return Ok(false);
+            }
+        }
+
         if transfer.source != Address::Internal(Masp) {
             // No shielded spends nor shielded converts are allowed
             if shielded_tx.sapling_bundle().is_some_and(|bundle| {
@@ -593,7 +654,10 @@ where
             // The following boundary conditions must be satisfied
             // 1. Total of transparent output values equals containing transfer
             // amount 2. Asset type must be properly derived
-            // 3. Public key must be the hash of the target
+            // 3. Public key must be the hash of the target or, for a fee
+            // payment, of the fee payer of the wrapper. This binds the
+            // transaction to the wrapper, so that it cannot be replayed to
+            // pay the fees of another one
 
             let transp_bundle =
                 shielded_tx.transparent_bundle().ok_or_err_msg(
@@ -601,7 +665,10 @@ where
                 )?;
 
             let mut total_out_values = token::Amount::zero();
-            let target_enc = transfer.target.serialize_to_vec();
+            let target_enc = match &fee_payment {
+                Some(wrapper) => wrapper.fee_payer().serialize_to_vec(),
+                None => transfer.target.serialize_to_vec(),
+            };
             let hash =
                 ripemd::Ripemd160::digest(sha2::Sha256::digest(&target_enc));
             // To help recognize asset types not in the conversion tree
@@ -740,3 +807,255 @@ impl From<native_vp::Error> for Error {
         Self::NativeVpError(err)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::sync::Mutex;
+
+    use masp_primitives::consensus::TestNetwork;
+    use masp_primitives::memo::MemoBytes;
+    use masp_primitives::merkle_tree::IncrementalWitness;
+    use masp_primitives::sapling::Rseed;
+    use masp_primitives::transaction::builder::Builder;
+    use masp_primitives::transaction::components::{TxOut, U64Sum};
+    use masp_primitives::transaction::fees::fixed::FeeRule;
+    use masp_primitives::transaction::TransparentAddress;
+    use masp_primitives::zip32::ExtendedSpendingKey;
+    use namada_core::storage::Epoch;
+    use namada_core::token::DenominatedAmount;
+    use namada_core::validity_predicate::VpSentinel;
+    use namada_gas::TxGasMeter;
+    use namada_sdk::masp::testing::MockTxProver;
+    use namada_state::testing::TestState;
+    use namada_tx::data::{Fee, TxType, WrapperTx};
+    use rand_core::OsRng;
+
+    use super::*;
+    use crate::core::address::testing::{established_address_1, nam};
+    use crate::key::testing::{keypair_1, keypair_2};
+    use crate::key::{common, RefTo};
+    use crate::ledger::gas::VpGasMeter;
+    use crate::storage::TxIndex;
+    use crate::vm::wasm::compilation_cache::common::testing::cache as wasm_cache;
+
+    const ADDRESS: Address = Address::Internal(Masp);
+    const GAS_LIMIT: u64 = 10;
+
+    // Make a wrapper paying its fees in the native token
+    fn fee_wrapper(fee_payer: &common::SecretKey, gas_limit: u64) -> WrapperTx {
+        WrapperTx::new(
+            Fee {
+                amount_per_gas_unit: DenominatedAmount::native(1.into()),
+                token: nam(),
+            },
+            fee_payer.ref_to(),
+            Epoch(0),
+            gas_limit.into(),
+            None,
+        )
+    }
+
+    fn wrapper_fee(state: &TestState, wrapper: &WrapperTx) -> Amount {
+        token::denom_to_amount(
+            wrapper.get_tx_fee().unwrap(),
+            &wrapper.fee.token,
+            state,
+        )
+        .unwrap()
+    }
+
+    // Make a MASP transaction spending a note of the given amount to
+    // transparent outputs tied to the given address. Optionally, the
+    // transaction also shields a transparent input of the same amount.
+    fn masp_fee_payment(
+        state: &TestState,
+        amount: Amount,
+        output_owner: &Address,
+        transparent_input: bool,
+    ) -> Transaction {
+        let denom = read_denom(state, &nam()).unwrap().unwrap();
+        let asset_type =
+            encode_asset_type(nam(), denom, MaspDigitPos::Zero, None).unwrap();
+        let value = amount.raw_amount().as_u64();
+        let esk = ExtendedSpendingKey::master(&[1; 32]);
+        let (_, payment_address) = esk.default_address();
+        let note = payment_address
+            .create_note(asset_type, value, Rseed::AfterZip212([0; 32]))
+            .unwrap();
+        let mut tree = CommitmentTree::<Node>::empty();
+        tree.append(Node::from_scalar(note.cmu())).unwrap();
+        let witness = IncrementalWitness::from_tree(&tree);
+        let owner_hash = ripemd::Ripemd160::digest(sha2::Sha256::digest(
+            output_owner.serialize_to_vec(),
+        ));
+        let owner = TransparentAddress(owner_hash.into());
+
+        let mut builder = Builder::<TestNetwork, _>::new_with_rng(
+            TestNetwork,
+            1u32.into(),
+            OsRng,
+        );
+        builder
+            .add_sapling_spend(
+                esk,
+                *payment_address.diversifier(),
+                note,
+                witness.path().unwrap(),
+            )
+            .unwrap();
+        builder
+            .add_transparent_output(&owner, asset_type, value)
+            .unwrap();
+        if transparent_input {
+            builder
+                .add_transparent_input(TxOut {
+                    asset_type,
+                    value,
+                    address: owner,
+                })
+                .unwrap();
+            builder
+                .add_sapling_output(
+                    None,
+                    payment_address,
+                    asset_type,
+                    value,
+                    MemoBytes::empty(),
+                )
+                .unwrap();
+        }
+        let (masp_tx, _metadata) = builder
+            .build(
+                &MockTxProver(Mutex::new(OsRng)),
+                &FeeRule::non_standard(U64Sum::zero()),
+            )
+            .unwrap();
+        masp_tx
+    }
+
+    // Run the VP on the given wrapper paying its fees with the given MASP
+    // transaction, after applying the changes that the protocol does for
+    // this payment
+    fn validate_fee_payment(
+        mut state: TestState,
+        masp_tx: Transaction,
+        wrapper: WrapperTx,
+    ) -> bool {
+        let mut keys_changed = BTreeSet::new();
+
+        // The fee is moved from the MASP to the block proposer
+        let fee = wrapper_fee(&state, &wrapper);
+        let masp_balance_key = balance_key(&nam(), &ADDRESS);
+        state
+            .db_write(&masp_balance_key, fee.serialize_to_vec())
+            .expect("write failed");
+        state
+            .write_log_mut()
+            .write(&masp_balance_key, Amount::zero().serialize_to_vec())
+            .expect("write failed");
+        let proposer_balance_key =
+            balance_key(&nam(), &established_address_1());
+        state
+            .write_log_mut()
+            .write(&proposer_balance_key, fee.serialize_to_vec())
+            .expect("write failed");
+        keys_changed.extend([masp_balance_key, proposer_balance_key]);
+
+        // The nullifiers are revealed and the note commitments appended
+        let bundle = masp_tx.sapling_bundle().unwrap();
+        let tree_key = masp_commitment_tree_key();
+        let mut tree = CommitmentTree::<Node>::empty();
+        state
+            .db_write(&tree_key, tree.serialize_to_vec())
+            .expect("write failed");
+        for spend in &bundle.shielded_spends {
+            state
+                .db_write(
+                    &masp_commitment_anchor_key(spend.anchor),
+                    ().serialize_to_vec(),
+                )
+                .expect("write failed");
+            let nullifier_key = masp_nullifier_key(&spend.nullifier);
+            state
+                .write_log_mut()
+                .write(&nullifier_key, ().serialize_to_vec())
+                .expect("write failed");
+            keys_changed.insert(nullifier_key);
+        }
+        for output in &bundle.shielded_outputs {
+            tree.append(Node::from_scalar(output.cmu)).unwrap();
+        }
+        state
+            .write_log_mut()
+            .write(&tree_key, tree.serialize_to_vec())
+            .expect("write failed");
+        keys_changed.insert(tree_key);
+
+        let mut tx = Tx::from_type(TxType::Raw);
+        let (_, masp_hash) = tx.add_masp_tx_section(masp_tx);
+        tx.update_header(TxType::Wrapper(Box::new(WrapperTx {
+            unshield_section_hash: Some(masp_hash),
+            ..wrapper
+        })));
+
+        let tx_index = TxIndex::default();
+        let gas_meter = RefCell::new(VpGasMeter::new_from_tx_meter(
+            &TxGasMeter::new_from_sub_limit(u64::MAX.into()),
+        ));
+        let (vp_wasm_cache, _vp_cache_dir) = wasm_cache();
+        let verifiers = BTreeSet::new();
+        let sentinel = RefCell::new(VpSentinel::default());
+        let ctx = Ctx::new(
+            &ADDRESS,
+            &state,
+            &tx,
+            &tx_index,
+            &gas_meter,
+            &sentinel,
+            &keys_changed,
+            &verifiers,
+            vp_wasm_cache,
+        );
+
+        let vp = MaspVp { ctx };
+        vp.validate_tx(&tx, &keys_changed, &verifiers)
+            .expect("validation failed")
+    }
+
+    #[test]
+    fn test_masp_fee_payment_fee_mismatch() {
+        let state = TestState::default();
+        let fee = wrapper_fee(&state, &fee_wrapper(&keypair_1(), GAS_LIMIT));
+        // The wrapper requires twice the fee paid by the MASP transaction
+        let wrapper = fee_wrapper(&keypair_1(), 2 * GAS_LIMIT);
+        let masp_tx =
+            masp_fee_payment(&state, fee, &wrapper.fee_payer(), false);
+
+        assert!(!validate_fee_payment(state, masp_tx, wrapper));
+    }
+
+    #[test]
+    fn test_masp_fee_payment_transparent_input() {
+        let state = TestState::default();
+        let wrapper = fee_wrapper(&keypair_1(), GAS_LIMIT);
+        let fee = wrapper_fee(&state, &wrapper);
+        let masp_tx = masp_fee_payment(&state, fee, &wrapper.fee_payer(), true);
+
+        assert!(!validate_fee_payment(state, masp_tx, wrapper));
+    }
+
+    #[test]
+    fn test_masp_fee_payment_replay() {
+        let state = TestState::default();
+        let wrapper = fee_wrapper(&keypair_1(), GAS_LIMIT);
+        let fee = wrapper_fee(&state, &wrapper);
+        let masp_tx =
+            masp_fee_payment(&state, fee, &wrapper.fee_payer(), false);
+
+        // The MASP transaction is bound to the fee payer of the original
+        // wrapper, so it cannot pay the same fee for another one
+        let other_wrapper = fee_wrapper(&keypair_2(), GAS_LIMIT);
+        assert!(!validate_fee_payment(state, masp_tx, other_wrapper));
+    }
+}
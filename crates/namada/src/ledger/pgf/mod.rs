@@ -5,8 +5,10 @@ pub mod utils;
 
 use std::collections::BTreeSet;
 
+use namada_governance::pgf::rounds::storage as rounds_storage;
 use namada_governance::pgf::storage::keys as pgf_storage;
 use namada_governance::{is_proposal_accepted, pgf};
+use namada_proof_of_stake::queries::find_delegations;
 use namada_state::StateRead;
 use namada_tx::Tx;
 use thiserror::Error;
@@ -15,6 +17,7 @@ use crate::address::{Address, InternalAddress};
 use crate::ledger::native_vp;
 use crate::ledger::native_vp::{Ctx, NativeVp};
 use crate::storage::Key;
+use crate::token;
 use crate::vm::WasmCacheAccess;
 
 /// for handling Pgf NativeVP errors
@@ -97,6 +100,14 @@ where
                     Ok(is_valid)
                 }
                 KeyType::FUNDINGS => Ok(false),
+                KeyType::FUNDING_ROUNDS => Ok(false),
+                KeyType::FUNDING_ROUND_PROJECT(round_id, project) => self
+                    .is_valid_project_registration(
+                        round_id, project, verifiers,
+                    ),
+                KeyType::FUNDING_ROUND_BALLOT(round_id, voter) => {
+                    self.is_valid_ballot(round_id, voter, verifiers)
+                }
                 KeyType::PGF_INFLATION_RATE
                 | KeyType::STEWARD_INFLATION_RATE => {
                     self.is_valid_parameter_change(tx_data)
@@ -123,16 +134,114 @@ where
             None => Ok(false),
         }
     }
+
+    /// Validate the registration of a project in a funding round. A project
+    /// can only be registered once, while the round is open for
+    /// registrations and with its own authorization.
+    pub fn is_valid_project_registration(
+        &self,
+        round_id: u64,
+        project: &Address,
+        verifiers: &BTreeSet<Address>,
+    ) -> Result<bool> {
+        let round =
+            match rounds_storage::get_funding_round(&self.ctx.pre(), round_id)?
+            {
+                Some(round) => round,
+                None => return Ok(false),
+            };
+        let current_epoch = self.ctx.get_block_epoch()?;
+        let was_registered = rounds_storage::is_registered_project(
+            &self.ctx.pre(),
+            round_id,
+            project,
+        )?;
+        let registration_epoch =
+            rounds_storage::funding_round_projects_handle()
+                .at(&round_id)
+                .get(&self.ctx.post(), project)?;
+
+        Ok(!round.finalized
+            && round.round.is_registration_open(current_epoch)
+            && !was_registered
+            && registration_epoch == Some(current_epoch)
+            && verifiers.contains(project))
+    }
+
+    /// Validate the ballot of a voter in a funding round. The ballot must be
+    /// cast while the round is open for voting, with the voter's
+    /// authorization, for registered projects only and without exceeding
+    /// the voice credits of the round. The voter must have bonded at least
+    /// the minimum stake of the round at its start.
+    pub fn is_valid_ballot(
+        &self,
+        round_id: u64,
+        voter: &Address,
+        verifiers: &BTreeSet<Address>,
+    ) -> Result<bool> {
+        let round =
+            match rounds_storage::get_funding_round(&self.ctx.pre(), round_id)?
+            {
+                Some(round) => round,
+                None => return Ok(false),
+            };
+        let current_epoch = self.ctx.get_block_epoch()?;
+        if round.finalized
+            || !round.round.is_voting_open(current_epoch)
+            || !verifiers.contains(voter)
+        {
+            return Ok(false);
+        }
+
+        let ballot = match rounds_storage::get_ballot(
+            &self.ctx.post(),
+            round_id,
+            voter,
+        )? {
+            Some(ballot) if !ballot.is_empty() => ballot,
+            _ => return Ok(false),
+        };
+        let is_within_credits = pgf::rounds::ballot_cost(&ballot)
+            .map_or(false, |cost| cost <= round.round.voice_credits);
+        if !is_within_credits {
+            return Ok(false);
+        }
+        for project in ballot.keys() {
+            if !rounds_storage::is_registered_project(
+                &self.ctx.pre(),
+                round_id,
+                project,
+            )? {
+                return Ok(false);
+            }
+        }
+
+        let bonded_stake =
+            find_delegations(&self.ctx.pre(), voter, &round.round.start_epoch)?
+                .values()
+                .try_fold(token::Amount::zero(), |acc, amount| {
+                    acc.checked_add(*amount)
+                });
+
+        Ok(bonded_stake
+            .map_or(false, |stake| stake >= round.round.min_bonded_stake))
+    }
 }
 
 #[allow(clippy::upper_case_acronyms)]
 #[derive(Debug)]
-enum KeyType {
+enum KeyType<'a> {
     #[allow(non_camel_case_types)]
     STEWARDS,
     #[allow(non_camel_case_types)]
     FUNDINGS,
     #[allow(non_camel_case_types)]
+    FUNDING_ROUNDS,
+    #[allow(non_camel_case_types)]
+    FUNDING_ROUND_PROJECT(u64, &'a Address),
+    #[allow(non_camel_case_types)]
+    FUNDING_ROUND_BALLOT(u64, &'a Address),
+    #[allow(non_camel_case_types)]
     PGF_INFLATION_RATE,
     #[allow(non_camel_case_types)]
     STEWARD_INFLATION_RATE,
@@ -142,12 +251,22 @@ enum KeyType {
     UNKNOWN,
 }
 
-impl From<&Key> for KeyType {
-    fn from(key: &Key) -> Self {
+impl<'a> From<&'a Key> for KeyType<'a> {
+    fn from(key: &'a Key) -> Self {
         if pgf_storage::is_stewards_key(key).is_some() {
             Self::STEWARDS
         } else if pgf_storage::is_fundings_key(key) {
             KeyType::FUNDINGS
+        } else if rounds_storage::is_funding_round_key(key) {
+            KeyType::FUNDING_ROUNDS
+        } else if let Some((round_id, project)) =
+            rounds_storage::is_funding_round_project_key(key)
+        {
+            KeyType::FUNDING_ROUND_PROJECT(round_id, project)
+        } else if let Some((round_id, voter)) =
+            rounds_storage::is_funding_round_ballot_key(key)
+        {
+            KeyType::FUNDING_ROUND_BALLOT(round_id, voter)
         } else if pgf_storage::is_pgf_inflation_rate_key(key) {
             Self::PGF_INFLATION_RATE
         } else if pgf_storage::is_steward_inflation_rate_key(key) {
@@ -5,12 +5,13 @@ use std::fmt::Debug;
 
 use borsh_ext::BorshSerializeExt;
 use eyre::{eyre, WrapErr};
+use masp_primitives::merkle_tree::CommitmentTree;
+use masp_primitives::sapling::Node;
 use masp_primitives::transaction::Transaction;
 use namada_core::hash::Hash;
 use namada_core::storage::Key;
 use namada_core::validity_predicate::VpSentinel;
 use namada_gas::TxGasMeter;
-use namada_sdk::tx::TX_TRANSFER_WASM;
 use namada_state::StorageWrite;
 use namada_tx::data::protocol::ProtocolTxType;
 use namada_tx::data::{
@@ -37,6 +38,7 @@ use crate::ledger::pos::{self, PosVP};
 use crate::state::{DBIter, State, StorageHasher, StorageRead, WlState, DB};
 use crate::storage;
 use crate::storage::TxIndex;
+use crate::token::storage_key::{masp_commitment_tree_key, masp_nullifier_key};
 use crate::token::Amount;
 use crate::vm::wasm::{TxCache, VpCache};
 use crate::vm::{self, wasm, WasmCacheAccess};
@@ -56,8 +58,8 @@ pub enum Error {
     ProtocolTxError(#[from] eyre::Error),
     #[error("Txs must either be encrypted or a decryption of an encrypted tx")]
     TxTypeError,
-    #[error("Fee ushielding error: {0}")]
-    FeeUnshieldingError(namada_tx::data::WrapperTxErr),
+    #[error("Masp fee payment error: {0}")]
+    MaspFeePaymentError(namada_tx::data::WrapperTxErr),
     #[error("Gas error: {0}")]
     GasError(String),
     #[error("Error while processing transaction's fees: {0}")]
@@ -146,7 +148,7 @@ pub type Result<T> = std::result::Result<T, Error>;
 pub struct WrapperArgs<'a> {
     /// The block proposer for the current block
     pub block_proposer: &'a Address,
-    /// Flag if the wrapper transaction committed the fee unshielding operation
+    /// Flag if the wrapper transaction committed the masp fee payment
     pub is_committed_fee_unshield: bool,
 }
 
@@ -219,22 +221,6 @@ where
     }
 }
 
-/// Load the wasm hash for a transfer from storage.
-///
-/// # Panics
-/// If the transaction hash is not found in storage
-pub fn get_transfer_hash_from_storage<S>(storage: &S) -> Hash
-where
-    S: StorageRead,
-{
-    let transfer_code_name_key =
-        Key::wasm_code_name(TX_TRANSFER_WASM.to_string());
-    storage
-        .read(&transfer_code_name_key)
-        .expect("Could not read the storage")
-        .expect("Expected tx transfer hash in storage")
-}
-
 /// Performs the required operation on a wrapper transaction:
 ///  - replay protection
 ///  - fee payment
@@ -266,6 +252,7 @@ where
 
     // Charge fee before performing any fallible operations
     charge_fee(
+        &tx,
         wrapper,
         fee_unshield_transaction,
         &mut shell_params,
@@ -283,7 +270,7 @@ where
     Ok(changed_keys)
 }
 
-/// Retrieve the Masp `Transaction` for fee unshielding from the provided
+/// Retrieve the Masp `Transaction` paying the fee of the provided
 /// transaction, if present
 pub fn get_fee_unshielding_transaction(
     tx: &Tx,
@@ -303,11 +290,11 @@ pub fn get_fee_unshielding_transaction(
 
 /// Charge fee for the provided wrapper transaction. In ABCI returns an error if
 /// the balance of the block proposer overflows. In ABCI plus returns error if:
-/// - The unshielding fails
 /// - Fee amount overflows
 /// - Not enough funds are available to pay the entire amount of the fee
 /// - The accumulated fee amount to be credited to the block proposer overflows
 fn charge_fee<'a, S, D, H, CA>(
+    tx: &Tx,
     wrapper: &WrapperTx,
     masp_transaction: Option<Transaction>,
     shell_params: &mut ShellParams<'a, S, D, H, CA>,
@@ -324,73 +311,40 @@ where
         tx_gas_meter: _,
         state,
         vp_wasm_cache,
-        tx_wasm_cache,
+        tx_wasm_cache: _,
     } = shell_params;
 
-    // Unshield funds if requested
-    let requires_fee_unshield = if let Some(transaction) = masp_transaction {
-        // The unshielding tx does not charge gas, instantiate a
-        // custom gas meter for this step
-        let  tx_gas_meter =
-            RefCell::new(TxGasMeter::new(GasLimit::from(
-                state
-                    .read::<u64>(
-                        &namada_parameters::storage::get_fee_unshielding_gas_limit_key(
-                        ),
-                    )
-                    .expect("Error reading the storage")
-                    .expect("Missing fee unshielding gas limit in storage")),
-            ));
-
-        // If it fails, do not return early
-        // from this function but try to take the funds from the unshielded
-        // balance
-        match wrapper.generate_fee_unshielding(
-            get_transfer_hash_from_storage(*state),
-            Some(TX_TRANSFER_WASM.to_string()),
-            transaction,
+    // Pay the fee from the value balance of the masp transaction if requested.
+    // If it fails, do not return early from this function but try to take the
+    // funds from the transparent balance
+    let is_masp_fee_paid = if let Some(transaction) = masp_transaction {
+        // The fees are credited straight to the block proposer, when known,
+        // otherwise to the fee payer for the subsequent checks
+        let fee_target = wrapper_args.as_ref().map_or_else(
+            || wrapper.fee_payer(),
+            |args| args.block_proposer.clone(),
+        );
+        // NOTE: A clean tx write log must be provided to this call
+        // for a correct vp validation. Block write log, instead,
+        // should contain any prior changes (if any)
+        state.write_log_mut().precommit_tx();
+        match pay_masp_fee(
+            tx,
+            wrapper,
+            &transaction,
+            &fee_target,
+            *state,
+            vp_wasm_cache,
         ) {
-            Ok(fee_unshielding_tx) => {
-                // NOTE: A clean tx write log must be provided to this call
-                // for a correct vp validation. Block write log, instead,
-                // should contain any prior changes (if any)
-                state.write_log_mut().precommit_tx();
-                match apply_wasm_tx(
-                    fee_unshielding_tx,
-                    &TxIndex::default(),
-                    ShellParams {
-                        tx_gas_meter: &tx_gas_meter,
-                        state: *state,
-                        vp_wasm_cache,
-                        tx_wasm_cache,
-                    },
-                ) {
-                    Ok(result) => {
-                        // NOTE: do not commit yet cause this could be
-                        // exploited to get free unshieldings
-                        if !result.is_accepted() {
-                            state.write_log_mut().drop_tx_keep_precommit();
-                            tracing::error!(
-                                "The unshielding tx is invalid, some VPs \
-                                 rejected it: {:#?}",
-                                result.vps_result.rejected_vps
-                            );
-                        }
-                    }
-                    Err(e) => {
-                        state.write_log_mut().drop_tx_keep_precommit();
-                        tracing::error!(
-                            "The unshielding tx is invalid, wasm run failed: \
-                             {}",
-                            e
-                        );
-                    }
-                }
+            Ok(()) => true,
+            Err(e) => {
+                // NOTE: do not commit yet cause this could be exploited to
+                // get free fee payments
+                state.write_log_mut().drop_tx_keep_precommit();
+                tracing::error!("The masp fee payment is invalid: {}", e);
+                false
             }
-            Err(e) => tracing::error!("{}", e),
         }
-
-        true
     } else {
         false
     };
@@ -400,7 +354,10 @@ where
         Some(WrapperArgs {
             block_proposer,
             is_committed_fee_unshield: _,
-        }) => transfer_fee(*state, block_proposer, wrapper)?,
+        }) if !is_masp_fee_paid => {
+            transfer_fee(*state, block_proposer, wrapper)?
+        }
+        Some(_) => {}
         None => check_fees(*state, wrapper)?,
     }
 
@@ -410,12 +367,118 @@ where
     state.write_log_mut().commit_tx();
     // Update the flag only after the fee payment has been committed
     if let Some(args) = wrapper_args {
-        args.is_committed_fee_unshield = requires_fee_unshield;
+        args.is_committed_fee_unshield = is_masp_fee_paid;
     }
 
     Ok(())
 }
 
+/// Pay the fee of the wrapper from the value balance of the provided masp
+/// transaction. The shielded changes and the transfer of the fee from the masp
+/// to `fee_target` are applied to the tx write log and then validated by the
+/// triggered validity predicates, the masp one included. The latter requires
+/// the fee to be sent to transparent outputs tied to the fee payer of the
+/// wrapper, so that the masp transaction cannot pay for another wrapper.
+pub fn pay_masp_fee<S, D, H, CA>(
+    tx: &Tx,
+    wrapper: &WrapperTx,
+    masp_transaction: &Transaction,
+    fee_target: &Address,
+    state: &mut S,
+    vp_wasm_cache: &mut VpCache<CA>,
+) -> Result<()>
+where
+    S: State<D = D, H = H> + Sync,
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+    CA: 'static + WasmCacheAccess + Sync,
+{
+    // The fee payment does not charge gas, instantiate a custom gas meter for
+    // the validation
+    let mut tx_gas_meter = TxGasMeter::new(GasLimit::from(
+        state
+            .read::<u64>(
+                &namada_parameters::storage::get_fee_unshielding_gas_limit_key(
+                ),
+            )
+            .expect("Error reading the storage")
+            .expect("Missing fee unshielding gas limit in storage"),
+    ));
+
+    let fees = wrapper
+        .get_tx_fee()
+        .map_err(|e| Error::FeeError(e.to_string()))?;
+    let fees = crate::token::denom_to_amount(fees, &wrapper.fee.token, state)
+        .map_err(|e| Error::FeeError(e.to_string()))?;
+
+    if let Some(bundle) = masp_transaction.sapling_bundle() {
+        // Reveal the nullifiers
+        for description in &bundle.shielded_spends {
+            state
+                .write_log_mut()
+                .write(
+                    &masp_nullifier_key(&description.nullifier),
+                    ().serialize_to_vec(),
+                )
+                .map_err(|e| Error::FeeError(e.to_string()))?;
+        }
+
+        // Append the note commitments of the change to the merkle tree
+        if !bundle.shielded_outputs.is_empty() {
+            let tree_key = masp_commitment_tree_key();
+            let mut commitment_tree: CommitmentTree<Node> = state
+                .read(&tree_key)
+                .map_err(Error::StorageError)?
+                .ok_or_else(|| {
+                    Error::FeeError(
+                        "Missing note commitment tree in storage".to_string(),
+                    )
+                })?;
+            for description in &bundle.shielded_outputs {
+                commitment_tree
+                    .append(Node::from_scalar(description.cmu))
+                    .map_err(|_| {
+                        Error::FeeError(
+                            "Note commitment tree is full".to_string(),
+                        )
+                    })?;
+            }
+            state
+                .write_log_mut()
+                .write(&tree_key, commitment_tree.serialize_to_vec())
+                .map_err(|e| Error::FeeError(e.to_string()))?;
+        }
+    }
+
+    token_transfer(
+        state,
+        &wrapper.fee.token,
+        &Address::Internal(InternalAddress::Masp),
+        fee_target,
+        fees,
+    )?;
+
+    let vps_result = check_vps(CheckVps {
+        tx,
+        tx_index: &TxIndex::default(),
+        state,
+        tx_gas_meter: &mut tx_gas_meter,
+        verifiers_from_tx: &BTreeSet::from([Address::Internal(
+            InternalAddress::Masp,
+        )]),
+        vp_wasm_cache,
+    })?;
+
+    if vps_result.rejected_vps.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::FeeError(format!(
+            "Some VPs rejected the masp fee payment: {:#?}",
+            vps_result.rejected_vps
+        )))
+    }
+}
+
 /// Perform the actual transfer of fess from the fee payer to the block
 /// proposer.
 pub fn transfer_fee<S>(
@@ -708,6 +771,10 @@ where
             )
             .map_err(Error::ProtocolTxError)
         }
+        EthereumTxData::EthBridgeEquivocation(evidence) => {
+            transactions::equivocation::apply_derived_tx(state, evidence)
+                .map_err(Error::ProtocolTxError)
+        }
         EthereumTxData::EthereumEvents(_)
         | EthereumTxData::BridgePool(_)
         | EthereumTxData::ValidatorSetUpdate(_) => {
@@ -46,6 +46,22 @@ pub enum BondError {
     InactiveValidator(Address),
     #[error("Voting power overflow: {0}")]
     VotingPowerOverflow(TryFromIntError),
+    #[error(
+        "Delegation to validator {0} would exceed its maximum delegated \
+         stake. Delegated stake after the delegation: {1}, maximum: {2}"
+    )]
+    DelegationCapExceeded(Address, String, String),
+    #[error(
+        "Delegation to validator {0} would bring its self-bond below the \
+         minimum self-bond ratio. Self-bond: {1}, required: {2}"
+    )]
+    SelfBondBelowMinimum(Address, String, String),
+    #[error("Validator {0} does not accept delegations")]
+    DelegationsClosed(Address),
+    #[error(
+        "Delegator {0} is not in the delegation allowlist of validator {1}"
+    )]
+    DelegatorNotAllowed(Address, Address),
 }
 
 #[allow(missing_docs)]
@@ -176,6 +192,20 @@ pub enum ConsensusKeyChangeError {
     MustBeEd25519,
 }
 
+#[allow(missing_docs)]
+#[derive(Error, Debug)]
+pub enum SlashSimulationError {
+    #[error("The given address {0} is not a validator address")]
+    NotAValidator(Address),
+    #[error("The infraction epoch {0} is after the current epoch {1}")]
+    InfractionInFuture(Epoch, Epoch),
+    #[error(
+        "An infraction in epoch {0} would have already been processed by the \
+         current epoch {1}"
+    )]
+    InfractionAlreadyProcessed(Epoch, Epoch),
+}
+
 impl From<BecomeValidatorError> for namada_storage::Error {
     fn from(err: BecomeValidatorError) -> Self {
         Self::new(err)
@@ -247,3 +277,9 @@ impl From<ConsensusKeyChangeError> for namada_storage::Error {
         Self::new(err)
     }
 }
+
+impl From<SlashSimulationError> for namada_storage::Error {
+    fn from(err: SlashSimulationError) -> Self {
+        Self::new(err)
+    }
+}
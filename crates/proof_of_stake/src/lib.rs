@@ -11,6 +11,7 @@ pub mod parameters;
 pub mod pos_queries;
 pub mod queries;
 pub mod rewards;
+pub mod simulation;
 pub mod slashing;
 pub mod storage;
 pub mod storage_key;
@@ -55,11 +56,11 @@ use crate::storage::{
     delegator_redelegated_unbonds_handle, get_last_reward_claim_epoch,
     liveness_missed_votes_handle, liveness_sum_missed_votes_handle,
     read_consensus_validator_set_addresses, read_non_pos_owned_params,
-    read_pos_params, read_validator_last_slash_epoch,
-    read_validator_max_commission_rate_change, read_validator_stake,
-    total_bonded_handle, total_consensus_stake_handle, total_unbonded_handle,
-    try_insert_consensus_key, unbond_handle, update_total_deltas,
-    update_validator_deltas, validator_addresses_handle,
+    read_pos_params, read_validator_delegation_policy,
+    read_validator_last_slash_epoch, read_validator_max_commission_rate_change,
+    read_validator_stake, total_bonded_handle, total_consensus_stake_handle,
+    total_unbonded_handle, try_insert_consensus_key, unbond_handle,
+    update_total_deltas, update_validator_deltas, validator_addresses_handle,
     validator_commission_rate_handle, validator_consensus_key_handle,
     validator_deltas_handle, validator_eth_cold_key_handle,
     validator_eth_hot_key_handle, validator_incoming_redelegations_handle,
@@ -69,19 +70,21 @@ use crate::storage::{
     validator_total_redelegated_bonded_handle,
     validator_total_redelegated_unbonded_handle, write_last_reward_claim_epoch,
     write_pos_params, write_validator_address_raw_hash, write_validator_avatar,
-    write_validator_description, write_validator_discord_handle,
-    write_validator_email, write_validator_max_commission_rate_change,
-    write_validator_metadata, write_validator_website,
+    write_validator_delegation_policy, write_validator_description,
+    write_validator_discord_handle, write_validator_email,
+    write_validator_max_commission_rate_change, write_validator_metadata,
+    write_validator_website,
 };
 use crate::storage_key::{bonds_for_source_prefix, is_bond_key};
 use crate::types::{
-    BondId, ConsensusValidator, ConsensusValidatorSet,
+    BondId, ConsensusValidator, ConsensusValidatorSet, DelegationPolicy,
     EagerRedelegatedBondsMap, RedelegatedBondsOrUnbonds, RedelegatedTokens,
     ResultSlashing, Slash, Unbonds, ValidatorMetaData, ValidatorSetUpdate,
     ValidatorState, VoteInfo,
 };
 use crate::validator_set_update::{
-    copy_validator_sets_and_positions, insert_validator_into_validator_set,
+    copy_validator_sets_and_positions, demote_validator_below_min_self_bond,
+    insert_validator_into_validator_set,
     promote_next_below_capacity_validator_to_consensus,
     remove_below_capacity_validator, remove_consensus_validator,
     update_validator_set,
@@ -247,6 +250,18 @@ where
         return Err(BondError::NotAValidator(validator.clone()).into());
     }
 
+    // Check the validator's delegation policy and limits
+    if source != validator {
+        check_delegation_policy(storage, source, validator)?;
+        check_delegation_limits(
+            storage,
+            &params,
+            validator,
+            amount,
+            offset_epoch,
+        )?;
+    }
+
     let bond_handle = bond_handle(source, validator);
     let total_bonded_handle = total_bonded_handle(validator);
 
@@ -300,6 +315,97 @@ where
         offset_opt,
     )?;
 
+    // A self-bond that is not sufficient to satisfy the minimum self-bond
+    // ratio must not promote the validator into the consensus set
+    if !is_jailed_or_inactive_at_pipeline {
+        demote_validator_below_min_self_bond(
+            storage,
+            &params,
+            validator,
+            offset_epoch,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Check that the `validator`'s delegation policy allows the `delegator` to
+/// delegate to it.
+fn check_delegation_policy<S>(
+    storage: &S,
+    delegator: &Address,
+    validator: &Address,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead,
+{
+    match read_validator_delegation_policy(storage, validator)? {
+        DelegationPolicy::Closed => {
+            Err(BondError::DelegationsClosed(validator.clone()).into())
+        }
+        policy if !policy.allows(delegator) => {
+            Err(BondError::DelegatorNotAllowed(
+                delegator.clone(),
+                validator.clone(),
+            )
+            .into())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Check that a delegation of the given `amount` to the `validator` neither
+/// exceeds the `max_delegated_stake` nor brings the validator's self-bond below
+/// the `min_self_bond_ratio` of its stake at the given epoch.
+fn check_delegation_limits<S>(
+    storage: &S,
+    params: &PosParams,
+    validator: &Address,
+    amount: token::Amount,
+    epoch: Epoch,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead,
+{
+    if params.max_delegated_stake.is_none()
+        && params.min_self_bond_ratio.is_zero()
+    {
+        return Ok(());
+    }
+    let stake = read_validator_stake(storage, params, validator, epoch)?;
+    let self_bond = bond_amount(
+        storage,
+        &BondId {
+            source: validator.clone(),
+            validator: validator.clone(),
+        },
+        epoch,
+    )?;
+    let stake_after = stake + amount;
+
+    if let Some(max_delegated_stake) = params.max_delegated_stake {
+        let delegated_after =
+            stake_after.checked_sub(self_bond).unwrap_or_default();
+        if delegated_after > max_delegated_stake {
+            return Err(BondError::DelegationCapExceeded(
+                validator.clone(),
+                delegated_after.to_string_native(),
+                max_delegated_stake.to_string_native(),
+            )
+            .into());
+        }
+    }
+
+    let min_self_bond = stake_after.mul_ceil(params.min_self_bond_ratio);
+    if self_bond < min_self_bond {
+        return Err(BondError::SelfBondBelowMinimum(
+            validator.clone(),
+            self_bond.to_string_native(),
+            min_self_bond.to_string_native(),
+        )
+        .into());
+    }
+
     Ok(())
 }
 
@@ -682,6 +788,17 @@ where
         None,
     )?;
 
+    // The validator falls to the below-capacity set if its self-bond drops
+    // below the minimum self-bond ratio
+    if !is_jailed_or_inactive_at_pipeline {
+        demote_validator_below_min_self_bond(
+            storage,
+            &params,
+            validator,
+            pipeline_epoch,
+        )?;
+    }
+
     if tracing::level_enabled!(tracing::Level::DEBUG) {
         let bonds = find_bonds(storage, source, validator)?;
         tracing::debug!("\nBonds after decrementing: {bonds:#?}");
@@ -2001,6 +2118,9 @@ where
         );
     }
 
+    // The dest validator must accept delegations from the delegator
+    check_delegation_policy(storage, delegator, dest_validator)?;
+
     let params = read_pos_params(storage)?;
     let pipeline_epoch = current_epoch + params.pipeline_len;
     let src_redel_end_epoch =
@@ -2047,6 +2167,15 @@ where
         amount_after_slashing.to_string_native()
     );
 
+    // Check the dest validator's delegation limits
+    check_delegation_limits(
+        storage,
+        &params,
+        dest_validator,
+        amount_after_slashing,
+        pipeline_epoch,
+    )?;
+
     // Add incoming redelegated bonds to the dest validator.
     // `updatedRedelegatedBonds` with updates to delegatorState
     // `redelegatedBonded`
@@ -2205,6 +2334,7 @@ where
             // Promote the next below-capacity validator to consensus
             promote_next_below_capacity_validator_to_consensus(
                 storage,
+                &params,
                 pipeline_epoch,
             )?;
         }
@@ -2426,6 +2556,49 @@ where
     Ok(())
 }
 
+/// Derive the number of missed votes within the liveness window at which a
+/// consensus validator gets jailed from the `liveness_threshold` percentage.
+pub fn liveness_missed_votes_threshold(
+    params: &PosParams,
+) -> namada_storage::Result<u64> {
+    Ok(((Dec::one() - params.liveness_threshold)
+        * params.liveness_window_check)
+        .to_uint()
+        .ok_or_else(|| {
+            namada_storage::Error::SimpleMessage(
+                "Found negative liveness threshold",
+            )
+        })?
+        .as_u64())
+}
+
+/// Demote all the consensus validators whose self-bond is below the
+/// `min_self_bond_ratio` of their stake at the given epoch to the
+/// below-capacity set
+pub fn demote_validators_below_min_self_bond<S>(
+    storage: &mut S,
+    params: &PosParams,
+    epoch: Epoch,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    if params.min_self_bond_ratio.is_zero() {
+        return Ok(());
+    }
+    let consensus_validators = consensus_validator_set_handle()
+        .at(&epoch)
+        .iter(storage)?
+        .map(|res| res.map(|(_sub_key, address)| address))
+        .collect::<namada_storage::Result<Vec<_>>>()?;
+    for validator in consensus_validators {
+        demote_validator_below_min_self_bond(
+            storage, params, &validator, epoch,
+        )?;
+    }
+    Ok(())
+}
+
 /// Jail validators who failed to match the liveness threshold
 pub fn jail_for_liveness<S>(
     storage: &mut S,
@@ -2436,16 +2609,7 @@ pub fn jail_for_liveness<S>(
 where
     S: StorageRead + StorageWrite,
 {
-    // Derive the actual missing votes limit from the percentage
-    let missing_votes_threshold = ((Dec::one() - params.liveness_threshold)
-        * params.liveness_window_check)
-        .to_uint()
-        .ok_or_else(|| {
-            namada_storage::Error::SimpleMessage(
-                "Found negative liveness threshold",
-            )
-        })?
-        .as_u64();
+    let missing_votes_threshold = liveness_missed_votes_threshold(params)?;
 
     // Jail inactive validators
     let validators_to_jail = liveness_sum_missed_votes_handle()
@@ -2617,6 +2781,22 @@ where
     Ok(())
 }
 
+/// Change the policy on who is allowed to delegate to a validator. Existing
+/// delegations are not affected.
+pub fn change_validator_delegation_policy<S>(
+    storage: &mut S,
+    validator: &Address,
+    policy: &DelegationPolicy,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    if !is_validator(storage, validator)? {
+        return Err(BondError::NotAValidator(validator.clone()).into());
+    }
+    write_validator_delegation_policy(storage, validator, policy)
+}
+
 /// Claim available rewards, triggering an immediate transfer of tokens from the
 /// PoS account to the source address.
 pub fn claim_reward_tokens<S>(
@@ -2718,7 +2898,7 @@ where
                 // validator set at the pipeline offset
                 if epoch == pipeline_epoch {
                     promote_next_below_capacity_validator_to_consensus(
-                        storage, epoch,
+                        storage, params, epoch,
                     )?;
                 }
             }
@@ -2795,6 +2975,14 @@ where
         // Compute the total stake of the consensus validator set and record
         // it in storage
         compute_and_store_total_consensus_stake(storage, current_epoch)?;
+
+        // Demote the consensus validators whose self-bond is below the
+        // minimum ratio at the pipeline epoch
+        demote_validators_below_min_self_bond(
+            storage,
+            &pos_params,
+            current_epoch + pos_params.pipeline_len,
+        )?;
     }
 
     // Invariant: Has to be applied before `record_slashes_from_evidence`
@@ -57,6 +57,11 @@ pub struct OwnedPosParams {
     /// Fraction of validator's stake that should be slashed on a light client
     /// attack.
     pub light_client_attack_min_slash_rate: Dec,
+    /// Fraction of validator's stake that should be slashed on an equivocation
+    /// with its Ethereum bridge hot key. It is stored under its own key to keep
+    /// the layout of the parameters stored before this slash type existed.
+    #[borsh(skip)]
+    pub eth_bridge_equivocation_min_slash_rate: Dec,
     /// Number of epochs above and below (separately) the current epoch to
     /// consider when doing cubic slashing
     pub cubic_slashing_window_length: u64,
@@ -73,6 +78,15 @@ pub struct OwnedPosParams {
     pub rewards_gain_p: Dec,
     /// PoS gain d (read only)
     pub rewards_gain_d: Dec,
+    /// The minimum ratio of a validator's self-bond to its total stake
+    /// required to be in the `consensus` validator set and to receive new
+    /// delegations. It is stored under its own key.
+    #[borsh(skip)]
+    pub min_self_bond_ratio: Dec,
+    /// The maximum amount of delegated (not self-bonded) stake that a
+    /// validator may receive, if any. It is stored under its own key.
+    #[borsh(skip)]
+    pub max_delegated_stake: Option<token::Amount>,
 }
 
 impl Default for PosParams {
@@ -106,12 +120,17 @@ impl Default for OwnedPosParams {
             // slash 0.1%
             light_client_attack_min_slash_rate: Dec::new(1, 3)
                 .expect("Test failed"),
+            // slash 0.1%
+            eth_bridge_equivocation_min_slash_rate: Dec::new(1, 3)
+                .expect("Test failed"),
             cubic_slashing_window_length: 1,
             validator_stake_threshold: token::Amount::native_whole(1_u64),
             liveness_window_check: 10_000,
             liveness_threshold: Dec::new(9, 1).expect("Test failed"),
             rewards_gain_p: Dec::from_str("0.25").expect("Test failed"),
             rewards_gain_d: Dec::from_str("0.25").expect("Test failed"),
+            min_self_bond_ratio: Dec::zero(),
+            max_delegated_stake: None,
         }
     }
 }
@@ -133,6 +152,8 @@ pub enum ValidationError {
          pipeline: {1}"
     )]
     UnbondingLenTooShort(u64, u64),
+    #[error("Minimum self-bond ratio must be between 0 and 1, got {0}")]
+    InvalidMinSelfBondRatio(Dec),
 }
 
 /// The number of fundamental units per whole token of the native staking token
@@ -190,6 +211,15 @@ impl OwnedPosParams {
             ))
         }
 
+        // Check that the minimum self-bond ratio is a valid ratio
+        if self.min_self_bond_ratio.is_negative()
+            || self.min_self_bond_ratio > Dec::one()
+        {
+            errors.push(ValidationError::InvalidMinSelfBondRatio(
+                self.min_self_bond_ratio,
+            ))
+        }
+
         errors
     }
 
@@ -1,7 +1,7 @@
 //! Queriezzz
 
 use std::cmp;
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
 use borsh::BorshDeserialize;
 use namada_core::address::Address;
@@ -12,12 +12,19 @@ use namada_storage::collections::lazy_map::{NestedSubKey, SubKey};
 use namada_storage::StorageRead;
 
 use crate::slashing::{find_validator_slashes, get_slashed_amount};
-use crate::storage::{bond_handle, read_pos_params, unbond_handle};
+use crate::storage::{
+    bond_handle, liveness_missed_votes_handle,
+    liveness_sum_missed_votes_handle, read_pos_params, unbond_handle,
+};
 use crate::types::{
     BondDetails, BondId, BondsAndUnbondsDetail, BondsAndUnbondsDetails, Slash,
-    UnbondDetails,
+    UnbondDetails, ValidatorLiveness,
 };
-use crate::{storage_key, PosParams};
+use crate::{liveness_missed_votes_threshold, storage_key, PosParams};
+
+/// The percentage of the liveness missed votes threshold from which a
+/// validator is flagged as close to being jailed for liveness.
+pub const LIVENESS_WARNING_PERCENT: u64 = 80;
 
 /// Find all validators to which a given bond `owner` (or source) has a
 /// delegation
@@ -138,6 +145,42 @@ where
         .collect()
 }
 
+/// Find the liveness record of a validator within the current liveness
+/// window. Returns `None` when there is no liveness data for the validator,
+/// i.e. when it is not in the consensus validator set.
+pub fn validator_liveness<S>(
+    storage: &S,
+    params: &PosParams,
+    validator: &Address,
+) -> namada_storage::Result<Option<ValidatorLiveness>>
+where
+    S: StorageRead,
+{
+    let sum_missed_votes =
+        match liveness_sum_missed_votes_handle().get(storage, validator)? {
+            Some(sum) => sum,
+            None => return Ok(None),
+        };
+    let missed_votes = liveness_missed_votes_handle()
+        .at(validator)
+        .iter(storage)?
+        .collect::<namada_storage::Result<BTreeSet<u64>>>()?;
+    let missed_votes_threshold = liveness_missed_votes_threshold(params)?;
+    // Without any missed vote allowed, there is nothing to warn about before
+    // a validator gets jailed
+    let jailing_imminent = missed_votes_threshold > 0
+        && sum_missed_votes * 100
+            >= missed_votes_threshold * LIVENESS_WARNING_PERCENT;
+
+    Ok(Some(ValidatorLiveness {
+        missed_votes,
+        sum_missed_votes,
+        liveness_window_check: params.liveness_window_check,
+        missed_votes_threshold,
+        jailing_imminent,
+    }))
+}
+
 /// Collect the details of all bonds and unbonds that match the source and
 /// validator arguments. If either source or validator is `None`, then grab the
 /// information for all sources or validators, respectively.
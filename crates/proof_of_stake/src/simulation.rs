@@ -0,0 +1,337 @@
+//! Offline slash simulation. Hypothetical infractions are injected into a PoS
+//! state and processed with the same code paths as the ledger (see
+//! [`slash`] and [`process_slashes`]), so that the resulting slash rates and
+//! their impact on delegations can be inspected.
+//!
+//! The simulation writes to the given storage, which is expected to be
+//! discarded afterwards (e.g. a state whose write log never gets committed).
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use namada_core::address::Address;
+use namada_core::storage::Epoch;
+use namada_core::token;
+use namada_storage::collections::lazy_map::{NestedSubKey, SubKey};
+use namada_storage::{StorageRead, StorageWrite};
+
+use crate::queries::bonds_and_unbonds;
+use crate::slashing::{process_slashes, slash};
+use crate::storage::{
+    delegator_redelegated_bonds_handle, read_all_validator_addresses,
+    read_pos_params, read_validator_stake, validator_slashes_handle,
+};
+use crate::types::{BondId, BondsAndUnbondsDetails, Slash, SlashType};
+use crate::validator_set_update::copy_validator_sets_and_positions;
+use crate::{
+    compute_and_store_total_consensus_stake, is_validator, SlashSimulationError,
+};
+
+/// A hypothetical infraction to inject into a slash simulation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimulatedInfraction {
+    /// The misbehaving validator
+    pub validator: Address,
+    /// The epoch in which the infraction was committed
+    pub epoch: Epoch,
+    /// The block height at which the infraction was committed
+    pub block_height: u64,
+    /// The type of the infraction
+    pub slash_type: SlashType,
+}
+
+/// Stake of a validator before and after the simulated slashes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StakeChange {
+    /// Stake without the simulated slashes
+    pub before: token::Amount,
+    /// Stake after the simulated slashes have been processed
+    pub after: token::Amount,
+}
+
+/// Impact of the simulated slashes on the bonds and unbonds of a single bond
+/// ID
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DelegationSlashImpact {
+    /// Sum of the bond amounts
+    pub bonds_total: token::Amount,
+    /// Amount of the bonds slashed by the simulated slashes
+    pub bonds_slashed: token::Amount,
+    /// Sum of the unbond amounts
+    pub unbonds_total: token::Amount,
+    /// Amount of the unbonds slashed by the simulated slashes
+    pub unbonds_slashed: token::Amount,
+}
+
+/// A redelegated bond that was contributing to the stake of its source
+/// validator at the epoch of a simulated infraction of that validator
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AffectedRedelegation {
+    /// The misbehaving source validator of the redelegation
+    pub src_validator: Address,
+    /// The destination validator of the redelegation
+    pub dest_validator: Address,
+    /// The epoch in which the redelegation started contributing to the
+    /// destination validator
+    pub redelegation_epoch: Epoch,
+    /// The start epoch of the redelegated bond at the source validator
+    pub bond_start: Epoch,
+    /// The redelegated amount
+    pub amount: token::Amount,
+}
+
+/// The outcome of a slash simulation
+#[derive(Debug, Clone, Default)]
+pub struct SlashSimulationReport {
+    /// The epoch in which the last of the simulated slashes got processed
+    pub processing_epoch: Epoch,
+    /// The processed slashes with their final rates, by validator
+    pub slashes: BTreeMap<Address, Vec<Slash>>,
+    /// The validators whose stake has changed, at the processing epoch
+    pub validator_stakes: BTreeMap<Address, StakeChange>,
+    /// The delegations (and self-bonds) affected by the slashes
+    pub delegations: BTreeMap<BondId, DelegationSlashImpact>,
+    /// The redelegations affected by the slashes, by delegator
+    pub redelegations: BTreeMap<Address, Vec<AffectedRedelegation>>,
+}
+
+/// Inject the given infractions into the PoS state as if their evidence was
+/// received in the `current_epoch`, advance the PoS state up to the epoch in
+/// which the slashes get processed and report the resulting slashes.
+///
+/// Note that the given `storage` is modified by the simulation.
+pub fn simulate_slashes<S>(
+    storage: &mut S,
+    current_epoch: Epoch,
+    infractions: &[SimulatedInfraction],
+) -> namada_storage::Result<SlashSimulationReport>
+where
+    S: StorageRead + StorageWrite,
+{
+    let params = read_pos_params(storage)?;
+
+    for infraction in infractions {
+        if !is_validator(storage, &infraction.validator)? {
+            return Err(SlashSimulationError::NotAValidator(
+                infraction.validator.clone(),
+            )
+            .into());
+        }
+        if infraction.epoch > current_epoch {
+            return Err(SlashSimulationError::InfractionInFuture(
+                infraction.epoch,
+                current_epoch,
+            )
+            .into());
+        }
+        if infraction.epoch + params.slash_processing_epoch_offset()
+            <= current_epoch
+        {
+            return Err(SlashSimulationError::InfractionAlreadyProcessed(
+                infraction.epoch,
+                current_epoch,
+            )
+            .into());
+        }
+    }
+
+    let processing_epoch = infractions
+        .iter()
+        .map(|infraction| infraction.epoch)
+        .max()
+        .map(|epoch| epoch + params.slash_processing_epoch_offset())
+        .unwrap_or(current_epoch);
+
+    // Record the state that may be affected before slashing
+    let validators = read_all_validator_addresses(storage, current_epoch)?;
+    let mut stakes_before = BTreeMap::new();
+    for validator in validators {
+        let stake = read_validator_stake(
+            storage,
+            &params,
+            &validator,
+            processing_epoch,
+        )?;
+        stakes_before.insert(validator, stake);
+    }
+    let details_before = bonds_and_unbonds(storage, None, None)?;
+    let redelegations =
+        find_affected_redelegations(storage, &details_before, infractions)?;
+
+    // Enqueue the slashes the same way as when evidence is received
+    let validator_set_update_epoch = current_epoch.next();
+    for infraction in infractions {
+        slash(
+            storage,
+            &params,
+            current_epoch,
+            infraction.epoch,
+            infraction.block_height,
+            infraction.slash_type,
+            &infraction.validator,
+            validator_set_update_epoch,
+        )?;
+    }
+
+    // Advance the epochs until all the enqueued slashes are processed
+    for epoch in
+        Epoch::iter_bounds_inclusive(current_epoch.next(), processing_epoch)
+    {
+        copy_validator_sets_and_positions(
+            storage,
+            &params,
+            epoch,
+            epoch + params.pipeline_len,
+        )?;
+        compute_and_store_total_consensus_stake(storage, epoch)?;
+        process_slashes(storage, epoch)?;
+    }
+
+    // Collect the processed slashes of the simulated infractions
+    let mut slashes = BTreeMap::<Address, Vec<Slash>>::new();
+    let misbehaving = infractions
+        .iter()
+        .map(|infraction| &infraction.validator)
+        .collect::<BTreeSet<_>>();
+    for validator in misbehaving {
+        let validator_slashes = validator_slashes_handle(validator)
+            .iter(storage)?
+            .filter(|slash| {
+                slash.as_ref().map_or(true, |slash| {
+                    infractions.iter().any(|infraction| {
+                        &infraction.validator == validator
+                            && infraction.epoch == slash.epoch
+                            && infraction.block_height == slash.block_height
+                            && infraction.slash_type == slash.r#type
+                    })
+                })
+            })
+            .collect::<namada_storage::Result<Vec<Slash>>>()?;
+        slashes.insert(validator.clone(), validator_slashes);
+    }
+
+    let mut validator_stakes = BTreeMap::new();
+    for (validator, before) in stakes_before {
+        let after = read_validator_stake(
+            storage,
+            &params,
+            &validator,
+            processing_epoch,
+        )?;
+        if before != after {
+            validator_stakes.insert(validator, StakeChange { before, after });
+        }
+    }
+
+    let details_after = bonds_and_unbonds(storage, None, None)?;
+    let mut delegations = BTreeMap::new();
+    for (bond_id, details) in details_after {
+        let (bonds_slashed_before, unbonds_slashed_before) = details_before
+            .get(&bond_id)
+            .map(|details| {
+                (
+                    details
+                        .bonds
+                        .iter()
+                        .filter_map(|bond| bond.slashed_amount)
+                        .sum::<token::Amount>(),
+                    details
+                        .unbonds
+                        .iter()
+                        .filter_map(|unbond| unbond.slashed_amount)
+                        .sum::<token::Amount>(),
+                )
+            })
+            .unwrap_or_default();
+        let impact = DelegationSlashImpact {
+            bonds_total: details.bonds.iter().map(|bond| bond.amount).sum(),
+            bonds_slashed: details
+                .bonds
+                .iter()
+                .filter_map(|bond| bond.slashed_amount)
+                .sum::<token::Amount>()
+                - bonds_slashed_before,
+            unbonds_total: details
+                .unbonds
+                .iter()
+                .map(|unbond| unbond.amount)
+                .sum(),
+            unbonds_slashed: details
+                .unbonds
+                .iter()
+                .filter_map(|unbond| unbond.slashed_amount)
+                .sum::<token::Amount>()
+                - unbonds_slashed_before,
+        };
+        if !impact.bonds_slashed.is_zero() || !impact.unbonds_slashed.is_zero()
+        {
+            delegations.insert(bond_id, impact);
+        }
+    }
+
+    Ok(SlashSimulationReport {
+        processing_epoch,
+        slashes,
+        validator_stakes,
+        delegations,
+        redelegations,
+    })
+}
+
+/// Find the redelegated bonds of all the delegators in `details` that were
+/// contributing to the stake of a misbehaving source validator in the epoch of
+/// its infraction.
+fn find_affected_redelegations<S>(
+    storage: &S,
+    details: &BondsAndUnbondsDetails,
+    infractions: &[SimulatedInfraction],
+) -> namada_storage::Result<BTreeMap<Address, Vec<AffectedRedelegation>>>
+where
+    S: StorageRead,
+{
+    let delegators = details
+        .keys()
+        .map(|bond_id| &bond_id.source)
+        .collect::<BTreeSet<_>>();
+    let mut affected = BTreeMap::<Address, Vec<AffectedRedelegation>>::new();
+
+    for delegator in delegators {
+        for redelegation in
+            delegator_redelegated_bonds_handle(delegator).iter(storage)?
+        {
+            let (
+                NestedSubKey::Data {
+                    key: dest_validator,
+                    nested_sub_key:
+                        NestedSubKey::Data {
+                            key: redelegation_epoch,
+                            nested_sub_key:
+                                NestedSubKey::Data {
+                                    key: src_validator,
+                                    nested_sub_key: SubKey::Data(bond_start),
+                                },
+                        },
+                },
+                amount,
+            ) = redelegation?;
+
+            let is_affected = infractions.iter().any(|infraction| {
+                infraction.validator == src_validator
+                    && bond_start <= infraction.epoch
+                    && infraction.epoch < redelegation_epoch
+            });
+            if is_affected {
+                affected.entry(delegator.clone()).or_default().push(
+                    AffectedRedelegation {
+                        src_validator,
+                        dest_validator,
+                        redelegation_epoch,
+                        bond_start,
+                        amount,
+                    },
+                );
+            }
+        }
+    }
+
+    Ok(affected)
+}
@@ -189,6 +189,48 @@ where
     Ok(())
 }
 
+/// Check if an infraction of the given type, committed by the validator in the
+/// given epoch and block height, may still be slashed. It may not if its
+/// evidence is outdated, or if the same infraction has already been enqueued
+/// to be slashed.
+pub fn is_infraction_slashable<S>(
+    storage: &S,
+    params: &PosParams,
+    current_epoch: Epoch,
+    evidence_epoch: Epoch,
+    evidence_block_height: impl Into<u64>,
+    slash_type: SlashType,
+    validator: &Address,
+) -> namada_storage::Result<bool>
+where
+    S: StorageRead,
+{
+    let evidence_block_height: u64 = evidence_block_height.into();
+    if evidence_epoch + params.slash_processing_epoch_offset()
+        - params.cubic_slashing_window_length
+        <= current_epoch
+    {
+        return Ok(false);
+    }
+    let processing_epoch =
+        evidence_epoch + params.slash_processing_epoch_offset();
+    for slash in enqueued_slashes_handle()
+        .get_data_handler()
+        .at(&processing_epoch)
+        .at(validator)
+        .iter(storage)?
+    {
+        let slash = slash?;
+        if slash.epoch == evidence_epoch
+            && slash.block_height == evidence_block_height
+            && slash.r#type == slash_type
+        {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
 /// Process enqueued slashes that were discovered earlier. This function is
 /// called upon a new epoch. The final slash rate considering according to the
 /// cubic slashing rate is computed. Then, each slash is recorded in storage
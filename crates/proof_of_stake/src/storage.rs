@@ -18,7 +18,7 @@ use num_traits::CheckedAdd;
 use crate::storage_key::consensus_keys_key;
 use crate::types::{
     BelowCapacityValidatorSets, BondId, Bonds, CommissionRates,
-    ConsensusValidatorSets, DelegatorRedelegatedBonded,
+    ConsensusValidatorSets, DelegationPolicy, DelegatorRedelegatedBonded,
     DelegatorRedelegatedUnbonded, EpochedSlashes, IncomingRedelegations,
     LivenessMissedVotes, LivenessSumMissedVotes, OutgoingRedelegations,
     ReverseOrdTokenAmount, RewardsAccumulator, RewardsProducts, Slashes,
@@ -251,13 +251,53 @@ pub fn read_pos_params<S>(storage: &S) -> namada_storage::Result<PosParams>
 where
     S: StorageRead,
 {
-    let params = storage
+    let mut params: OwnedPosParams = storage
         .read(&storage_key::params_key())
         .transpose()
         .expect("PosParams should always exist in storage after genesis")?;
+    params.eth_bridge_equivocation_min_slash_rate =
+        read_eth_bridge_equivocation_min_slash_rate(storage)?;
+    params.min_self_bond_ratio = read_min_self_bond_ratio(storage)?;
+    params.max_delegated_stake = read_max_delegated_stake(storage)?;
     read_non_pos_owned_params(storage, params)
 }
 
+/// Read the PoS parameter of the minimum slash rate of Ethereum bridge
+/// equivocations, defaulting it when it is missing from storage.
+pub fn read_eth_bridge_equivocation_min_slash_rate<S>(
+    storage: &S,
+) -> namada_storage::Result<Dec>
+where
+    S: StorageRead,
+{
+    let key = storage_key::eth_bridge_equivocation_min_slash_rate_key();
+    Ok(storage.read(&key)?.unwrap_or_else(|| {
+        OwnedPosParams::default().eth_bridge_equivocation_min_slash_rate
+    }))
+}
+
+/// Read the PoS parameter of the minimum self-bond ratio of validators. It is
+/// disabled when missing from storage.
+pub fn read_min_self_bond_ratio<S>(storage: &S) -> namada_storage::Result<Dec>
+where
+    S: StorageRead,
+{
+    let key = storage_key::min_self_bond_ratio_key();
+    Ok(storage.read(&key)?.unwrap_or_else(Dec::zero))
+}
+
+/// Read the PoS parameter of the maximum delegated stake of validators. It is
+/// disabled when missing from storage.
+pub fn read_max_delegated_stake<S>(
+    storage: &S,
+) -> namada_storage::Result<Option<token::Amount>>
+where
+    S: StorageRead,
+{
+    let key = storage_key::max_delegated_stake_key();
+    Ok(storage.read(&key)?.flatten())
+}
+
 /// Read non-PoS-owned parameters to add them to `OwnedPosParams` to construct
 /// `PosParams`.
 pub fn read_non_pos_owned_params<S>(
@@ -283,7 +323,13 @@ where
     S: StorageRead + StorageWrite,
 {
     let key = storage_key::params_key();
-    storage.write(&key, params)
+    storage.write(&key, params)?;
+    let key = storage_key::eth_bridge_equivocation_min_slash_rate_key();
+    storage.write(&key, params.eth_bridge_equivocation_min_slash_rate)?;
+    let key = storage_key::min_self_bond_ratio_key();
+    storage.write(&key, params.min_self_bond_ratio)?;
+    let key = storage_key::max_delegated_stake_key();
+    storage.write(&key, params.max_delegated_stake)
 }
 
 /// Get the validator address given the raw hash of the Tendermint consensus key
@@ -807,6 +853,37 @@ where
     Ok(())
 }
 
+/// Read PoS validator's delegation policy. Validators that have never set one
+/// are open to delegations.
+pub fn read_validator_delegation_policy<S>(
+    storage: &S,
+    validator: &Address,
+) -> namada_storage::Result<DelegationPolicy>
+where
+    S: StorageRead,
+{
+    Ok(storage
+        .read(&storage_key::validator_delegation_policy_key(validator))?
+        .unwrap_or_default())
+}
+
+/// Write PoS validator's delegation policy. An open policy removes the data.
+pub fn write_validator_delegation_policy<S>(
+    storage: &mut S,
+    validator: &Address,
+    policy: &DelegationPolicy,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let key = storage_key::validator_delegation_policy_key(validator);
+    if let DelegationPolicy::Open = policy {
+        storage.delete(&key)
+    } else {
+        storage.write(&key, policy)
+    }
+}
+
 /// Get the last epoch in which rewards were claimed from storage, if any
 pub fn get_last_reward_claim_epoch<S>(
     storage: &S,
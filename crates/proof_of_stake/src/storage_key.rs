@@ -9,6 +9,10 @@ use crate::epoched;
 use crate::types::BondId;
 
 const PARAMS_STORAGE_KEY: &str = "params";
+const ETH_BRIDGE_EQUIVOCATION_MIN_SLASH_RATE_KEY: &str =
+    "eth_bridge_equivocation_min_slash_rate";
+const MIN_SELF_BOND_RATIO_KEY: &str = "min_self_bond_ratio";
+const MAX_DELEGATED_STAKE_KEY: &str = "max_delegated_stake";
 const VALIDATOR_ADDRESSES_KEY: &str = "validator_addresses";
 #[allow(missing_docs)]
 pub const VALIDATOR_STORAGE_PREFIX: &str = "validator";
@@ -55,6 +59,7 @@ const VALIDATOR_DESCRIPTION_KEY: &str = "description";
 const VALIDATOR_WEBSITE_KEY: &str = "website";
 const VALIDATOR_DISCORD_KEY: &str = "discord_handle";
 const VALIDATOR_AVATAR_KEY: &str = "avatar";
+const VALIDATOR_DELEGATION_POLICY_KEY: &str = "delegation_policy";
 const LIVENESS_PREFIX: &str = "liveness";
 const LIVENESS_MISSED_VOTES: &str = "missed_votes";
 const LIVENESS_MISSED_VOTES_SUM: &str = "sum_missed_votes";
@@ -74,9 +79,42 @@ pub fn params_key() -> Key {
         .expect("Cannot obtain a storage key")
 }
 
+/// Storage key for the PoS parameter of the minimum slash rate of Ethereum
+/// bridge equivocations. It is stored separately from the other PoS
+/// parameters to keep their storage layout.
+pub fn eth_bridge_equivocation_min_slash_rate_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&ETH_BRIDGE_EQUIVOCATION_MIN_SLASH_RATE_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Storage key for the PoS parameter of the minimum self-bond ratio of
+/// validators. It is stored separately from the other PoS parameters to keep
+/// their storage layout.
+pub fn min_self_bond_ratio_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&MIN_SELF_BOND_RATIO_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Storage key for the PoS parameter of the maximum delegated stake of
+/// validators. It is stored separately from the other PoS parameters to keep
+/// their storage layout.
+pub fn max_delegated_stake_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&MAX_DELEGATED_STAKE_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
 /// Is storage key for PoS parameters?
 pub fn is_params_key(key: &Key) -> bool {
-    matches!(&key.segments[..], [DbKeySeg::AddressSeg(addr), DbKeySeg::StringSeg(key)] if addr == &ADDRESS && key == PARAMS_STORAGE_KEY)
+    const PARAMS_KEYS: [&str; 4] = [
+        PARAMS_STORAGE_KEY,
+        ETH_BRIDGE_EQUIVOCATION_MIN_SLASH_RATE_KEY,
+        MIN_SELF_BOND_RATIO_KEY,
+        MAX_DELEGATED_STAKE_KEY,
+    ];
+    matches!(&key.segments[..], [DbKeySeg::AddressSeg(addr), DbKeySeg::StringSeg(key)] if addr == &ADDRESS && PARAMS_KEYS.contains(&key.as_str()))
 }
 
 /// Storage key prefix for validator data.
@@ -1024,6 +1062,31 @@ pub fn validator_avatar_key(validator: &Address) -> Key {
         .expect("Cannot obtain a storage key")
 }
 
+/// Storage key for a validator's delegation policy
+pub fn validator_delegation_policy_key(validator: &Address) -> Key {
+    validator_prefix(validator)
+        .push(&VALIDATOR_DELEGATION_POLICY_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Is storage key for a validator's delegation policy?
+pub fn is_validator_delegation_policy_key(key: &Key) -> Option<&Address> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::AddressSeg(validator),
+            DbKeySeg::StringSeg(key),
+        ] if addr == &ADDRESS
+            && prefix == VALIDATOR_STORAGE_PREFIX
+            && key == VALIDATOR_DELEGATION_POLICY_KEY =>
+        {
+            Some(validator)
+        }
+        _ => None,
+    }
+}
+
 /// Storage prefix for the liveness data of the cosnensus validator set.
 pub fn liveness_data_prefix() -> Key {
     Key::from(ADDRESS.to_db_key())
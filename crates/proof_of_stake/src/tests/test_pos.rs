@@ -11,7 +11,7 @@ use namada_core::storage::{BlockHeight, Epoch};
 use namada_core::{address, key};
 use namada_state::testing::TestState;
 use namada_storage::collections::lazy_map::Collectable;
-use namada_storage::StorageRead;
+use namada_storage::{StorageRead, StorageWrite};
 use proptest::prelude::*;
 use proptest::test_runner::Config;
 // Use `RUST_LOG=info` (or another tracing level) and `--nocapture` to see
@@ -21,7 +21,7 @@ use token::storage_key::minted_balance_key;
 
 use crate::parameters::testing::arb_pos_params;
 use crate::parameters::OwnedPosParams;
-use crate::queries::bonds_and_unbonds;
+use crate::queries::{bonds_and_unbonds, validator_liveness};
 use crate::rewards::{
     log_block_rewards_aux, update_rewards_products_and_mint_inflation,
     PosRewardsCalculator,
@@ -30,9 +30,9 @@ use crate::slashing::{process_slashes, slash};
 use crate::storage::{
     get_consensus_key_set, liveness_sum_missed_votes_handle,
     read_below_threshold_validator_set_addresses,
-    read_consensus_validator_set_addresses_with_stake, read_total_stake,
-    read_validator_deltas_value, rewards_accumulator_handle,
-    total_deltas_handle,
+    read_consensus_validator_set_addresses_with_stake, read_pos_params,
+    read_total_stake, read_validator_deltas_value, rewards_accumulator_handle,
+    total_deltas_handle, write_pos_params,
 };
 use crate::test_utils::test_init_genesis;
 use crate::tests::helpers::{
@@ -47,10 +47,11 @@ use crate::types::{
 use crate::{
     below_capacity_validator_set_handle, bond_handle, bond_tokens,
     change_consensus_key, consensus_validator_set_handle, is_delegator,
-    is_validator, jail_for_liveness, read_validator_stake, redelegate_tokens,
-    staking_token_address, token, unbond_handle, unbond_tokens,
-    unjail_validator, validator_consensus_key_handle,
-    validator_set_positions_handle, validator_state_handle, withdraw_tokens,
+    is_validator, jail_for_liveness, read_validator_stake,
+    record_liveness_data, redelegate_tokens, staking_token_address, token,
+    unbond_handle, unbond_tokens, unjail_validator,
+    validator_consensus_key_handle, validator_set_positions_handle,
+    validator_state_handle, withdraw_tokens,
 };
 
 proptest! {
@@ -194,6 +195,20 @@ proptest! {
     }
 }
 
+proptest! {
+    // Generate arb valid input for `test_validator_liveness_aux`
+    #![proptest_config(Config {
+        cases: 10,
+        .. Config::default()
+    })]
+    #[test]
+    fn test_validator_liveness(
+        genesis_validators in arb_genesis_validators(2..3, None),
+    ) {
+        test_validator_liveness_aux(genesis_validators)
+    }
+}
+
 /// Test genesis initialization
 fn test_test_init_genesis_aux(
     params: OwnedPosParams,
@@ -1751,3 +1766,113 @@ fn test_jail_for_liveness_aux(validators: Vec<GenesisValidator>) {
         &storage_clone.write_log()
     );
 }
+
+/// Test the liveness query against recorded liveness data
+fn test_validator_liveness_aux(validators: Vec<GenesisValidator>) {
+    let params = OwnedPosParams {
+        max_validator_slots: 2,
+        validator_stake_threshold: token::Amount::zero(),
+        liveness_window_check: 10,
+        liveness_threshold: Dec::new(5, 1).expect("Test failed"),
+        ..Default::default()
+    };
+
+    let mut s = TestState::default();
+    let current_epoch = s.in_mem().block.epoch;
+    let params = test_init_genesis(
+        &mut s,
+        params,
+        validators.clone().into_iter(),
+        current_epoch,
+    )
+    .unwrap();
+    s.commit_block().unwrap();
+
+    let absent = &validators[0].address;
+    let voter = &validators[1].address;
+    let votes = vec![VoteInfo {
+        validator_address: voter.clone(),
+        validator_vp: 1,
+    }];
+
+    // The first validator misses 4 votes out of the 5 allowed
+    for height in 1..=4_u64 {
+        record_liveness_data(
+            &mut s,
+            &votes,
+            current_epoch,
+            BlockHeight(height),
+            &params,
+        )
+        .unwrap();
+    }
+
+    let absent_liveness = validator_liveness(&s, &params, absent)
+        .unwrap()
+        .expect("Liveness data should exist for a consensus validator");
+    assert_eq!(absent_liveness.sum_missed_votes, 4);
+    assert_eq!(
+        absent_liveness.missed_votes.into_iter().collect::<Vec<_>>(),
+        vec![1, 2, 3, 4]
+    );
+    assert_eq!(absent_liveness.liveness_window_check, 10);
+    assert_eq!(absent_liveness.missed_votes_threshold, 5);
+    assert!(absent_liveness.jailing_imminent);
+
+    let voter_liveness = validator_liveness(&s, &params, voter)
+        .unwrap()
+        .expect("Liveness data should exist for a consensus validator");
+    assert_eq!(voter_liveness.sum_missed_votes, 0);
+    assert!(voter_liveness.missed_votes.is_empty());
+    assert!(!voter_liveness.jailing_imminent);
+
+    // Jailing isn't reported as imminent when no missed vote is allowed
+    let mut strict_params = params.clone();
+    strict_params.owned.liveness_threshold = Dec::one();
+    let voter_liveness = validator_liveness(&s, &strict_params, voter)
+        .unwrap()
+        .expect("Liveness data should exist for a consensus validator");
+    assert_eq!(voter_liveness.missed_votes_threshold, 0);
+    assert!(!voter_liveness.jailing_imminent);
+
+    let non_validator = address::testing::gen_implicit_address();
+    assert!(
+        validator_liveness(&s, &params, &non_validator)
+            .unwrap()
+            .is_none()
+    );
+}
+
+/// Test that the PoS parameters stored before the Ethereum bridge equivocation
+/// slash type and the self-bond and delegation limits existed are still read,
+/// with the default slash rate and the limits disabled
+#[test]
+fn test_read_pos_params_stored_without_separate_params() {
+    let mut s = TestState::default();
+    let params = OwnedPosParams {
+        eth_bridge_equivocation_min_slash_rate: Dec::new(5, 2).unwrap(),
+        min_self_bond_ratio: Dec::new(1, 1).unwrap(),
+        max_delegated_stake: Some(token::Amount::native_whole(100)),
+        ..Default::default()
+    };
+    s.write(&crate::storage_key::params_key(), &params).unwrap();
+
+    let read_params = read_pos_params(&s).unwrap();
+    assert_eq!(
+        read_params.eth_bridge_equivocation_min_slash_rate,
+        OwnedPosParams::default().eth_bridge_equivocation_min_slash_rate
+    );
+    assert_eq!(read_params.min_self_bond_ratio, Dec::zero());
+    assert_eq!(read_params.max_delegated_stake, None);
+    assert_eq!(read_params.max_validator_slots, params.max_validator_slots);
+    assert_eq!(read_params.liveness_threshold, params.liveness_threshold);
+
+    write_pos_params(&mut s, &params).unwrap();
+    let read_params = read_pos_params(&s).unwrap();
+    assert_eq!(
+        read_params.eth_bridge_equivocation_min_slash_rate,
+        params.eth_bridge_equivocation_min_slash_rate
+    );
+    assert_eq!(read_params.min_self_bond_ratio, params.min_self_bond_ratio);
+    assert_eq!(read_params.max_delegated_stake, params.max_delegated_stake);
+}
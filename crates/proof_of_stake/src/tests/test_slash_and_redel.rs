@@ -16,6 +16,7 @@ use proptest::test_runner::Config;
 use test_log::test;
 
 use crate::queries::bonds_and_unbonds;
+use crate::simulation::{simulate_slashes, SimulatedInfraction};
 use crate::slashing::{process_slashes, slash};
 use crate::storage::{
     bond_handle, delegator_redelegated_bonds_handle,
@@ -1500,3 +1501,125 @@ fn test_slashed_bond_amount_aux(validators: Vec<GenesisValidator>) {
     let diff = val_stake - self_bond_amount - del_bond_amount;
     assert!(diff <= 2.into());
 }
+
+proptest! {
+    // Generate arb valid input for `test_slash_simulation_aux`
+    #![proptest_config(Config {
+        cases: 10,
+        .. Config::default()
+    })]
+    #[test]
+    fn test_slash_simulation(
+
+    genesis_validators in arb_genesis_validators(4..6, None),
+
+    ) {
+        test_slash_simulation_aux(genesis_validators)
+    }
+}
+
+/// Test that a simulated infraction reports the processed slash together with
+/// the affected validator stake, delegations and redelegations
+fn test_slash_simulation_aux(mut validators: Vec<GenesisValidator>) {
+    validators.sort_by(|a, b| b.tokens.cmp(&a.tokens));
+
+    let misbehaving = validators[0].address.clone();
+    let dest_validator = validators[1].address.clone();
+
+    let mut storage = TestState::default();
+    let params = OwnedPosParams {
+        unbonding_len: 4,
+        ..Default::default()
+    };
+
+    // Genesis
+    let mut current_epoch = storage.in_mem().block.epoch;
+    let params = test_init_genesis(
+        &mut storage,
+        params,
+        validators.clone().into_iter(),
+        current_epoch,
+    )
+    .unwrap();
+    storage.commit_block().unwrap();
+
+    // Delegate to the misbehaving validator
+    let staking_token = staking_token_address(&storage);
+    let delegator = address::testing::gen_implicit_address();
+    let del_balance = token::Amount::native_whole(1_000);
+    credit_tokens(&mut storage, &staking_token, &delegator, del_balance)
+        .unwrap();
+    bond_tokens(
+        &mut storage,
+        Some(&delegator),
+        &misbehaving,
+        token::Amount::native_whole(100),
+        current_epoch,
+        None,
+    )
+    .unwrap();
+
+    for _ in 0..params.pipeline_len {
+        current_epoch = advance_epoch(&mut storage, &params);
+        process_slashes(&mut storage, current_epoch).unwrap();
+    }
+
+    // Redelegate a part of the bond
+    let redelegated = token::Amount::native_whole(40);
+    redelegate_tokens(
+        &mut storage,
+        &delegator,
+        &misbehaving,
+        &dest_validator,
+        current_epoch,
+        redelegated,
+    )
+    .unwrap();
+
+    // Infractions outside of the slashable range must be rejected
+    let future_infraction = SimulatedInfraction {
+        validator: misbehaving.clone(),
+        epoch: current_epoch.next(),
+        block_height: 1,
+        slash_type: SlashType::DuplicateVote,
+    };
+    assert!(
+        simulate_slashes(&mut storage, current_epoch, &[future_infraction])
+            .is_err()
+    );
+
+    let infraction = SimulatedInfraction {
+        validator: misbehaving.clone(),
+        epoch: current_epoch,
+        block_height: 1,
+        slash_type: SlashType::DuplicateVote,
+    };
+    let report =
+        simulate_slashes(&mut storage, current_epoch, &[infraction]).unwrap();
+
+    assert_eq!(
+        report.processing_epoch,
+        current_epoch + params.slash_processing_epoch_offset()
+    );
+
+    let slashes = &report.slashes[&misbehaving];
+    assert_eq!(slashes.len(), 1);
+    assert_eq!(slashes[0].epoch, current_epoch);
+    assert!(slashes[0].rate >= params.duplicate_vote_min_slash_rate);
+
+    let stake = report.validator_stakes[&misbehaving];
+    assert!(stake.after < stake.before);
+
+    let bond_id = BondId {
+        source: delegator.clone(),
+        validator: misbehaving.clone(),
+    };
+    let impact = &report.delegations[&bond_id];
+    assert!(!impact.bonds_slashed.is_zero());
+
+    let redelegations = &report.redelegations[&delegator];
+    assert_eq!(redelegations.len(), 1);
+    assert_eq!(redelegations[0].src_validator, misbehaving);
+    assert_eq!(redelegations[0].dest_validator, dest_validator);
+    assert_eq!(redelegations[0].amount, redelegated);
+}
@@ -11,6 +11,7 @@ use namada_core::storage::Epoch;
 use namada_core::token;
 use namada_state::testing::TestState;
 use namada_storage::collections::lazy_map;
+use namada_storage::StorageRead;
 use proptest::prelude::*;
 use proptest::test_runner::Config;
 // Use `RUST_LOG=info` (or another tracing level) and `--nocapture` to see
@@ -24,10 +25,13 @@ use crate::storage::{
     get_num_consensus_validators,
     read_below_capacity_validator_set_addresses_with_stake,
     read_below_threshold_validator_set_addresses,
-    read_consensus_validator_set_addresses_with_stake, update_validator_deltas,
+    read_consensus_validator_set_addresses_with_stake,
+    read_validator_delegation_policy, update_validator_deltas,
     validator_addresses_handle, validator_consensus_key_handle,
-    validator_set_positions_handle, write_validator_address_raw_hash,
+    validator_set_positions_handle, validator_state_handle,
+    write_validator_address_raw_hash,
 };
+use crate::storage_key::validator_delegation_policy_key;
 use crate::test_utils::{init_genesis_helper, test_init_genesis};
 use crate::tests::helpers::{
     advance_epoch, arb_genesis_validators, arb_params_and_genesis_validators,
@@ -35,14 +39,16 @@ use crate::tests::helpers::{
 };
 use crate::token::credit_tokens;
 use crate::types::{
-    into_tm_voting_power, ConsensusValidator, GenesisValidator, Position,
-    ReverseOrdTokenAmount, ValidatorSetUpdate, WeightedValidator,
+    into_tm_voting_power, ConsensusValidator, DelegationPolicy,
+    GenesisValidator, Position, ReverseOrdTokenAmount, ValidatorSetUpdate,
+    ValidatorState, WeightedValidator,
 };
 use crate::validator_set_update::{
     insert_validator_into_validator_set, update_validator_set,
 };
 use crate::{
-    become_validator, bond_tokens, change_consensus_key, is_validator,
+    become_validator, bond_tokens, change_consensus_key,
+    change_validator_delegation_policy, is_validator, redelegate_tokens,
     staking_token_address, unbond_tokens, withdraw_tokens, BecomeValidator,
     OwnedPosParams,
 };
@@ -1369,3 +1375,429 @@ fn test_purge_validator_information_aux(validators: Vec<GenesisValidator>) {
         assert!(!consensus_val_set.at(&ep).is_empty(&s).unwrap());
     }
 }
+
+proptest! {
+    // Generate arb valid input for `test_delegation_limits_aux`
+    #![proptest_config(Config {
+        cases: 10,
+        .. Config::default()
+    })]
+    #[test]
+    fn test_delegation_limits(
+
+    genesis_validators in arb_genesis_validators(2..4, None),
+
+    ) {
+        test_delegation_limits_aux(genesis_validators)
+    }
+}
+
+/// Test that the delegations are limited by the max delegated stake and the
+/// min self-bond ratio and that a validator whose self-bond drops below the
+/// ratio falls to the below-capacity set.
+fn test_delegation_limits_aux(validators: Vec<GenesisValidator>) {
+    let max_delegated_stake = token::Amount::native_whole(5);
+    let owned = OwnedPosParams {
+        // Set the stake threshold to 0 so no validators are in the
+        // below-threshold set
+        validator_stake_threshold: token::Amount::zero(),
+        min_self_bond_ratio: Dec::new(5, 1).expect("Test failed"),
+        max_delegated_stake: Some(max_delegated_stake),
+        ..Default::default()
+    };
+
+    let mut s = TestState::default();
+    let current_epoch = s.in_mem().block.epoch;
+    let params = test_init_genesis(
+        &mut s,
+        owned,
+        validators.clone().into_iter(),
+        current_epoch,
+    )
+    .unwrap();
+    s.commit_block().unwrap();
+
+    let validator = validators[0].address.clone();
+    let self_bond = validators[0].tokens;
+    let pipeline_epoch = current_epoch + params.pipeline_len;
+
+    let delegator = address::testing::gen_implicit_address();
+    let staking_token = staking_token_address(&s);
+    credit_tokens(
+        &mut s,
+        &staking_token,
+        &delegator,
+        token::Amount::native_whole(100),
+    )
+    .unwrap();
+
+    // A delegation above the max delegated stake must be rejected
+    let res = bond_tokens(
+        &mut s,
+        Some(&delegator),
+        &validator,
+        max_delegated_stake + token::Amount::from(1),
+        current_epoch,
+        None,
+    );
+    assert!(res.is_err());
+
+    // Delegate as much as is allowed by both the limits
+    let delegation = min(self_bond, max_delegated_stake);
+    bond_tokens(
+        &mut s,
+        Some(&delegator),
+        &validator,
+        delegation,
+        current_epoch,
+        None,
+    )
+    .unwrap();
+
+    // Any further delegation must be rejected
+    let res = bond_tokens(
+        &mut s,
+        Some(&delegator),
+        &validator,
+        token::Amount::from(1),
+        current_epoch,
+        None,
+    );
+    assert!(res.is_err());
+    assert_eq!(
+        validator_state_handle(&validator)
+            .get(&s, pipeline_epoch, &params)
+            .unwrap(),
+        Some(ValidatorState::Consensus)
+    );
+
+    // Unbond enough of the self-bond to drop below the min self-bond ratio
+    unbond_tokens(
+        &mut s,
+        None,
+        &validator,
+        self_bond - delegation + token::Amount::from(1),
+        current_epoch,
+        false,
+    )
+    .unwrap();
+
+    // The validator must have fallen to the below-capacity set
+    assert_eq!(
+        validator_state_handle(&validator)
+            .get(&s, pipeline_epoch, &params)
+            .unwrap(),
+        Some(ValidatorState::BelowCapacity)
+    );
+    let consensus_set =
+        read_consensus_validator_set_addresses_with_stake(&s, pipeline_epoch)
+            .unwrap();
+    assert!(!consensus_set.iter().any(|val| val.address == validator));
+    let below_cap_set = read_below_capacity_validator_set_addresses_with_stake(
+        &s,
+        pipeline_epoch,
+    )
+    .unwrap();
+    assert!(below_cap_set.iter().any(|val| val.address == validator));
+    // The demotion must be carried over to the next epochs
+    let current_epoch = advance_epoch(&mut s, &params);
+    assert_eq!(
+        validator_state_handle(&validator)
+            .get(&s, current_epoch + params.pipeline_len, &params)
+            .unwrap(),
+        Some(ValidatorState::BelowCapacity)
+    );
+}
+
+/// Test that a validator whose self-bond is below the min self-bond ratio is
+/// not promoted to the consensus set when another validator unbonds.
+#[test]
+fn test_promotion_requires_min_self_bond() {
+    let owned = OwnedPosParams {
+        max_validator_slots: 2,
+        // Set the stake threshold to 0 so no validators are in the
+        // below-threshold set
+        validator_stake_threshold: token::Amount::zero(),
+        min_self_bond_ratio: Dec::new(5, 1).expect("Test failed"),
+        ..Default::default()
+    };
+
+    let mut address_gen = EstablishedAddressGen::new("seed");
+    let mut gen_validator = |seed: u64, tokens: u64| {
+        let consensus_sk = common_sk_from_simple_seed(seed);
+        let protocol_sk = common_sk_from_simple_seed(seed + 100);
+        GenesisValidator {
+            address: address_gen.generate_address("validator"),
+            tokens: token::Amount::native_whole(tokens),
+            consensus_key: consensus_sk.to_public(),
+            protocol_key: protocol_sk.to_public(),
+            eth_hot_key: key::common::PublicKey::Secp256k1(
+                key::testing::gen_keypair::<key::secp256k1::SigScheme>()
+                    .ref_to(),
+            ),
+            eth_cold_key: key::common::PublicKey::Secp256k1(
+                key::testing::gen_keypair::<key::secp256k1::SigScheme>()
+                    .ref_to(),
+            ),
+            commission_rate: Dec::new(1, 1).expect("Dec creation failed"),
+            max_commission_rate_change: Dec::new(1, 1)
+                .expect("Dec creation failed"),
+            metadata: Default::default(),
+        }
+    };
+    // Two consensus validators and two below-capacity validators
+    let validators = [
+        gen_validator(0, 20),
+        gen_validator(1, 18),
+        gen_validator(2, 8),
+        gen_validator(3, 10),
+    ];
+    let unbonding_validator = validators[1].address.clone();
+    let ineligible_validator = validators[2].address.clone();
+    let eligible_validator = validators[3].address.clone();
+
+    let mut s = TestState::default();
+    let current_epoch = s.in_mem().block.epoch;
+    let params =
+        test_init_genesis(&mut s, owned, validators.into_iter(), current_epoch)
+            .unwrap();
+    s.commit_block().unwrap();
+    let pipeline_epoch = current_epoch + params.pipeline_len;
+
+    // Delegate to the validator as much as allowed by the min self-bond ratio,
+    // then unbond some of its self-bond to drop below the ratio. It stays in
+    // the below-capacity set, with more stake than the other below-capacity
+    // validator.
+    let delegator = address::testing::gen_implicit_address();
+    let staking_token = staking_token_address(&s);
+    credit_tokens(
+        &mut s,
+        &staking_token,
+        &delegator,
+        token::Amount::native_whole(100),
+    )
+    .unwrap();
+    bond_tokens(
+        &mut s,
+        Some(&delegator),
+        &ineligible_validator,
+        token::Amount::native_whole(8),
+        current_epoch,
+        None,
+    )
+    .unwrap();
+    unbond_tokens(
+        &mut s,
+        None,
+        &ineligible_validator,
+        token::Amount::native_whole(1),
+        current_epoch,
+        false,
+    )
+    .unwrap();
+    assert_eq!(
+        validator_state_handle(&ineligible_validator)
+            .get(&s, pipeline_epoch, &params)
+            .unwrap(),
+        Some(ValidatorState::BelowCapacity)
+    );
+
+    // A consensus validator unbonds enough to fall below both below-capacity
+    // validators
+    unbond_tokens(
+        &mut s,
+        None,
+        &unbonding_validator,
+        token::Amount::native_whole(17),
+        current_epoch,
+        false,
+    )
+    .unwrap();
+
+    // Only the below-capacity validator that satisfies the min self-bond ratio
+    // can be promoted
+    for (validator, state) in [
+        (&unbonding_validator, ValidatorState::BelowCapacity),
+        (&ineligible_validator, ValidatorState::BelowCapacity),
+        (&eligible_validator, ValidatorState::Consensus),
+    ] {
+        assert_eq!(
+            validator_state_handle(validator)
+                .get(&s, pipeline_epoch, &params)
+                .unwrap(),
+            Some(state)
+        );
+    }
+    let consensus_set =
+        read_consensus_validator_set_addresses_with_stake(&s, pipeline_epoch)
+            .unwrap();
+    assert!(
+        consensus_set
+            .iter()
+            .any(|val| val.address == eligible_validator)
+    );
+    assert!(
+        !consensus_set
+            .iter()
+            .any(|val| val.address == ineligible_validator)
+    );
+}
+
+proptest! {
+    // Generate arb valid input for `test_delegation_policy_aux`
+    #![proptest_config(Config {
+        cases: 10,
+        .. Config::default()
+    })]
+    #[test]
+    fn test_delegation_policy(
+
+    genesis_validators in arb_genesis_validators(2..4, None),
+
+    ) {
+        test_delegation_policy_aux(genesis_validators)
+    }
+}
+
+/// Test that bonds and redelegations are subject to the delegation policy of
+/// the target validator, while self-bonds are not.
+fn test_delegation_policy_aux(validators: Vec<GenesisValidator>) {
+    let mut s = TestState::default();
+    let current_epoch = s.in_mem().block.epoch;
+    test_init_genesis(
+        &mut s,
+        OwnedPosParams::default(),
+        validators.clone().into_iter(),
+        current_epoch,
+    )
+    .unwrap();
+    s.commit_block().unwrap();
+
+    let validator = validators[0].address.clone();
+    let other_validator = validators[1].address.clone();
+    let amount = token::Amount::native_whole(1);
+
+    let allowed = address::testing::gen_implicit_address();
+    let not_allowed = address::testing::gen_implicit_address();
+    let staking_token = staking_token_address(&s);
+    for delegator in [&allowed, &not_allowed, &validator] {
+        credit_tokens(
+            &mut s,
+            &staking_token,
+            delegator,
+            token::Amount::native_whole(100),
+        )
+        .unwrap();
+    }
+    // Both delegators start with a delegation to the other validator
+    for delegator in [&allowed, &not_allowed] {
+        bond_tokens(
+            &mut s,
+            Some(delegator),
+            &other_validator,
+            amount,
+            current_epoch,
+            None,
+        )
+        .unwrap();
+    }
+
+    // Only validators can set a delegation policy
+    assert!(
+        change_validator_delegation_policy(
+            &mut s,
+            &allowed,
+            &DelegationPolicy::Closed
+        )
+        .is_err()
+    );
+
+    // Open by default
+    assert_eq!(
+        read_validator_delegation_policy(&s, &validator).unwrap(),
+        DelegationPolicy::Open
+    );
+
+    // Only the allowlisted delegator can bond or redelegate
+    change_validator_delegation_policy(
+        &mut s,
+        &validator,
+        &DelegationPolicy::Allowlist([allowed.clone()].into()),
+    )
+    .unwrap();
+    bond_tokens(
+        &mut s,
+        Some(&allowed),
+        &validator,
+        amount,
+        current_epoch,
+        None,
+    )
+    .unwrap();
+    let res = bond_tokens(
+        &mut s,
+        Some(&not_allowed),
+        &validator,
+        amount,
+        current_epoch,
+        None,
+    );
+    assert!(res.is_err());
+    let res = redelegate_tokens(
+        &mut s,
+        &not_allowed,
+        &other_validator,
+        &validator,
+        current_epoch,
+        amount,
+    );
+    assert!(res.is_err());
+    redelegate_tokens(
+        &mut s,
+        &allowed,
+        &other_validator,
+        &validator,
+        current_epoch,
+        amount,
+    )
+    .unwrap();
+
+    // No delegations are accepted when closed, but self-bonds still are
+    change_validator_delegation_policy(
+        &mut s,
+        &validator,
+        &DelegationPolicy::Closed,
+    )
+    .unwrap();
+    let res = bond_tokens(
+        &mut s,
+        Some(&allowed),
+        &validator,
+        amount,
+        current_epoch,
+        None,
+    );
+    assert!(res.is_err());
+    bond_tokens(&mut s, None, &validator, amount, current_epoch, None).unwrap();
+
+    // Re-opening removes the policy from storage
+    change_validator_delegation_policy(
+        &mut s,
+        &validator,
+        &DelegationPolicy::Open,
+    )
+    .unwrap();
+    assert!(
+        !s.has_key(&validator_delegation_policy_key(&validator))
+            .unwrap()
+    );
+    bond_tokens(
+        &mut s,
+        Some(&not_allowed),
+        &validator,
+        amount,
+        current_epoch,
+        None,
+    )
+    .unwrap();
+}
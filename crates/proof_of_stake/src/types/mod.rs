@@ -3,7 +3,7 @@
 mod rev_order;
 
 use core::fmt::Debug;
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fmt::Display;
 use std::hash::Hash;
 use std::ops::Sub;
@@ -262,6 +262,33 @@ pub type LivenessMissedVotes = NestedMap<Address, LazySet<u64>>;
 /// elements in the corresponding inner LazySet of [`LivenessMissedVotes`].
 pub type LivenessSumMissedVotes = LazyMap<Address, u64>;
 
+/// Liveness record of a single consensus validator within the current
+/// liveness window, as exposed by queries.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+)]
+pub struct ValidatorLiveness {
+    /// The block heights within the liveness window at which the validator
+    /// missed a vote
+    pub missed_votes: BTreeSet<u64>,
+    /// The number of missed votes within the liveness window
+    pub sum_missed_votes: u64,
+    /// The length of the liveness window in blocks
+    pub liveness_window_check: u64,
+    /// The number of missed votes within the liveness window at which the
+    /// validator gets jailed
+    pub missed_votes_threshold: u64,
+    /// Set when the number of missed votes is close to the jailing threshold
+    pub jailing_imminent: bool,
+}
+
 #[derive(
     Debug,
     Clone,
@@ -376,6 +403,74 @@ pub struct ValidatorMetaData {
     pub avatar: Option<String>,
 }
 
+/// A validator's policy on who is allowed to delegate to it. Self-bonds are
+/// never restricted by the policy.
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    BorshSerialize,
+    BorshSchema,
+    BorshDeserialize,
+    BorshDeserializer,
+    Deserialize,
+    Serialize,
+    Eq,
+    PartialEq,
+)]
+pub enum DelegationPolicy {
+    /// Anyone can delegate to the validator
+    #[default]
+    Open,
+    /// Only the given addresses can delegate to the validator
+    Allowlist(BTreeSet<Address>),
+    /// No one can delegate to the validator
+    Closed,
+}
+
+impl DelegationPolicy {
+    /// Is the given delegator allowed to delegate under this policy?
+    pub fn allows(&self, delegator: &Address) -> bool {
+        match self {
+            Self::Open => true,
+            Self::Allowlist(allowed) => allowed.contains(delegator),
+            Self::Closed => false,
+        }
+    }
+}
+
+/// A tx data type to change the delegation policy of a validator.
+#[derive(
+    Clone,
+    Debug,
+    BorshSerialize,
+    BorshSchema,
+    BorshDeserialize,
+    BorshDeserializer,
+    Deserialize,
+    Serialize,
+    Eq,
+    PartialEq,
+)]
+pub struct DelegationPolicyChange {
+    /// Validator address
+    pub validator: Address,
+    /// The new delegation policy of the validator
+    pub policy: DelegationPolicy,
+}
+
+impl Display for DelegationPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Open => write!(f, "open"),
+            Self::Allowlist(allowed) => {
+                write!(f, "allowlist of {} address(es)", allowed.len())
+            }
+            Self::Closed => write!(f, "closed"),
+        }
+    }
+}
+
 #[cfg(any(test, feature = "testing"))]
 impl Default for ValidatorMetaData {
     fn default() -> Self {
@@ -597,6 +692,10 @@ pub enum SlashType {
     DuplicateVote,
     /// Light client attack.
     LightClientAttack,
+    /// Equivocation with an Ethereum bridge hot key, i.e. signing a Bridge
+    /// pool root or a validator set update that conflicts with the one
+    /// agreed upon by the protocol.
+    EthBridgeEquivocation,
 }
 
 /// VoteInfo inspired from tendermint for validators whose signature was
@@ -704,6 +803,9 @@ impl SlashType {
             SlashType::LightClientAttack => {
                 params.light_client_attack_min_slash_rate
             }
+            SlashType::EthBridgeEquivocation => {
+                params.eth_bridge_equivocation_min_slash_rate
+            }
         }
     }
 }
@@ -713,6 +815,9 @@ impl Display for SlashType {
         match self {
             SlashType::DuplicateVote => write!(f, "Duplicate vote"),
             SlashType::LightClientAttack => write!(f, "Light client attack"),
+            SlashType::EthBridgeEquivocation => {
+                write!(f, "Ethereum bridge key equivocation")
+            }
         }
     }
 }
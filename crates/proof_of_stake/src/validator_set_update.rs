@@ -18,11 +18,11 @@ use crate::storage::{
     validator_set_positions_handle, validator_state_handle,
 };
 use crate::types::{
-    into_tm_voting_power, BelowCapacityValidatorSet, ConsensusValidator,
-    ConsensusValidatorSet, Position, ReverseOrdTokenAmount,
+    into_tm_voting_power, BelowCapacityValidatorSet, BondId,
+    ConsensusValidator, ConsensusValidatorSet, Position, ReverseOrdTokenAmount,
     ValidatorPositionAddresses, ValidatorSetUpdate, ValidatorState,
 };
-use crate::PosParams;
+use crate::{bond_amount, PosParams};
 
 /// Update validator set at the pipeline epoch when a validator receives a new
 /// bond and when its bond is unbonded (self-bond or delegation).
@@ -91,12 +91,12 @@ where
             // First remove the consensus validator
             consensus_vals_pre.remove(storage, &position)?;
 
-            let max_below_capacity_validator_amount =
-                get_max_below_capacity_validator_amount(
-                    &below_capacity_val_handle,
-                    storage,
-                )?
-                .unwrap_or_default();
+            // Only a below-capacity validator that satisfies the minimum
+            // self-bond ratio can be promoted to the consensus set
+            let max_eligible_below_capacity =
+                find_max_eligible_below_capacity_validator(
+                    storage, params, epoch,
+                )?;
 
             if tokens_post < params.validator_stake_threshold {
                 tracing::debug!(
@@ -115,22 +115,20 @@ where
                     .at(&epoch)
                     .remove(storage, validator)?;
 
-                // Promote the next below-cap validator if there is one
-                if let Some(max_bc_amount) =
-                    get_max_below_capacity_validator_amount(
-                        &below_capacity_val_handle,
-                        storage,
-                    )?
+                // Promote the next eligible below-cap validator if there is
+                // one
+                if let Some((max_bc_amount, position, max_bc_validator)) =
+                    max_eligible_below_capacity
                 {
                     // Remove the max below-capacity validator first
-                    let below_capacity_vals_max =
-                        below_capacity_val_handle.at(&max_bc_amount.into());
-                    let lowest_position =
-                        find_first_position(&below_capacity_vals_max, storage)?
-                            .unwrap();
-                    let removed_max_below_capacity = below_capacity_vals_max
-                        .remove(storage, &lowest_position)?
+                    let removed_max_below_capacity = below_capacity_val_handle
+                        .at(&max_bc_amount.into())
+                        .remove(storage, &position)?
                         .expect("Must have been removed");
+                    debug_assert_eq!(
+                        removed_max_below_capacity,
+                        max_bc_validator
+                    );
 
                     // Insert the previous max below-capacity validator into the
                     // consensus set
@@ -147,30 +145,29 @@ where
                         offset,
                     )?;
                 }
-            } else if tokens_post < max_below_capacity_validator_amount {
+            } else if let Some((max_bc_amount, position, max_bc_validator)) =
+                max_eligible_below_capacity
+                    .filter(|(amount, _, _)| tokens_post < *amount)
+            {
                 tracing::debug!(
                     "Demoting this validator to the below-capacity set and \
                      promoting another to the consensus set"
                 );
                 // Place the validator into the below-capacity set and promote
-                // the lowest position max below-capacity
-                // validator.
+                // the lowest position max below-capacity validator that
+                // satisfies the minimum self-bond ratio.
 
                 // Remove the max below-capacity validator first
-                let below_capacity_vals_max = below_capacity_val_handle
-                    .at(&max_below_capacity_validator_amount.into());
-                let lowest_position =
-                    find_first_position(&below_capacity_vals_max, storage)?
-                        .unwrap();
-                let removed_max_below_capacity = below_capacity_vals_max
-                    .remove(storage, &lowest_position)?
+                let removed_max_below_capacity = below_capacity_val_handle
+                    .at(&max_bc_amount.into())
+                    .remove(storage, &position)?
                     .expect("Must have been removed");
+                debug_assert_eq!(removed_max_below_capacity, max_bc_validator);
 
                 // Insert the previous max below-capacity validator into the
                 // consensus set
                 insert_validator_into_set(
-                    &consensus_val_handle
-                        .at(&max_below_capacity_validator_amount),
+                    &consensus_val_handle.at(&max_bc_amount),
                     storage,
                     &epoch,
                     &removed_max_below_capacity,
@@ -517,25 +514,25 @@ where
 
 /// Promote the next below-capacity validator to the consensus validator set,
 /// determined as the validator in the below-capacity set with the largest stake
-/// and the lowest `Position`. Assumes that there is adequate space within the
-/// consensus set already.
+/// and the lowest `Position` that satisfies the minimum self-bond ratio.
+/// Assumes that there is adequate space within the consensus set already.
 pub fn promote_next_below_capacity_validator_to_consensus<S>(
     storage: &mut S,
+    params: &PosParams,
     epoch: Epoch,
 ) -> namada_storage::Result<()>
 where
     S: StorageRead + StorageWrite,
 {
     let below_cap_set = below_capacity_validator_set_handle().at(&epoch);
-    let max_below_capacity_amount =
-        get_max_below_capacity_validator_amount(&below_cap_set, storage)?;
+    let max_below_capacity =
+        find_max_eligible_below_capacity_validator(storage, params, epoch)?;
 
-    if let Some(max_below_capacity_amount) = max_below_capacity_amount {
-        let max_bc_vals = below_cap_set.at(&max_below_capacity_amount.into());
-        let position_to_promote = find_first_position(&max_bc_vals, storage)?
-            .expect("Should be at least one below-capacity validator");
-
-        let promoted_validator = max_bc_vals
+    if let Some((max_below_capacity_amount, position_to_promote, _address)) =
+        max_below_capacity
+    {
+        let promoted_validator = below_cap_set
+            .at(&max_below_capacity_amount.into())
             .remove(storage, &position_to_promote)?
             .expect("Should have returned a removed validator.");
 
@@ -558,6 +555,114 @@ where
     Ok(())
 }
 
+/// Demote a consensus validator whose self-bond is below the
+/// `min_self_bond_ratio` of its stake at the given epoch to the below-capacity
+/// validator set. The freed consensus slot is taken by the below-capacity
+/// validator with the largest stake and the lowest `Position` that satisfies
+/// the minimum self-bond ratio, if any.
+pub fn demote_validator_below_min_self_bond<S>(
+    storage: &mut S,
+    params: &PosParams,
+    validator: &Address,
+    epoch: Epoch,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    if params.min_self_bond_ratio.is_zero() {
+        return Ok(());
+    }
+    let state =
+        validator_state_handle(validator).get(storage, epoch, params)?;
+    if state != Some(ValidatorState::Consensus)
+        || has_min_self_bond(storage, params, validator, epoch)?
+    {
+        return Ok(());
+    }
+    tracing::info!(
+        "Demoting validator {validator} to the below-capacity set in epoch \
+         {epoch} for having a self-bond below the minimum ratio"
+    );
+
+    let stake = read_validator_stake(storage, params, validator, epoch)?;
+    remove_consensus_validator(storage, params, epoch, validator)?;
+
+    // Promote the first eligible below-capacity validator, before inserting
+    // the demoted validator into the below-capacity set
+    promote_next_below_capacity_validator_to_consensus(storage, params, epoch)?;
+
+    let below_cap_set = below_capacity_validator_set_handle().at(&epoch);
+    insert_validator_into_set(
+        &below_cap_set.at(&stake.into()),
+        storage,
+        &epoch,
+        validator,
+    )?;
+    validator_state_handle(validator).set(
+        storage,
+        ValidatorState::BelowCapacity,
+        epoch,
+        0,
+    )?;
+
+    Ok(())
+}
+
+/// Find the below-capacity validator with the largest stake and the lowest
+/// `Position` whose self-bond satisfies the `min_self_bond_ratio` of its stake
+/// at the given epoch, if any.
+fn find_max_eligible_below_capacity_validator<S>(
+    storage: &S,
+    params: &PosParams,
+    epoch: Epoch,
+) -> namada_storage::Result<Option<(token::Amount, Position, Address)>>
+where
+    S: StorageRead,
+{
+    for res in below_capacity_validator_set_handle()
+        .at(&epoch)
+        .iter(storage)?
+    {
+        let (
+            NestedSubKey::Data {
+                key: amount,
+                nested_sub_key: SubKey::Data(position),
+            },
+            address,
+        ) = res?;
+        if has_min_self_bond(storage, params, &address, epoch)? {
+            return Ok(Some((token::Amount::from(amount), position, address)));
+        }
+    }
+    Ok(None)
+}
+
+/// Check if the validator's self-bond satisfies the `min_self_bond_ratio` of
+/// its stake at the given epoch.
+pub fn has_min_self_bond<S>(
+    storage: &S,
+    params: &PosParams,
+    validator: &Address,
+    epoch: Epoch,
+) -> namada_storage::Result<bool>
+where
+    S: StorageRead,
+{
+    if params.min_self_bond_ratio.is_zero() {
+        return Ok(true);
+    }
+    let stake = read_validator_stake(storage, params, validator, epoch)?;
+    let self_bond = bond_amount(
+        storage,
+        &BondId {
+            source: validator.clone(),
+            validator: validator.clone(),
+        },
+        epoch,
+    )?;
+    Ok(self_bond >= stake.mul_ceil(params.min_self_bond_ratio))
+}
+
 /// Communicate imminent validator set updates to Tendermint. This function is
 /// called two blocks before the start of a new epoch because Tendermint
 /// validator updates become active two blocks after the updates are submitted.
@@ -933,22 +1038,6 @@ where
     Ok(())
 }
 
-/// Find the first (lowest) position in a validator set if it is not empty
-fn find_first_position<S>(
-    handle: &ValidatorPositionAddresses,
-    storage: &S,
-) -> namada_storage::Result<Option<Position>>
-where
-    S: StorageRead,
-{
-    let lowest_position = handle
-        .iter(storage)?
-        .next()
-        .transpose()?
-        .map(|(position, _addr)| position);
-    Ok(lowest_position)
-}
-
 /// Find the last (greatest) position in a validator set if it is not empty
 fn find_last_position<S>(
     handle: &ValidatorPositionAddresses,
@@ -1002,26 +1091,6 @@ where
         .unwrap_or_default())
 }
 
-/// Returns `Ok(None)` when the below capacity set is empty.
-fn get_max_below_capacity_validator_amount<S>(
-    handle: &BelowCapacityValidatorSet,
-    storage: &S,
-) -> namada_storage::Result<Option<token::Amount>>
-where
-    S: StorageRead,
-{
-    Ok(handle
-        .iter(storage)?
-        .next()
-        .transpose()?
-        .map(|(subkey, _address)| match subkey {
-            NestedSubKey::Data {
-                key,
-                nested_sub_key: _,
-            } => token::Amount::from(key),
-        }))
-}
-
 /// Inserts a validator into the provided `handle` within some validator set at
 /// the next position. Also updates the validator set position for the
 /// validator.
@@ -15,7 +15,8 @@ use namada_core::storage::{BlockHeight, Epoch};
 use namada_core::time::DateTimeUtc;
 use namada_core::{storage, token};
 use namada_governance::cli::onchain::{
-    DefaultProposal, PgfFundingProposal, PgfStewardProposal,
+    DefaultProposal, ParameterChangeProposal, PgfFundingProposal,
+    PgfStewardProposal, UpgradeProposal,
 };
 use namada_tx::data::GasLimit;
 use namada_tx::Memo;
@@ -24,6 +25,7 @@ use zeroize::Zeroizing;
 
 use crate::eth_bridge::bridge_pool;
 use crate::ibc::core::host::types::identifiers::{ChannelId, PortId};
+use crate::masp::ShieldedHistoryFormat;
 use crate::signing::SigningTxData;
 use crate::{rpc, tx, Namada};
 
@@ -423,6 +425,10 @@ pub struct InitProposal<C: NamadaTypes = SdkTypes> {
     pub is_pgf_stewards: bool,
     /// Flag if proposal is of type Pgf funding
     pub is_pgf_funding: bool,
+    /// Flag if proposal is of type parameter change
+    pub is_parameter_change: bool,
+    /// Flag if proposal is of type chain upgrade
+    pub is_upgrade: bool,
     /// Path to the tx WASM file
     pub tx_code_path: PathBuf,
 }
@@ -469,6 +475,19 @@ impl<C: NamadaTypes> InitProposal<C> {
         }
     }
 
+    /// Flag if proposal is of type parameter change
+    pub fn is_parameter_change(self, is_parameter_change: bool) -> Self {
+        Self {
+            is_parameter_change,
+            ..self
+        }
+    }
+
+    /// Flag if proposal is of type chain upgrade
+    pub fn is_upgrade(self, is_upgrade: bool) -> Self {
+        Self { is_upgrade, ..self }
+    }
+
     /// Path to the tx WASM file
     pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
         Self {
@@ -531,6 +550,62 @@ impl InitProposal {
                 })?;
 
             tx::build_pgf_stewards_proposal(context, self, proposal).await
+        } else if self.is_parameter_change {
+            let proposal = ParameterChangeProposal::try_from(
+                self.proposal_data.as_ref(),
+            )
+            .map_err(|e| {
+                crate::error::TxSubmitError::FailedGovernaneProposalDeserialize(
+                    e.to_string(),
+                )
+            })?;
+            let nam_address = context.native_token();
+            let author_balance = rpc::get_token_balance(
+                context.client(),
+                &nam_address,
+                &proposal.proposal.author,
+            )
+            .await?;
+            let proposal = proposal
+                .validate(
+                    &governance_parameters,
+                    current_epoch,
+                    author_balance,
+                    self.tx.force,
+                )
+                .map_err(|e| {
+                    crate::error::TxSubmitError::InvalidProposal(e.to_string())
+                })?;
+
+            tx::build_parameter_change_proposal(context, self, proposal).await
+        } else if self.is_upgrade {
+            let proposal = UpgradeProposal::try_from(
+                self.proposal_data.as_ref(),
+            )
+            .map_err(|e| {
+                crate::error::TxSubmitError::FailedGovernaneProposalDeserialize(
+                    e.to_string(),
+                )
+            })?;
+            let nam_address = context.native_token();
+            let author_balance = rpc::get_token_balance(
+                context.client(),
+                &nam_address,
+                &proposal.proposal.author,
+            )
+            .await?;
+            let proposal = proposal
+                .validate(
+                    &governance_parameters,
+                    current_epoch,
+                    author_balance,
+                    self.tx.force,
+                )
+                .map_err(|e| {
+                    crate::error::TxSubmitError::InvalidProposal(e.to_string())
+                })?;
+
+            tx::build_upgrade_proposal(context, self, proposal).await
         } else {
             let proposal = DefaultProposal::try_from(
                 self.proposal_data.as_ref(),
@@ -658,6 +733,8 @@ pub struct TxInitAccount<C: NamadaTypes = SdkTypes> {
     pub public_keys: Vec<C::PublicKey>,
     /// The account multisignature threshold
     pub threshold: Option<u8>,
+    /// The JSON encoded vesting of the account's tokens
+    pub vesting: Option<C::Data>,
 }
 
 impl<C: NamadaTypes> TxBuilder<C> for TxInitAccount<C> {
@@ -689,6 +766,14 @@ impl<C: NamadaTypes> TxInitAccount<C> {
         }
     }
 
+    /// The JSON encoded vesting of the new account's tokens
+    pub fn vesting(self, vesting: C::Data) -> Self {
+        Self {
+            vesting: Some(vesting),
+            ..self
+        }
+    }
+
     /// Path to the VP WASM code file
     pub fn vp_code_path(self, vp_code_path: PathBuf) -> Self {
         Self {
@@ -1131,6 +1216,17 @@ pub struct QueryProposalVotes<C: NamadaTypes = SdkTypes> {
     pub voter: Option<C::Address>,
 }
 
+/// Query governance representatives
+#[derive(Clone, Debug)]
+pub struct QueryRepresentatives<C: NamadaTypes = SdkTypes> {
+    /// Common query args
+    pub query: Query<C>,
+    /// Delegator whose representative to query
+    pub delegator: Option<C::Address>,
+    /// Representative whose represented delegators to query
+    pub representative: Option<C::Address>,
+}
+
 /// Query proposal
 #[derive(Clone, Debug)]
 pub struct QueryProposal<C: NamadaTypes = SdkTypes> {
@@ -1152,6 +1248,12 @@ pub struct QueryProtocolParameters<C: NamadaTypes = SdkTypes> {
 pub struct QueryPgf<C: NamadaTypes = SdkTypes> {
     /// Common query args
     pub query: Query<C>,
+    /// Show the pgf treasury history instead of the current state
+    pub history: bool,
+    /// Restrict the pgf treasury history to the given epoch
+    pub epoch: Option<Epoch>,
+    /// Show the pgf funding rounds instead of the current state
+    pub rounds: bool,
 }
 
 /// Withdraw arguments
@@ -1283,6 +1385,24 @@ pub struct QueryBalance<C: NamadaTypes = SdkTypes> {
     pub no_conversions: bool,
 }
 
+/// Project the MASP rewards of the notes of a viewing key
+#[derive(Clone, Debug)]
+pub struct QueryShieldedRewards<C: NamadaTypes = SdkTypes> {
+    /// Common query args
+    pub query: Query<C>,
+    /// The viewing key whose rewards to project
+    pub viewing_key: C::ViewingKey,
+}
+
+/// Query the MASP limits of a token
+#[derive(Clone, Debug)]
+pub struct QueryMaspTokenLimits<C: NamadaTypes = SdkTypes> {
+    /// Common query args
+    pub query: Query<C>,
+    /// Address of a token
+    pub token: C::Address,
+}
+
 /// Query historical transfer(s)
 #[derive(Clone, Debug)]
 pub struct QueryTransfers<C: NamadaTypes = SdkTypes> {
@@ -1294,6 +1414,45 @@ pub struct QueryTransfers<C: NamadaTypes = SdkTypes> {
     pub token: Option<C::Address>,
 }
 
+/// Export the shielded history of a viewing key
+#[derive(Clone, Debug)]
+pub struct QueryShieldedHistory<C: NamadaTypes = SdkTypes> {
+    /// Common query args
+    pub query: Query<C>,
+    /// The viewing key whose history to export
+    pub viewing_key: C::ViewingKey,
+    /// Address of a token
+    pub token: Option<C::Address>,
+    /// The format of the export
+    pub format: ShieldedHistoryFormat,
+    /// The file to write the export to, instead of the standard output
+    pub output: Option<PathBuf>,
+}
+
+/// Disclose the notes received by a viewing key in a transaction
+#[derive(Clone, Debug)]
+pub struct DiscloseNotes<C: NamadaTypes = SdkTypes> {
+    /// Common query args
+    pub query: Query<C>,
+    /// The viewing key that received or sent the notes
+    pub viewing_key: C::ViewingKey,
+    /// The height of the block that included the transaction
+    pub height: BlockHeight,
+    /// The index of the transaction in its block
+    pub tx_index: storage::TxIndex,
+    /// The file to write the disclosure to, instead of the standard output
+    pub output: Option<PathBuf>,
+}
+
+/// Verify a disclosure of notes
+#[derive(Clone, Debug)]
+pub struct VerifyNoteDisclosure<C: NamadaTypes = SdkTypes> {
+    /// Common query args
+    pub query: Query<C>,
+    /// The file containing the disclosure
+    pub input: PathBuf,
+}
+
 /// Query PoS bond(s)
 #[derive(Clone, Debug)]
 pub struct QueryBonds<C: NamadaTypes = SdkTypes> {
@@ -1327,6 +1486,15 @@ pub struct QueryValidatorState<C: NamadaTypes = SdkTypes> {
     pub epoch: Option<Epoch>,
 }
 
+/// Query the liveness record of a validator
+#[derive(Clone, Debug)]
+pub struct QueryValidatorLiveness<C: NamadaTypes = SdkTypes> {
+    /// Common query args
+    pub query: Query<C>,
+    /// Address of a validator
+    pub validator: C::Address,
+}
+
 #[derive(Clone, Debug)]
 /// Commission rate change args
 pub struct CommissionRateChange<C: NamadaTypes = SdkTypes> {
@@ -1551,6 +1719,128 @@ impl MetaDataChange {
     }
 }
 
+#[derive(Clone, Debug)]
+/// Validator delegation policy change args
+pub struct DelegationPolicyChange<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// Validator address (should be self)
+    pub validator: C::Address,
+    /// Reject all delegations
+    pub closed: bool,
+    /// Only accept delegations from these addresses. The validator is open
+    /// to all delegations when this is empty and `closed` is not set.
+    pub allowlist: Vec<C::Address>,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for DelegationPolicyChange<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        DelegationPolicyChange {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> DelegationPolicyChange<C> {
+    /// Validator address (should be self)
+    pub fn validator(self, validator: C::Address) -> Self {
+        Self { validator, ..self }
+    }
+
+    /// Reject all delegations
+    pub fn closed(self, closed: bool) -> Self {
+        Self { closed, ..self }
+    }
+
+    /// Only accept delegations from these addresses
+    pub fn allowlist(self, allowlist: Vec<C::Address>) -> Self {
+        Self { allowlist, ..self }
+    }
+
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+}
+
+impl DelegationPolicyChange {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_validator_delegation_policy_change(context, self).await
+    }
+}
+
+#[derive(Clone, Debug)]
+/// Governance representative change args
+pub struct ChangeRepresentative<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// Delegator address
+    pub delegator: C::Address,
+    /// The representative voting on behalf of the delegator, or `None` to
+    /// revoke the current representative
+    pub representative: Option<C::Address>,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for ChangeRepresentative<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        ChangeRepresentative {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> ChangeRepresentative<C> {
+    /// Delegator address
+    pub fn delegator(self, delegator: C::Address) -> Self {
+        Self { delegator, ..self }
+    }
+
+    /// The representative voting on behalf of the delegator
+    pub fn representative(self, representative: Option<C::Address>) -> Self {
+        Self {
+            representative,
+            ..self
+        }
+    }
+
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+}
+
+impl ChangeRepresentative {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_change_representative(context, self).await
+    }
+}
+
 #[derive(Clone, Debug)]
 /// Commission rate change args
 pub struct UpdateStewardCommission<C: NamadaTypes = SdkTypes> {
@@ -1654,6 +1944,123 @@ impl ResignSteward {
     }
 }
 
+#[derive(Clone, Debug)]
+/// Pgf funding round project registration args
+pub struct RegisterPgfProject<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// Funding round id
+    pub round_id: u64,
+    /// Project address
+    pub project: C::Address,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for RegisterPgfProject<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        RegisterPgfProject {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> RegisterPgfProject<C> {
+    /// Funding round id
+    pub fn round_id(self, round_id: u64) -> Self {
+        Self { round_id, ..self }
+    }
+
+    /// Project address
+    pub fn project(self, project: C::Address) -> Self {
+        Self { project, ..self }
+    }
+
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+}
+
+impl RegisterPgfProject {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_register_pgf_project(context, self).await
+    }
+}
+
+#[derive(Clone, Debug)]
+/// Pgf funding round vote args
+pub struct VotePgfFundingRound<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// Funding round id
+    pub round_id: u64,
+    /// Voter address
+    pub voter: C::Address,
+    /// The votes given to each project
+    pub votes: Vec<(C::Address, u64)>,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for VotePgfFundingRound<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        VotePgfFundingRound {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> VotePgfFundingRound<C> {
+    /// Funding round id
+    pub fn round_id(self, round_id: u64) -> Self {
+        Self { round_id, ..self }
+    }
+
+    /// Voter address
+    pub fn voter(self, voter: C::Address) -> Self {
+        Self { voter, ..self }
+    }
+
+    /// The votes given to each project
+    pub fn votes(self, votes: Vec<(C::Address, u64)>) -> Self {
+        Self { votes, ..self }
+    }
+
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+}
+
+impl VotePgfFundingRound {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_vote_pgf_funding_round(context, self).await
+    }
+}
+
 #[derive(Clone, Debug)]
 /// Re-activate a jailed validator args
 pub struct TxUnjailValidator<C: NamadaTypes = SdkTypes> {
@@ -1809,6 +2216,20 @@ pub struct SignTx<C: NamadaTypes = SdkTypes> {
     pub owner: C::Address,
 }
 
+#[derive(Clone, Debug)]
+/// Sign offline the shielded spends of a transaction built with a proof
+/// generation key
+pub struct SignMaspSpends<C: NamadaTypes = SdkTypes> {
+    /// The path to the dumped transaction
+    pub tx_path: PathBuf,
+    /// The path to the randomizers of the spends
+    pub spend_randomizers_path: PathBuf,
+    /// The spending key authorizing the spends
+    pub spending_key: C::SpendingKey,
+    /// The folder where the signatures are written
+    pub output_folder: Option<PathBuf>,
+}
+
 #[derive(Clone, Debug)]
 /// Sync notes from MASP owned by the provided spending /
 /// viewing keys. Syncing can be told to stop at a given
@@ -1826,6 +2247,9 @@ pub struct ShieldedSync<C: NamadaTypes = SdkTypes> {
     pub spending_keys: Vec<C::SpendingKey>,
     /// Viewing keys used to determine note ownership
     pub viewing_keys: Vec<C::ViewingKey>,
+    /// Birthday of the given spending and viewing keys, overriding the one
+    /// stored in the wallet
+    pub birthday: Option<BlockHeight>,
 }
 
 /// Query PoS commission rate
@@ -1926,7 +2350,7 @@ pub struct Tx<C: NamadaTypes = SdkTypes> {
     pub wrapper_fee_payer: Option<C::PublicKey>,
     /// The token in which the fee is being paid
     pub fee_token: C::AddrOrNativeToken,
-    /// The optional spending key for fee unshielding
+    /// The optional spending key paying the fee from the shielded pool
     pub fee_unshield: Option<C::TransferSource>,
     /// The max amount of gas used to process tx
     pub gas_limit: GasLimit,
@@ -2037,7 +2461,7 @@ pub trait TxBuilder<C: NamadaTypes>: Sized {
             ..x
         })
     }
-    /// The optional spending key for fee unshielding
+    /// The optional spending key paying the fee from the shielded pool
     fn fee_unshield(self, fee_unshield: C::TransferSource) -> Self {
         self.tx(|x| Tx {
             fee_unshield: Some(fee_unshield),
@@ -2124,6 +2548,8 @@ pub struct KeyGen {
     pub prompt_bip39_passphrase: bool,
     /// Allow non-compliant derivation path
     pub allow_non_compliant: bool,
+    /// Block height from which to look for notes of the shielded key
+    pub birthday: Option<BlockHeight>,
 }
 
 /// Wallet restore key and implicit address arguments
@@ -2147,6 +2573,8 @@ pub struct KeyDerive {
     pub prompt_bip39_passphrase: bool,
     /// Use device to generate key and address
     pub use_device: bool,
+    /// Block height from which to look for notes of the shielded key
+    pub birthday: Option<BlockHeight>,
 }
 
 /// Wallet list arguments
@@ -2226,6 +2654,8 @@ pub struct KeyAddressAdd {
     pub value: String,
     /// Don't encrypt the key
     pub unsafe_dont_encrypt: bool,
+    /// Block height from which to look for notes of a shielded key
+    pub birthday: Option<BlockHeight>,
 }
 
 /// Wallet key / address remove arguments
@@ -156,6 +156,9 @@ pub enum TxSubmitError {
     /// The address is not a valid steward
     #[error("The address {0} is not a valid steward.")]
     InvalidSteward(Address),
+    /// The pgf funding round registration or vote is not valid
+    #[error("Invalid pgf funding round {0}: {1}.")]
+    InvalidPgfFundingRound(u64, String),
     /// Invalid bond pair
     #[error("Invalid bond pair: source {0} cannot bond to validator {1}.")]
     InvalidBondPair(Address, Address),
@@ -228,8 +231,8 @@ pub enum TxSubmitError {
     /// No Balance found for token
     #[error("{0}")]
     MaspError(String),
-    /// Error in the fee unshielding transaction
-    #[error("Error in fee unshielding: {0}")]
+    /// Error in the masp fee payment transaction
+    #[error("Error in masp fee payment: {0}")]
     FeeUnshieldingError(String),
     /// Encoding transaction failure
     #[error("Encoding tx data, {0}, shouldn't fail")]
@@ -278,6 +281,9 @@ pub enum TxSubmitError {
     /// Account threshold is not set
     #[error("Account threshold must be set.")]
     MissingAccountThreshold,
+    /// Invalid account vesting
+    #[error("Invalid account vesting: {0}")]
+    InvalidVesting(String),
     /// Not enough signature
     #[error("Account threshold is {0} but the valid signatures are {1}.")]
     MissingSigningKeys(u8, u8),
@@ -58,12 +58,14 @@ use crate::token::{DenominatedAmount, NATIVE_MAX_DECIMAL_PLACES};
 use crate::tx::{
     ProcessTxResponse, TX_BECOME_VALIDATOR_WASM, TX_BOND_WASM,
     TX_BRIDGE_POOL_WASM, TX_CHANGE_COMMISSION_WASM,
-    TX_CHANGE_CONSENSUS_KEY_WASM, TX_CHANGE_METADATA_WASM,
+    TX_CHANGE_CONSENSUS_KEY_WASM, TX_CHANGE_DELEGATION_POLICY_WASM,
+    TX_CHANGE_METADATA_WASM, TX_CHANGE_REPRESENTATIVE_WASM,
     TX_CLAIM_REWARDS_WASM, TX_DEACTIVATE_VALIDATOR_WASM, TX_IBC_WASM,
     TX_INIT_ACCOUNT_WASM, TX_INIT_PROPOSAL, TX_REACTIVATE_VALIDATOR_WASM,
-    TX_REDELEGATE_WASM, TX_RESIGN_STEWARD, TX_REVEAL_PK, TX_TRANSFER_WASM,
-    TX_UNBOND_WASM, TX_UNJAIL_VALIDATOR_WASM, TX_UPDATE_ACCOUNT_WASM,
-    TX_UPDATE_STEWARD_COMMISSION, TX_VOTE_PROPOSAL, TX_WITHDRAW_WASM,
+    TX_REDELEGATE_WASM, TX_REGISTER_PGF_PROJECT_WASM, TX_RESIGN_STEWARD,
+    TX_REVEAL_PK, TX_TRANSFER_WASM, TX_UNBOND_WASM, TX_UNJAIL_VALIDATOR_WASM,
+    TX_UPDATE_ACCOUNT_WASM, TX_UPDATE_STEWARD_COMMISSION,
+    TX_VOTE_PGF_FUNDING_ROUND_WASM, TX_VOTE_PROPOSAL, TX_WITHDRAW_WASM,
     VP_USER_WASM,
 };
 use crate::wallet::{Wallet, WalletIo, WalletStorage};
@@ -181,6 +183,7 @@ pub trait Namada: Sized + MaybeSync + MaybeSend {
             tx_code_path: PathBuf::from(TX_INIT_ACCOUNT_WASM),
             public_keys,
             threshold,
+            vesting: None,
         }
     }
 
@@ -276,6 +279,8 @@ pub trait Namada: Sized + MaybeSync + MaybeSend {
             is_offline: false,
             is_pgf_stewards: false,
             is_pgf_funding: false,
+            is_parameter_change: false,
+            is_upgrade: false,
             tx_code_path: PathBuf::from(TX_INIT_PROPOSAL),
             tx: self.tx_builder(),
         }
@@ -310,6 +315,21 @@ pub trait Namada: Sized + MaybeSync + MaybeSend {
         }
     }
 
+    /// Make a ChangeRepresentative builder from the given minimum set of
+    /// arguments
+    fn new_change_representative(
+        &self,
+        delegator: Address,
+        representative: Option<Address>,
+    ) -> args::ChangeRepresentative {
+        args::ChangeRepresentative {
+            delegator,
+            representative,
+            tx_code_path: PathBuf::from(TX_CHANGE_REPRESENTATIVE_WASM),
+            tx: self.tx_builder(),
+        }
+    }
+
     /// Make a CommissionRateChange builder from the given minimum set of
     /// arguments
     fn new_change_commission_rate(
@@ -355,6 +375,21 @@ pub trait Namada: Sized + MaybeSync + MaybeSend {
         }
     }
 
+    /// Make a DelegationPolicyChange builder from the given minimum set of
+    /// arguments
+    fn new_change_delegation_policy(
+        &self,
+        validator: Address,
+    ) -> args::DelegationPolicyChange {
+        args::DelegationPolicyChange {
+            validator,
+            closed: false,
+            allowlist: vec![],
+            tx_code_path: PathBuf::from(TX_CHANGE_DELEGATION_POLICY_WASM),
+            tx: self.tx_builder(),
+        }
+    }
+
     /// Make a TxBecomeValidator builder from the given minimum set of arguments
     fn new_become_validator(
         &self,
@@ -524,6 +559,38 @@ pub trait Namada: Sized + MaybeSync + MaybeSend {
         }
     }
 
+    /// Make a RegisterPgfProject builder from the given minimum set of
+    /// arguments
+    fn new_register_pgf_project(
+        &self,
+        round_id: u64,
+        project: Address,
+    ) -> args::RegisterPgfProject {
+        args::RegisterPgfProject {
+            round_id,
+            project,
+            tx: self.tx_builder(),
+            tx_code_path: PathBuf::from(TX_REGISTER_PGF_PROJECT_WASM),
+        }
+    }
+
+    /// Make a VotePgfFundingRound builder from the given minimum set of
+    /// arguments
+    fn new_vote_pgf_funding_round(
+        &self,
+        round_id: u64,
+        voter: Address,
+        votes: Vec<(Address, u64)>,
+    ) -> args::VotePgfFundingRound {
+        args::VotePgfFundingRound {
+            round_id,
+            voter,
+            votes,
+            tx: self.tx_builder(),
+            tx_code_path: PathBuf::from(TX_VOTE_PGF_FUNDING_ROUND_WASM),
+        }
+    }
+
     /// Make a TxCustom builder from the given minimum set of arguments
     fn new_custom(&self, owner: Address) -> args::TxCustom {
         args::TxCustom {
@@ -10,6 +10,8 @@ use std::str::FromStr;
 
 use borsh::{BorshDeserialize, BorshSerialize};
 use borsh_ext::BorshSerializeExt;
+use futures::channel::mpsc;
+use futures::StreamExt;
 use itertools::Either;
 use lazy_static::lazy_static;
 use masp_primitives::asset_type::AssetType;
@@ -17,25 +19,28 @@ use masp_primitives::asset_type::AssetType;
 use masp_primitives::consensus::MainNetwork;
 #[cfg(not(feature = "mainnet"))]
 use masp_primitives::consensus::TestNetwork;
+use masp_primitives::constants::SPENDING_KEY_GENERATOR;
 use masp_primitives::convert::AllowedConversion;
-use masp_primitives::ff::PrimeField;
+use masp_primitives::ff::{Field, PrimeField};
 use masp_primitives::group::GroupEncoding;
-use masp_primitives::memo::MemoBytes;
+use masp_primitives::jubjub;
+use masp_primitives::memo::{Memo, MemoBytes};
 use masp_primitives::merkle_tree::{
     CommitmentTree, IncrementalWitness, MerklePath,
 };
 use masp_primitives::sapling::keys::FullViewingKey;
 use masp_primitives::sapling::note_encryption::*;
-use masp_primitives::sapling::redjubjub::PublicKey;
+use masp_primitives::sapling::prover::TxProver;
+use masp_primitives::sapling::redjubjub::{PrivateKey, PublicKey, Signature};
 use masp_primitives::sapling::{
-    Diversifier, Node, Note, Nullifier, ViewingKey,
+    Diversifier, Node, Note, Nullifier, ProofGenerationKey, Rseed, ViewingKey,
 };
 use masp_primitives::transaction::builder::{self, *};
 use masp_primitives::transaction::components::sapling::builder::SaplingMetadata;
 use masp_primitives::transaction::components::transparent::builder::TransparentBuilder;
 use masp_primitives::transaction::components::{
     ConvertDescription, I128Sum, OutputDescription, SpendDescription, TxOut,
-    U64Sum, ValueSum,
+    U64Sum, ValueSum, GROTH_PROOF_SIZE,
 };
 use masp_primitives::transaction::fees::fixed::FeeRule;
 use masp_primitives::transaction::sighash::{signature_hash, SignableInput};
@@ -53,8 +58,8 @@ use masp_proofs::sapling::SaplingVerificationContext;
 use namada_core::address::{Address, MASP};
 use namada_core::dec::Dec;
 pub use namada_core::masp::{
-    encode_asset_type, AssetData, BalanceOwner, ExtendedViewingKey,
-    PaymentAddress, TransferSource, TransferTarget,
+    encode_asset_type, AssetData, BalanceOwner, ExtendedProofGenerationKey,
+    ExtendedViewingKey, PaymentAddress, TransferSource, TransferTarget,
 };
 use namada_core::storage::{BlockHeight, Epoch, IndexedTx, TxIndex};
 use namada_core::time::{DateTimeUtc, DurationSecs};
@@ -63,11 +68,15 @@ use namada_ibc::IbcMessage;
 use namada_macros::BorshDeserializer;
 #[cfg(feature = "migrations")]
 use namada_migrations::*;
+use namada_token::conversion::MaspRewards;
 use namada_token::{self as token, Denomination, MaspDigitPos, Transfer};
 use namada_tx::data::{TxResult, WrapperTx};
 use namada_tx::Tx;
 use rand_core::{CryptoRng, OsRng, RngCore};
+#[cfg(feature = "multicore")]
+use rayon::prelude::*;
 use ripemd::Digest as RipemdDigest;
+use serde::{Deserialize, Serialize};
 use sha2::Digest;
 use thiserror::Error;
 use token::storage_key::{balance_key, is_any_shielded_action_balance_key};
@@ -129,6 +138,29 @@ pub struct ShieldedTransfer {
     pub metadata: SaplingMetadata,
     /// Epoch in which the transaction was created
     pub epoch: Epoch,
+    /// Randomizers of the spends that were built with a proof generation key
+    pub spend_randomizers: Vec<SpendRandomizer>,
+}
+
+/// The randomizer of the spend authorization key of a shielded spend built
+/// with a proof generation key. It must only be handed to the holder of the
+/// spending key, since anyone knowing it can link the spend to its key.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SpendRandomizer {
+    /// The randomized spend authorization key of the spend
+    pub rk: [u8; 32],
+    /// The randomizer applied to the spend authorization key
+    pub alpha: [u8; 32],
+}
+
+/// A spend authorization signature made separately from the construction of
+/// the MASP transaction
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SpendAuthorization {
+    /// The randomized spend authorization key of the signed spend
+    pub rk: [u8; 32],
+    /// The serialized signature of the spend
+    pub signature: Vec<u8>,
 }
 
 /// Shielded pool data for a token
@@ -142,6 +174,59 @@ pub struct MaspTokenRewardData {
     pub locked_amount_target: Uint,
 }
 
+/// The MASP rewards of a token held by a viewing key
+#[derive(Debug, Clone)]
+pub struct TokenRewardsProjection {
+    /// Rewards of the token projected for the next epoch
+    pub next_epoch: MaspRewards,
+    /// Reward in the native token projected for the held amount of the token
+    /// in the next epoch
+    pub projected_reward: token::Amount,
+    /// Rewards of the token in the last epochs, from oldest to newest
+    pub history: Vec<(Epoch, MaspRewards)>,
+}
+
+/// The shielded balances of a viewing key and the projection of their MASP
+/// rewards
+#[derive(Debug, Clone)]
+pub struct ShieldedRewardsProjection {
+    /// The epoch of the projection
+    pub epoch: Epoch,
+    /// The token in which rewards are paid out
+    pub native_token: Address,
+    /// Balances of the tokens held in the notes of the viewing key
+    pub balances: BTreeMap<Address, token::Amount>,
+    /// Balances of the tokens once all the allowed conversions are applied
+    pub exchanged_balances: BTreeMap<Address, token::Amount>,
+    /// Rewards of each of the held tokens that earn MASP rewards
+    pub rewards: BTreeMap<Address, TokenRewardsProjection>,
+}
+
+impl ShieldedRewardsProjection {
+    /// The rewards already earned that are claimed by applying the allowed
+    /// conversions when spending the notes
+    pub fn claimable_rewards(&self) -> token::Amount {
+        let balance = |balances: &BTreeMap<Address, token::Amount>| {
+            balances
+                .get(&self.native_token)
+                .copied()
+                .unwrap_or_default()
+        };
+        balance(&self.exchanged_balances)
+            .checked_sub(balance(&self.balances))
+            .unwrap_or_default()
+    }
+
+    /// The total reward in the native token projected for the next epoch
+    pub fn projected_reward(&self) -> token::Amount {
+        self.rewards
+            .values()
+            .fold(token::Amount::zero(), |acc, rewards| {
+                acc.checked_add(rewards.projected_reward).unwrap_or(acc)
+            })
+    }
+}
+
 /// A return type for gen_shielded_transfer
 #[derive(Error, Debug)]
 pub enum TransferErr {
@@ -322,6 +407,136 @@ fn partial_deauthorize(
     ))
 }
 
+/// Compute the hash signed by the spend authorization signatures and the
+/// binding signature of a shielded transaction
+fn shielded_sighash(transaction: &Transaction) -> Option<[u8; 32]> {
+    let unauth_tx_data = partial_deauthorize(transaction.deref())?;
+    let txid_parts = unauth_tx_data.digest(TxIdDigester);
+    let sighash =
+        signature_hash(&unauth_tx_data, &SignableInput::Shielded, &txid_parts);
+    Some(*sighash.as_ref())
+}
+
+/// Sign the spends of a shielded transaction that the given spending key
+/// authorizes. The transaction must have been built with the proof generation
+/// key of the spending key and the given randomizers must be the ones recorded
+/// at that point. Spends belonging to other keys are left out.
+pub fn sign_shielded_spends<R: RngCore + CryptoRng>(
+    transaction: &Transaction,
+    randomizers: &[SpendRandomizer],
+    spending_key: &ExtendedSpendingKey,
+    rng: &mut R,
+) -> Result<Vec<SpendAuthorization>, Error> {
+    let sighash = shielded_sighash(transaction).ok_or_else(|| {
+        Error::Other(
+            "unable to compute the sighash of the MASP transaction".to_string(),
+        )
+    })?;
+    let Some(bundle) = transaction.sapling_bundle() else {
+        return Ok(vec![]);
+    };
+    let ask = PrivateKey(spending_key.expsk.ask);
+    let mut authorizations = vec![];
+    for spend in &bundle.shielded_spends {
+        let rk = spend.rk.0.to_bytes();
+        let Some(randomizer) =
+            randomizers.iter().find(|randomizer| randomizer.rk == rk)
+        else {
+            continue;
+        };
+        let alpha: Option<jubjub::Fr> =
+            jubjub::Fr::from_repr(randomizer.alpha).into();
+        let alpha = alpha.ok_or_else(|| {
+            Error::Other("invalid spend authorization randomizer".to_string())
+        })?;
+        let rsk = ask.randomize(alpha);
+        // Only sign the spends whose key is randomized from ours
+        if PublicKey::from_private(&rsk, SPENDING_KEY_GENERATOR)
+            .0
+            .to_bytes()
+            != rk
+        {
+            continue;
+        }
+        let mut data_to_be_signed = [0u8; 64];
+        data_to_be_signed[0..32].copy_from_slice(&rk);
+        data_to_be_signed[32..64].copy_from_slice(&sighash);
+        let signature =
+            rsk.sign(&data_to_be_signed, rng, SPENDING_KEY_GENERATOR);
+        let mut bytes = vec![];
+        signature
+            .write(&mut bytes)
+            .expect("should be able to serialize a signature");
+        authorizations.push(SpendAuthorization {
+            rk,
+            signature: bytes,
+        });
+    }
+    Ok(authorizations)
+}
+
+/// Replace the spend authorization signatures of a shielded transaction by the
+/// given ones, after checking them against the keys of their spends. The
+/// transaction ID is left unchanged since it does not commit to signatures.
+pub fn authorize_shielded_spends(
+    transaction: &Transaction,
+    authorizations: &[SpendAuthorization],
+) -> Result<Transaction, Error> {
+    let sighash = shielded_sighash(transaction).ok_or_else(|| {
+        Error::Other(
+            "unable to compute the sighash of the MASP transaction".to_string(),
+        )
+    })?;
+    let tx_data = transaction.deref();
+    let mut bundle = tx_data.sapling_bundle().cloned().ok_or_else(|| {
+        Error::Other("the MASP transaction has no shielded spends".to_string())
+    })?;
+    for authorization in authorizations {
+        let spend = bundle
+            .shielded_spends
+            .iter_mut()
+            .find(|spend| spend.rk.0.to_bytes() == authorization.rk)
+            .ok_or_else(|| {
+                Error::Other(
+                    "a spend authorization does not belong to any spend of \
+                     the MASP transaction"
+                        .to_string(),
+                )
+            })?;
+        let signature =
+            Signature::read(&authorization.signature[..]).map_err(|err| {
+                Error::Other(format!(
+                    "invalid spend authorization signature: {err}"
+                ))
+            })?;
+        let mut data_to_be_signed = [0u8; 64];
+        data_to_be_signed[0..32].copy_from_slice(&authorization.rk);
+        data_to_be_signed[32..64].copy_from_slice(&sighash);
+        if !spend.rk.verify_with_zip216(
+            &data_to_be_signed,
+            &signature,
+            SPENDING_KEY_GENERATOR,
+            true,
+        ) {
+            return Err(Error::Other(
+                "a spend authorization signature is not valid for its spend"
+                    .to_string(),
+            ));
+        }
+        spend.spend_auth_sig = signature;
+    }
+    TransactionData::from_parts(
+        tx_data.version(),
+        tx_data.consensus_branch_id(),
+        tx_data.lock_time(),
+        tx_data.expiry_height(),
+        tx_data.transparent_bundle().cloned(),
+        Some(bundle),
+    )
+    .freeze()
+    .map_err(|err| Error::Other(err.to_string()))
+}
+
 /// Verify a shielded transaction.
 pub fn verify_shielded_tx(transaction: &Transaction) -> bool {
     tracing::info!("entered verify_shielded_tx()");
@@ -440,6 +655,185 @@ impl<P1, R1, N1>
     fn map_notifier(&self, _s: N1) {}
 }
 
+/// Freeze a Builder that spent with a proxy spending key, recording the viewing
+/// key of the proof generation key that the proxy stands for.
+struct ProxyWalletMap(ExtendedFullViewingKey);
+
+impl<P1>
+    masp_primitives::transaction::components::sapling::builder::MapBuilder<
+        P1,
+        ExtendedSpendingKey,
+        (),
+        ExtendedFullViewingKey,
+    > for ProxyWalletMap
+{
+    fn map_params(&self, _s: P1) {}
+
+    fn map_key(&self, _s: ExtendedSpendingKey) -> ExtendedFullViewingKey {
+        self.0
+    }
+}
+
+impl<P1, R1, N1>
+    MapBuilder<
+        P1,
+        R1,
+        ExtendedSpendingKey,
+        N1,
+        (),
+        (),
+        ExtendedFullViewingKey,
+        (),
+    > for ProxyWalletMap
+{
+    fn map_rng(&self, _s: R1) {}
+
+    fn map_notifier(&self, _s: N1) {}
+}
+
+/// Make a spending key standing in for the given proof generation key in a
+/// Builder. Its spend authorizing key is a random placeholder, so the spend
+/// authorization signatures produced with it are invalid until they get
+/// replaced by the ones of the actual spending key.
+fn proxy_spending_key<R: RngCore + CryptoRng>(
+    key: &ExtendedProofGenerationKey,
+    rng: &mut R,
+) -> ExtendedSpendingKey {
+    // The extended spending and full viewing keys share the same layout, except
+    // for the expanded spending key taking the place of the full viewing key
+    let mut bytes = key.viewing_key().to_bytes();
+    bytes[41..73].copy_from_slice(&jubjub::Fr::random(rng).to_repr());
+    bytes[73..105].copy_from_slice(&key.proof_generation_key().nsk.to_repr());
+    ExtendedSpendingKey::read(&mut &bytes[..])
+        .expect("should be able to deserialize a proxy spending key")
+}
+
+/// A prover that builds the spend proofs of a proxy spending key with the
+/// proof generation key that it stands for, and records the randomizers of the
+/// spend authorization keys so that the spends can be signed later on.
+struct DelegatedTxProver<'a, P: TxProver> {
+    prover: &'a P,
+    proxy_ak: jubjub::SubgroupPoint,
+    key: ExtendedProofGenerationKey,
+    randomizers: std::sync::Mutex<Vec<SpendRandomizer>>,
+}
+
+impl<'a, P: TxProver> DelegatedTxProver<'a, P> {
+    fn new(
+        prover: &'a P,
+        proxy: &ExtendedSpendingKey,
+        key: &ExtendedProofGenerationKey,
+    ) -> Self {
+        Self {
+            prover,
+            proxy_ak: proxy.expsk.proof_generation_key().ak,
+            key: *key,
+            randomizers: Default::default(),
+        }
+    }
+
+    fn into_randomizers(self) -> Vec<SpendRandomizer> {
+        self.randomizers
+            .into_inner()
+            .expect("the randomizers lock should not be poisoned")
+    }
+}
+
+impl<'a, P: TxProver> TxProver for DelegatedTxProver<'a, P> {
+    type SaplingProvingContext = P::SaplingProvingContext;
+
+    fn new_sapling_proving_context(&self) -> Self::SaplingProvingContext {
+        self.prover.new_sapling_proving_context()
+    }
+
+    fn spend_proof(
+        &self,
+        ctx: &mut Self::SaplingProvingContext,
+        proof_generation_key: ProofGenerationKey,
+        diversifier: Diversifier,
+        rseed: Rseed,
+        ar: jubjub::Fr,
+        asset_type: AssetType,
+        value: u64,
+        anchor: masp_proofs::bls12_381::Scalar,
+        merkle_path: MerklePath<Node>,
+    ) -> Result<([u8; GROTH_PROOF_SIZE], jubjub::ExtendedPoint, PublicKey), ()>
+    {
+        let is_proxy = proof_generation_key.ak == self.proxy_ak;
+        let proof_generation_key = if is_proxy {
+            self.key.proof_generation_key()
+        } else {
+            proof_generation_key
+        };
+        let (zkproof, cv, rk) = self.prover.spend_proof(
+            ctx,
+            proof_generation_key,
+            diversifier,
+            rseed,
+            ar,
+            asset_type,
+            value,
+            anchor,
+            merkle_path,
+        )?;
+        if is_proxy {
+            self.randomizers
+                .lock()
+                .expect("the randomizers lock should not be poisoned")
+                .push(SpendRandomizer {
+                    rk: rk.0.to_bytes(),
+                    alpha: ar.to_repr(),
+                });
+        }
+        Ok((zkproof, cv, rk))
+    }
+
+    fn output_proof(
+        &self,
+        ctx: &mut Self::SaplingProvingContext,
+        esk: jubjub::Fr,
+        payment_address: masp_primitives::sapling::PaymentAddress,
+        rcm: jubjub::Fr,
+        asset_type: AssetType,
+        value: u64,
+    ) -> ([u8; GROTH_PROOF_SIZE], jubjub::ExtendedPoint) {
+        self.prover.output_proof(
+            ctx,
+            esk,
+            payment_address,
+            rcm,
+            asset_type,
+            value,
+        )
+    }
+
+    fn convert_proof(
+        &self,
+        ctx: &mut Self::SaplingProvingContext,
+        allowed_conversion: AllowedConversion,
+        value: u64,
+        anchor: masp_proofs::bls12_381::Scalar,
+        merkle_path: MerklePath<Node>,
+    ) -> Result<([u8; GROTH_PROOF_SIZE], jubjub::ExtendedPoint), ()> {
+        self.prover.convert_proof(
+            ctx,
+            allowed_conversion,
+            value,
+            anchor,
+            merkle_path,
+        )
+    }
+
+    fn binding_sig(
+        &self,
+        ctx: &mut Self::SaplingProvingContext,
+        assets_and_values: &I128Sum,
+        sighash: &[u8; 32],
+    ) -> Result<Signature, ()> {
+        self.prover.binding_sig(ctx, assets_and_values, sighash)
+    }
+}
+
 /// Abstracts platform specific details away from the logic of shielded pool
 /// operations.
 #[cfg_attr(feature = "async-send", async_trait::async_trait)]
@@ -527,6 +921,193 @@ pub type TransferDelta = HashMap<Address, MaspChange>;
 
 /// Represents the changes that were made to a list of shielded accounts
 pub type TransactionDelta = HashMap<ViewingKey, I128Sum>;
+
+/// The direction of a change in the shielded balance of a viewing key
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    BorshSerialize,
+    BorshDeserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum TransferDirection {
+    /// Funds were received by the viewing key
+    Incoming,
+    /// Funds were spent by the viewing key
+    Outgoing,
+}
+
+impl std::fmt::Display for TransferDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Incoming => write!(f, "incoming"),
+            Self::Outgoing => write!(f, "outgoing"),
+        }
+    }
+}
+
+/// A change in the shielded balance of a viewing key in a given token,
+/// caused by a single transaction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShieldedHistoryEntry {
+    /// The time of the block that included the transaction, if known
+    pub time: Option<DateTimeUtc>,
+    /// The height of the block that included the transaction
+    pub height: BlockHeight,
+    /// The index of the transaction in its block
+    pub index: TxIndex,
+    /// The hash of the transaction, if known
+    pub tx_hash: Option<String>,
+    /// The epoch of the transaction
+    pub epoch: Epoch,
+    /// The token whose balance changed
+    pub token: Address,
+    /// The absolute value of the change
+    pub amount: token::DenominatedAmount,
+    /// Whether the balance increased or decreased
+    pub direction: TransferDirection,
+    /// The text memo of the notes received by the viewing key in the
+    /// transaction, if any
+    pub memo: Option<String>,
+    /// The transparent account that funds were shielded from or unshielded
+    /// to, if any
+    pub counterparty: Option<Address>,
+}
+
+impl ShieldedHistoryEntry {
+    /// The header of a CSV export of history entries
+    pub const CSV_HEADER: &'static str = "time,height,index,tx_hash,epoch,\
+                                          token,amount,direction,memo,\
+                                          counterparty";
+
+    /// Write this entry as a CSV record, without the trailing newline
+    pub fn to_csv_record(&self) -> String {
+        let fields = [
+            self.time.map(|time| time.to_string()).unwrap_or_default(),
+            self.height.to_string(),
+            self.index.0.to_string(),
+            self.tx_hash.clone().unwrap_or_default(),
+            self.epoch.to_string(),
+            self.token.to_string(),
+            self.amount.to_string_precise(),
+            self.direction.to_string(),
+            self.memo.clone().unwrap_or_default(),
+            self.counterparty
+                .as_ref()
+                .map(Address::to_string)
+                .unwrap_or_default(),
+        ];
+        fields
+            .iter()
+            .map(|field| {
+                if field.contains([',', '"', '\n', '\r']) {
+                    format!("\"{}\"", field.replace('"', "\"\""))
+                } else {
+                    field.clone()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+/// The opening of a note of the shielded pool together with the path of its
+/// commitment in the note commitment tree. It proves to a third party that
+/// the note was paid to the given payment address, without revealing the
+/// viewing key that decrypted or recovered it.
+#[derive(Clone, Debug, BorshSerialize, BorshDeserialize)]
+pub struct NoteDisclosure {
+    /// Whether the note was received or sent by the disclosing viewing key
+    pub direction: TransferDirection,
+    /// The payment address that received the note
+    pub payment_address: masp_primitives::sapling::PaymentAddress,
+    /// The opening of the note
+    pub note: Note,
+    /// The path from the note commitment to the root of the tree
+    pub merkle_path: MerklePath<Node>,
+}
+
+impl NoteDisclosure {
+    /// Check that the note was paid to the disclosed payment address and
+    /// get the root of the note commitment tree that includes it
+    pub fn anchor(&self) -> Result<Node, Error> {
+        let expected = self
+            .payment_address
+            .create_note(self.note.asset_type, self.note.value, self.note.rseed)
+            .map(|note| note.cmu());
+        if expected != Some(self.note.cmu()) {
+            return Err(Error::Other(
+                "The disclosed note was not paid to the disclosed payment \
+                 address"
+                    .to_string(),
+            ));
+        }
+        Ok(self.merkle_path.root(Node::from_scalar(self.note.cmu())))
+    }
+}
+
+/// Verify the given note disclosure, checking that the note commitment is
+/// included in a tree whose root is a known anchor of the chain
+pub async fn verify_note_disclosure<C: Client + Sync>(
+    client: &C,
+    disclosure: &NoteDisclosure,
+) -> Result<(), Error> {
+    let anchor = disclosure.anchor()?;
+    let anchor_key = token::storage_key::masp_commitment_anchor_key(anchor);
+    if rpc::query_has_storage_key(client, &anchor_key).await? {
+        Ok(())
+    } else {
+        Err(Error::Other(
+            "The disclosed note is not included in a known note commitment \
+             tree"
+                .to_string(),
+        ))
+    }
+}
+
+/// The formats in which a shielded history can be exported
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShieldedHistoryFormat {
+    /// A JSON array of history entries
+    Json,
+    /// CSV records of history entries, with a header
+    Csv,
+}
+
+impl FromStr for ShieldedHistoryFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            _ => Err(format!("Unknown shielded history format {}", s)),
+        }
+    }
+}
+
+/// Export the given shielded history entries in the given format
+pub fn export_shielded_history(
+    history: &[ShieldedHistoryEntry],
+    format: ShieldedHistoryFormat,
+) -> String {
+    match format {
+        ShieldedHistoryFormat::Json => serde_json::to_string_pretty(history)
+            .expect("history entries should serialize to JSON"),
+        ShieldedHistoryFormat::Csv => {
+            std::iter::once(ShieldedHistoryEntry::CSV_HEADER.to_string())
+                .chain(history.iter().map(ShieldedHistoryEntry::to_csv_record))
+                .map(|line| line + "\n")
+                .collect()
+        }
+    }
+}
+
 /// A cache of fetched indexed transactions.
 ///
 /// The cache is designed so that it either contains
@@ -546,8 +1127,8 @@ impl Unscanned {
         self.txs.extend(items);
     }
 
-    fn contains_height(&self, height: u64) -> bool {
-        self.txs.keys().any(|k| k.height.0 == height)
+    fn heights(&self) -> BTreeSet<u64> {
+        self.txs.keys().map(|k| k.height.0).collect()
     }
 
     /// We remove all indices from blocks that have been entirely scanned.
@@ -567,6 +1148,133 @@ impl IntoIterator for Unscanned {
     }
 }
 
+/// The notes of a shielded transaction that a viewing key can decrypt,
+/// indexed by their position among the outputs of the transaction
+pub type DecryptedNotes = BTreeMap<
+    usize,
+    (Note, masp_primitives::sapling::PaymentAddress, MemoBytes),
+>;
+
+/// Try to decrypt each output note of the given shielded transaction with
+/// the given viewing key
+pub fn trial_decrypt(
+    shielded: &Transaction,
+    vk: &ViewingKey,
+) -> DecryptedNotes {
+    let ivk = PreparedIncomingViewingKey::new(&vk.ivk());
+    shielded
+        .sapling_bundle()
+        .map_or(&vec![], |x| &x.shielded_outputs)
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, so)| {
+            try_sapling_note_decryption::<_, OutputDescription<<<Authorized as Authorization>::SaplingAuth as masp_primitives::transaction::components::sapling::Authorization>::Proof>>(
+                &NETWORK,
+                1.into(),
+                &ivk,
+                so,
+            )
+            .map(|decrypted| (idx, decrypted))
+        })
+        .collect()
+}
+
+/// The notes of a shielded transaction decrypted by each of the viewing keys
+/// that have to scan it
+type TxDecryptedNotes = BTreeMap<ViewingKey, DecryptedNotes>;
+
+/// The trial decryption stage of the shielded sync. Transactions are
+/// submitted as soon as they are fetched and their notes are decrypted on the
+/// rayon thread pool when the `multicore` feature is enabled, or right away
+/// otherwise. The results are sent to the scanning stage in no particular
+/// order.
+struct TrialDecryptor {
+    /// The height to which each viewing key has been synced
+    vk_heights: BTreeMap<ViewingKey, Option<IndexedTx>>,
+    /// The channel to the scanning stage
+    sender: mpsc::UnboundedSender<(IndexedTx, TxDecryptedNotes)>,
+}
+
+impl TrialDecryptor {
+    /// Start the decryption stage for the given viewing keys, returning the
+    /// receiving end of its results
+    fn new(
+        vk_heights: BTreeMap<ViewingKey, Option<IndexedTx>>,
+    ) -> (Self, DecryptedTxs) {
+        let (sender, receiver) = mpsc::unbounded();
+        let decrypted = DecryptedTxs {
+            receiver,
+            buffer: BTreeMap::new(),
+        };
+        (Self { vk_heights, sender }, decrypted)
+    }
+
+    /// Decrypt the notes of a transaction for the viewing keys that have not
+    /// scanned it yet
+    fn submit(&self, indexed_tx: IndexedTx, shielded: &Transaction) {
+        let vks: Vec<ViewingKey> = self
+            .vk_heights
+            .iter()
+            .filter(|(_vk, h)| **h < Some(indexed_tx))
+            .map(|(vk, _h)| *vk)
+            .collect();
+        let sender = self.sender.clone();
+
+        #[cfg(feature = "multicore")]
+        {
+            let shielded = shielded.clone();
+            rayon::spawn(move || {
+                let notes = vks
+                    .into_par_iter()
+                    .map(|vk| (vk, trial_decrypt(&shielded, &vk)))
+                    .collect();
+                // The scanning stage may have stopped on an error
+                let _ = sender.unbounded_send((indexed_tx, notes));
+            });
+        }
+        #[cfg(not(feature = "multicore"))]
+        {
+            let notes = vks
+                .into_iter()
+                .map(|vk| (vk, trial_decrypt(shielded, &vk)))
+                .collect();
+            let _ = sender.unbounded_send((indexed_tx, notes));
+        }
+    }
+}
+
+/// The results of the trial decryption stage, as consumed by the scanning
+/// stage
+struct DecryptedTxs {
+    /// The channel from the decryption stage
+    receiver: mpsc::UnboundedReceiver<(IndexedTx, TxDecryptedNotes)>,
+    /// The results received ahead of their turn
+    buffer: BTreeMap<IndexedTx, TxDecryptedNotes>,
+}
+
+impl DecryptedTxs {
+    /// Wait for the decrypted notes of the given transaction
+    async fn take(
+        &mut self,
+        indexed_tx: &IndexedTx,
+    ) -> Result<TxDecryptedNotes, Error> {
+        loop {
+            if let Some(notes) = self.buffer.remove(indexed_tx) {
+                return Ok(notes);
+            }
+            let (decrypted_tx, notes) =
+                self.receiver.next().await.ok_or_else(|| {
+                    Error::Other(format!(
+                        "Missing the decrypted notes of the MASP transaction \
+                         {:?}",
+                        indexed_tx
+                    ))
+                })?;
+            self.buffer.insert(decrypted_tx, notes);
+        }
+    }
+}
+
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
 /// The possible sync states of the shielded context
 pub enum ContextSyncStatus {
@@ -643,6 +1351,220 @@ impl<U: ShieldedUtils + Default> Default for ShieldedContext<U> {
     }
 }
 
+/// The segments in which a [`ShieldedContext`] is split to be persisted.
+/// Segments are serialized independently so that a store only needs to
+/// rewrite the ones that changed since the last save. The segments holding
+/// the data of each note or block are further split into chunks, persisted
+/// as the segments `<segment>.<chunk>`, so that adding notes and blocks does
+/// not rewrite the data of the older ones.
+pub const CONTEXT_SEGMENTS: [&str; 7] = [
+    "sync",
+    "notes",
+    "note_data",
+    "witnesses",
+    "deltas",
+    "assets",
+    "unscanned",
+];
+
+/// The number of note positions, or of block heights, in each chunk of a
+/// chunked context segment
+const SEGMENT_CHUNK_SIZE: u64 = 1000;
+
+/// The chunk of a chunked context segment holding the data of the given note
+/// position
+fn note_chunk(pos: &usize) -> u64 {
+    *pos as u64 / SEGMENT_CHUNK_SIZE
+}
+
+/// The chunk of a chunked context segment holding the data of the given
+/// transaction
+fn tx_chunk(indexed_tx: &IndexedTx) -> u64 {
+    indexed_tx.height.0 / SEGMENT_CHUNK_SIZE
+}
+
+/// Allocate the given chunks of a chunked context segment
+fn empty_chunks(
+    chunks: impl IntoIterator<Item = u64>,
+) -> BTreeMap<u64, Vec<u8>> {
+    chunks
+        .into_iter()
+        .map(|chunk| (chunk, Vec::new()))
+        .collect()
+}
+
+/// Serialize the given map entries into the given chunks, with the encoding of
+/// a `BTreeMap`. A chunk without entries gets an empty map.
+fn serialize_chunked<'a, K, V>(
+    chunks: &mut BTreeMap<u64, Vec<u8>>,
+    entries: impl IntoIterator<Item = (&'a K, &'a V)>,
+    chunk_of: impl Fn(&K) -> u64,
+) -> std::io::Result<()>
+where
+    K: BorshSerialize + Ord + 'a,
+    V: BorshSerialize + 'a,
+{
+    let mut grouped = BTreeMap::<u64, Vec<(&K, &V)>>::new();
+    for (key, value) in entries {
+        grouped.entry(chunk_of(key)).or_default().push((key, value));
+    }
+    for (chunk, bytes) in chunks.iter_mut() {
+        let mut entries = grouped.remove(chunk).unwrap_or_default();
+        entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+        let len = u32::try_from(entries.len()).map_err(|_| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Too many entries in a shielded context chunk",
+            )
+        })?;
+        len.serialize(bytes)?;
+        for (key, value) in entries {
+            key.serialize(bytes)?;
+            value.serialize(bytes)?;
+        }
+    }
+    Ok(())
+}
+
+impl<U: ShieldedUtils> ShieldedContext<U> {
+    /// Serialize this context into the segments listed in
+    /// [`CONTEXT_SEGMENTS`]
+    pub fn to_segments(&self) -> std::io::Result<BTreeMap<String, Vec<u8>>> {
+        let mut sync = Vec::new();
+        self.tree.serialize(&mut sync)?;
+        self.vk_heights.serialize(&mut sync)?;
+        self.tx_note_map.serialize(&mut sync)?;
+        self.sync_status.serialize(&mut sync)?;
+
+        let mut notes = Vec::new();
+        self.pos_map.serialize(&mut notes)?;
+        self.nf_map.serialize(&mut notes)?;
+        self.spents.serialize(&mut notes)?;
+
+        let mut note_data = empty_chunks(
+            self.note_map
+                .keys()
+                .chain(self.memo_map.keys())
+                .chain(self.div_map.keys())
+                .chain(self.vk_map.keys())
+                .map(note_chunk),
+        );
+        serialize_chunked(&mut note_data, &self.note_map, note_chunk)?;
+        serialize_chunked(&mut note_data, &self.memo_map, note_chunk)?;
+        serialize_chunked(&mut note_data, &self.div_map, note_chunk)?;
+        serialize_chunked(&mut note_data, &self.vk_map, note_chunk)?;
+        let mut witnesses =
+            empty_chunks(self.witness_map.keys().map(note_chunk));
+        serialize_chunked(&mut witnesses, &self.witness_map, note_chunk)?;
+        let mut deltas = empty_chunks(self.delta_map.keys().map(tx_chunk));
+        serialize_chunked(&mut deltas, &self.delta_map, tx_chunk)?;
+        let mut unscanned =
+            empty_chunks(self.unscanned.txs.keys().map(tx_chunk));
+        serialize_chunked(&mut unscanned, &self.unscanned.txs, tx_chunk)?;
+
+        let mut segments = BTreeMap::from([
+            ("sync".to_string(), sync),
+            ("notes".to_string(), notes),
+            ("assets".to_string(), self.asset_types.serialize_to_vec()),
+        ]);
+        for (segment, chunks) in [
+            ("note_data", note_data),
+            ("witnesses", witnesses),
+            ("deltas", deltas),
+            ("unscanned", unscanned),
+        ] {
+            segments.extend(chunks.into_iter().map(|(chunk, bytes)| {
+                (format!("{}.{}", segment, chunk), bytes)
+            }));
+        }
+        Ok(segments)
+    }
+
+    /// Deserialize a context from the segments listed in
+    /// [`CONTEXT_SEGMENTS`], giving it the supplied utils
+    pub fn from_segments(
+        utils: U,
+        segments: &BTreeMap<String, Vec<u8>>,
+    ) -> std::io::Result<Self> {
+        let segment = |name: &str| {
+            segments.get(name).map(Vec::as_slice).ok_or_else(|| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("Missing shielded context segment {}", name),
+                )
+            })
+        };
+        let chunks = |name: &'static str| {
+            segments.iter().filter_map(move |(segment, bytes)| {
+                segment
+                    .strip_prefix(name)
+                    .and_then(|chunk| chunk.strip_prefix('.'))
+                    .map(|_| bytes.as_slice())
+            })
+        };
+
+        let sync = &mut segment("sync")?;
+        let tree = BorshDeserialize::deserialize(sync)?;
+        let vk_heights = BorshDeserialize::deserialize(sync)?;
+        let tx_note_map = BorshDeserialize::deserialize(sync)?;
+        let sync_status = BorshDeserialize::deserialize(sync)?;
+
+        let notes = &mut segment("notes")?;
+        let pos_map = BorshDeserialize::deserialize(notes)?;
+        let nf_map = BorshDeserialize::deserialize(notes)?;
+        let spents = BorshDeserialize::deserialize(notes)?;
+
+        let mut note_map = HashMap::new();
+        let mut memo_map = HashMap::new();
+        let mut div_map = HashMap::new();
+        let mut vk_map = HashMap::new();
+        for mut chunk in chunks("note_data") {
+            let chunk = &mut chunk;
+            note_map.extend(BTreeMap::<usize, Note>::deserialize(chunk)?);
+            memo_map.extend(BTreeMap::<usize, MemoBytes>::deserialize(chunk)?);
+            div_map.extend(BTreeMap::<usize, Diversifier>::deserialize(chunk)?);
+            vk_map.extend(BTreeMap::<usize, ViewingKey>::deserialize(chunk)?);
+        }
+        let mut witness_map = HashMap::new();
+        for chunk in chunks("witnesses") {
+            witness_map.extend(
+                BTreeMap::<usize, IncrementalWitness<Node>>::try_from_slice(
+                    chunk,
+                )?,
+            );
+        }
+        let mut delta_map = BTreeMap::new();
+        for chunk in chunks("deltas") {
+            delta_map.append(&mut BorshDeserialize::try_from_slice(chunk)?);
+        }
+        let mut unscanned = Unscanned::default();
+        for chunk in chunks("unscanned") {
+            unscanned
+                .txs
+                .append(&mut BorshDeserialize::try_from_slice(chunk)?);
+        }
+
+        Ok(Self {
+            utils,
+            tree,
+            vk_heights,
+            pos_map,
+            nf_map,
+            note_map,
+            memo_map,
+            div_map,
+            witness_map,
+            delta_map,
+            spents,
+            asset_types: BorshDeserialize::try_from_slice(segment("assets")?)?,
+            vk_map,
+            tx_note_map,
+            unscanned,
+            sync_status,
+        })
+    }
+}
+
 impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
     /// Try to load the last saved shielded context from the given context
     /// directory. If this fails, then leave the current context unchanged.
@@ -701,7 +1623,8 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
     }
 
     /// Fetch the current state of the multi-asset shielded pool into a
-    /// ShieldedContext
+    /// ShieldedContext. The notes of the viewing keys found in `birthdays`
+    /// are only looked for from their birthday height onwards.
     #[allow(clippy::too_many_arguments)]
     pub async fn fetch<C: Client + Sync, IO: Io>(
         &mut self,
@@ -712,6 +1635,7 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
         _batch_size: u64,
         sks: &[ExtendedSpendingKey],
         fvks: &[ViewingKey],
+        birthdays: &BTreeMap<ViewingKey, BlockHeight>,
     ) -> Result<(), Error> {
         // add new viewing keys
         // Reload the state from file to get the last confirmed state and
@@ -735,6 +1659,23 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
         for vk in fvks {
             self.vk_heights.entry(*vk).or_default();
         }
+        // mark everything before the birthday of a key as already scanned,
+        // without ever going back on what has been scanned for it so far
+        for (vk, birthday) in birthdays {
+            let Some(height) = self.vk_heights.get_mut(vk) else {
+                continue;
+            };
+            if birthday.0 <= 1 {
+                continue;
+            }
+            let before_birthday = Some(IndexedTx {
+                height: birthday.prev_height(),
+                index: TxIndex(u32::MAX),
+            });
+            if *height < before_birthday {
+                *height = before_birthday;
+            }
+        }
         let _ = self.save().await;
         let native_token = query_native_token(client).await?;
         // the latest block height which has been added to the witness Merkle
@@ -747,37 +1688,85 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
         let start_idx =
             std::cmp::min(last_witnessed_tx, least_idx).map(|ix| ix.height);
         let start_idx = start_query_height.or(start_idx);
-        // Load all transactions accepted until this point
-        // N.B. the cache is a hash map
-        self.unscanned.extend(
-            self.fetch_shielded_transfers(
+        // The notes of the transactions are trial decrypted as soon as they
+        // are available, starting with the cached ones, concurrently with
+        // the fetching of the following blocks
+        let (decryptor, decrypted) =
+            TrialDecryptor::new(self.vk_heights.clone());
+        for (indexed_tx, (_epoch, _changed_keys, stx)) in &self.unscanned.txs {
+            decryptor.submit(*indexed_tx, stx);
+        }
+        // Load all transactions accepted until this point, handing each of
+        // them to the decryption stage and to the scanning stage as soon as it
+        // is fetched
+        let cached_heights = self.unscanned.heights();
+        let (fetched_sender, fetched) = mpsc::unbounded();
+        let fetching = async move {
+            let result = Self::fetch_shielded_transfers_with(
                 client,
                 logger,
+                &cached_heights,
                 start_idx,
                 last_query_height,
+                |(indexed_tx, tx)| {
+                    decryptor.submit(indexed_tx, &tx.2);
+                    // The scanning stage may have stopped on an error
+                    let _ = fetched_sender.unbounded_send((indexed_tx, tx));
+                },
             )
-            .await?,
-        );
+            .await;
+            // Close the following stages so that missing results are
+            // reported instead of awaited forever
+            drop(decryptor);
+            drop(fetched_sender);
+            result
+        };
+        let scanning =
+            self.scan_fetched(logger, fetched, decrypted, &native_token);
+        futures::try_join!(fetching, scanning)?;
+
+        Ok(())
+    }
+
+    /// Apply the cached and the fetched transactions to the context in their
+    /// order. The fetched transactions are cached as they arrive, while their
+    /// notes are decrypted concurrently, and are then scanned under a single
+    /// progress report. The decrypted notes are applied in the order of the
+    /// transactions, as they become available, so that the results do not
+    /// depend on the order in which the decryption completes.
+    async fn scan_fetched<IO: Io>(
+        &mut self,
+        logger: &impl ProgressLogger<IO>,
+        mut fetched: mpsc::UnboundedReceiver<IndexedNoteEntry>,
+        mut decrypted: DecryptedTxs,
+        native_token: &Address,
+    ) -> Result<(), Error> {
+        while let Some(entry) = fetched.next().await {
+            self.unscanned.extend([entry]);
+        }
         // persist the cache in case of interruptions.
         let _ = self.save().await;
 
+        let last_witnessed_tx = self.tx_note_map.keys().max().cloned();
         let txs = logger.scan(self.unscanned.clone());
         for (indexed_tx, (epoch, tx, stx)) in txs {
             if Some(indexed_tx) > last_witnessed_tx {
                 self.update_witness_map(indexed_tx, &stx)?;
             }
+            let mut tx_notes = decrypted.take(&indexed_tx).await?;
             let mut vk_heights = BTreeMap::new();
             std::mem::swap(&mut vk_heights, &mut self.vk_heights);
             for (vk, h) in vk_heights
                 .iter_mut()
                 .filter(|(_vk, h)| **h < Some(indexed_tx))
             {
-                self.scan_tx(
+                self.scan_decrypted_tx(
                     indexed_tx,
                     epoch,
                     &tx,
                     &stx,
                     vk,
+                    tx_notes.remove(vk).unwrap_or_default(),
                     native_token.clone(),
                 )?;
                 *h = Some(indexed_tx);
@@ -800,19 +1789,70 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
         last_indexed_tx: Option<BlockHeight>,
         last_query_height: Option<BlockHeight>,
     ) -> Result<IndexedNoteData, Error> {
+        let mut shielded_txs = BTreeMap::new();
+        Self::fetch_shielded_transfers_with(
+            client,
+            logger,
+            &self.unscanned.heights(),
+            last_indexed_tx,
+            last_query_height,
+            |(indexed_tx, tx)| {
+                shielded_txs.insert(indexed_tx, tx);
+            },
+        )
+        .await?;
+
+        Ok(shielded_txs)
+    }
+
+    /// Fetch the shielded part of the given accepted transaction from a node
+    pub async fn fetch_shielded_transfer<C: Client + Sync, IO: Io>(
+        &self,
+        client: &C,
+        logger: &impl ProgressLogger<IO>,
+        indexed_tx: IndexedTx,
+    ) -> Result<Option<Transaction>, Error> {
+        let mut shielded = None;
+        Self::fetch_shielded_transfers_with(
+            client,
+            logger,
+            &BTreeSet::new(),
+            Some(indexed_tx.height),
+            Some(indexed_tx.height),
+            |(fetched_tx, (_epoch, _changed_keys, stx))| {
+                if fetched_tx == indexed_tx {
+                    shielded = Some(stx);
+                }
+            },
+        )
+        .await?;
+
+        Ok(shielded)
+    }
+
+    /// Fetch the accepted shielded transactions from a node in chronological
+    /// order, skipping the blocks at the given cached heights, and hand each
+    /// of them to the given callback as soon as it is fetched.
+    async fn fetch_shielded_transfers_with<C: Client + Sync, IO: Io>(
+        client: &C,
+        logger: &impl ProgressLogger<IO>,
+        cached_heights: &BTreeSet<u64>,
+        last_indexed_tx: Option<BlockHeight>,
+        last_query_height: Option<BlockHeight>,
+        mut on_fetched: impl FnMut(IndexedNoteEntry) + MaybeSend,
+    ) -> Result<(), Error> {
         // Query for the last produced block height
         let last_block_height = query_block(client)
             .await?
             .map_or_else(BlockHeight::first, |block| block.height);
         let last_query_height = last_query_height.unwrap_or(last_block_height);
 
-        let mut shielded_txs = BTreeMap::new();
         // Fetch all the transactions we do not have yet
         let first_height_to_query =
             last_indexed_tx.map_or_else(|| 1, |last| last.0);
         let heights = logger.fetch(first_height_to_query..=last_query_height.0);
         for height in heights {
-            if self.unscanned.contains_height(height) {
+            if cached_heights.contains(&height) {
                 continue;
             }
             // Get the valid masp transactions at the specified height
@@ -860,17 +1900,18 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
                 .await?;
 
                 // Collect the current transaction
-                shielded_txs.insert(
-                    IndexedTx {
-                        height: height.into(),
-                        index: idx,
-                    },
+                let indexed_tx = IndexedTx {
+                    height: height.into(),
+                    index: idx,
+                };
+                on_fetched((
+                    indexed_tx,
                     (epoch, changed_keys, masp_transaction),
-                );
+                ));
             }
         }
 
-        Ok(shielded_txs)
+        Ok(())
     }
 
     /// Extract the relevant shield portions of a [`Tx`], if any.
@@ -1033,6 +2074,37 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
         shielded: &Transaction,
         vk: &ViewingKey,
         native_token: Address,
+    ) -> Result<(), Error> {
+        // Only try to decrypt the notes if we are syncing (i.e. in a
+        // confirmed status)
+        let decrypted = match self.sync_status {
+            ContextSyncStatus::Confirmed => trial_decrypt(shielded, vk),
+            ContextSyncStatus::Speculative => DecryptedNotes::new(),
+        };
+        self.scan_decrypted_tx(
+            indexed_tx,
+            epoch,
+            tx_changed_keys,
+            shielded,
+            vk,
+            decrypted,
+            native_token,
+        )
+    }
+
+    /// Applies the given transaction to the supplied context like
+    /// [`Self::scan_tx`], given the notes of the transaction that were
+    /// already decrypted with the viewing key.
+    #[allow(clippy::too_many_arguments)]
+    fn scan_decrypted_tx(
+        &mut self,
+        indexed_tx: IndexedTx,
+        epoch: Epoch,
+        tx_changed_keys: &BTreeSet<namada_core::storage::Key>,
+        shielded: &Transaction,
+        vk: &ViewingKey,
+        mut decrypted: DecryptedNotes,
+        native_token: Address,
     ) -> Result<(), Error> {
         // For tracking the account changes caused by this Transaction
         let mut transaction_delta = TransactionDelta::new();
@@ -1040,19 +2112,15 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
             let mut note_pos = self.tx_note_map[&indexed_tx];
             // Listen for notes sent to our viewing keys, only if we are syncing
             // (i.e. in a confirmed status)
-            for so in shielded
+            for (output_idx, _so) in shielded
                 .sapling_bundle()
                 .map_or(&vec![], |x| &x.shielded_outputs)
+                .iter()
+                .enumerate()
             {
-                // Let's try to see if this viewing key can decrypt latest
-                // note
+                // Let's see if this viewing key could decrypt latest note
                 let notes = self.pos_map.entry(*vk).or_default();
-                let decres = try_sapling_note_decryption::<_, OutputDescription<<<Authorized as Authorization>::SaplingAuth as masp_primitives::transaction::components::sapling::Authorization>::Proof>>(
-                &NETWORK,
-                1.into(),
-                &PreparedIncomingViewingKey::new(&vk.ivk()),
-                so,
-            );
+                let decres = decrypted.remove(&output_idx);
                 // So this current viewing key does decrypt this current note...
                 if let Some((note, pa, memo)) = decres {
                     // Add this note to list of notes decrypted by this viewing
@@ -1936,7 +3004,37 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
                     .expect("unable to construct decoded amount");
             }
         }
-        res
+        res
+    }
+
+    /// Make the shielded components paying the fee of a wrapper transaction
+    /// from the given spending key. The fee leaves the pool through
+    /// transparent outputs tied to the fee payer of the wrapper, which binds
+    /// the transaction to it, and the protocol credits it to the block
+    /// proposer.
+    pub async fn gen_shielded_fee_payment(
+        context: &impl Namada,
+        source: &TransferSource,
+        fee_payer: &Address,
+        token: &Address,
+        amount: token::DenominatedAmount,
+        update_ctx: bool,
+    ) -> Result<Option<ShieldedTransfer>, TransferErr> {
+        // The spends of the fee payment cannot wait for separate signatures
+        if source.proof_generation_key().is_some() {
+            return Err(TransferErr::General(Error::Other(
+                "fee unshielding requires a spending key".to_string(),
+            )));
+        }
+        Self::gen_shielded_transfer(
+            context,
+            source,
+            &TransferTarget::Address(fee_payer.clone()),
+            token,
+            amount,
+            update_ctx,
+        )
+        .await
     }
 
     /// Make shielded components to embed within a Transfer object. If no
@@ -1961,14 +3059,28 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
         use rand_core::SeedableRng;
 
         let spending_key = source.spending_key();
+        let proof_generation_key = source.proof_generation_key();
         let payment_address = target.payment_address();
         // No shielded components are needed when neither source nor
         // destination are shielded
-        if spending_key.is_none() && payment_address.is_none() {
+        if spending_key.is_none()
+            && proof_generation_key.is_none()
+            && payment_address.is_none()
+        {
             return Ok(None);
         }
-        // We want to fund our transaction solely from supplied spending key
-        let spending_key = spending_key.map(|x| x.into());
+        // We want to fund our transaction solely from supplied spending key,
+        // or from a proxy of it when only its proof generation key is supplied
+        let spending_key = spending_key
+            .map(|x| {
+                let sk = ExtendedSpendingKey::from(x);
+                (sk, ExtendedFullViewingKey::from(&sk))
+            })
+            .or_else(|| {
+                proof_generation_key.map(|x| {
+                    (proxy_spending_key(&x, &mut OsRng), x.viewing_key().into())
+                })
+            });
         {
             // Load the current shielded context given the spending key we
             // possess
@@ -2073,14 +3185,14 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
         };
 
         // If there are shielded inputs
-        if let Some(sk) = spending_key {
+        if let Some((sk, efvk)) = spending_key {
             // Locate unspent notes that can help us meet the transaction amount
             let (_, unspent_notes, used_convs) = context
                 .shielded_mut()
                 .await
                 .collect_unspent_notes(
                     context,
-                    &to_viewing_key(&sk).vk,
+                    &efvk.fvk.vk,
                     I128Sum::from_sum(masp_amount),
                     epoch,
                 )
@@ -2171,7 +3283,7 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
         let mut rem_amount = amount.amount().raw_amount().0;
         // If we are sending to a shielded address, we may need the outgoing
         // viewing key in the following computations.
-        let ovk_opt = spending_key.map(|x| x.expsk.ovk);
+        let ovk_opt = spending_key.map(|(_, efvk)| efvk.fvk.ovk);
 
         // Now handle the outputs of this transaction
         // Loop through the value balance components and see which
@@ -2241,7 +3353,7 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
         }
 
         // Now add outputs representing the change from this payment
-        if let Some(sk) = spending_key {
+        if let Some((_, efvk)) = spending_key {
             // Represents the amount of inputs we are short by
             let mut additional = I128Sum::zero();
             for (asset_type, amt) in builder
@@ -2259,8 +3371,8 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
                         // Send the change in this asset type back to the sender
                         builder
                             .add_sapling_output(
-                                Some(sk.expsk.ovk),
-                                sk.default_address().1,
+                                Some(efvk.fvk.ovk),
+                                efvk.default_address().1,
                                 *asset_type,
                                 *amt as u64,
                                 memo.clone(),
@@ -2291,14 +3403,36 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
             }
         }
 
-        let builder_clone = builder.clone().map_builder(WalletMap);
         // Build and return the constructed transaction
         #[cfg(not(feature = "testing"))]
         let prover = context.shielded().await.utils.local_tx_prover();
         #[cfg(feature = "testing")]
         let prover = testing::MockTxProver(std::sync::Mutex::new(OsRng));
-        let (masp_tx, metadata) =
-            builder.build(&prover, &FeeRule::non_standard(U64Sum::zero()))?;
+        let fee_rule = FeeRule::non_standard(U64Sum::zero());
+        let (builder_clone, masp_tx, metadata, spend_randomizers) =
+            match (proof_generation_key, spending_key) {
+                // The spends of a proxy spending key are proven with the proof
+                // generation key and are to be signed later on
+                (Some(key), Some((proxy, efvk))) => {
+                    let builder_clone =
+                        builder.clone().map_builder(ProxyWalletMap(efvk));
+                    let prover = DelegatedTxProver::new(&prover, &proxy, &key);
+                    let (masp_tx, metadata) =
+                        builder.build(&prover, &fee_rule)?;
+                    (
+                        builder_clone,
+                        masp_tx,
+                        metadata,
+                        prover.into_randomizers(),
+                    )
+                }
+                _ => {
+                    let builder_clone = builder.clone().map_builder(WalletMap);
+                    let (masp_tx, metadata) =
+                        builder.build(&prover, &fee_rule)?;
+                    (builder_clone, masp_tx, metadata, vec![])
+                }
+            };
 
         if update_ctx {
             // Cache the generated transfer
@@ -2315,6 +3449,7 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
             masp_tx,
             metadata,
             epoch,
+            spend_randomizers,
         }))
     }
 
@@ -2404,8 +3539,17 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
             .values()
             .map(|fvk| ExtendedFullViewingKey::from(*fvk).fvk.vk)
             .collect();
-        self.fetch(client, &DefaultLogger::new(io), None, None, 1, &[], &fvks)
-            .await?;
+        self.fetch(
+            client,
+            &DefaultLogger::new(io),
+            None,
+            None,
+            1,
+            &[],
+            &fvks,
+            &BTreeMap::new(),
+        )
+        .await?;
         // Save the update state so that future fetches can be short-circuited
         let _ = self.save().await;
         // Required for filtering out rejected transactions from Tendermint
@@ -2505,6 +3649,307 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
         Ok(transfers)
     }
 
+    /// Get the history of the changes in the shielded balance of the given
+    /// viewing key, optionally restricted to the given token. The context is
+    /// expected to be synced already. Changes in asset types that cannot be
+    /// decoded are left out.
+    pub async fn query_shielded_history<C: Client + Sync, IO: Io>(
+        &mut self,
+        client: &C,
+        io: &IO,
+        vk: &ViewingKey,
+        query_token: Option<&Address>,
+    ) -> Result<Vec<ShieldedHistoryEntry>, Error> {
+        let deltas: Vec<_> = self
+            .delta_map
+            .iter()
+            .filter_map(|(indexed_tx, (epoch, tfer_delta, tx_delta))| {
+                let counterparty =
+                    tfer_delta.keys().find(|account| **account != MASP);
+                tx_delta.get(vk).map(|amt| {
+                    (*indexed_tx, *epoch, counterparty.cloned(), amt.clone())
+                })
+            })
+            .collect();
+        let mut history = vec![];
+        // The last queried block, to look up transaction times and hashes
+        let mut block: Option<(
+            BlockHeight,
+            Option<DateTimeUtc>,
+            Vec<Vec<u8>>,
+        )> = None;
+        for (indexed_tx, epoch, counterparty, amt) in deltas {
+            let (decoded, _undecoded) =
+                self.decode_combine_sum(client, amt).await;
+            let mut changes = BTreeMap::<Address, token::Change>::new();
+            for ((_epoch, token), change) in decoded.components() {
+                if query_token.map_or(true, |query| query == token) {
+                    *changes.entry(token.clone()).or_default() += *change;
+                }
+            }
+            changes.retain(|_, change| !change.is_zero());
+            if changes.is_empty() {
+                continue;
+            }
+
+            if block
+                .as_ref()
+                .map_or(true, |(height, _, _)| *height != indexed_tx.height)
+            {
+                let response =
+                    client.block(indexed_tx.height.0 as u32).await.map_err(
+                        |e| Error::from(QueryError::General(e.to_string())),
+                    )?;
+                let time =
+                    DateTimeUtc::try_from(response.block.header.time).ok();
+                block = Some((indexed_tx.height, time, response.block.data));
+            }
+            let (_, time, txs) =
+                block.as_ref().expect("block was just queried");
+            let tx_hash = txs
+                .get(indexed_tx.index.0 as usize)
+                .and_then(|tx| Tx::try_from(tx.as_ref()).ok())
+                .map(|tx| tx.header_hash().to_string());
+            let memo = self.received_memo(&indexed_tx, vk);
+
+            for (token, change) in changes {
+                let direction = if change.is_negative() {
+                    TransferDirection::Outgoing
+                } else {
+                    TransferDirection::Incoming
+                };
+                let amount = rpc::denominate_amount(
+                    client,
+                    io,
+                    &token,
+                    token::Amount::from_change(change),
+                )
+                .await;
+                history.push(ShieldedHistoryEntry {
+                    time: *time,
+                    height: indexed_tx.height,
+                    index: indexed_tx.index,
+                    tx_hash: tx_hash.clone(),
+                    epoch,
+                    token,
+                    amount,
+                    direction,
+                    memo: memo.clone(),
+                    counterparty: counterparty.clone(),
+                });
+            }
+        }
+        Ok(history)
+    }
+
+    /// Get the positions of the notes of the given transaction that were
+    /// received by the given viewing key, in increasing order
+    fn received_notes(
+        &self,
+        indexed_tx: &IndexedTx,
+        vk: &ViewingKey,
+    ) -> Vec<usize> {
+        let Some(first_note) = self.tx_note_map.get(indexed_tx) else {
+            return vec![];
+        };
+        let next_tx_note = self
+            .tx_note_map
+            .range((
+                std::ops::Bound::Excluded(indexed_tx),
+                std::ops::Bound::Unbounded,
+            ))
+            .next()
+            .map(|(_, note_pos)| *note_pos);
+        let mut notes: Vec<_> = self
+            .pos_map
+            .get(vk)
+            .into_iter()
+            .flatten()
+            .copied()
+            .filter(|note_pos| {
+                note_pos >= first_note
+                    && next_tx_note.map_or(true, |next| *note_pos < next)
+            })
+            .collect();
+        notes.sort_unstable();
+        notes
+    }
+
+    /// Get the first non-empty text memo of the notes of the given
+    /// transaction that were received by the given viewing key
+    fn received_memo(
+        &self,
+        indexed_tx: &IndexedTx,
+        vk: &ViewingKey,
+    ) -> Option<String> {
+        self.received_notes(indexed_tx, vk)
+            .into_iter()
+            .filter_map(|note_pos| self.memo_map.get(&note_pos))
+            .find_map(|memo| match Memo::try_from(memo.clone()) {
+                Ok(Memo::Text(text)) if !text.is_empty() => {
+                    Some(String::from(&*text))
+                }
+                _ => None,
+            })
+    }
+
+    /// Disclose the notes of the given transaction that were received or sent
+    /// by the given viewing key. The received notes are found in the context,
+    /// while the sent notes are recovered with the outgoing viewing key from
+    /// the outputs of the given shielded part of the transaction. The
+    /// disclosures are anchored at the current root of the note commitment
+    /// tree, so the context should be synced to the end of a block for them
+    /// to be verifiable.
+    pub fn disclose_notes(
+        &self,
+        fvk: &FullViewingKey,
+        indexed_tx: &IndexedTx,
+        shielded: &Transaction,
+    ) -> Result<Vec<NoteDisclosure>, Error> {
+        let Some(first_note) = self.tx_note_map.get(indexed_tx) else {
+            return Ok(vec![]);
+        };
+        let received = self.received_notes(indexed_tx, &fvk.vk);
+        let mut disclosures = received
+            .iter()
+            .map(|note_pos| {
+                let note = *self.note_map.get(note_pos).ok_or_else(|| {
+                    Error::Other(format!("Unable to get note {note_pos}"))
+                })?;
+                let payment_address = self
+                    .div_map
+                    .get(note_pos)
+                    .and_then(|div| fvk.vk.to_payment_address(*div))
+                    .ok_or_else(|| {
+                        Error::Other(format!(
+                            "Unable to get the payment address of note \
+                             {note_pos}"
+                        ))
+                    })?;
+                Ok(NoteDisclosure {
+                    direction: TransferDirection::Incoming,
+                    payment_address,
+                    note,
+                    merkle_path: self.note_merkle_path(*note_pos)?,
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        for (output_idx, so) in shielded
+            .sapling_bundle()
+            .map_or(&vec![], |x| &x.shielded_outputs)
+            .iter()
+            .enumerate()
+        {
+            let note_pos = first_note + output_idx;
+            // The notes sent back to the viewing key, like change, are
+            // already disclosed as received
+            if received.contains(&note_pos) {
+                continue;
+            }
+            let Some((note, payment_address, _memo)) =
+                try_sapling_output_recovery(&NETWORK, 1.into(), &fvk.ovk, so)
+            else {
+                continue;
+            };
+            disclosures.push(NoteDisclosure {
+                direction: TransferDirection::Outgoing,
+                payment_address,
+                note,
+                merkle_path: self.note_merkle_path(note_pos)?,
+            });
+        }
+        Ok(disclosures)
+    }
+
+    /// Get the path from the commitment of the note at the given position to
+    /// the current root of the note commitment tree
+    fn note_merkle_path(
+        &self,
+        note_pos: usize,
+    ) -> Result<MerklePath<Node>, Error> {
+        self.witness_map
+            .get(&note_pos)
+            .and_then(IncrementalWitness::path)
+            .ok_or_else(|| {
+                Error::Other(format!(
+                    "Unable to get the merkle path of note {note_pos}"
+                ))
+            })
+    }
+
+    /// Project the MASP rewards of the tokens held by the given viewing key.
+    /// The balances are computed both as held in the notes and as exchanged
+    /// with all the allowed conversions, the difference being the rewards
+    /// that can already be claimed. The context is expected to be synced
+    /// already.
+    pub async fn project_shielded_rewards<C: Client + Sync, IO: Io>(
+        &mut self,
+        client: &C,
+        io: &IO,
+        vk: &ViewingKey,
+    ) -> Result<ShieldedRewardsProjection, Error> {
+        let missing_key = || {
+            Error::Other(
+                "The viewing key is not in the shielded context".to_string(),
+            )
+        };
+        let epoch = rpc::query_epoch(client).await?;
+        let native_token = query_native_token(client).await?;
+        let balance = self
+            .compute_shielded_balance(vk)
+            .await?
+            .ok_or_else(missing_key)?;
+        let exchanged = self
+            .compute_exchanged_balance(client, io, vk, epoch)
+            .await?
+            .ok_or_else(missing_key)?;
+        let balances = self.decode_balances(client, balance, epoch).await;
+        let exchanged_balances =
+            self.decode_balances(client, exchanged, epoch).await;
+
+        let mut rewards = BTreeMap::new();
+        for (token, amount) in &exchanged_balances {
+            if let Some((next_epoch, history)) =
+                rpc::query_masp_rewards(client, token).await?
+            {
+                rewards.insert(
+                    token.clone(),
+                    TokenRewardsProjection {
+                        next_epoch,
+                        projected_reward: next_epoch.reward(*amount),
+                        history,
+                    },
+                );
+            }
+        }
+        Ok(ShieldedRewardsProjection {
+            epoch,
+            native_token,
+            balances,
+            exchanged_balances,
+            rewards,
+        })
+    }
+
+    /// Decode the given amount into the positive balances of each token,
+    /// leaving out the asset types that cannot be decoded
+    async fn decode_balances<C: Client + Sync>(
+        &mut self,
+        client: &C,
+        amt: I128Sum,
+        epoch: Epoch,
+    ) -> BTreeMap<Address, token::Amount> {
+        let (decoded, _undecoded) =
+            self.decode_combine_sum_to_epoch(client, amt, epoch).await;
+        decoded
+            .components()
+            .filter(|(_, change)| !change.is_negative() && !change.is_zero())
+            .map(|(token, change)| {
+                (token.clone(), token::Amount::from_change(*change))
+            })
+            .collect()
+    }
+
     /// Get the asset type with the given epoch, token, and denomination. If it
     /// does not exist in the protocol, then remove the timestamp. Make sure to
     /// store the derived AssetType so that future decoding is possible.
@@ -2776,6 +4221,203 @@ mod tests {
         );
     }
 
+    /// Check that the fields of a CSV history export are escaped
+    #[test]
+    fn test_shielded_history_csv() {
+        use namada_core::address::MASP;
+        use namada_core::storage::{BlockHeight, Epoch, TxIndex};
+        use namada_core::token::{Amount, DenominatedAmount};
+
+        use super::{
+            export_shielded_history, ShieldedHistoryEntry,
+            ShieldedHistoryFormat, TransferDirection,
+        };
+
+        let entry = ShieldedHistoryEntry {
+            time: None,
+            height: BlockHeight(10),
+            index: TxIndex(2),
+            tx_hash: None,
+            epoch: Epoch(1),
+            token: MASP,
+            amount: DenominatedAmount::native(Amount::from(1_500_000)),
+            direction: TransferDirection::Incoming,
+            memo: Some("rent, \"march\"".to_string()),
+            counterparty: None,
+        };
+        let csv = export_shielded_history(
+            &[entry.clone()],
+            ShieldedHistoryFormat::Csv,
+        );
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some(ShieldedHistoryEntry::CSV_HEADER));
+        assert_eq!(
+            lines.next().unwrap(),
+            format!(
+                ",10,2,,1,{},1.500000,incoming,\"rent, \"\"march\"\"\",",
+                MASP
+            )
+        );
+        assert_eq!(lines.next(), None);
+
+        let json =
+            export_shielded_history(&[entry], ShieldedHistoryFormat::Json);
+        let parsed: Vec<ShieldedHistoryEntry> =
+            serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed[0].memo.as_deref(), Some("rent, \"march\""));
+        assert_eq!(parsed[0].direction, TransferDirection::Incoming);
+    }
+
+    /// Check that a note disclosure is anchored at the root of the tree that
+    /// includes the note, and only for the address that received it
+    #[test]
+    fn test_note_disclosure_anchor() {
+        use masp_primitives::asset_type::AssetType;
+        use masp_primitives::merkle_tree::{
+            CommitmentTree, IncrementalWitness,
+        };
+        use masp_primitives::sapling::{Node, Rseed};
+        use masp_primitives::zip32::ExtendedSpendingKey;
+
+        use super::NoteDisclosure;
+
+        let (_, payment_address) =
+            ExtendedSpendingKey::master(&[1; 32]).default_address();
+        let (_, other_address) =
+            ExtendedSpendingKey::master(&[2; 32]).default_address();
+        let asset_type = AssetType::new(b"test").unwrap();
+        let note = payment_address
+            .create_note(asset_type, 100, Rseed::AfterZip212([3; 32]))
+            .unwrap();
+        let other_note = other_address
+            .create_note(asset_type, 200, Rseed::AfterZip212([4; 32]))
+            .unwrap();
+
+        let mut tree = CommitmentTree::<Node>::empty();
+        tree.append(Node::from_scalar(note.cmu())).unwrap();
+        let mut witness = IncrementalWitness::from_tree(&tree);
+        let other_node = Node::from_scalar(other_note.cmu());
+        tree.append(other_node).unwrap();
+        witness.append(other_node).unwrap();
+
+        let mut disclosure = NoteDisclosure {
+            direction: super::TransferDirection::Incoming,
+            payment_address,
+            note,
+            merkle_path: witness.path().unwrap(),
+        };
+        assert_eq!(disclosure.anchor().unwrap(), tree.root());
+
+        disclosure.payment_address = other_address;
+        assert!(disclosure.anchor().is_err());
+    }
+
+    /// Check that the spends of a transaction built with a proof generation
+    /// key only get authorized by the signatures of its spending key
+    #[test]
+    fn test_delegated_spend_authorization() {
+        use std::sync::Mutex;
+
+        use masp_primitives::asset_type::AssetType;
+        use masp_primitives::consensus::TestNetwork;
+        use masp_primitives::merkle_tree::{
+            CommitmentTree, IncrementalWitness,
+        };
+        use masp_primitives::sapling::{Node, Rseed};
+        use masp_primitives::transaction::builder::Builder;
+        use masp_primitives::transaction::components::U64Sum;
+        use masp_primitives::transaction::fees::fixed::FeeRule;
+        use masp_primitives::transaction::TransparentAddress;
+        use masp_primitives::zip32::ExtendedSpendingKey;
+        use rand_core::OsRng;
+
+        use super::testing::MockTxProver;
+        use super::{
+            authorize_shielded_spends, proxy_spending_key,
+            sign_shielded_spends, DelegatedTxProver,
+            ExtendedProofGenerationKey, NETWORK,
+        };
+
+        let spending_key = ExtendedSpendingKey::master(&[1; 32]);
+        let (_, payment_address) = spending_key.default_address();
+        let asset_type = AssetType::new(b"test").unwrap();
+        let note = payment_address
+            .create_note(asset_type, 100, Rseed::AfterZip212([3; 32]))
+            .unwrap();
+        let mut tree = CommitmentTree::<Node>::empty();
+        tree.append(Node::from_scalar(note.cmu())).unwrap();
+        let witness = IncrementalWitness::from_tree(&tree);
+
+        // Build the transaction without the spend authorizing key
+        let key = ExtendedProofGenerationKey::from(
+            namada_core::masp::ExtendedSpendingKey::from(spending_key),
+        );
+        let proxy = proxy_spending_key(&key, &mut OsRng);
+        let mut builder = Builder::<TestNetwork, _>::new_with_rng(
+            NETWORK,
+            1000.into(),
+            OsRng,
+        );
+        builder
+            .add_sapling_spend(
+                proxy,
+                *payment_address.diversifier(),
+                note,
+                witness.path().unwrap(),
+            )
+            .unwrap();
+        builder
+            .add_transparent_output(
+                &TransparentAddress([0; 20]),
+                asset_type,
+                100,
+            )
+            .unwrap();
+        let mock_prover = MockTxProver(Mutex::new(OsRng));
+        let prover = DelegatedTxProver::new(&mock_prover, &proxy, &key);
+        let (masp_tx, _) = builder
+            .build(&prover, &FeeRule::non_standard(U64Sum::zero()))
+            .unwrap();
+        let randomizers = prover.into_randomizers();
+        assert_eq!(randomizers.len(), 1);
+
+        // The signature made by the builder with the proxy is not valid
+        let mut placeholder = vec![];
+        masp_tx.sapling_bundle().unwrap().shielded_spends[0]
+            .spend_auth_sig
+            .write(&mut placeholder)
+            .unwrap();
+        let placeholder = super::SpendAuthorization {
+            rk: randomizers[0].rk,
+            signature: placeholder,
+        };
+        assert!(authorize_shielded_spends(&masp_tx, &[placeholder]).is_err());
+
+        // Another spending key cannot sign the spend
+        let other_key = ExtendedSpendingKey::master(&[2; 32]);
+        let authorizations = sign_shielded_spends(
+            &masp_tx,
+            &randomizers,
+            &other_key,
+            &mut OsRng,
+        )
+        .unwrap();
+        assert!(authorizations.is_empty());
+
+        let authorizations = sign_shielded_spends(
+            &masp_tx,
+            &randomizers,
+            &spending_key,
+            &mut OsRng,
+        )
+        .unwrap();
+        assert_eq!(authorizations.len(), 1);
+        let authorized =
+            authorize_shielded_spends(&masp_tx, &authorizations).unwrap();
+        // The authorizations do not change the transaction ID
+        assert_eq!(authorized.txid(), masp_tx.txid());
+    }
+
     /// a more involved test, using dummy parameters with the right
     /// size but the wrong hash.
     #[test]
@@ -3663,6 +5305,7 @@ pub mod testing {
                 metadata,
                 masp_tx,
                 epoch,
+                spend_randomizers: vec![],
             }, asset_types)
         }
     }
@@ -3689,6 +5332,7 @@ pub mod testing {
                 metadata,
                 masp_tx,
                 epoch,
+                spend_randomizers: vec![],
             }, asset_types)
         }
     }
@@ -3715,6 +5359,7 @@ pub mod testing {
                 metadata,
                 masp_tx,
                 epoch,
+                spend_randomizers: vec![],
             }, asset_types)
         }
     }
@@ -3724,18 +5369,37 @@ pub mod testing {
 /// Implementation of MASP functionality depending on a standard filesystem
 pub mod fs {
     use std::fs::{File, OpenOptions};
-    use std::io::{Read, Write};
+    use std::io::{ErrorKind, Read, Write};
+    use std::path::Path;
+
+    use fd_lock::RwLock;
 
     use super::*;
 
-    /// Shielded context file name
+    /// Legacy shielded context file name
     const FILE_NAME: &str = "shielded.dat";
-    const TMP_FILE_NAME: &str = "shielded.tmp";
+    /// Legacy speculative shielded context file name
     const SPECULATIVE_FILE_NAME: &str = "speculative_shielded.dat";
-    const SPECULATIVE_TMP_FILE_NAME: &str = "speculative_shielded.tmp";
+    /// Shielded context store directory name
+    const STORE_DIR_NAME: &str = "shielded";
+    /// Speculative shielded context store directory name
+    const SPECULATIVE_STORE_DIR_NAME: &str = "speculative_shielded";
+    /// Name of the file listing the segment files of a store
+    const MANIFEST_FILE_NAME: &str = "MANIFEST";
+    /// Name of the file locked while a store is read or written
+    const LOCK_FILE_NAME: &str = "shielded.lock";
 
     #[derive(Debug, BorshSerialize, BorshDeserialize, Clone)]
-    /// An implementation of ShieldedUtils for standard filesystems
+    /// An implementation of ShieldedUtils for standard filesystems.
+    ///
+    /// The context is persisted in a store directory holding one file per
+    /// segment of the context (see [`CONTEXT_SEGMENTS`]), named after the
+    /// hash of its content, and a manifest listing the current segment
+    /// files. Saving only writes the segments that changed and then
+    /// atomically replaces the manifest, so that a crash never leaves a
+    /// partially written context behind. Concurrent clients are serialized
+    /// with a lock file. Contexts saved in the legacy single file format are
+    /// migrated to a store on load.
     pub struct FsShieldedUtils {
         #[borsh(skip)]
         context_dir: PathBuf,
@@ -3764,15 +5428,17 @@ pub mod fs {
             }
             // Finally initialize a shielded context with the supplied directory
 
-            let sync_status =
-                if std::fs::read(context_dir.join(SPECULATIVE_FILE_NAME))
-                    .is_ok()
-                {
-                    // Load speculative state
-                    ContextSyncStatus::Speculative
-                } else {
-                    ContextSyncStatus::Confirmed
-                };
+            let sync_status = if context_dir
+                .join(SPECULATIVE_STORE_DIR_NAME)
+                .join(MANIFEST_FILE_NAME)
+                .exists()
+                || context_dir.join(SPECULATIVE_FILE_NAME).exists()
+            {
+                // Load speculative state
+                ContextSyncStatus::Speculative
+            } else {
+                ContextSyncStatus::Confirmed
+            };
 
             let utils = Self { context_dir };
             ShieldedContext {
@@ -3781,6 +5447,34 @@ pub mod fs {
                 ..Default::default()
             }
         }
+
+        /// Open the file locked while a store of this context directory is
+        /// read or written
+        fn store_lock(&self) -> std::io::Result<RwLock<File>> {
+            let file = OpenOptions::new()
+                .create(true)
+                .write(true)
+                .open(self.context_dir.join(LOCK_FILE_NAME))?;
+            Ok(RwLock::new(file))
+        }
+
+        /// Get the store directory and the legacy file of the context with
+        /// the given sync status
+        fn store_paths(
+            &self,
+            status: &ContextSyncStatus,
+        ) -> (PathBuf, PathBuf) {
+            match status {
+                ContextSyncStatus::Confirmed => (
+                    self.context_dir.join(STORE_DIR_NAME),
+                    self.context_dir.join(FILE_NAME),
+                ),
+                ContextSyncStatus::Speculative => (
+                    self.context_dir.join(SPECULATIVE_STORE_DIR_NAME),
+                    self.context_dir.join(SPECULATIVE_FILE_NAME),
+                ),
+            }
+        }
     }
 
     impl Default for FsShieldedUtils {
@@ -3791,6 +5485,85 @@ pub mod fs {
         }
     }
 
+    /// Write the given bytes to a file through a temporary file, so that the
+    /// file is either left untouched or completely written
+    fn write_atomically(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+        let mut tmp_path = path.as_os_str().to_owned();
+        tmp_path.push(".tmp");
+        {
+            let mut tmp_file = File::create(&tmp_path)?;
+            tmp_file.write_all(bytes)?;
+            tmp_file.sync_all()?;
+        }
+        std::fs::rename(tmp_path, path)?;
+        // Make the rename itself durable
+        #[cfg(unix)]
+        if let Some(dir) = path.parent() {
+            File::open(dir)?.sync_all()?;
+        }
+        Ok(())
+    }
+
+    /// Read the segments of the context persisted in the given store
+    fn read_store(
+        store_dir: &Path,
+    ) -> std::io::Result<BTreeMap<String, Vec<u8>>> {
+        let manifest: BTreeMap<String, String> =
+            BorshDeserialize::try_from_slice(&std::fs::read(
+                store_dir.join(MANIFEST_FILE_NAME),
+            )?)?;
+        manifest
+            .into_iter()
+            .map(|(segment, file_name)| {
+                let mut bytes = Vec::new();
+                File::open(store_dir.join(file_name))?
+                    .read_to_end(&mut bytes)?;
+                Ok((segment, bytes))
+            })
+            .collect()
+    }
+
+    /// Persist the given segments of a context in the given store, only
+    /// writing the segments that are not already present
+    fn write_store(
+        store_dir: &Path,
+        segments: BTreeMap<String, Vec<u8>>,
+    ) -> std::io::Result<()> {
+        std::fs::create_dir_all(store_dir)?;
+        let mut manifest = BTreeMap::new();
+        for (segment, bytes) in segments {
+            let hash = sha2::Sha256::digest(&bytes);
+            let file_name = format!(
+                "{}-{}",
+                segment,
+                data_encoding::HEXLOWER.encode(&hash)
+            );
+            let path = store_dir.join(&file_name);
+            if !path.exists() {
+                write_atomically(&path, &bytes)?;
+            }
+            manifest.insert(segment, file_name);
+        }
+        // Switch to the new segments at once
+        write_atomically(
+            &store_dir.join(MANIFEST_FILE_NAME),
+            &manifest.serialize_to_vec(),
+        )?;
+        // Clean up the segments that are not in use anymore
+        for entry in std::fs::read_dir(store_dir)? {
+            let entry = entry?;
+            let file_name = entry.file_name();
+            let in_use = file_name == MANIFEST_FILE_NAME
+                || manifest
+                    .values()
+                    .any(|segment_file| file_name == segment_file.as_str());
+            if !in_use {
+                let _ = std::fs::remove_file(entry.path());
+            }
+        }
+        Ok(())
+    }
+
     #[cfg_attr(feature = "async-send", async_trait::async_trait)]
     #[cfg_attr(not(feature = "async-send"), async_trait::async_trait(?Send))]
     impl ShieldedUtils for FsShieldedUtils {
@@ -3814,70 +5587,385 @@ pub mod fs {
             ctx: &mut ShieldedContext<U>,
             force_confirmed: bool,
         ) -> std::io::Result<()> {
-            // Try to load shielded context from file
-            let file_name = if force_confirmed {
-                FILE_NAME
+            // Try to load shielded context from its store
+            let status = if force_confirmed {
+                &ContextSyncStatus::Confirmed
             } else {
-                match ctx.sync_status {
-                    ContextSyncStatus::Confirmed => FILE_NAME,
-                    ContextSyncStatus::Speculative => SPECULATIVE_FILE_NAME,
+                &ctx.sync_status
+            };
+            let (store_dir, legacy_file) = self.store_paths(status);
+            let mut lock = self.store_lock()?;
+            let segments = {
+                let _guard = lock.read()?;
+                read_store(&store_dir)
+            };
+            let segments = match segments {
+                Err(err) if err.kind() == ErrorKind::NotFound => {
+                    // Migrate a context saved in the legacy format, unless
+                    // another client did it in the meantime
+                    let _guard = lock.write()?;
+                    match read_store(&store_dir) {
+                        Err(err) if err.kind() == ErrorKind::NotFound => {
+                            let bytes = std::fs::read(&legacy_file)?;
+                            let legacy = ShieldedContext::<U>::deserialize(
+                                &mut &bytes[..],
+                            )?;
+                            let segments = legacy.to_segments()?;
+                            write_store(&store_dir, segments)?;
+                            std::fs::remove_file(&legacy_file)?;
+                            read_store(&store_dir)?
+                        }
+                        segments => segments?,
+                    }
                 }
+                segments => segments?,
             };
-            let mut ctx_file = File::open(self.context_dir.join(file_name))?;
-            let mut bytes = Vec::new();
-            ctx_file.read_to_end(&mut bytes)?;
             // Fill the supplied context with the deserialized object
-            *ctx = ShieldedContext {
-                utils: ctx.utils.clone(),
-                ..ShieldedContext::<U>::deserialize(&mut &bytes[..])?
-            };
+            *ctx =
+                ShieldedContext::from_segments(ctx.utils.clone(), &segments)?;
             Ok(())
         }
 
         /// Save this confirmed shielded context into its associated context
-        /// directory. At the same time, delete the speculative file if present
+        /// directory. At the same time, delete the speculative store if
+        /// present
         async fn save<U: ShieldedUtils + MaybeSync>(
             &self,
             ctx: &ShieldedContext<U>,
         ) -> std::io::Result<()> {
-            // TODO: use mktemp crate?
-            let (tmp_file_name, file_name) = match ctx.sync_status {
-                ContextSyncStatus::Confirmed => (TMP_FILE_NAME, FILE_NAME),
-                ContextSyncStatus::Speculative => {
-                    (SPECULATIVE_TMP_FILE_NAME, SPECULATIVE_FILE_NAME)
-                }
+            let (store_dir, legacy_file) = self.store_paths(&ctx.sync_status);
+            let segments = ctx.to_segments()?;
+            let mut lock = self.store_lock()?;
+            let _guard = lock.write()?;
+            write_store(&store_dir, segments)?;
+            // The legacy file is superseded by the store
+            let _ = std::fs::remove_file(legacy_file);
+
+            // Remove the speculative store if present since it's state is
+            // overruled by the confirmed one we just saved
+            if let ContextSyncStatus::Confirmed = ctx.sync_status {
+                let (speculative_dir, speculative_file) =
+                    self.store_paths(&ContextSyncStatus::Speculative);
+                let _ = std::fs::remove_dir_all(speculative_dir);
+                let _ = std::fs::remove_file(speculative_file);
+            }
+
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        /// Test that a context saved in the legacy format is migrated to a
+        /// store and that unchanged segments are not rewritten on save
+        #[tokio::test]
+        async fn test_shielded_store_migration() {
+            let context_dir = tempfile::tempdir().unwrap().into_path();
+            let utils = FsShieldedUtils {
+                context_dir: context_dir.clone(),
+            };
+            let mut ctx = ShieldedContext {
+                utils: utils.clone(),
+                ..Default::default()
+            };
+            ctx.spents.insert(1);
+            let next_chunk_pos = SEGMENT_CHUNK_SIZE as usize;
+            ctx.memo_map.insert(1, MemoBytes::empty());
+            ctx.memo_map.insert(next_chunk_pos, MemoBytes::empty());
+            std::fs::write(context_dir.join(FILE_NAME), ctx.serialize_to_vec())
+                .unwrap();
+
+            let mut loaded = ShieldedContext {
+                utils: utils.clone(),
+                ..Default::default()
+            };
+            loaded.load().await.unwrap();
+            assert_eq!(loaded.spents, ctx.spents);
+            assert!(!context_dir.join(FILE_NAME).exists());
+            let store_dir = context_dir.join(STORE_DIR_NAME);
+            let read_manifest = || -> BTreeMap<String, String> {
+                BorshDeserialize::try_from_slice(
+                    &std::fs::read(store_dir.join(MANIFEST_FILE_NAME)).unwrap(),
+                )
+                .unwrap()
+            };
+            let manifest_before = read_manifest();
+
+            loaded.spents.insert(2);
+            loaded
+                .memo_map
+                .insert(next_chunk_pos + 1, MemoBytes::empty());
+            loaded.save().await.unwrap();
+            let manifest = read_manifest();
+            assert_ne!(manifest["notes"], manifest_before["notes"]);
+            assert_eq!(manifest["assets"], manifest_before["assets"]);
+            // Only the chunk of the new note is rewritten
+            assert_eq!(manifest["note_data.0"], manifest_before["note_data.0"]);
+            assert_ne!(manifest["note_data.1"], manifest_before["note_data.1"]);
+            // Only the segments in the manifest are kept
+            assert_eq!(
+                std::fs::read_dir(&store_dir).unwrap().count(),
+                manifest.len() + 1
+            );
+
+            let mut reloaded = ShieldedContext {
+                utils,
+                ..Default::default()
+            };
+            reloaded.load().await.unwrap();
+            assert_eq!(reloaded.spents, HashSet::from([1, 2]));
+            assert_eq!(reloaded.memo_map.len(), 3);
+        }
+
+        /// Test that the pipelined sync, which scans the cached and the
+        /// fetched transactions as soon as their notes are decrypted, builds
+        /// the same context as scanning the transactions sequentially
+        #[tokio::test]
+        async fn test_pipelined_scan_matches_sequential_scan() {
+            use std::sync::Mutex;
+
+            use crate::io::StdIo;
+            use crate::masp::testing::MockTxProver;
+
+            let native_token = namada_core::address::testing::nam();
+            let vks: Vec<ViewingKey> = (0..2u8)
+                .map(|seed| {
+                    let esk = ExtendedSpendingKey::master(&[seed; 32]);
+                    ExtendedFullViewingKey::from(&esk).fvk.vk
+                })
+                .collect();
+            let asset_type = AssetType::new(b"test").unwrap();
+            // Every transaction pays a note to each of the viewing keys
+            let txs: IndexedNoteData = (0..6u64)
+                .map(|idx| {
+                    let mut builder = Builder::<TestNetwork, _>::new_with_rng(
+                        NETWORK,
+                        1u32.into(),
+                        OsRng,
+                    );
+                    builder
+                        .add_transparent_input(TxOut {
+                            asset_type,
+                            value: 20,
+                            address: TransparentAddress([0; 20]),
+                        })
+                        .unwrap();
+                    for vk in &vks {
+                        let (div, _g_d) = find_valid_diversifier(&mut OsRng);
+                        let payment_addr = vk.to_payment_address(div).unwrap();
+                        builder
+                            .add_sapling_output(
+                                None,
+                                payment_addr,
+                                asset_type,
+                                10,
+                                MemoBytes::empty(),
+                            )
+                            .unwrap();
+                    }
+                    let (masp_tx, _metadata) = builder
+                        .build(
+                            &MockTxProver(Mutex::new(OsRng)),
+                            &FeeRule::non_standard(U64Sum::zero()),
+                        )
+                        .unwrap();
+                    let indexed_tx = IndexedTx {
+                        height: BlockHeight(idx / 2 + 1),
+                        index: TxIndex((idx % 2) as u32),
+                    };
+                    (indexed_tx, (Epoch(0), BTreeSet::new(), masp_tx))
+                })
+                .collect();
+            let new_context = || ShieldedContext {
+                utils: FsShieldedUtils {
+                    context_dir: tempfile::tempdir().unwrap().into_path(),
+                },
+                vk_heights: vks.iter().map(|vk| (*vk, None)).collect(),
+                ..Default::default()
             };
-            let tmp_path = self.context_dir.join(tmp_file_name);
+
+            let mut sequential = new_context();
+            for (indexed_tx, (epoch, changed_keys, stx)) in &txs {
+                sequential.update_witness_map(*indexed_tx, stx).unwrap();
+                for vk in &vks {
+                    sequential
+                        .scan_tx(
+                            *indexed_tx,
+                            *epoch,
+                            changed_keys,
+                            stx,
+                            vk,
+                            native_token.clone(),
+                        )
+                        .unwrap();
+                    sequential.vk_heights.insert(*vk, Some(*indexed_tx));
+                }
+            }
+
+            // The transactions of the first block are cached, as if a
+            // previous sync had been interrupted, and the other ones are
+            // fetched while the scanning is in progress
+            let mut pipelined = new_context();
+            let (cached, fetched): (IndexedNoteData, IndexedNoteData) = txs
+                .into_iter()
+                .partition(|(indexed_tx, _)| indexed_tx.height.0 == 1);
+            pipelined.unscanned.extend(cached);
+            let (decryptor, decrypted) =
+                TrialDecryptor::new(pipelined.vk_heights.clone());
+            for (indexed_tx, (_epoch, _changed_keys, stx)) in
+                &pipelined.unscanned.txs
             {
-                // First serialize the shielded context into a temporary file.
-                // Inability to create this file implies a simultaneuous write
-                // is in progress. In this case, immediately
-                // fail. This is unproblematic because the data
-                // intended to be stored can always be re-fetched
-                // from the blockchain.
-                let mut ctx_file = OpenOptions::new()
-                    .write(true)
-                    .create_new(true)
-                    .open(tmp_path.clone())?;
-                let mut bytes = Vec::new();
-                ctx.serialize(&mut bytes)
-                    .expect("cannot serialize shielded context");
-                ctx_file.write_all(&bytes[..])?;
+                decryptor.submit(*indexed_tx, stx);
             }
-            // Atomically update the old shielded context file with new data.
-            // Atomicity is required to prevent other client instances from
-            // reading corrupt data.
-            std::fs::rename(tmp_path, self.context_dir.join(file_name))?;
+            let (fetched_sender, fetched_receiver) = mpsc::unbounded();
+            let fetching = async move {
+                for (indexed_tx, tx) in fetched {
+                    decryptor.submit(indexed_tx, &tx.2);
+                    fetched_sender.unbounded_send((indexed_tx, tx)).unwrap();
+                    // Let the scanning stage run before the next fetch
+                    tokio::task::yield_now().await;
+                }
+            };
+            let io = StdIo;
+            let logger = DefaultLogger::new(&io);
+            let scanning = pipelined.scan_fetched(
+                &logger,
+                fetched_receiver,
+                decrypted,
+                &native_token,
+            );
+            let ((), scanned) = futures::join!(fetching, scanning);
+            scanned.unwrap();
+
+            let scanned_state = |ctx: &ShieldedContext<FsShieldedUtils>| {
+                (
+                    &ctx.vk_heights,
+                    &ctx.tx_note_map,
+                    &ctx.pos_map,
+                    &ctx.note_map,
+                    &ctx.nf_map,
+                    &ctx.witness_map,
+                    &ctx.delta_map,
+                )
+                    .serialize_to_vec()
+            };
+            assert_eq!(sequential.note_map.len(), 12);
+            assert_eq!(sequential.witness_map.len(), 12);
+            assert_eq!(scanned_state(&pipelined), scanned_state(&sequential));
+        }
 
-            // Remove the speculative file if present since it's state is
-            // overruled by the confirmed one we just saved
-            if let ContextSyncStatus::Confirmed = ctx.sync_status {
-                let _ = std::fs::remove_file(
-                    self.context_dir.join(SPECULATIVE_FILE_NAME),
-                );
+        /// Test that the notes sent by a viewing key are disclosed together
+        /// with the ones it received, and only if they were encrypted with
+        /// its outgoing viewing key
+        #[test]
+        fn test_disclose_sent_notes() {
+            use std::sync::Mutex;
+
+            use crate::masp::testing::MockTxProver;
+
+            let native_token = namada_core::address::testing::nam();
+            let payment_address = |seed: u8| {
+                let esk = ExtendedSpendingKey::master(&[seed; 32]);
+                let fvk = ExtendedFullViewingKey::from(&esk).fvk;
+                let (div, _g_d) = find_valid_diversifier(&mut OsRng);
+                (fvk, fvk.vk.to_payment_address(div).unwrap())
+            };
+            let (sender, sender_addr) = payment_address(0);
+            let (_, recipient_addr) = payment_address(1);
+            let (_, other_addr) = payment_address(2);
+            let asset_type = AssetType::new(b"test").unwrap();
+
+            let mut builder = Builder::<TestNetwork, _>::new_with_rng(
+                NETWORK,
+                1u32.into(),
+                OsRng,
+            );
+            builder
+                .add_transparent_input(TxOut {
+                    asset_type,
+                    value: 30,
+                    address: TransparentAddress([0; 20]),
+                })
+                .unwrap();
+            for (ovk, addr, value) in [
+                (Some(sender.ovk), recipient_addr, 15),
+                (Some(sender.ovk), sender_addr, 5),
+                (None, other_addr, 10),
+            ] {
+                builder
+                    .add_sapling_output(
+                        ovk,
+                        addr,
+                        asset_type,
+                        value,
+                        MemoBytes::empty(),
+                    )
+                    .unwrap();
             }
+            let (masp_tx, metadata) = builder
+                .build(
+                    &MockTxProver(Mutex::new(OsRng)),
+                    &FeeRule::non_standard(U64Sum::zero()),
+                )
+                .unwrap();
+            let output_pos = |idx: usize| metadata.output_index(idx).unwrap();
 
-            Ok(())
+            let mut ctx = ShieldedContext {
+                utils: FsShieldedUtils {
+                    context_dir: tempfile::tempdir().unwrap().into_path(),
+                },
+                vk_heights: [(sender.vk, None)].into(),
+                ..Default::default()
+            };
+            let indexed_tx = IndexedTx {
+                height: BlockHeight(1),
+                index: TxIndex(0),
+            };
+            ctx.update_witness_map(indexed_tx, &masp_tx).unwrap();
+            ctx.scan_tx(
+                indexed_tx,
+                Epoch(0),
+                &BTreeSet::new(),
+                &masp_tx,
+                &sender.vk,
+                native_token,
+            )
+            .unwrap();
+
+            let mut disclosures =
+                ctx.disclose_notes(&sender, &indexed_tx, &masp_tx).unwrap();
+            disclosures.sort_by_key(|disclosure| disclosure.direction as u8);
+            let disclosed: Vec<_> = disclosures
+                .iter()
+                .map(|disclosure| {
+                    assert_eq!(disclosure.anchor().unwrap(), ctx.tree.root());
+                    (
+                        disclosure.direction,
+                        disclosure.payment_address,
+                        disclosure.note.value,
+                        disclosure.merkle_path.position as usize,
+                    )
+                })
+                .collect();
+            assert_eq!(
+                disclosed,
+                vec![
+                    (
+                        TransferDirection::Incoming,
+                        sender_addr,
+                        5,
+                        output_pos(1)
+                    ),
+                    (
+                        TransferDirection::Outgoing,
+                        recipient_addr,
+                        15,
+                        output_pos(0)
+                    ),
+                ]
+            );
         }
     }
 }
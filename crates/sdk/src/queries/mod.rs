@@ -4,7 +4,7 @@
 // Re-export to show in rustdoc!
 use namada_core::storage::BlockHeight;
 use namada_state::{DBIter, StorageHasher, DB};
-pub use shell::Shell;
+pub use shell::{Shell, MAX_MASP_COMPACT_BLOCKS};
 use shell::SHELL;
 pub use types::{
     EncodedResponseQuery, Error, RequestCtx, RequestQuery, ResponseQuery,
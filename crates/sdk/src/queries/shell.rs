@@ -12,7 +12,7 @@ use namada_core::address::Address;
 use namada_core::dec::Dec;
 use namada_core::hash::Hash;
 use namada_core::hints;
-use namada_core::masp::TokenMap;
+use namada_core::masp::{CompactBlock, TokenMap};
 use namada_core::storage::{
     self, BlockHeight, BlockResults, Epoch, KeySeg, PrefixValue,
 };
@@ -20,7 +20,14 @@ use namada_core::token::{Denomination, MaspDigitPos};
 use namada_core::uint::Uint;
 use namada_state::{DBIter, LastBlock, StateRead, StorageHasher, DB};
 use namada_storage::{ResultExt, StorageRead};
+use namada_token::conversion::{
+    compute_masp_rewards, read_masp_rewards_history, MaspRewards,
+};
 use namada_token::storage_key::masp_token_map_key;
+use namada_token::{
+    read_masp_token_capacity, read_masp_token_limits, MaspTokenCapacity,
+    MaspTokenLimits,
+};
 #[cfg(any(test, feature = "async-client"))]
 use namada_tx::data::TxResult;
 
@@ -97,9 +104,22 @@ router! {SHELL,
     // Conversion state access - read conversion
     ( "masp_reward_tokens" ) -> Vec<MaspTokenRewardData> = masp_reward_tokens,
 
+    // MASP rewards of a token projected for the next epoch and in the last
+    // epochs
+    ( "masp_rewards" / [token: Address] )
+        -> Option<(MaspRewards, Vec<(Epoch, MaspRewards)>)> = masp_rewards,
+
+    // MASP limits of a token and its remaining capacity in the current epoch
+    ( "masp_token_limits" / [token: Address] )
+        -> Option<(MaspTokenLimits, MaspTokenCapacity)> = masp_token_limits,
+
     // Block results access - read bit-vec
     ( "results" ) -> Vec<BlockResults> = read_results,
 
+    // Compact MASP data of the indexed blocks in the given range of heights
+    ( "masp_compact_blocks" / [from: BlockHeight] / [to: BlockHeight] )
+        -> Vec<CompactBlock> = masp_compact_blocks,
+
     // was the transaction accepted?
     ( "accepted" / [tx_hash: Hash] ) -> Option<Event> = accepted,
 
@@ -169,6 +189,41 @@ where
     Ok(results)
 }
 
+/// The maximum number of block heights whose compact MASP data can be
+/// queried at once
+pub const MAX_MASP_COMPACT_BLOCKS: u64 = 1000;
+
+/// Query to read the compact MASP data of the blocks in the given inclusive
+/// range of heights. Only the blocks indexed by the node are returned, so a
+/// missing height must be fetched in full from another source.
+fn masp_compact_blocks<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    from: BlockHeight,
+    to: BlockHeight,
+) -> namada_storage::Result<Vec<CompactBlock>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let last_height = ctx.state.in_mem().get_last_block_height();
+    let to = std::cmp::min(to, last_height);
+    if to.0.saturating_sub(from.0) >= MAX_MASP_COMPACT_BLOCKS {
+        return Err(namada_storage::Error::new(format!(
+            "Cannot query the compact MASP data of more than {} blocks at once",
+            MAX_MASP_COMPACT_BLOCKS
+        )));
+    }
+    (from.0..=to.0)
+        .filter_map(|height| {
+            ctx.state
+                .db()
+                .read_masp_compact_block(BlockHeight(height))
+                .into_storage_result()
+                .transpose()
+        })
+        .collect()
+}
+
 /// Query to read the conversion state
 fn read_conversions<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
@@ -303,6 +358,48 @@ where
     Ok(data)
 }
 
+/// Query to project the MASP rewards of a token for the next epoch by
+/// applying the PD-controller to the current state, along with the rewards of
+/// the last epochs. Returns `None` if the token does not earn MASP rewards.
+fn masp_rewards<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    token: Address,
+) -> namada_storage::Result<Option<(MaspRewards, Vec<(Epoch, MaspRewards)>)>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let token_map: TokenMap =
+        ctx.state.read(&masp_token_map_key())?.unwrap_or_default();
+    if !token_map
+        .values()
+        .any(|reward_token| *reward_token == token)
+    {
+        return Ok(None);
+    }
+    let (projection, _denom) = compute_masp_rewards(ctx.state, &token)?;
+    let history = read_masp_rewards_history(ctx.state, &token)?;
+    Ok(Some((projection, history)))
+}
+
+/// Query to read the MASP limits of a token and its remaining capacity in the
+/// current epoch.
+fn masp_token_limits<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    token: Address,
+) -> namada_storage::Result<Option<(MaspTokenLimits, MaspTokenCapacity)>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let Some(limits) = read_masp_token_limits(ctx.state, &token)? else {
+        return Ok(None);
+    };
+    let capacity =
+        read_masp_token_capacity(ctx.state, &token)?.unwrap_or_default();
+    Ok(Some((limits, capacity)))
+}
+
 fn epoch<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
 ) -> namada_storage::Result<Epoch>
@@ -1,8 +1,12 @@
 // cd namada && cargo expand ledger::queries::vp::governance
 
+use std::collections::BTreeMap;
+
+use namada_core::address::Address;
 use namada_governance::parameters::GovernanceParameters;
 use namada_governance::storage::proposal::StorageProposal;
 use namada_governance::utils::{ProposalResult, Vote};
+use namada_governance::ProposalVote;
 use namada_state::{DBIter, StorageHasher, DB};
 
 use crate::queries::types::RequestCtx;
@@ -11,6 +15,10 @@ use crate::queries::types::RequestCtx;
 router! {GOV,
     ( "proposal" / [id: u64 ] ) -> Option<StorageProposal> = proposal_id,
     ( "proposal" / [id: u64 ] / "votes" ) -> Vec<Vote> = proposal_id_votes,
+    ( "proposal" / [id: u64 ] / "representative_votes" ) -> BTreeMap<Address, ProposalVote> = proposal_id_representative_votes,
+    ( "representative" / [delegator: Address] ) -> Option<Address> = representative,
+    ( "represented" / [representative: Address] ) -> Vec<Address> = represented_delegators,
+    ( "representatives" ) -> BTreeMap<Address, Address> = representatives,
     ( "parameters" ) -> GovernanceParameters = parameters,
     ( "stored_proposal_result" / [id: u64] ) -> Option<ProposalResult> = proposal_result,
 }
@@ -39,6 +47,58 @@ where
     namada_governance::storage::get_proposal_votes(ctx.state, id)
 }
 
+/// Query all the votes cast by representatives for the given proposal id
+fn proposal_id_representative_votes<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    id: u64,
+) -> namada_storage::Result<BTreeMap<Address, ProposalVote>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    namada_governance::storage::get_proposal_representative_votes(
+        ctx.state, id,
+    )
+}
+
+/// Query the representative of the given delegator
+fn representative<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    delegator: Address,
+) -> namada_storage::Result<Option<Address>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    namada_governance::storage::get_representative(ctx.state, &delegator)
+}
+
+/// Query the delegators represented by the given representative
+fn represented_delegators<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    representative: Address,
+) -> namada_storage::Result<Vec<Address>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    namada_governance::storage::get_represented_delegators(
+        ctx.state,
+        &representative,
+    )
+}
+
+/// Query the representatives of all the delegators, keyed by delegator
+fn representatives<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+) -> namada_storage::Result<BTreeMap<Address, Address>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    namada_governance::storage::get_representatives(ctx.state)
+}
+
 /// Get the governance parameters
 fn parameters<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
@@ -1,7 +1,15 @@
+use std::collections::{BTreeMap, BTreeSet};
+
 use namada_core::address::Address;
+use namada_core::storage::Epoch;
+use namada_core::token;
 use namada_governance::pgf::parameters::PgfParameters;
+use namada_governance::pgf::rounds::StorageFundingRound;
+use namada_governance::pgf::storage::history::PgfEpochReport;
 use namada_governance::pgf::storage::steward::StewardDetail;
-use namada_governance::storage::proposal::StoragePgfFunding;
+use namada_governance::storage::proposal::{
+    StoragePgfFunding, StoragePgfFundingSchedule,
+};
 use namada_state::{DBIter, StorageHasher, DB};
 
 use crate::queries::types::RequestCtx;
@@ -10,8 +18,15 @@ use crate::queries::types::RequestCtx;
 router! {PGF,
     ( "stewards" / [ address: Address ] ) -> bool = is_steward,
     ( "stewards" ) -> Vec<StewardDetail> = stewards,
+    ( "fundings" / "budget" ) -> BTreeMap<String, Option<token::Amount>> = funding_budget,
+    ( "fundings" / "schedules" ) -> BTreeMap<String, StoragePgfFundingSchedule> = funding_schedules,
     ( "fundings" ) -> Vec<StoragePgfFunding> = funding,
     ( "parameters" ) -> PgfParameters = parameters,
+    ( "history" / [epoch: opt Epoch] ) -> Vec<PgfEpochReport> = history,
+    ( "rounds" / [id: u64] / "projects" ) -> BTreeSet<Address> = funding_round_projects,
+    ( "rounds" / [id: u64] / "tally" ) -> BTreeMap<Address, u64> = funding_round_tally,
+    ( "rounds" / [id: u64] ) -> Option<StorageFundingRound> = funding_round,
+    ( "rounds" ) -> Vec<StorageFundingRound> = funding_rounds,
 }
 
 /// Query the current pgf steward set
@@ -48,6 +63,28 @@ where
     namada_governance::pgf::storage::get_payments(ctx.state)
 }
 
+/// Query the remaining budget of the continuous pgf fundings
+fn funding_budget<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+) -> namada_storage::Result<BTreeMap<String, Option<token::Amount>>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    namada_governance::pgf::storage::get_payments_remaining_budget(ctx.state)
+}
+
+/// Query the schedules of the continuous pgf fundings
+fn funding_schedules<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+) -> namada_storage::Result<BTreeMap<String, StoragePgfFundingSchedule>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    namada_governance::pgf::storage::get_payments_schedules(ctx.state)
+}
+
 /// Query the PGF parameters
 fn parameters<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
@@ -58,3 +95,63 @@ where
 {
     namada_governance::pgf::storage::get_parameters(ctx.state)
 }
+
+/// Query the PGF inflows and outflows of every recorded epoch, or only of the
+/// given epoch
+fn history<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    epoch: Option<Epoch>,
+) -> namada_storage::Result<Vec<PgfEpochReport>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    namada_governance::pgf::storage::get_history(ctx.state, epoch)
+}
+
+/// Query the pgf funding rounds
+fn funding_rounds<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+) -> namada_storage::Result<Vec<StorageFundingRound>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    namada_governance::pgf::rounds::storage::get_funding_rounds(ctx.state)
+}
+
+/// Query a pgf funding round by id
+fn funding_round<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    id: u64,
+) -> namada_storage::Result<Option<StorageFundingRound>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    namada_governance::pgf::rounds::storage::get_funding_round(ctx.state, id)
+}
+
+/// Query the projects registered in a pgf funding round
+fn funding_round_projects<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    id: u64,
+) -> namada_storage::Result<BTreeSet<Address>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    namada_governance::pgf::rounds::storage::get_projects(ctx.state, id)
+}
+
+/// Query the votes received by each project of a pgf funding round
+fn funding_round_tally<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    id: u64,
+) -> namada_storage::Result<BTreeMap<Address, u64>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    namada_governance::pgf::rounds::storage::get_tally(ctx.state, id)
+}
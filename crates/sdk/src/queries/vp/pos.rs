@@ -27,7 +27,8 @@ use namada_proof_of_stake::storage::{
 };
 use namada_proof_of_stake::types::{
     BondId, BondsAndUnbondsDetail, BondsAndUnbondsDetails, CommissionPair,
-    Slash, ValidatorMetaData, ValidatorState, WeightedValidator,
+    Slash, ValidatorLiveness, ValidatorMetaData, ValidatorState,
+    WeightedValidator,
 };
 use namada_proof_of_stake::{bond_amount, query_reward_tokens};
 use namada_state::{DBIter, StorageHasher, DB};
@@ -66,6 +67,9 @@ router! {POS,
 
         ( "last_infraction_epoch" / [validator: Address] )
             -> Option<Epoch> = validator_last_infraction_epoch,
+
+        ( "liveness" / [validator: Address] )
+            -> Option<ValidatorLiveness> = validator_liveness,
     },
 
     ( "validator_set" ) = {
@@ -328,6 +332,22 @@ where
     read_validator_last_slash_epoch(ctx.state, &validator)
 }
 
+/// Get the liveness record of a validator within the current liveness window.
+/// Returns `None` when the validator is not in the consensus set.
+fn validator_liveness<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    validator: Address,
+) -> namada_storage::Result<Option<ValidatorLiveness>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let params = read_pos_params(ctx.state)?;
+    namada_proof_of_stake::queries::validator_liveness(
+        ctx.state, &params, &validator,
+    )
+}
+
 /// Get the total stake of a validator at the given epoch or current when
 /// `None`. The total stake is a sum of validator's self-bonds and delegations
 /// to their address.
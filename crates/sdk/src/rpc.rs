@@ -13,6 +13,7 @@ use namada_account::Account;
 use namada_core::address::{Address, InternalAddress};
 use namada_core::hash::Hash;
 use namada_core::key::common;
+use namada_core::masp::CompactBlock;
 use namada_core::storage::{
     BlockHeight, BlockResults, Epoch, Key, PrefixValue,
 };
@@ -22,8 +23,13 @@ use namada_core::token::{
 use namada_core::{storage, token};
 use namada_governance::parameters::GovernanceParameters;
 use namada_governance::pgf::parameters::PgfParameters;
+use namada_governance::pgf::rounds::StorageFundingRound;
+use namada_governance::pgf::storage::history::PgfEpochReport;
 use namada_governance::pgf::storage::steward::StewardDetail;
-use namada_governance::storage::proposal::StorageProposal;
+use namada_governance::storage::proposal::{
+    StoragePgfFundingSchedule, StorageProposal,
+};
+use namada_governance::storage::vote::ProposalVote;
 use namada_governance::utils::{
     compute_proposal_result, ProposalResult, ProposalVotes, Vote,
 };
@@ -33,9 +39,12 @@ use namada_ibc::storage::{
 use namada_parameters::{storage as params_storage, EpochDuration};
 use namada_proof_of_stake::parameters::PosParams;
 use namada_proof_of_stake::types::{
-    BondsAndUnbondsDetails, CommissionPair, ValidatorMetaData, ValidatorState,
+    BondsAndUnbondsDetails, CommissionPair, ValidatorLiveness,
+    ValidatorMetaData, ValidatorState,
 };
 use namada_state::LastBlock;
+use namada_token::conversion::MaspRewards;
+use namada_token::{MaspTokenCapacity, MaspTokenLimits};
 use namada_tx::data::{ResultCode, TxResult};
 use serde::Serialize;
 
@@ -249,6 +258,77 @@ pub async fn query_pgf_stewards<C: crate::queries::Client + Sync>(
     )
 }
 
+/// Get the remaining budget of the continuous pgf fundings, indexed by their
+/// target
+pub async fn query_pgf_fundings_budget<C: crate::queries::Client + Sync>(
+    client: &C,
+) -> Result<BTreeMap<String, Option<token::Amount>>, error::Error> {
+    convert_response::<C, BTreeMap<String, Option<token::Amount>>>(
+        RPC.vp().pgf().funding_budget(client).await,
+    )
+}
+
+/// Get the schedules of the continuous pgf fundings, indexed by their target
+pub async fn query_pgf_fundings_schedules<C: crate::queries::Client + Sync>(
+    client: &C,
+) -> Result<BTreeMap<String, StoragePgfFundingSchedule>, error::Error> {
+    convert_response::<C, BTreeMap<String, StoragePgfFundingSchedule>>(
+        RPC.vp().pgf().funding_schedules(client).await,
+    )
+}
+
+/// Get the pgf inflows and outflows of every recorded epoch, or only of the
+/// given epoch
+pub async fn query_pgf_history<C: crate::queries::Client + Sync>(
+    client: &C,
+    epoch: Option<Epoch>,
+) -> Result<Vec<PgfEpochReport>, error::Error> {
+    convert_response::<C, Vec<PgfEpochReport>>(
+        RPC.vp().pgf().history(client, &epoch).await,
+    )
+}
+
+/// Get the pgf funding rounds
+pub async fn query_pgf_funding_rounds<C: crate::queries::Client + Sync>(
+    client: &C,
+) -> Result<Vec<StorageFundingRound>, error::Error> {
+    convert_response::<C, Vec<StorageFundingRound>>(
+        RPC.vp().pgf().funding_rounds(client).await,
+    )
+}
+
+/// Get a pgf funding round by id
+pub async fn query_pgf_funding_round<C: crate::queries::Client + Sync>(
+    client: &C,
+    id: u64,
+) -> Result<Option<StorageFundingRound>, error::Error> {
+    convert_response::<C, Option<StorageFundingRound>>(
+        RPC.vp().pgf().funding_round(client, &id).await,
+    )
+}
+
+/// Get the projects registered in a pgf funding round
+pub async fn query_pgf_funding_round_projects<
+    C: crate::queries::Client + Sync,
+>(
+    client: &C,
+    id: u64,
+) -> Result<BTreeSet<Address>, error::Error> {
+    convert_response::<C, BTreeSet<Address>>(
+        RPC.vp().pgf().funding_round_projects(client, &id).await,
+    )
+}
+
+/// Get the votes received by each project of a pgf funding round
+pub async fn query_pgf_funding_round_tally<C: crate::queries::Client + Sync>(
+    client: &C,
+    id: u64,
+) -> Result<BTreeMap<Address, u64>, error::Error> {
+    convert_response::<C, BTreeMap<Address, u64>>(
+        RPC.vp().pgf().funding_round_tally(client, &id).await,
+    )
+}
+
 /// Query the consensus key by validator address
 pub async fn query_validator_consensus_keys<
     C: crate::queries::Client + Sync,
@@ -323,6 +403,19 @@ pub async fn query_conversions<C: crate::queries::Client + Sync>(
     convert_response::<C, _>(RPC.shell().read_conversions(client).await)
 }
 
+/// Query the compact MASP data of the blocks in the given inclusive range of
+/// heights, at most [`crate::queries::MAX_MASP_COMPACT_BLOCKS`] at once. Only
+/// the blocks indexed by the node are returned.
+pub async fn query_masp_compact_blocks<C: crate::queries::Client + Sync>(
+    client: &C,
+    from: BlockHeight,
+    to: BlockHeight,
+) -> Result<Vec<CompactBlock>, error::Error> {
+    convert_response::<C, _>(
+        RPC.shell().masp_compact_blocks(client, &from, &to).await,
+    )
+}
+
 /// Query to read the tokens that earn masp rewards.
 pub async fn query_masp_reward_tokens<C: crate::queries::Client + Sync>(
     client: &C,
@@ -330,6 +423,25 @@ pub async fn query_masp_reward_tokens<C: crate::queries::Client + Sync>(
     convert_response::<C, _>(RPC.shell().masp_reward_tokens(client).await)
 }
 
+/// Query the MASP rewards of a token projected for the next epoch, along with
+/// the rewards of the last epochs. Returns `None` if the token does not earn
+/// MASP rewards.
+pub async fn query_masp_rewards<C: crate::queries::Client + Sync>(
+    client: &C,
+    token: &Address,
+) -> Result<Option<(MaspRewards, Vec<(Epoch, MaspRewards)>)>, Error> {
+    convert_response::<C, _>(RPC.shell().masp_rewards(client, token).await)
+}
+
+/// Query the MASP limits of a token and its remaining capacity in the current
+/// epoch. Returns `None` if the token has no limits.
+pub async fn query_masp_token_limits<C: crate::queries::Client + Sync>(
+    client: &C,
+    token: &Address,
+) -> Result<Option<(MaspTokenLimits, MaspTokenCapacity)>, Error> {
+    convert_response::<C, _>(RPC.shell().masp_token_limits(client, token).await)
+}
+
 /// Query a wasm code hash
 pub async fn query_wasm_code_hash(
     context: &impl Namada,
@@ -809,6 +921,16 @@ pub async fn get_validator_state<C: crate::queries::Client + Sync>(
     )
 }
 
+/// Query and return a validator's liveness record
+pub async fn get_validator_liveness<C: crate::queries::Client + Sync>(
+    client: &C,
+    validator: &Address,
+) -> Result<Option<ValidatorLiveness>, error::Error> {
+    convert_response::<C, Option<ValidatorLiveness>>(
+        RPC.vp().pos().validator_liveness(client, validator).await,
+    )
+}
+
 /// Get the delegator's delegation
 pub async fn get_delegators_delegation<C: crate::queries::Client + Sync>(
     client: &C,
@@ -1019,16 +1141,71 @@ pub async fn query_proposal_result<C: crate::queries::Client + Sync>(
                     }
                 }
             }
-            compute_proposal_result(
+            add_represented_votes(
+                client,
+                proposal_id,
+                tally_epoch,
+                &mut proposal_votes,
+            )
+            .await?;
+            let mut proposal_result = compute_proposal_result(
                 proposal_votes,
                 total_staked_token,
                 tally_type,
-            )
+            );
+            let veto_threshold = query_governance_parameters(client)
+                .await
+                .proposal_veto_threshold;
+            proposal_result.apply_veto(veto_threshold);
+            proposal_result
         }
     };
     Ok(Some(proposal_result))
 }
 
+/// Add the votes of the representatives of a proposal to the given votes,
+/// together with the voting power of the delegators they represent. The
+/// delegators that voted directly and validators are left out.
+pub async fn add_represented_votes<C: crate::queries::Client + Sync>(
+    client: &C,
+    proposal_id: u64,
+    epoch: Epoch,
+    proposal_votes: &mut ProposalVotes,
+) -> Result<(), Error> {
+    let representative_votes =
+        query_proposal_representative_votes(client, proposal_id).await?;
+    if representative_votes.is_empty() {
+        return Ok(());
+    }
+    for (representative, vote) in &representative_votes {
+        proposal_votes.add_representative(representative, vote.clone().into());
+    }
+
+    for (delegator, representative) in query_representatives(client).await? {
+        if proposal_votes.delegators_vote.contains_key(&delegator)
+            || !representative_votes.contains_key(&representative)
+            || is_validator(client, &delegator).await?
+        {
+            continue;
+        }
+        let delegations =
+            get_delegators_delegation_at(client, &delegator, epoch).await?;
+        for validator in delegations.keys() {
+            let voting_power =
+                get_bond_amount_at(client, &delegator, validator, epoch)
+                    .await
+                    .unwrap_or_default();
+            proposal_votes.add_represented_delegator(
+                &delegator,
+                &representative,
+                validator,
+                voting_power,
+            );
+        }
+    }
+    Ok(())
+}
+
 /// Query a validator's unbonds for a given epoch
 pub async fn query_and_print_unbonds(
     context: &impl Namada,
@@ -1123,6 +1300,52 @@ pub async fn query_proposal_votes<C: crate::queries::Client + Sync>(
     )
 }
 
+/// Query the votes cast by representatives for a proposal
+pub async fn query_proposal_representative_votes<
+    C: crate::queries::Client + Sync,
+>(
+    client: &C,
+    proposal_id: u64,
+) -> Result<BTreeMap<Address, ProposalVote>, error::Error> {
+    convert_response::<C, _>(
+        RPC.vp()
+            .gov()
+            .proposal_id_representative_votes(client, &proposal_id)
+            .await,
+    )
+}
+
+/// Query the governance representative of a delegator
+pub async fn query_representative<C: crate::queries::Client + Sync>(
+    client: &C,
+    delegator: &Address,
+) -> Result<Option<Address>, error::Error> {
+    convert_response::<C, _>(
+        RPC.vp().gov().representative(client, delegator).await,
+    )
+}
+
+/// Query the delegators represented by a governance representative
+pub async fn query_represented_delegators<C: crate::queries::Client + Sync>(
+    client: &C,
+    representative: &Address,
+) -> Result<Vec<Address>, error::Error> {
+    convert_response::<C, _>(
+        RPC.vp()
+            .gov()
+            .represented_delegators(client, representative)
+            .await,
+    )
+}
+
+/// Query the governance representatives of all the delegators, keyed by
+/// delegator
+pub async fn query_representatives<C: crate::queries::Client + Sync>(
+    client: &C,
+) -> Result<BTreeMap<Address, Address>, error::Error> {
+    convert_response::<C, _>(RPC.vp().gov().representatives(client).await)
+}
+
 pub async fn query_next_epoch_info<C: crate::queries::Client + Sync>(
     client: &C,
 ) -> Result<(BlockHeight, EpochDuration), error::Error> {
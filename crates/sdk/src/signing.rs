@@ -11,7 +11,9 @@ use masp_primitives::transaction::components::sapling::fees::{
     InputView, OutputView,
 };
 use masp_primitives::transaction::Transaction;
-use namada_account::{AccountPublicKeysMap, InitAccount, UpdateAccount};
+use namada_account::{
+    AccountPublicKeysMap, InitAccount, UpdateAccount, Vesting, VestingSchedule,
+};
 use namada_core::address::{Address, ImplicitAddress, InternalAddress, MASP};
 use namada_core::key::*;
 use namada_core::masp::{AssetData, ExtendedViewingKey, PaymentAddress};
@@ -22,12 +24,15 @@ use namada_core::token::Transfer;
 // use namada_core::storage::Key;
 use namada_core::token::{Amount, DenominatedAmount};
 use namada_governance::storage::proposal::{
-    InitProposalData, ProposalType, VoteProposalData,
+    ChangeRepresentativeData, InitProposalData, ProposalType, VoteProposalData,
 };
 use namada_governance::storage::vote::ProposalVote;
 use namada_parameters::storage as parameter_storage;
+use namada_proof_of_stake::types::{DelegationPolicy, DelegationPolicyChange};
 use namada_token::storage_key::balance_key;
-use namada_tx::data::pgf::UpdateStewardCommission;
+use namada_tx::data::pgf::{
+    RegisterFundingRoundProject, UpdateStewardCommission, VoteFundingRound,
+};
 use namada_tx::data::pos::BecomeValidator;
 use namada_tx::data::{pos, Fee};
 use namada_tx::{MaspBuilder, Section, Tx};
@@ -37,11 +42,13 @@ use serde::{Deserialize, Serialize};
 use sha2::Digest;
 use tokio::sync::RwLock;
 
-use super::masp::{ShieldedContext, ShieldedTransfer};
+use super::masp::{ShieldedContext, ShieldedTransfer, SpendRandomizer};
 use crate::args::SdkTypes;
 use crate::error::{EncodingError, Error, TxSubmitError};
 use crate::eth_bridge_pool::PendingTransfer;
-use crate::governance::storage::proposal::{AddRemove, PGFAction, PGFTarget};
+use crate::governance::storage::proposal::{
+    AddRemove, PGFAction, PGFContinuousFunding, PGFFundingSchedule, PGFTarget,
+};
 use crate::ibc::apps::transfer::types::msgs::transfer::MsgTransfer;
 use crate::ibc::primitives::proto::Any;
 use crate::io::*;
@@ -49,12 +56,14 @@ use crate::rpc::validate_amount;
 use crate::tx::{
     Commitment, TX_BECOME_VALIDATOR_WASM, TX_BOND_WASM, TX_BRIDGE_POOL_WASM,
     TX_CHANGE_COMMISSION_WASM, TX_CHANGE_CONSENSUS_KEY_WASM,
-    TX_CHANGE_METADATA_WASM, TX_CLAIM_REWARDS_WASM,
+    TX_CHANGE_DELEGATION_POLICY_WASM, TX_CHANGE_METADATA_WASM,
+    TX_CHANGE_REPRESENTATIVE_WASM, TX_CLAIM_REWARDS_WASM,
     TX_DEACTIVATE_VALIDATOR_WASM, TX_IBC_WASM, TX_INIT_ACCOUNT_WASM,
     TX_INIT_PROPOSAL, TX_REACTIVATE_VALIDATOR_WASM, TX_REDELEGATE_WASM,
-    TX_RESIGN_STEWARD, TX_REVEAL_PK, TX_TRANSFER_WASM, TX_UNBOND_WASM,
-    TX_UNJAIL_VALIDATOR_WASM, TX_UPDATE_ACCOUNT_WASM,
-    TX_UPDATE_STEWARD_COMMISSION, TX_VOTE_PROPOSAL, TX_WITHDRAW_WASM,
+    TX_REGISTER_PGF_PROJECT_WASM, TX_RESIGN_STEWARD, TX_REVEAL_PK,
+    TX_TRANSFER_WASM, TX_UNBOND_WASM, TX_UNJAIL_VALIDATOR_WASM,
+    TX_UPDATE_ACCOUNT_WASM, TX_UPDATE_STEWARD_COMMISSION,
+    TX_VOTE_PGF_FUNDING_ROUND_WASM, TX_VOTE_PROPOSAL, TX_WITHDRAW_WASM,
     VP_USER_WASM,
 };
 pub use crate::wallet::store::AddressVpType;
@@ -74,6 +83,9 @@ pub struct SigningTxData {
     pub account_public_keys_map: Option<AccountPublicKeysMap>,
     /// The public keys of the fee payer
     pub fee_payer: common::PublicKey,
+    /// The randomizers of the shielded spends that are to be authorized
+    /// separately by the holders of their spending keys
+    pub spend_randomizers: Vec<SpendRandomizer>,
 }
 
 /// Find the public key for the given address and try to load the keypair
@@ -360,6 +372,7 @@ pub async fn aux_signing_data(
         threshold,
         account_public_keys_map,
         fee_payer,
+        spend_randomizers: vec![],
     })
 }
 
@@ -400,6 +413,7 @@ pub async fn init_validator_signing_data(
         threshold: 0,
         account_public_keys_map,
         fee_payer,
+        spend_randomizers: vec![],
     })
 }
 
@@ -496,24 +510,19 @@ pub async fn validate_fee_and_gen_unshield<N: Namada>(
     let unshield = match total_fee.checked_sub(balance) {
         Some(diff) if !diff.is_zero() => {
             if let Some(spending_key) = args.fee_unshield.clone() {
-                // Unshield funds for fee payment
-                let target = namada_core::masp::TransferTarget::Address(
-                    fee_payer_address.clone(),
-                );
+                // Pay the fee from the value balance of a masp transaction
                 let fee_amount = DenominatedAmount::new(
-                    // NOTE: must unshield the total fee amount, not the
-                    // diff, because the ledger evaluates the transaction in
-                    // reverse (wrapper first, inner second) and cannot know
-                    // ahead of time if the inner will modify the balance of
-                    // the gas payer
+                    // NOTE: the masp transaction pays the total fee amount,
+                    // not the diff, since the protocol credits it straight
+                    // to the block proposer
                     total_fee,
                     0.into(),
                 );
 
-                match ShieldedContext::<N::ShieldedUtils>::gen_shielded_transfer(
+                match ShieldedContext::<N::ShieldedUtils>::gen_shielded_fee_payment(
                         context,
                         &spending_key,
-                        &target,
+                        &fee_payer_address,
                         &args.fee_token,
                         fee_amount,
                     !(args.dry_run || args.dry_run_wrapper)
@@ -525,6 +534,7 @@ pub async fn validate_fee_and_gen_unshield<N: Namada>(
                         masp_tx: transaction,
                         metadata: _data,
                         epoch: _unshielding_epoch,
+                        spend_randomizers: _,
                     })) => {
                         let spends = transaction
                             .sapling_bundle()
@@ -572,7 +582,7 @@ pub async fn validate_fee_and_gen_unshield<N: Namada>(
                         if !args.force {
                             return Err(Error::from(
                                 TxSubmitError::FeeUnshieldingError(
-                                    "Missing unshielding transaction"
+                                    "Missing masp fee payment transaction"
                                         .to_string(),
                                 ),
                             ));
@@ -905,6 +915,14 @@ impl<'a> Display for LedgerProposalVote<'a> {
             ProposalVote::Yay => write!(f, "yay"),
             ProposalVote::Nay => write!(f, "nay"),
             ProposalVote::Abstain => write!(f, "abstain"),
+            ProposalVote::Veto => write!(f, "veto"),
+            ProposalVote::Weighted(weights) => write!(
+                f,
+                "yay {}, nay {}, abstain {}",
+                to_ledger_decimal(&weights.yay.to_string()),
+                to_ledger_decimal(&weights.nay.to_string()),
+                to_ledger_decimal(&weights.abstain.to_string()),
+            ),
         }
     }
 }
@@ -929,6 +947,65 @@ impl<'a> Display for LedgerProposalType<'a> {
             }
             ProposalType::PGFSteward(_) => write!(f, "PGF Steward"),
             ProposalType::PGFPayment(_) => write!(f, "PGF Payment"),
+            ProposalType::ParameterChange(_) => write!(f, "Parameter Change"),
+            ProposalType::Upgrade(_) => write!(f, "Upgrade"),
+        }
+    }
+}
+
+/// Push the optional fields of a continuous PGF funding schedule to the Ledger
+/// output
+fn push_pgf_funding_schedule(
+    output: &mut Vec<String>,
+    schedule: &PGFFundingSchedule,
+) {
+    if let Some(start_epoch) = schedule.start_epoch {
+        output.push(format!("Start epoch : {}", start_epoch));
+    }
+    if let Some(end_epoch) = schedule.end_epoch {
+        output.push(format!("End epoch : {}", end_epoch));
+    }
+    if let Some(cap) = schedule.cap {
+        output.push(format!(
+            "Cap: NAM {}",
+            to_ledger_decimal(&cap.to_string_native())
+        ));
+    }
+}
+
+/// Adds the Ledger output lines describing the vesting of an account
+fn push_vesting(
+    tokens: &HashMap<Address, String>,
+    output: &mut Vec<String>,
+    vesting: &Vesting,
+) {
+    // The denomination of the token is not known offline, so the raw amounts
+    // are displayed
+    make_ledger_amount_addr(
+        tokens,
+        output,
+        DenominatedAmount::new(vesting.amount, 0.into()),
+        &vesting.token,
+        "Vesting ",
+    );
+    match &vesting.schedule {
+        VestingSchedule::Linear {
+            start_epoch,
+            cliff_epoch,
+            end_epoch,
+        } => output.extend(vec![
+            format!("Vesting start epoch : {}", start_epoch),
+            format!("Vesting cliff epoch : {}", cliff_epoch),
+            format!("Vesting end epoch : {}", end_epoch),
+        ]),
+        VestingSchedule::Custom(unlocks) => {
+            output.extend(unlocks.iter().map(|unlock| {
+                format!(
+                    "Vesting unlock : {} at epoch {}",
+                    to_ledger_decimal(&unlock.amount.to_string()),
+                    unlock.epoch
+                )
+            }))
         }
     }
 }
@@ -1037,6 +1114,41 @@ fn proposal_type_to_ledger_vector(
                         output
                             .push(format!("Channel ID: {}", target.channel_id));
                     }
+                    PGFAction::ScheduledContinuous(PGFContinuousFunding {
+                        target: PGFTarget::Internal(target),
+                        schedule,
+                    }) => {
+                        output.push(
+                            "PGF Action : Add Continuous Payment".to_string(),
+                        );
+                        output.push(format!("Target: {}", target.target));
+                        output.push(format!(
+                            "Amount: NAM {}",
+                            to_ledger_decimal(
+                                &target.amount.to_string_native()
+                            )
+                        ));
+                        push_pgf_funding_schedule(&mut output, schedule);
+                    }
+                    PGFAction::ScheduledContinuous(PGFContinuousFunding {
+                        target: PGFTarget::Ibc(target),
+                        schedule,
+                    }) => {
+                        output.push(
+                            "PGF Action : Add Continuous Payment".to_string(),
+                        );
+                        output.push(format!("Target: {}", target.target));
+                        output.push(format!(
+                            "Amount: NAM {}",
+                            to_ledger_decimal(
+                                &target.amount.to_string_native()
+                            )
+                        ));
+                        output.push(format!("Port ID: {}", target.port_id));
+                        output
+                            .push(format!("Channel ID: {}", target.channel_id));
+                        push_pgf_funding_schedule(&mut output, schedule);
+                    }
                     PGFAction::Retro(PGFTarget::Internal(target)) => {
                         output.push("PGF Action : Retro Payment".to_string());
                         output.push(format!("Target: {}", target.target));
@@ -1060,9 +1172,42 @@ fn proposal_type_to_ledger_vector(
                         output
                             .push(format!("Channel ID: {}", target.channel_id));
                     }
+                    PGFAction::FundingRound(round) => {
+                        output.push("PGF Action : Funding Round".to_string());
+                        output.push(format!(
+                            "Budget: NAM {}",
+                            to_ledger_decimal(&round.budget.to_string_native())
+                        ));
+                        output.push(format!(
+                            "Start Epoch: {}",
+                            round.start_epoch
+                        ));
+                        output.push(format!("End Epoch: {}", round.end_epoch));
+                        output.push(format!(
+                            "Voice Credits: {}",
+                            round.voice_credits
+                        ));
+                        output.push(format!(
+                            "Min Bonded Stake: NAM {}",
+                            to_ledger_decimal(
+                                &round.min_bonded_stake.to_string_native()
+                            )
+                        ));
+                    }
                 }
             }
         }
+        ProposalType::ParameterChange(changes) => {
+            output.push("Proposal type : Parameter Change".to_string());
+            for change in changes {
+                output.push(format!("Parameter : {}", change));
+            }
+        }
+        ProposalType::Upgrade(upgrade) => {
+            output.push("Proposal type : Upgrade".to_string());
+            output.push(format!("Upgrade name : {}", upgrade.name));
+            output.push(format!("Upgrade height : {}", upgrade.height));
+        }
     }
 }
 
@@ -1133,6 +1278,9 @@ pub async fn to_ledger_vector(
             format!("Threshold : {}", init_account.threshold),
             format!("VP type : {}", vp_code),
         ]);
+        if let Some(vesting) = &init_account.vesting {
+            push_vesting(&tokens, &mut tv.output, vesting);
+        }
 
         tv.output_expert.extend(
             init_account
@@ -1144,6 +1292,9 @@ pub async fn to_ledger_vector(
             format!("Threshold : {}", init_account.threshold),
             format!("VP type : {}", HEXLOWER.encode(&extra.code.hash().0)),
         ]);
+        if let Some(vesting) = &init_account.vesting {
+            push_vesting(&tokens, &mut tv.output_expert, vesting);
+        }
     } else if code_sec.tag == Some(TX_BECOME_VALIDATOR_WASM.to_string()) {
         let init_validator = BecomeValidator::try_from_slice(
             &tx.data()
@@ -1242,6 +1393,9 @@ pub async fn to_ledger_vector(
             format!("Grace epoch : {}", init_proposal_data.grace_epoch),
             format!("Content : {}", HEXLOWER.encode(&extra.0)),
         ]);
+        if init_proposal_data.expedited {
+            tv.output.push("Expedited : true".to_string());
+        }
 
         tv.output_expert
             .push(format!("ID : {}", init_proposal_data.id));
@@ -1263,6 +1417,9 @@ pub async fn to_ledger_vector(
             format!("Grace epoch : {}", init_proposal_data.grace_epoch),
             format!("Content : {}", HEXLOWER.encode(&extra.0)),
         ]);
+        if init_proposal_data.expedited {
+            tv.output_expert.push("Expedited : true".to_string());
+        }
     } else if code_sec.tag == Some(TX_VOTE_PROPOSAL.to_string()) {
         let vote_proposal = VoteProposalData::try_from_slice(
             &tx.data()
@@ -1658,6 +1815,60 @@ pub async fn to_ledger_vector(
             other_items.push(format!("Commission rate : {}", commission_rate));
         }
 
+        tv.output.extend(other_items.clone());
+        tv.output_expert.extend(other_items);
+    } else if code_sec.tag == Some(TX_CHANGE_DELEGATION_POLICY_WASM.to_string())
+    {
+        let policy_change = DelegationPolicyChange::try_from_slice(
+            &tx.data()
+                .ok_or_else(|| Error::Other("Invalid Data".to_string()))?,
+        )
+        .map_err(|err| {
+            Error::from(EncodingError::Conversion(err.to_string()))
+        })?;
+
+        tv.name = "Change_Delegation_Policy_0".to_string();
+
+        tv.output
+            .extend(vec!["Type : Change delegation policy".to_string()]);
+
+        let mut other_items = vec![
+            format!("Validator : {}", policy_change.validator),
+            format!("Delegation policy : {}", policy_change.policy),
+        ];
+        if let DelegationPolicy::Allowlist(allowlist) = policy_change.policy {
+            other_items.extend(
+                allowlist.iter().map(|delegator| {
+                    format!("Allowed delegator : {}", delegator)
+                }),
+            );
+        }
+
+        tv.output.extend(other_items.clone());
+        tv.output_expert.extend(other_items);
+    } else if code_sec.tag == Some(TX_CHANGE_REPRESENTATIVE_WASM.to_string()) {
+        let representative_change = ChangeRepresentativeData::try_from_slice(
+            &tx.data()
+                .ok_or_else(|| Error::Other("Invalid Data".to_string()))?,
+        )
+        .map_err(|err| {
+            Error::from(EncodingError::Conversion(err.to_string()))
+        })?;
+
+        tv.name = "Change_Representative_0".to_string();
+
+        tv.output
+            .extend(vec!["Type : Change representative".to_string()]);
+
+        let representative = representative_change
+            .representative
+            .map(|representative| representative.to_string())
+            .unwrap_or_else(|| "(none)".to_string());
+        let other_items = vec![
+            format!("Delegator : {}", representative_change.delegator),
+            format!("Representative : {}", representative),
+        ];
+
         tv.output.extend(other_items.clone());
         tv.output_expert.extend(other_items);
     } else if code_sec.tag == Some(TX_CHANGE_CONSENSUS_KEY_WASM.to_string()) {
@@ -1814,6 +2025,52 @@ pub async fn to_ledger_vector(
         ]);
 
         tv.output_expert.push(format!("Steward : {}", address));
+    } else if code_sec.tag == Some(TX_REGISTER_PGF_PROJECT_WASM.to_string()) {
+        let registration = RegisterFundingRoundProject::try_from_slice(
+            &tx.data()
+                .ok_or_else(|| Error::Other("Invalid Data".to_string()))?,
+        )
+        .map_err(|err| {
+            Error::from(EncodingError::Conversion(err.to_string()))
+        })?;
+
+        tv.name = "Register_Pgf_Project_0".to_string();
+
+        tv.output
+            .extend(vec!["Type : Register PGF Project".to_string()]);
+
+        let other_items = vec![
+            format!("Funding Round : {}", registration.round_id),
+            format!("Project : {}", registration.project),
+        ];
+
+        tv.output.extend(other_items.clone());
+        tv.output_expert.extend(other_items);
+    } else if code_sec.tag == Some(TX_VOTE_PGF_FUNDING_ROUND_WASM.to_string()) {
+        let vote = VoteFundingRound::try_from_slice(
+            &tx.data()
+                .ok_or_else(|| Error::Other("Invalid Data".to_string()))?,
+        )
+        .map_err(|err| {
+            Error::from(EncodingError::Conversion(err.to_string()))
+        })?;
+
+        tv.name = "Vote_Pgf_Funding_Round_0".to_string();
+
+        tv.output
+            .extend(vec!["Type : Vote PGF Funding Round".to_string()]);
+
+        let mut other_items = vec![
+            format!("Funding Round : {}", vote.round_id),
+            format!("Voter : {}", vote.voter),
+        ];
+        for (project, votes) in &vote.votes {
+            other_items.push(format!("Project : {}", project));
+            other_items.push(format!("Votes : {}", votes));
+        }
+
+        tv.output.extend(other_items.clone());
+        tv.output_expert.extend(other_items);
     } else if code_sec.tag == Some(TX_BRIDGE_POOL_WASM.to_string()) {
         let transfer = PendingTransfer::try_from_slice(
             &tx.data()
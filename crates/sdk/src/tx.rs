@@ -18,7 +18,7 @@ use masp_primitives::transaction::components::transparent::fees::{
     InputView as TransparentInputView, OutputView as TransparentOutputView,
 };
 use masp_primitives::transaction::components::I128Sum;
-use namada_account::{InitAccount, UpdateAccount};
+use namada_account::{InitAccount, UpdateAccount, Vesting};
 use namada_core::address::{Address, InternalAddress, MASP};
 use namada_core::dec::Dec;
 use namada_core::hash::Hash;
@@ -36,19 +36,24 @@ use namada_core::storage::Epoch;
 use namada_core::time::DateTimeUtc;
 use namada_core::{storage, token};
 use namada_governance::cli::onchain::{
-    DefaultProposal, OnChainProposal, PgfFundingProposal, PgfStewardProposal,
+    DefaultProposal, OnChainProposal, ParameterChangeProposal,
+    PgfFundingProposal, PgfStewardProposal, UpgradeProposal,
 };
 use namada_governance::pgf::cli::steward::Commission;
 use namada_governance::storage::proposal::{
-    InitProposalData, ProposalType, VoteProposalData,
+    ChangeRepresentativeData, InitProposalData, ProposalType, VoteProposalData,
 };
 use namada_governance::storage::vote::ProposalVote;
 use namada_ibc::storage::channel_key;
 use namada_proof_of_stake::parameters::PosParams;
-use namada_proof_of_stake::types::{CommissionPair, ValidatorState};
+use namada_proof_of_stake::types::{
+    CommissionPair, DelegationPolicy, DelegationPolicyChange, ValidatorState,
+};
 use namada_token::storage_key::balance_key;
 use namada_token::DenominatedAmount;
-use namada_tx::data::pgf::UpdateStewardCommission;
+use namada_tx::data::pgf::{
+    RegisterFundingRoundProject, UpdateStewardCommission, VoteFundingRound,
+};
 use namada_tx::data::{pos, ResultCode, TxResult};
 pub use namada_tx::{Signature, *};
 
@@ -83,6 +88,8 @@ pub const TX_REACTIVATE_VALIDATOR_WASM: &str = "tx_reactivate_validator.wasm";
 pub const TX_INIT_PROPOSAL: &str = "tx_init_proposal.wasm";
 /// Vote transaction WASM path
 pub const TX_VOTE_PROPOSAL: &str = "tx_vote_proposal.wasm";
+/// Change governance representative transaction WASM path
+pub const TX_CHANGE_REPRESENTATIVE_WASM: &str = "tx_change_representative.wasm";
 /// Reveal public key transaction WASM path
 pub const TX_REVEAL_PK: &str = "tx_reveal_pk.wasm";
 /// Update validity predicate WASM path
@@ -110,11 +117,19 @@ pub const TX_CHANGE_COMMISSION_WASM: &str =
 pub const TX_CHANGE_CONSENSUS_KEY_WASM: &str = "tx_change_consensus_key.wasm";
 /// Change validator metadata WASM path
 pub const TX_CHANGE_METADATA_WASM: &str = "tx_change_validator_metadata.wasm";
+/// Change validator delegation policy WASM path
+pub const TX_CHANGE_DELEGATION_POLICY_WASM: &str =
+    "tx_change_validator_delegation_policy.wasm";
 /// Resign steward WASM path
 pub const TX_RESIGN_STEWARD: &str = "tx_resign_steward.wasm";
 /// Update steward commission WASM path
 pub const TX_UPDATE_STEWARD_COMMISSION: &str =
     "tx_update_steward_commission.wasm";
+/// Register pgf funding round project WASM path
+pub const TX_REGISTER_PGF_PROJECT_WASM: &str = "tx_register_pgf_project.wasm";
+/// Vote in pgf funding round WASM path
+pub const TX_VOTE_PGF_FUNDING_ROUND_WASM: &str =
+    "tx_vote_pgf_funding_round.wasm";
 /// Redelegate transaction WASM path
 pub const TX_REDELEGATE_WASM: &str = "tx_redelegate.wasm";
 
@@ -823,6 +838,74 @@ pub async fn build_validator_metadata_change(
     .map(|tx| (tx, signing_data))
 }
 
+/// Submit validator delegation policy change
+pub async fn build_validator_delegation_policy_change(
+    context: &impl Namada,
+    args::DelegationPolicyChange {
+        tx: tx_args,
+        validator,
+        closed,
+        allowlist,
+        tx_code_path,
+    }: &args::DelegationPolicyChange,
+) -> Result<(Tx, SigningTxData)> {
+    let default_signer = Some(validator.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx_args,
+        Some(validator.clone()),
+        default_signer,
+    )
+    .await?;
+    let (fee_amount, _, unshield) = validate_fee_and_gen_unshield(
+        context,
+        tx_args,
+        &signing_data.fee_payer,
+    )
+    .await?;
+
+    // The validator must actually be a validator
+    let validator =
+        known_validator_or_err(validator.clone(), tx_args.force, context)
+            .await?;
+
+    let policy = if *closed {
+        if !allowlist.is_empty() {
+            edisplay_line!(
+                context.io(),
+                "A delegation allowlist cannot be used together with a closed \
+                 delegation policy."
+            );
+            return Err(Error::Other(
+                "Conflicting delegation policy arguments".to_string(),
+            ));
+        }
+        DelegationPolicy::Closed
+    } else if allowlist.is_empty() {
+        DelegationPolicy::Open
+    } else {
+        DelegationPolicy::Allowlist(allowlist.iter().cloned().collect())
+    };
+
+    let data = DelegationPolicyChange {
+        validator: validator.clone(),
+        policy,
+    };
+
+    build(
+        context,
+        tx_args,
+        tx_code_path.clone(),
+        data,
+        do_nothing,
+        unshield,
+        fee_amount,
+        &signing_data.fee_payer,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
 /// Craft transaction to update a steward commission
 pub async fn build_update_steward_commission(
     context: &impl Namada,
@@ -946,6 +1029,203 @@ pub async fn build_resign_steward(
     .map(|tx| (tx, signing_data))
 }
 
+/// Craft transaction to register a project in a pgf funding round
+pub async fn build_register_pgf_project(
+    context: &impl Namada,
+    args::RegisterPgfProject {
+        tx: tx_args,
+        round_id,
+        project,
+        tx_code_path,
+    }: &args::RegisterPgfProject,
+) -> Result<(Tx, SigningTxData)> {
+    let default_signer = Some(project.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx_args,
+        Some(project.clone()),
+        default_signer,
+    )
+    .await?;
+    let (fee_amount, _, unshield) = validate_fee_and_gen_unshield(
+        context,
+        tx_args,
+        &signing_data.fee_payer,
+    )
+    .await?;
+
+    let current_epoch = rpc::query_epoch(context.client()).await?;
+    let invalid_reason = match rpc::query_pgf_funding_round(
+        context.client(),
+        *round_id,
+    )
+    .await?
+    {
+        None => Some("the funding round doesn't exist".to_string()),
+        Some(round)
+            if round.finalized
+                || !round.round.is_registration_open(current_epoch) =>
+        {
+            Some(format!(
+                "registrations are closed since epoch {}",
+                round.round.end_epoch
+            ))
+        }
+        Some(_) => {
+            let projects = rpc::query_pgf_funding_round_projects(
+                context.client(),
+                *round_id,
+            )
+            .await?;
+            projects.contains(project).then(|| {
+                format!("the project {} is already registered", project)
+            })
+        }
+    };
+    if let Some(reason) = invalid_reason {
+        edisplay_line!(
+            context.io(),
+            "Invalid registration in pgf funding round {}: {}.",
+            round_id,
+            reason
+        );
+        if !tx_args.force {
+            return Err(Error::from(TxSubmitError::InvalidPgfFundingRound(
+                *round_id, reason,
+            )));
+        }
+    }
+
+    let data = RegisterFundingRoundProject {
+        round_id: *round_id,
+        project: project.clone(),
+    };
+
+    build(
+        context,
+        tx_args,
+        tx_code_path.clone(),
+        data,
+        do_nothing,
+        unshield,
+        fee_amount,
+        &signing_data.fee_payer,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
+/// Craft transaction to vote in a pgf funding round
+pub async fn build_vote_pgf_funding_round(
+    context: &impl Namada,
+    args::VotePgfFundingRound {
+        tx: tx_args,
+        round_id,
+        voter,
+        votes,
+        tx_code_path,
+    }: &args::VotePgfFundingRound,
+) -> Result<(Tx, SigningTxData)> {
+    let default_signer = Some(voter.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx_args,
+        Some(voter.clone()),
+        default_signer,
+    )
+    .await?;
+    let (fee_amount, _, unshield) = validate_fee_and_gen_unshield(
+        context,
+        tx_args,
+        &signing_data.fee_payer,
+    )
+    .await?;
+
+    let mut ballot = BTreeMap::new();
+    for (project, project_votes) in votes {
+        if ballot.insert(project.clone(), *project_votes).is_some() {
+            return Err(Error::from(TxSubmitError::InvalidPgfFundingRound(
+                *round_id,
+                format!("the project {} is voted more than once", project),
+            )));
+        }
+    }
+
+    let current_epoch = rpc::query_epoch(context.client()).await?;
+    let invalid_reason = match rpc::query_pgf_funding_round(
+        context.client(),
+        *round_id,
+    )
+    .await?
+    {
+        None => Some("the funding round doesn't exist".to_string()),
+        Some(round)
+            if round.finalized
+                || !round.round.is_voting_open(current_epoch) =>
+        {
+            Some(format!(
+                "voting is only open from epoch {} to epoch {}",
+                round.round.start_epoch, round.round.end_epoch
+            ))
+        }
+        Some(round) => {
+            let projects = rpc::query_pgf_funding_round_projects(
+                context.client(),
+                *round_id,
+            )
+            .await?;
+            let cost = namada_governance::pgf::rounds::ballot_cost(&ballot);
+            if ballot.is_empty() {
+                Some("no votes are given".to_string())
+            } else if let Some(project) =
+                ballot.keys().find(|project| !projects.contains(project))
+            {
+                Some(format!("the project {} is not registered", project))
+            } else if cost.map_or(true, |cost| cost > round.round.voice_credits)
+            {
+                Some(format!(
+                    "the votes cost more than the {} voice credits available",
+                    round.round.voice_credits
+                ))
+            } else {
+                None
+            }
+        }
+    };
+    if let Some(reason) = invalid_reason {
+        edisplay_line!(
+            context.io(),
+            "Invalid vote in pgf funding round {}: {}.",
+            round_id,
+            reason
+        );
+        if !tx_args.force {
+            return Err(Error::from(TxSubmitError::InvalidPgfFundingRound(
+                *round_id, reason,
+            )));
+        }
+    }
+
+    let data = VoteFundingRound {
+        round_id: *round_id,
+        voter: voter.clone(),
+        votes: ballot,
+    };
+
+    build(
+        context,
+        tx_args,
+        tx_code_path.clone(),
+        data,
+        do_nothing,
+        unshield,
+        fee_amount,
+        &signing_data.fee_payer,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
 /// Submit transaction to unjail a jailed validator
 pub async fn build_unjail_validator(
     context: &impl Namada,
@@ -1901,6 +2181,8 @@ pub async fn build_default_proposal(
         is_offline: _,
         is_pgf_stewards: _,
         is_pgf_funding: _,
+        is_parameter_change: _,
+        is_upgrade: _,
         tx_code_path,
     }: &args::InitProposal,
     proposal: DefaultProposal,
@@ -1949,6 +2231,76 @@ pub async fn build_default_proposal(
     .map(|tx| (tx, signing_data))
 }
 
+/// Build a governance representative change
+pub async fn build_change_representative(
+    context: &impl Namada,
+    args::ChangeRepresentative {
+        tx: tx_args,
+        delegator,
+        representative,
+        tx_code_path,
+    }: &args::ChangeRepresentative,
+) -> Result<(Tx, SigningTxData)> {
+    let default_signer = Some(delegator.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx_args,
+        Some(delegator.clone()),
+        default_signer,
+    )
+    .await?;
+    let (fee_amount, _, unshield) = validate_fee_and_gen_unshield(
+        context,
+        tx_args,
+        &signing_data.fee_payer,
+    )
+    .await?;
+
+    if representative.as_ref() == Some(delegator) {
+        edisplay_line!(
+            context.io(),
+            "The delegator {} cannot be its own representative.",
+            delegator
+        );
+        if !tx_args.force {
+            return Err(Error::Other(
+                "A delegator cannot be its own representative".to_string(),
+            ));
+        }
+    }
+    if rpc::is_validator(context.client(), delegator).await? {
+        edisplay_line!(
+            context.io(),
+            "The address {} is a validator. Validators always vote with their \
+             own voting power.",
+            delegator
+        );
+        if !tx_args.force {
+            return Err(Error::Other(
+                "Validators cannot assign a representative".to_string(),
+            ));
+        }
+    }
+
+    let data = ChangeRepresentativeData {
+        delegator: delegator.clone(),
+        representative: representative.clone(),
+    };
+
+    build(
+        context,
+        tx_args,
+        tx_code_path.clone(),
+        data,
+        do_nothing,
+        unshield,
+        fee_amount,
+        &signing_data.fee_payer,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
 /// Build a proposal vote
 pub async fn build_vote_proposal(
     context: &impl Namada,
@@ -2019,9 +2371,14 @@ pub async fn build_vote_proposal(
     .cloned()
     .collect::<Vec<Address>>();
 
-    if delegations.is_empty() {
+    if delegations.is_empty()
+        && rpc::query_represented_delegators(context.client(), voter)
+            .await?
+            .is_empty()
+    {
         return Err(Error::Other(
-            "Voter address must have delegations".to_string(),
+            "Voter address must have delegations or represent delegators"
+                .to_string(),
         ));
     }
 
@@ -2055,6 +2412,8 @@ pub async fn build_pgf_funding_proposal(
         is_offline: _,
         is_pgf_stewards: _,
         is_pgf_funding: _,
+        is_parameter_change: _,
+        is_upgrade: _,
         tx_code_path,
     }: &args::InitProposal,
     proposal: PgfFundingProposal,
@@ -2104,6 +2463,8 @@ pub async fn build_pgf_stewards_proposal(
         is_offline: _,
         is_pgf_stewards: _,
         is_pgf_funding: _,
+        is_parameter_change: _,
+        is_upgrade: _,
         tx_code_path,
     }: &args::InitProposal,
     proposal: PgfStewardProposal,
@@ -2145,11 +2506,120 @@ pub async fn build_pgf_stewards_proposal(
     .map(|tx| (tx, signing_data))
 }
 
+/// Build a parameter change proposal governance
+pub async fn build_parameter_change_proposal(
+    context: &impl Namada,
+    args::InitProposal {
+        tx,
+        proposal_data: _,
+        is_offline: _,
+        is_pgf_stewards: _,
+        is_pgf_funding: _,
+        is_parameter_change: _,
+        is_upgrade: _,
+        tx_code_path,
+    }: &args::InitProposal,
+    proposal: ParameterChangeProposal,
+) -> Result<(Tx, SigningTxData)> {
+    let default_signer = Some(proposal.proposal.author.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx,
+        Some(proposal.proposal.author.clone()),
+        default_signer,
+    )
+    .await?;
+    let (fee_amount, _updated_balance, unshield) =
+        validate_fee_and_gen_unshield(context, tx, &signing_data.fee_payer)
+            .await?;
+
+    let init_proposal_data = InitProposalData::try_from(proposal.clone())
+        .map_err(|e| TxSubmitError::InvalidProposal(e.to_string()))?;
+
+    let add_section = |tx: &mut Tx, data: &mut InitProposalData| {
+        let (_, extra_section_hash) =
+            tx.add_extra_section(proposal_to_vec(proposal.proposal)?, None);
+        data.content = extra_section_hash;
+        Ok(())
+    };
+
+    build(
+        context,
+        tx,
+        tx_code_path.clone(),
+        init_proposal_data,
+        add_section,
+        unshield,
+        fee_amount,
+        &signing_data.fee_payer,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
+/// Build a chain upgrade proposal governance
+pub async fn build_upgrade_proposal(
+    context: &impl Namada,
+    args::InitProposal {
+        tx,
+        proposal_data: _,
+        is_offline: _,
+        is_pgf_stewards: _,
+        is_pgf_funding: _,
+        is_parameter_change: _,
+        is_upgrade: _,
+        tx_code_path,
+    }: &args::InitProposal,
+    proposal: UpgradeProposal,
+) -> Result<(Tx, SigningTxData)> {
+    let default_signer = Some(proposal.proposal.author.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx,
+        Some(proposal.proposal.author.clone()),
+        default_signer,
+    )
+    .await?;
+    let (fee_amount, _updated_balance, unshield) =
+        validate_fee_and_gen_unshield(context, tx, &signing_data.fee_payer)
+            .await?;
+
+    let init_proposal_data = InitProposalData::try_from(proposal.clone())
+        .map_err(|e| TxSubmitError::InvalidProposal(e.to_string()))?;
+
+    let add_section = |tx: &mut Tx, data: &mut InitProposalData| {
+        let (_, extra_section_hash) =
+            tx.add_extra_section(proposal_to_vec(proposal.proposal)?, None);
+        data.content = extra_section_hash;
+        Ok(())
+    };
+
+    build(
+        context,
+        tx,
+        tx_code_path.clone(),
+        init_proposal_data,
+        add_section,
+        unshield,
+        fee_amount,
+        &signing_data.fee_payer,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
 /// Submit an IBC transfer
 pub async fn build_ibc_transfer(
     context: &impl Namada,
     args: &args::TxIbcTransfer,
 ) -> Result<(Tx, SigningTxData, Option<Epoch>)> {
+    // The MASP transaction is also embedded in the IBC message, which would
+    // no longer match it once the spends get authorized separately
+    if args.source.proof_generation_key().is_some() {
+        return Err(Error::Other(
+            "IBC transfers require a spending key as the source".to_string(),
+        ));
+    }
     let source = args.source.effective_address();
     let signing_data = signing::aux_signing_data(
         context,
@@ -2465,7 +2935,7 @@ pub async fn build_transfer<N: Namada>(
     args: &mut args::TxTransfer,
 ) -> Result<(Tx, SigningTxData, Option<Epoch>)> {
     let default_signer = Some(args.source.effective_address());
-    let signing_data = signing::aux_signing_data(
+    let mut signing_data = signing::aux_signing_data(
         context,
         &args.tx,
         Some(args.source.effective_address()),
@@ -2542,6 +3012,12 @@ pub async fn build_transfer<N: Namada>(
     )
     .await?;
     let shielded_tx_epoch = shielded_parts.as_ref().map(|trans| trans.0.epoch);
+    // The spends built with a proof generation key get signed separately
+    if let Some((shielded_transfer, _)) = &shielded_parts {
+        signing_data
+            .spend_randomizers
+            .clone_from(&shielded_transfer.spend_randomizers);
+    }
 
     // Construct the corresponding transparent Transfer object
     let transfer = token::Transfer {
@@ -2562,6 +3038,7 @@ pub async fn build_transfer<N: Namada>(
                 masp_tx,
                 metadata,
                 epoch: _,
+                spend_randomizers: _,
             },
             asset_types,
         )) = shielded_parts
@@ -2655,6 +3132,7 @@ pub async fn build_init_account(
         tx_code_path,
         public_keys,
         threshold,
+        vesting,
     }: &args::TxInitAccount,
 ) -> Result<(Tx, SigningTxData)> {
     let signing_data =
@@ -2681,11 +3159,32 @@ pub async fn build_init_account(
         }
     };
 
+    let vesting = vesting
+        .as_ref()
+        .map(|vesting| {
+            let vesting: Vesting =
+                serde_json::from_slice(vesting).map_err(|err| {
+                    TxSubmitError::InvalidVesting(err.to_string())
+                })?;
+            vesting.validate().map_err(TxSubmitError::InvalidVesting)?;
+            Ok::<_, TxSubmitError>(vesting)
+        })
+        .transpose()?;
+    // The vesting is only enforced by the user VP
+    if vesting.is_some()
+        && vp_code_hash != query_wasm_code_hash(context, VP_USER_WASM).await?
+    {
+        return Err(Error::from(TxSubmitError::InvalidVesting(format!(
+            "a vesting account must use the {VP_USER_WASM} VP"
+        ))));
+    }
+
     let data = InitAccount {
         public_keys: public_keys.clone(),
         // We will add the hash inside the add_code_hash function
         vp_code_hash: Hash::zero(),
         threshold,
+        vesting,
     };
 
     let add_code_hash = |tx: &mut Tx, data: &mut InitAccount| {
@@ -17,6 +17,7 @@ use namada_core::key::*;
 use namada_core::masp::{
     ExtendedSpendingKey, ExtendedViewingKey, PaymentAddress,
 };
+use namada_core::storage::BlockHeight;
 pub use pre_genesis::gen_key_to_store;
 use rand::CryptoRng;
 use rand_core::RngCore;
@@ -389,6 +390,12 @@ impl<U> Wallet<U> {
         })
     }
 
+    /// Find the birthday of the shielded key with the given alias in the
+    /// wallet and return it
+    pub fn find_birthday(&self, alias: impl AsRef<str>) -> Option<BlockHeight> {
+        self.store.find_birthday(alias.as_ref()).copied()
+    }
+
     /// Find the payment address with the given alias in the wallet and return
     /// it
     pub fn find_payment_addr(
@@ -456,6 +463,15 @@ impl<U> Wallet<U> {
             .collect()
     }
 
+    /// Get the birthdays of all shielded keys by their alias
+    pub fn get_birthdays(&self) -> HashMap<String, BlockHeight> {
+        self.store
+            .get_birthdays()
+            .iter()
+            .map(|(alias, value)| (alias.into(), *value))
+            .collect()
+    }
+
     /// Get all known viewing keys by their alias
     pub fn get_spending_keys(
         &self,
@@ -1010,6 +1026,18 @@ impl<U: WalletIo> Wallet<U> {
             .map(Into::into)
     }
 
+    /// Set the birthday of the shielded key with the given alias, i.e. the
+    /// block height from which shielded sync starts scanning for its notes
+    pub fn insert_birthday(
+        &mut self,
+        alias: String,
+        birthday: BlockHeight,
+    ) -> Option<String> {
+        self.store
+            .insert_birthday(alias.into(), birthday)
+            .map(Into::into)
+    }
+
     /// Insert a spending key into the wallet under the given alias
     pub fn insert_spending_key(
         &mut self,
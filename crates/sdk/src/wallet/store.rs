@@ -12,6 +12,7 @@ use namada_core::key::*;
 use namada_core::masp::{
     ExtendedSpendingKey, ExtendedViewingKey, PaymentAddress,
 };
+use namada_core::storage::BlockHeight;
 use serde::{Deserialize, Serialize};
 use zeroize::Zeroizing;
 
@@ -70,6 +71,10 @@ pub struct Store {
     public_keys: BTreeMap<Alias, common::PublicKey>,
     /// Known derivation paths
     derivation_paths: BTreeMap<Alias, DerivationPath>,
+    /// Block heights before which the shielded keys of the given aliases
+    /// cannot have received any notes
+    #[serde(default)]
+    birthdays: BTreeMap<Alias, BlockHeight>,
     /// Namada address book
     addresses: BiBTreeMap<Alias, Address>,
     /// Known mappings of public key hashes to their aliases in the `keys`
@@ -142,6 +147,14 @@ impl Store {
         self.view_keys.get(&alias.into())
     }
 
+    /// Find the birthday of the shielded key with the given alias
+    pub fn find_birthday(
+        &self,
+        alias: impl AsRef<str>,
+    ) -> Option<&BlockHeight> {
+        self.birthdays.get(&alias.into())
+    }
+
     /// Find the payment address with the given alias and return it
     pub fn find_payment_addr(
         &self,
@@ -253,6 +266,11 @@ impl Store {
         &self.view_keys
     }
 
+    /// Get the birthdays of all shielded keys by their alias.
+    pub fn get_birthdays(&self) -> &BTreeMap<Alias, BlockHeight> {
+        &self.birthdays
+    }
+
     /// Get all known spending keys by their alias.
     pub fn get_spending_keys(
         &self,
@@ -435,6 +453,20 @@ impl Store {
         Some(alias)
     }
 
+    /// Set the birthday of the shielded key with the given alias. Returns
+    /// `None` if the wallet holds no viewing key under this alias.
+    pub fn insert_birthday(
+        &mut self,
+        alias: Alias,
+        birthday: BlockHeight,
+    ) -> Option<Alias> {
+        if !self.view_keys.contains_key(&alias) {
+            return None;
+        }
+        self.birthdays.insert(alias.clone(), birthday);
+        Some(alias)
+    }
+
     /// Insert public keys
     pub fn insert_public_key<U: WalletIo>(
         &mut self,
@@ -585,6 +617,7 @@ impl Store {
         self.pkhs.retain(|_key, val| val != alias);
         self.public_keys.remove(alias);
         self.derivation_paths.remove(alias);
+        self.birthdays.remove(alias);
     }
 
     /// Extend this store from another store (typically pre-genesis).
@@ -597,6 +630,7 @@ impl Store {
             secret_keys,
             public_keys,
             derivation_paths,
+            birthdays,
             addresses,
             pkhs,
             validator_data: _,
@@ -608,6 +642,7 @@ impl Store {
         secret_keys.extend(store.secret_keys);
         public_keys.extend(store.public_keys);
         derivation_paths.extend(store.derivation_paths);
+        birthdays.extend(store.birthdays);
         addresses.extend(store.addresses);
         pkhs.extend(store.pkhs);
         address_vp_types.extend(store.address_vp_types);
@@ -867,6 +902,46 @@ mod test_wallet {
         assert_eq!(&sk.to_string(), &sk_hard.to_string());
     }
 
+    #[test]
+    fn birthdays_follow_their_shielded_key() {
+        use crate::wallet::fs::FsWalletUtils;
+
+        let mut store = Store::default();
+        let alias = Alias::from("alice");
+        let birthday = BlockHeight(10);
+        // a birthday needs a viewing key to belong to
+        assert!(store.insert_birthday(alias.clone(), birthday).is_none());
+
+        let spend_key = zip32::ExtendedSpendingKey::master(&[0; 32]).into();
+        store
+            .insert_spending_key::<FsWalletUtils>(
+                alias.clone(),
+                spend_key,
+                None,
+                None,
+                true,
+            )
+            .expect("Inserting the spending key cannot fail");
+        assert!(store.insert_birthday(alias.clone(), birthday).is_some());
+        assert_eq!(store.find_birthday("alice"), Some(&birthday));
+
+        // overwriting the key drops its birthday
+        store
+            .insert_spending_key::<FsWalletUtils>(
+                alias.clone(),
+                spend_key,
+                None,
+                None,
+                true,
+            )
+            .expect("Inserting the spending key cannot fail");
+        assert_eq!(store.find_birthday("alice"), None);
+
+        assert!(store.insert_birthday(alias.clone(), birthday).is_some());
+        store.remove_alias(&alias);
+        assert_eq!(store.find_birthday("alice"), None);
+    }
+
     fn do_test_gen_sk_from_seed_and_derivation_path(
         scheme: SchemeType,
         seed: &str,
@@ -3,9 +3,11 @@
 use namada_core::address::{Address, MASP};
 #[cfg(any(feature = "multicore", test))]
 use namada_core::borsh::BorshSerializeExt;
+use namada_core::borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use namada_core::dec::Dec;
 #[cfg(any(feature = "multicore", test))]
 use namada_core::hash::Hash;
+use namada_core::storage::Epoch;
 use namada_core::uint::Uint;
 use namada_parameters as parameters;
 use namada_storage::{StorageRead, StorageWrite};
@@ -14,24 +16,62 @@ use namada_trans_token::inflation::{
 };
 use namada_trans_token::storage_key::{balance_key, minted_balance_key};
 use namada_trans_token::{read_denom, Amount, DenominatedAmount, Denomination};
+use serde::{Deserialize, Serialize};
 
 #[cfg(any(feature = "multicore", test))]
 use crate::storage_key::{masp_assets_hash_key, masp_token_map_key};
 use crate::storage_key::{
     masp_kd_gain_key, masp_kp_gain_key, masp_last_inflation_key,
     masp_last_locked_amount_key, masp_locked_amount_target_key,
-    masp_max_reward_rate_key,
+    masp_max_reward_rate_key, masp_rewards_history_key,
 };
 use crate::WithConversionState;
 
+/// The maximum number of past epochs whose MASP rewards are kept for each
+/// token
+pub const MASP_REWARDS_HISTORY_LEN: usize = 100;
+
+/// The MASP rewards of a token for an epoch, as computed by the PD-controller
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct MaspRewards {
+    /// Units of the native token rewarded for every `precision` units of the
+    /// token held in the shielded pool
+    pub noterized_inflation: u128,
+    /// The precision of the rewards of the token
+    pub precision: u128,
+    /// Total amount of the native token handed out as rewards
+    pub inflation: Amount,
+    /// Amount of the token locked in the shielded pool
+    pub locked_amount: Amount,
+}
+
+impl MaspRewards {
+    /// Compute the reward in the native token earned over the epoch by the
+    /// given amount of the token
+    pub fn reward(&self, amount: Amount) -> Amount {
+        (amount * (self.noterized_inflation, self.precision)).0
+    }
+}
+
 /// Compute the precision of MASP rewards for the given token. This function
 /// must be a non-zero constant for a given token.
 pub fn calculate_masp_rewards_precision<S>(
-    storage: &mut S,
+    storage: &S,
     addr: &Address,
 ) -> namada_storage::Result<(u128, Denomination)>
 where
-    S: StorageWrite + StorageRead,
+    S: StorageRead,
 {
     let denomination =
         read_denom(storage, addr)?.expect("failed to read token denomination");
@@ -50,12 +90,14 @@ where
 
 /// Compute the MASP rewards by applying the PD-controller to the genesis
 /// parameters and the last inflation and last locked rewards ratio values.
-pub fn calculate_masp_rewards<S>(
-    storage: &mut S,
+/// Nothing is written to storage, so this can be used to project the rewards
+/// of the next epoch.
+pub fn compute_masp_rewards<S>(
+    storage: &S,
     token: &Address,
-) -> namada_storage::Result<((u128, u128), Denomination)>
+) -> namada_storage::Result<(MaspRewards, Denomination)>
 where
-    S: StorageWrite + StorageRead,
+    S: StorageRead,
 {
     let (precision, denomination) =
         calculate_masp_rewards_precision(storage, token)?;
@@ -150,8 +192,6 @@ where
         0,
     )
     .unwrap();
-    let denom_amount = DenominatedAmount::new(inflation_amount, denomination);
-    tracing::info!("MASP inflation for {token} is {denom_amount}");
 
     tracing::debug!(
         "Controller, call: total_in_masp {:?}, total_native_tokens {:?}, \
@@ -174,15 +214,67 @@ where
     tracing::debug!("precision {}", precision);
     tracing::debug!("Noterized inflation: {}", noterized_inflation);
 
+    Ok((
+        MaspRewards {
+            noterized_inflation,
+            precision,
+            inflation: inflation_amount,
+            locked_amount: total_tokens_in_masp,
+        },
+        denomination,
+    ))
+}
+
+/// Compute the MASP rewards of the given token with [`compute_masp_rewards`]
+/// and record them as the last inflation and locked amount values, and in the
+/// rewards history of the token.
+pub fn calculate_masp_rewards<S>(
+    storage: &mut S,
+    token: &Address,
+) -> namada_storage::Result<((u128, u128), Denomination)>
+where
+    S: StorageWrite + StorageRead,
+{
+    let (rewards, denomination) = compute_masp_rewards(storage, token)?;
+    let denom_amount = DenominatedAmount::new(rewards.inflation, denomination);
+    tracing::info!("MASP inflation for {token} is {denom_amount}");
+
     // Is it fine to write the inflation rate, this is accurate,
     // but we should make sure the return value's ratio matches
     // this new inflation rate in 'update_allowed_conversions',
     // otherwise we will have an inaccurate view of inflation
-    storage.write(&masp_last_inflation_key(token), inflation_amount)?;
+    storage.write(&masp_last_inflation_key(token), rewards.inflation)?;
+
+    storage
+        .write(&masp_last_locked_amount_key(token), rewards.locked_amount)?;
+
+    // Keep the rewards of the last epochs for clients to query
+    let epoch = storage.get_block_epoch()?;
+    let mut history = read_masp_rewards_history(storage, token)?;
+    history.push((epoch, rewards));
+    if history.len() > MASP_REWARDS_HISTORY_LEN {
+        history.drain(..history.len() - MASP_REWARDS_HISTORY_LEN);
+    }
+    storage.write(&masp_rewards_history_key(token), history)?;
 
-    storage.write(&masp_last_locked_amount_key(token), total_tokens_in_masp)?;
+    Ok((
+        (rewards.noterized_inflation, rewards.precision),
+        denomination,
+    ))
+}
 
-    Ok(((noterized_inflation, precision), denomination))
+/// Read the MASP rewards of the given token in the last epochs, at most
+/// [`MASP_REWARDS_HISTORY_LEN`] of them, from oldest to newest.
+pub fn read_masp_rewards_history<S>(
+    storage: &S,
+    token: &Address,
+) -> namada_storage::Result<Vec<(Epoch, MaspRewards)>>
+where
+    S: StorageRead,
+{
+    Ok(storage
+        .read(&masp_rewards_history_key(token))?
+        .unwrap_or_default())
 }
 
 // This is only enabled when "wasm-runtime" is on, because we're using rayon
@@ -215,7 +307,6 @@ where
     use masp_primitives::sapling::Node;
     use masp_primitives::transaction::components::I128Sum as MaspAmount;
     use namada_core::masp::encode_asset_type;
-    use namada_core::storage::Epoch;
     use namada_storage::ResultExt;
     use namada_trans_token::{MaspDigitPos, NATIVE_MAX_DECIMAL_PLACES};
     use rayon::iter::{
@@ -569,6 +660,56 @@ mod tests {
         const ROUNDS: usize = 10;
 
         let mut s = TestStorage::default();
+        init_storage(&mut s, initial_balance, masp_locked_ratio);
+
+        for i in 0..ROUNDS {
+            println!("Round {i}");
+            update_allowed_conversions(&mut s).unwrap();
+            println!();
+            println!();
+        }
+    }
+
+    #[test]
+    fn test_masp_rewards_projection() {
+        let mut s = TestStorage::default();
+        init_storage(
+            &mut s,
+            Amount::native_whole(1_000_000),
+            Dec::from_str("0.25").unwrap(),
+        );
+        let token = address::testing::btc();
+
+        // The projection matches the rewards that are then handed out
+        let (projection, _) = compute_masp_rewards(&s, &token).unwrap();
+        let ((noterized_inflation, precision), _) =
+            calculate_masp_rewards(&mut s, &token).unwrap();
+        assert_eq!(projection.noterized_inflation, noterized_inflation);
+        assert_eq!(projection.precision, precision);
+        let last_inflation: Amount =
+            s.read(&masp_last_inflation_key(&token)).unwrap().unwrap();
+        assert_eq!(projection.inflation, last_inflation);
+
+        // The rewards are recorded in the history, which stays bounded
+        let epoch = s.get_block_epoch().unwrap();
+        assert_eq!(
+            read_masp_rewards_history(&s, &token).unwrap(),
+            vec![(epoch, projection)]
+        );
+        for _ in 0..MASP_REWARDS_HISTORY_LEN {
+            calculate_masp_rewards(&mut s, &token).unwrap();
+        }
+        assert_eq!(
+            read_masp_rewards_history(&s, &token).unwrap().len(),
+            MASP_REWARDS_HISTORY_LEN
+        );
+    }
+
+    fn init_storage(
+        s: &mut TestStorage,
+        initial_balance: Amount,
+        masp_locked_ratio: Dec,
+    ) {
         let params = Parameters {
             max_tx_bytes: 1024 * 1024,
             epoch_duration: EpochDuration {
@@ -593,7 +734,7 @@ mod tests {
         // Initialize the state
         {
             // Parameters
-            namada_parameters::init_storage(&params, &mut s).unwrap();
+            namada_parameters::init_storage(&params, s).unwrap();
 
             // Tokens
             let token_params = ShieldedParams {
@@ -604,11 +745,11 @@ mod tests {
             };
 
             for (token_addr, (alias, denom)) in tokens() {
-                namada_trans_token::write_params(&mut s, &token_addr).unwrap();
-                crate::write_params(&token_params, &mut s, &token_addr, &denom)
+                namada_trans_token::write_params(s, &token_addr).unwrap();
+                crate::write_params(&token_params, s, &token_addr, &denom)
                     .unwrap();
 
-                write_denom(&mut s, &token_addr, denom).unwrap();
+                write_denom(s, &token_addr, denom).unwrap();
 
                 // Write a minted token balance
                 let total_token_balance = initial_balance;
@@ -630,13 +771,6 @@ mod tests {
                 s.write(&token_map_key, token_map).unwrap();
             }
         }
-
-        for i in 0..ROUNDS {
-            println!("Round {i}");
-            update_allowed_conversions(&mut s).unwrap();
-            println!();
-            println!();
-        }
     }
 
     pub fn tokens() -> HashMap<Address, (&'static str, Denomination)> {
@@ -9,6 +9,7 @@ use std::str::FromStr;
 
 use namada_core::borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use namada_core::dec::Dec;
+use namada_core::token::Amount;
 pub use namada_storage::conversion_state::{
     ConversionState, WithConversionState,
 };
@@ -52,3 +53,134 @@ impl Default for ShieldedParams {
         }
     }
 }
+
+/// Governance-set limits on the amount of a token held in and moved through
+/// the shielded pool. Unset limits are not enforced.
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Deserialize,
+    Serialize,
+)]
+pub struct MaspTokenLimits {
+    /// Maximum balance of the token that can be held in the shielded pool
+    pub shielded_cap: Option<Amount>,
+    /// Maximum net amount of the token that can be shielded in an epoch
+    pub deposit_limit: Option<Amount>,
+    /// Maximum net amount of the token that can be unshielded in an epoch
+    pub withdraw_limit: Option<Amount>,
+}
+
+/// The amounts of a token that can still be moved in and out of the shielded
+/// pool under its [`MaspTokenLimits`]. A `None` stands for no limit.
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Deserialize,
+    Serialize,
+)]
+pub struct MaspTokenCapacity {
+    /// Amount that can be added before the shielded cap is reached
+    pub shielded: Option<Amount>,
+    /// Amount that can still be shielded in the current epoch
+    pub deposit: Option<Amount>,
+    /// Amount that can still be unshielded in the current epoch
+    pub withdraw: Option<Amount>,
+}
+
+impl MaspTokenLimits {
+    /// Compute the remaining capacity given the current balance of the
+    /// shielded pool and its balance at the start of the current epoch. The
+    /// flow limits apply to the net flow since the start of the epoch, so
+    /// unshielding frees deposit capacity and vice versa.
+    pub fn capacity(
+        &self,
+        balance: Amount,
+        epoch_start_balance: Amount,
+    ) -> MaspTokenCapacity {
+        let inflow =
+            balance.checked_sub(epoch_start_balance).unwrap_or_default();
+        let outflow =
+            epoch_start_balance.checked_sub(balance).unwrap_or_default();
+        let remaining = |limit: Amount, freed: Amount, used: Amount| {
+            limit
+                .checked_add(freed)
+                .and_then(|limit| limit.checked_sub(used))
+                .unwrap_or_default()
+        };
+        MaspTokenCapacity {
+            shielded: self
+                .shielded_cap
+                .map(|cap| cap.checked_sub(balance).unwrap_or_default()),
+            deposit: self
+                .deposit_limit
+                .map(|limit| remaining(limit, outflow, inflow)),
+            withdraw: self
+                .withdraw_limit
+                .map(|limit| remaining(limit, inflow, outflow)),
+        }
+    }
+}
+
+impl MaspTokenCapacity {
+    /// Check if the given amount can be shielded
+    pub fn can_deposit(&self, amount: Amount) -> bool {
+        self.shielded.map_or(true, |shielded| amount <= shielded)
+            && self.deposit.map_or(true, |deposit| amount <= deposit)
+    }
+
+    /// Check if the given amount can be unshielded
+    pub fn can_withdraw(&self, amount: Amount) -> bool {
+        self.withdraw.map_or(true, |withdraw| amount <= withdraw)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_masp_token_capacity() {
+        let limits = MaspTokenLimits {
+            shielded_cap: Some(Amount::native_whole(100)),
+            deposit_limit: Some(Amount::native_whole(20)),
+            withdraw_limit: None,
+        };
+        // 15 were shielded since the start of the epoch
+        let capacity =
+            limits.capacity(Amount::native_whole(85), Amount::native_whole(70));
+        assert_eq!(capacity.shielded, Some(Amount::native_whole(15)));
+        assert_eq!(capacity.deposit, Some(Amount::native_whole(5)));
+        assert_eq!(capacity.withdraw, None);
+        assert!(capacity.can_deposit(Amount::native_whole(5)));
+        assert!(!capacity.can_deposit(Amount::native_whole(6)));
+        assert!(capacity.can_withdraw(Amount::native_whole(85)));
+
+        // Unshielding frees up deposit capacity for the epoch
+        let capacity =
+            limits.capacity(Amount::native_whole(60), Amount::native_whole(70));
+        assert_eq!(capacity.shielded, Some(Amount::native_whole(40)));
+        assert_eq!(capacity.deposit, Some(Amount::native_whole(30)));
+        assert!(!capacity.can_deposit(Amount::native_whole(31)));
+
+        // The cap is exhausted regardless of the flow limits
+        let capacity = limits
+            .capacity(Amount::native_whole(100), Amount::native_whole(100));
+        assert_eq!(capacity.shielded, Some(Amount::zero()));
+        assert!(!capacity.can_deposit(Amount::native_whole(1)));
+        assert!(capacity.can_deposit(Amount::zero()));
+    }
+}
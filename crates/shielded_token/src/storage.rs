@@ -1,13 +1,15 @@
-use namada_core::address::Address;
+use namada_core::address::{self, Address};
+use namada_core::storage::{DbKeySeg, Epoch};
 use namada_core::token;
 use namada_core::token::Amount;
 use namada_core::uint::Uint;
 use namada_storage as storage;
 use namada_storage::{StorageRead, StorageWrite};
+use namada_trans_token::storage_key::balance_key;
 use storage::ResultExt;
 
 use crate::storage_key::*;
-use crate::ShieldedParams;
+use crate::{MaspTokenCapacity, MaspTokenLimits, ShieldedParams};
 
 /// Initialize parameters for the token in storage during the genesis block.
 pub fn write_params<S>(
@@ -37,3 +39,71 @@ where
     storage.write(&masp_locked_amount_target_key(address), raw_target)?;
     Ok(())
 }
+
+/// Read the MASP limits of the given token, if any were set by governance.
+pub fn read_masp_token_limits<S>(
+    storage: &S,
+    token: &Address,
+) -> storage::Result<Option<MaspTokenLimits>>
+where
+    S: StorageRead,
+{
+    storage.read(&masp_token_limits_key(token))
+}
+
+/// Read the remaining MASP capacity of the given token in the current epoch.
+/// Returns `None` if the token has no limits.
+pub fn read_masp_token_capacity<S>(
+    storage: &S,
+    token: &Address,
+) -> storage::Result<Option<MaspTokenCapacity>>
+where
+    S: StorageRead,
+{
+    let Some(limits) = read_masp_token_limits(storage, token)? else {
+        return Ok(None);
+    };
+    let balance: Amount = storage
+        .read(&balance_key(token, &address::MASP))?
+        .unwrap_or_default();
+    let epoch = storage.get_block_epoch()?;
+    // Without a snapshot for the current epoch, no flows were recorded yet
+    let epoch_start_balance = match storage
+        .read::<(Epoch, Amount)>(&masp_epoch_start_balance_key(token))?
+    {
+        Some((snapshot_epoch, amount)) if snapshot_epoch == epoch => amount,
+        _ => balance,
+    };
+    Ok(Some(limits.capacity(balance, epoch_start_balance)))
+}
+
+/// Record the MASP balances of all the tokens with limits at the start of a
+/// new epoch, against which the epoch's flow limits are checked.
+pub fn update_masp_epoch_start_balances<S>(
+    storage: &mut S,
+) -> storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let tokens = storage::iter_prefix::<MaspTokenLimits>(
+        storage,
+        &masp_token_limits_prefix(),
+    )?
+    .filter_map(|entry| match entry {
+        Ok((key, _)) => match key.segments.last() {
+            Some(DbKeySeg::AddressSeg(token)) => Some(Ok(token.clone())),
+            _ => None,
+        },
+        Err(err) => Some(Err(err)),
+    })
+    .collect::<storage::Result<Vec<Address>>>()?;
+    let epoch = storage.get_block_epoch()?;
+    for token in tokens {
+        let balance: Amount = storage
+            .read(&balance_key(&token, &address::MASP))?
+            .unwrap_or_default();
+        storage
+            .write(&masp_epoch_start_balance_key(&token), (epoch, balance))?;
+    }
+    Ok(())
+}
@@ -23,8 +23,15 @@ pub const MASP_TOKEN_MAP_KEY: &str = "tokens";
 pub const MASP_ASSETS_HASH_KEY: &str = "assets_hash";
 /// Last calculated inflation value handed out
 pub const MASP_LAST_INFLATION_KEY: &str = "last_inflation";
+/// The rewards handed out in the last epochs
+pub const MASP_REWARDS_HISTORY_KEY: &str = "rewards_history";
 /// The last locked amount
 pub const MASP_LAST_LOCKED_AMOUNT_KEY: &str = "last_locked_amount";
+/// Key segment prefix for the governance-set limits of a token
+pub const MASP_TOKEN_LIMITS_PREFIX: &str = "token_limits";
+/// Key segment prefix for the shielded pool balance of a token at the start of
+/// the epoch
+pub const MASP_EPOCH_START_BALANCE_PREFIX: &str = "epoch_start_balance";
 /// The key for the nominal proportional gain of a shielded pool for a given
 /// asset
 pub const MASP_KP_GAIN_KEY: &str = "proportional_gain";
@@ -69,6 +76,12 @@ pub fn masp_last_inflation_key(token_address: &Address) -> storage::Key {
         .with_segment(MASP_LAST_INFLATION_KEY.to_owned())
 }
 
+/// Obtain the storage key for the rewards history of a token
+pub fn masp_rewards_history_key(token_address: &Address) -> storage::Key {
+    parameter_prefix(token_address)
+        .with_segment(MASP_REWARDS_HISTORY_KEY.to_owned())
+}
+
 /// Check if the given storage key is a masp key
 pub fn is_masp_key(key: &storage::Key) -> bool {
     matches!(&key.segments[..],
@@ -95,6 +108,21 @@ pub fn is_masp_allowed_key(key: &storage::Key) -> bool {
     }
 }
 
+/// Check if the given storage key is a masp token limits key and return the
+/// token it belongs to
+pub fn is_masp_token_limits_key(key: &storage::Key) -> Option<&Address> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::AddressSeg(token),
+        ] if *addr == address::MASP && prefix == MASP_TOKEN_LIMITS_PREFIX => {
+            Some(token)
+        }
+        _ => None,
+    }
+}
+
 /// Check if the given storage key is a masp tx pin key
 pub fn is_masp_tx_pin_key(key: &storage::Key) -> bool {
     matches!(&key.segments[..],
@@ -164,3 +192,27 @@ pub fn masp_assets_hash_key() -> storage::Key {
         .push(&MASP_ASSETS_HASH_KEY.to_owned())
         .expect("Cannot obtain a storage key")
 }
+
+/// Get the prefix of the masp token limits keys
+pub fn masp_token_limits_prefix() -> storage::Key {
+    storage::Key::from(address::MASP.to_db_key())
+        .push(&MASP_TOKEN_LIMITS_PREFIX.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the key for the masp limits of the given token
+pub fn masp_token_limits_key(token_addr: &Address) -> storage::Key {
+    masp_token_limits_prefix()
+        .push(token_addr)
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the key for the masp balance of the given token at the start of the
+/// epoch
+pub fn masp_epoch_start_balance_key(token_addr: &Address) -> storage::Key {
+    storage::Key::from(address::MASP.to_db_key())
+        .push(&MASP_EPOCH_START_BALANCE_PREFIX.to_owned())
+        .expect("Cannot obtain a storage key")
+        .push(token_addr)
+        .expect("Cannot obtain a storage key")
+}
@@ -2,6 +2,7 @@ use std::fmt::Debug;
 
 use namada_core::address::EstablishedAddressGen;
 use namada_core::hash::{Error as HashError, Hash};
+use namada_core::masp::CompactBlock;
 use namada_core::storage::{
     BlockHash, BlockHeight, BlockResults, DbColFam, Epoch, Epochs,
     EthEventsQueue, Header, Key,
@@ -147,6 +148,13 @@ pub trait DB: Debug {
     /// Read the block header with the given height from the DB
     fn read_block_header(&self, height: BlockHeight) -> Result<Option<Header>>;
 
+    /// Read the compact MASP data of the block with the given height from the
+    /// DB, if it has been indexed
+    fn read_masp_compact_block(
+        &self,
+        height: BlockHeight,
+    ) -> Result<Option<CompactBlock>>;
+
     /// Read the merkle tree stores with the given epoch. If a store_type is
     /// given, it reads only the the specified tree. Otherwise, it reads all
     /// trees.
@@ -204,6 +212,10 @@ pub trait DB: Debug {
         persist_diffs: bool,
     ) -> Result<i64>;
 
+    /// Write the compact MASP data of a block to the DB index, replacing the
+    /// one previously written at the same height, if any
+    fn write_masp_compact_block(&mut self, block: &CompactBlock) -> Result<()>;
+
     /// Start write batch.
     fn batch() -> Self::WriteBatch;
 
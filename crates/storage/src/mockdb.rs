@@ -9,6 +9,7 @@ use std::str::FromStr;
 use itertools::Either;
 use namada_core::borsh::{BorshDeserialize, BorshSerializeExt};
 use namada_core::hash::Hash;
+use namada_core::masp::CompactBlock;
 use namada_core::storage::{
     BlockHeight, BlockResults, DbColFam, Epoch, EthEventsQueue, Header, Key,
     KeySeg, KEY_SEGMENT_SEPARATOR,
@@ -376,6 +377,17 @@ impl DB for MockDB {
         }
     }
 
+    fn read_masp_compact_block(
+        &self,
+        height: BlockHeight,
+    ) -> Result<Option<CompactBlock>> {
+        let key = format!("masp_compact/{}", height.raw());
+        match self.0.borrow().get(&key) {
+            Some(bytes) => Ok(Some(decode(bytes).map_err(Error::CodingError)?)),
+            None => Ok(None),
+        }
+    }
+
     fn read_merkle_tree_stores(
         &self,
         epoch: Epoch,
@@ -501,6 +513,12 @@ impl DB for MockDB {
         )
     }
 
+    fn write_masp_compact_block(&mut self, block: &CompactBlock) -> Result<()> {
+        let key = format!("masp_compact/{}", block.height.raw());
+        self.0.borrow_mut().insert(key, encode(block));
+        Ok(())
+    }
+
     fn batch() -> Self::WriteBatch {
         MockDBWriteBatch
     }
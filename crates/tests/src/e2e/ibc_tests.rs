@@ -1813,13 +1813,15 @@ fn propose_funding(
             target: bertha.to_string(),
             port_id: src_port_id.clone(),
             channel_id: src_channel_id.clone(),
-        })],
+        })
+        .into()],
         retro: vec![PGFTarget::Ibc(PGFIbcTarget {
             amount: Amount::from_u64(5),
             target: christel.to_string(),
             port_id: src_port_id.clone(),
             channel_id: src_channel_id.clone(),
         })],
+        rounds: vec![],
     };
 
     std::env::set_var(ENV_VAR_CHAIN_ID, test_a.net.chain_id.to_string());
@@ -2466,11 +2466,13 @@ fn pgf_governance_proposal() -> Result<()> {
         continuous: vec![PGFTarget::Internal(PGFInternalTarget {
             amount: token::Amount::from_u64(10),
             target: bertha.clone(),
-        })],
+        })
+        .into()],
         retro: vec![PGFTarget::Internal(PGFInternalTarget {
             amount: token::Amount::from_u64(5),
             target: christel,
         })],
+        rounds: vec![],
     };
 
     let valid_proposal_json_path =
@@ -180,6 +180,7 @@ where
                 vp: "vp_user".to_string(),
                 threshold: 1,
                 public_keys: vec![StringEncoded::new(sk.ref_to())],
+                vesting: None,
             };
             let address = pre_genesis_tx.derive_established_address();
             println!(
@@ -1447,14 +1447,14 @@ fn masp_txs_and_queries() -> Result<()> {
     Ok(())
 }
 
-/// Test the unshielding tx attached to a wrapper:
+/// Test the masp fee payment attached to a wrapper:
 ///
 /// 1. Shield some tokens to reduce the unshielded balance
-/// 2. Submit a new wrapper with a valid unshielding tx and assert
+/// 2. Submit a new wrapper with a valid masp fee payment and assert
 /// success
-/// 3. Submit another transaction with valid fee unshielding and an inner
+/// 3. Submit another transaction with a valid masp fee payment and an inner
 /// shielded transfer with the same source
-/// 4. Submit a new wrapper with an invalid unshielding tx and assert the
+/// 4. Submit a new wrapper with an invalid masp fee payment and assert the
 /// failure
 #[test]
 fn wrapper_fee_unshielding() -> Result<()> {
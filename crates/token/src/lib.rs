@@ -40,6 +40,7 @@ where
 {
     if is_new_epoch {
         conversion::update_allowed_conversions(storage)?;
+        update_masp_epoch_start_balances(storage)?;
     }
     Ok(())
 }
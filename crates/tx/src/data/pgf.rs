@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use namada_core::address::Address;
 use namada_core::borsh::{BorshDeserialize, BorshSerialize};
@@ -34,6 +34,45 @@ pub struct UpdateStewardCommission {
     pub commission: HashMap<Address, Dec>,
 }
 
+/// A tx data type to register a project in a pgf funding round
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    Serialize,
+    Deserialize,
+)]
+pub struct RegisterFundingRoundProject {
+    /// The funding round id
+    pub round_id: u64,
+    /// The project address, receiving the funds
+    pub project: Address,
+}
+
+/// A tx data type to vote in a pgf funding round
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    Serialize,
+    Deserialize,
+)]
+pub struct VoteFundingRound {
+    /// The funding round id
+    pub round_id: u64,
+    /// The voter address
+    pub voter: Address,
+    /// The votes given to each project. Giving `n` votes to a project costs
+    /// `n²` voice credits.
+    pub votes: BTreeMap<Address, u64>,
+}
+
 #[cfg(any(test, feature = "testing"))]
 /// Tests and strategies for PGF
 pub mod tests {
@@ -41,7 +80,9 @@ pub mod tests {
     use namada_core::dec::testing::arb_dec;
     use proptest::{collection, prop_compose};
 
-    use super::UpdateStewardCommission;
+    use super::{
+        RegisterFundingRoundProject, UpdateStewardCommission, VoteFundingRound,
+    };
 
     prop_compose! {
         /// Generate an arbitraary steward commission update
@@ -55,4 +96,32 @@ pub mod tests {
             }
         }
     }
+
+    prop_compose! {
+        /// Generate an arbitrary funding round project registration
+        pub fn arb_register_funding_round_project()(
+            round_id in 0..1_000u64,
+            project in arb_non_internal_address(),
+        ) -> RegisterFundingRoundProject {
+            RegisterFundingRoundProject {
+                round_id,
+                project,
+            }
+        }
+    }
+
+    prop_compose! {
+        /// Generate an arbitrary funding round vote
+        pub fn arb_vote_funding_round()(
+            round_id in 0..1_000u64,
+            voter in arb_non_internal_address(),
+            votes in collection::btree_map(arb_non_internal_address(), 0..100u64, 0..10),
+        ) -> VoteFundingRound {
+            VoteFundingRound {
+                round_id,
+                voter,
+                votes,
+            }
+        }
+    }
 }
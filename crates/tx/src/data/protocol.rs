@@ -81,6 +81,9 @@ pub enum ProtocolTxType {
     BridgePoolVext,
     /// Validator set update signed by some validator
     ValSetUpdateVext,
+    /// Evidence of an equivocation with the Ethereum bridge hot key of
+    /// some validator
+    EthBridgeEquivocation,
 }
 
 impl ProtocolTxType {
@@ -96,6 +99,7 @@ impl ProtocolTxType {
                 | Self::EthEventsVext
                 | Self::BridgePoolVext
                 | Self::ValSetUpdateVext
+                | Self::EthBridgeEquivocation
         )
     }
 }
@@ -8,14 +8,14 @@ pub mod wrapper_tx {
 
     pub use ark_bls12_381::Bls12_381 as EllipticCurve;
     use masp_primitives::transaction::Transaction;
-    use namada_core::address::{Address, MASP};
+    use namada_core::address::Address;
     use namada_core::borsh::{
         BorshDeserialize, BorshSchema, BorshSerialize, BorshSerializeExt,
     };
     use namada_core::hash::Hash;
     use namada_core::key::*;
     use namada_core::storage::Epoch;
-    use namada_core::token::{Amount, DenominatedAmount, Transfer};
+    use namada_core::token::{Amount, DenominatedAmount};
     use namada_core::uint::Uint;
     use namada_gas::Gas;
     use namada_macros::BorshDeserializer;
@@ -25,9 +25,6 @@ pub mod wrapper_tx {
     use sha2::{Digest, Sha256};
     use thiserror::Error;
 
-    use crate::data::{DecryptedTx, TxType};
-    use crate::{Code, Data, Section, Tx};
-
     /// TODO: Determine a sane number for this
     const GAS_LIMIT_RESOLUTION: u64 = 1;
 
@@ -49,8 +46,8 @@ pub mod wrapper_tx {
              differs from that in the WrapperTx"
         )]
         InvalidKeyPair,
-        #[error("The provided unshielding tx is invalid: {0}")]
-        InvalidUnshield(String),
+        #[error("The provided MASP fee payment is invalid: {0}")]
+        InvalidMaspFeePayment(String),
         #[error("The given Tx fee amount overflowed")]
         OverflowingFee,
         #[error("Error while converting the denominated fee amount")]
@@ -179,8 +176,8 @@ pub mod wrapper_tx {
         }
     }
 
-    /// A transaction with an encrypted payload, an optional shielded pool
-    /// unshielding tx for fee payment and some non-encrypted metadata for
+    /// A transaction with an encrypted payload, an optional MASP transaction
+    /// for fee payment and some non-encrypted metadata for
     /// inclusion and / or verification purposes
     #[derive(
         Debug,
@@ -203,14 +200,14 @@ pub mod wrapper_tx {
         pub epoch: Epoch,
         /// Max amount of gas that can be used when executing the inner tx
         pub gas_limit: GasLimit,
-        /// The hash of the optional, unencrypted, unshielding transaction for
-        /// fee payment
+        /// The hash of the optional, unencrypted, MASP transaction section
+        /// whose value balance pays the fee
         pub unshield_section_hash: Option<Hash>,
     }
 
     impl WrapperTx {
         /// Create a new wrapper tx from unencrypted tx, the personal keypair,
-        /// an optional unshielding tx, and the metadata surrounding the
+        /// an optional MASP fee payment, and the metadata surrounding the
         /// inclusion of the tx. This method constructs the signature of
         /// relevant data and encrypts the transaction
         #[allow(clippy::too_many_arguments)]
@@ -245,19 +242,28 @@ pub mod wrapper_tx {
             hasher
         }
 
-        /// Performs validation on the optional fee unshielding data carried by
-        /// the wrapper and generates the tx for execution.
-        pub fn check_and_generate_fee_unshielding(
+        /// Performs the stateless validation of the optional MASP transaction
+        /// whose value balance pays the fees of this wrapper.
+        pub fn check_masp_fee_payment(
             &self,
-            transfer_code_hash: Hash,
-            transfer_code_tag: Option<String>,
             descriptions_limit: u64,
-            unshield: Transaction,
-        ) -> Result<Tx, WrapperTxErr> {
+            masp_transaction: &Transaction,
+        ) -> Result<(), WrapperTxErr> {
+            // The fee is paid with shielded funds alone, no transparent
+            // inputs are allowed
+            if masp_transaction
+                .transparent_bundle()
+                .is_some_and(|bundle| !bundle.vin.is_empty())
+            {
+                return Err(WrapperTxErr::InvalidMaspFeePayment(
+                    "Unexpected transparent inputs".to_string(),
+                ));
+            }
+
             // Check that the number of descriptions is within a certain limit
             // to avoid a possible DoS vector
-            let sapling_bundle = unshield.sapling_bundle().ok_or(
-                WrapperTxErr::InvalidUnshield(
+            let sapling_bundle = masp_transaction.sapling_bundle().ok_or(
+                WrapperTxErr::InvalidMaspFeePayment(
                     "Missing required sapling bundle".to_string(),
                 ),
             )?;
@@ -268,63 +274,28 @@ pub mod wrapper_tx {
             let descriptions = spends
                 .checked_add(converts)
                 .ok_or_else(|| {
-                    WrapperTxErr::InvalidUnshield(
+                    WrapperTxErr::InvalidMaspFeePayment(
                         "Descriptions overflow".to_string(),
                     )
                 })?
                 .checked_add(outs)
                 .ok_or_else(|| {
-                    WrapperTxErr::InvalidUnshield(
+                    WrapperTxErr::InvalidMaspFeePayment(
                         "Descriptions overflow".to_string(),
                     )
                 })?;
 
-            if u64::try_from(descriptions)
-                .map_err(|e| WrapperTxErr::InvalidUnshield(e.to_string()))?
-                > descriptions_limit
+            if u64::try_from(descriptions).map_err(|e| {
+                WrapperTxErr::InvalidMaspFeePayment(e.to_string())
+            })? > descriptions_limit
             {
-                return Err(WrapperTxErr::InvalidUnshield(
+                return Err(WrapperTxErr::InvalidMaspFeePayment(
                     "Descriptions exceed the maximum amount allowed"
                         .to_string(),
                 ));
             }
-            self.generate_fee_unshielding(
-                transfer_code_hash,
-                transfer_code_tag,
-                unshield,
-            )
-        }
-
-        /// Generates the fee unshielding tx for execution.
-        pub fn generate_fee_unshielding(
-            &self,
-            transfer_code_hash: Hash,
-            transfer_code_tag: Option<String>,
-            unshield: Transaction,
-        ) -> Result<Tx, WrapperTxErr> {
-            let mut tx =
-                Tx::from_type(TxType::Decrypted(DecryptedTx::Decrypted));
-            let masp_section = tx.add_section(Section::MaspTx(unshield));
-            let masp_hash = Hash(
-                masp_section
-                    .hash(&mut Sha256::new())
-                    .finalize_reset()
-                    .into(),
-            );
 
-            let transfer = Transfer {
-                source: MASP,
-                target: self.fee_payer(),
-                token: self.fee.token.clone(),
-                amount: self.get_tx_fee()?,
-                key: None,
-                shielded: Some(masp_hash),
-            };
-            let data = transfer.serialize_to_vec();
-            tx.set_data(Data::new(data));
-            tx.set_code(Code::from_hash(transfer_code_hash, transfer_code_tag));
-
-            Ok(tx)
+            Ok(())
         }
 
         /// Get the [`Amount`] of fees to be paid by the given wrapper. Returns
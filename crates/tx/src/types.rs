@@ -953,6 +953,8 @@ pub enum TxError {
     SigError(String),
     #[error("Failed to deserialize Tx: {0}")]
     Deserialization(String),
+    #[error("No MASP transaction section with hash {0}")]
+    MissingMaspTx(namada_core::hash::Hash),
 }
 
 /// A Namada transaction is represented as a header followed by a series of
@@ -1457,6 +1459,58 @@ impl Tx {
         self
     }
 
+    /// Get the masp builder sections of this transaction
+    pub fn masp_builders(&self) -> Vec<&MaspBuilder> {
+        self.sections
+            .iter()
+            .filter_map(|section| match section {
+                Section::MaspBuilder(builder) => Some(builder),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Attach a masp builder section to the masp transaction section that it
+    /// targets, replacing the builder previously attached to it if any
+    pub fn attach_masp_builder(
+        &mut self,
+        builder: MaspBuilder,
+    ) -> Result<&mut Self, TxError> {
+        match self.get_section(&builder.target).as_deref() {
+            Some(Section::MaspTx(_)) => {}
+            _ => return Err(TxError::MissingMaspTx(builder.target)),
+        }
+        self.sections.retain(|section| {
+            !matches!(
+                section,
+                Section::MaspBuilder(attached)
+                    if attached.target == builder.target
+            )
+        });
+        Ok(self.add_masp_builder(builder))
+    }
+
+    /// Replace the masp transaction section that has the same hash as the
+    /// given masp transaction. This is the case when they only differ by their
+    /// authorizations, since the hash does not commit to them.
+    pub fn update_masp_tx(
+        &mut self,
+        masp_tx: Transaction,
+    ) -> Result<&mut Self, TxError> {
+        let section = Section::MaspTx(masp_tx);
+        let hash = section.get_hash();
+        let target = self.sections.iter_mut().find(|target| {
+            matches!(target, Section::MaspTx(_)) && target.get_hash() == hash
+        });
+        match target {
+            Some(target) => {
+                *target = section;
+                Ok(self)
+            }
+            None => Err(TxError::MissingMaspTx(hash)),
+        }
+    }
+
     /// Add wasm code to the tx builder from hash
     pub fn add_code_from_hash(
         &mut self,
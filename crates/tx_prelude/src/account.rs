@@ -1,18 +1,40 @@
 pub use namada_account::*;
+use namada_core::hash::Hash;
 
 use super::*;
 
+/// The name of the user VP, the only VP that enforces the vesting of an
+/// account's tokens
+const VP_USER_WASM: &str = "vp_user.wasm";
+
 pub fn init_account(
     ctx: &mut Ctx,
     owner: &Address,
+    vp_code_hash: &Hash,
     data: InitAccount,
 ) -> EnvResult<Address> {
+    if let Some(vesting) = &data.vesting {
+        vesting
+            .validate()
+            .map_err(|err| Error::wrap("Invalid vesting", err))?;
+        let vp_user_hash =
+            ctx.read_bytes(&storage::Key::wasm_hash(VP_USER_WASM))?;
+        if vp_user_hash.as_deref() != Some(&vp_code_hash.0[..]) {
+            return Err(Error::new_const(
+                "A vesting account must use the user VP",
+            ));
+        }
+    }
+
     namada_account::init_account_storage(
         ctx,
         owner,
         &data.public_keys,
         data.threshold,
     )?;
+    if let Some(vesting) = data.vesting {
+        namada_account::write_vesting(ctx, owner, vesting)?;
+    }
 
     Ok(owner.to_owned())
 }
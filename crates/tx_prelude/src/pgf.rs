@@ -1,4 +1,6 @@
-use namada_tx::data::pgf::UpdateStewardCommission;
+use namada_tx::data::pgf::{
+    RegisterFundingRoundProject, UpdateStewardCommission, VoteFundingRound,
+};
 
 use super::*;
 
@@ -20,3 +22,32 @@ pub fn remove_steward(ctx: &mut Ctx, data: &Address) -> EnvResult<()> {
 
     Ok(())
 }
+
+pub fn register_funding_round_project(
+    ctx: &mut Ctx,
+    data: RegisterFundingRoundProject,
+) -> EnvResult<()> {
+    let current_epoch = ctx.get_block_epoch()?;
+    namada_governance::pgf::rounds::storage::register_project(
+        ctx,
+        data.round_id,
+        data.project,
+        current_epoch,
+    )?;
+
+    Ok(())
+}
+
+pub fn vote_funding_round(
+    ctx: &mut Ctx,
+    data: VoteFundingRound,
+) -> EnvResult<()> {
+    namada_governance::pgf::rounds::storage::write_ballot(
+        ctx,
+        data.round_id,
+        data.voter,
+        data.votes,
+    )?;
+
+    Ok(())
+}
@@ -4,12 +4,15 @@ use namada_core::dec::Dec;
 use namada_core::{key, token};
 pub use namada_proof_of_stake::parameters::PosParams;
 use namada_proof_of_stake::storage::read_pos_params;
-use namada_proof_of_stake::types::{ResultSlashing, ValidatorMetaData};
+use namada_proof_of_stake::types::{
+    DelegationPolicy, ResultSlashing, ValidatorMetaData,
+};
 use namada_proof_of_stake::{
     become_validator, bond_tokens, change_consensus_key,
-    change_validator_commission_rate, change_validator_metadata,
-    claim_reward_tokens, deactivate_validator, reactivate_validator,
-    redelegate_tokens, unbond_tokens, unjail_validator, withdraw_tokens,
+    change_validator_commission_rate, change_validator_delegation_policy,
+    change_validator_metadata, claim_reward_tokens, deactivate_validator,
+    reactivate_validator, redelegate_tokens, unbond_tokens, unjail_validator,
+    withdraw_tokens,
 };
 pub use namada_proof_of_stake::{parameters, types};
 use namada_tx::data::pos::BecomeValidator;
@@ -197,4 +200,13 @@ impl Ctx {
             current_epoch,
         )
     }
+
+    /// Change the policy on who is allowed to delegate to a validator.
+    pub fn change_validator_delegation_policy(
+        &mut self,
+        validator: &Address,
+        policy: &DelegationPolicy,
+    ) -> TxResult {
+        change_validator_delegation_policy(self, validator, policy)
+    }
 }
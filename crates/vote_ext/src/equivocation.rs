@@ -0,0 +1,64 @@
+//! Contains types of evidence of equivocations with the Ethereum
+//! bridge hot keys of validators, found in their vote extensions.
+
+use namada_core::address::Address;
+use namada_core::borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use namada_core::ethereum_events::Uint;
+use namada_core::keccak::KeccakHash;
+use namada_macros::BorshDeserializer;
+#[cfg(feature = "migrations")]
+use namada_migrations::*;
+
+use crate::{bridge_pool_roots, validator_set_update};
+
+/// Evidence of a validator having signed a message with its
+/// Ethereum bridge hot key, which conflicts with the message
+/// agreed upon by the protocol.
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+)]
+pub enum EthBridgeEquivocation {
+    /// A Bridge pool root vote extension, whose inner signature
+    /// is over a root and nonce other than the ones at the
+    /// block height of the vote extension.
+    BridgePoolRoot {
+        /// The vote extension, signed with the validator's
+        /// protocol key.
+        vext: bridge_pool_roots::SignedVext,
+        /// The Bridge pool root signed over with the
+        /// validator's hot key.
+        root: KeccakHash,
+        /// The Bridge pool nonce signed over with the
+        /// validator's hot key.
+        nonce: Uint,
+    },
+    /// Two validator set update vote extensions, signed with
+    /// the validator's hot key at the same signing epoch, over
+    /// different voting powers.
+    ValidatorSetUpdate {
+        /// The first signed validator set update.
+        first: validator_set_update::SignedVext,
+        /// The second signed validator set update, conflicting
+        /// with the first one.
+        second: validator_set_update::SignedVext,
+    },
+}
+
+impl EthBridgeEquivocation {
+    /// Get the address of the validator that equivocated.
+    pub fn validator(&self) -> &Address {
+        match self {
+            Self::BridgePoolRoot { vext, .. } => &vext.data.validator_addr,
+            Self::ValidatorSetUpdate { first, .. } => {
+                &first.data.validator_addr
+            }
+        }
+    }
+}
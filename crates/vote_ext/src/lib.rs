@@ -1,6 +1,7 @@
 //! This module contains types necessary for processing vote extensions.
 
 pub mod bridge_pool_roots;
+pub mod equivocation;
 pub mod ethereum_events;
 pub mod validator_set_update;
 
@@ -115,6 +116,9 @@ ethereum_tx_data_declare! {
         BridgePoolVext(bridge_pool_roots::SignedVext),
         /// Validator set update signed by some validator
         ValSetUpdateVext(validator_set_update::SignedVext),
+        /// Evidence of an equivocation with the Ethereum bridge
+        /// hot key of some validator
+        EthBridgeEquivocation(equivocation::EthBridgeEquivocation),
     }
 }
 
@@ -176,6 +180,7 @@ impl EthereumTxData {
             EthEventsVext,
             BridgePoolVext,
             ValSetUpdateVext,
+            EthBridgeEquivocation,
         }
     }
 
@@ -209,6 +214,10 @@ impl EthereumTxData {
                 BorshDeserialize::try_from_slice(data)
                     .map(EthereumTxData::ValSetUpdateVext)
             },
+            ProtocolTxType::EthBridgeEquivocation => |data| {
+                BorshDeserialize::try_from_slice(data)
+                    .map(EthereumTxData::EthBridgeEquivocation)
+            },
         };
         deserialize(data)
             .map_err(|err| TxError::Deserialization(err.to_string()))
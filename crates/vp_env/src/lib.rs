@@ -119,6 +119,16 @@ where
         tx_data: &Tx,
     ) -> Result<Transaction, namada_storage::Error> {
         let signed = tx_data;
+        // Fee payment of a wrapper from the value balance of a MASP tx
+        if let Some(wrapper) = signed.header().wrapper() {
+            let masp_tx = wrapper
+                .unshield_section_hash
+                .and_then(|ref hash| signed.get_section(hash))
+                .and_then(|x| x.as_ref().masp_tx())
+                .ok_or_err_msg("unable to find fee payment section")?;
+            return Ok(masp_tx);
+        }
+
         let data = signed.data().ok_or_err_msg("No transaction data")?;
         if let Ok(transfer) = Transfer::try_from_slice(&data) {
             let shielded_hash = transfer
@@ -27,6 +27,7 @@ use namada_core::storage::{
     BlockHash, BlockHeight, Epoch, Epochs, Header, TxIndex, BLOCK_HASH_LENGTH,
 };
 pub use namada_core::*;
+pub use namada_governance::pgf::rounds::storage as pgf_rounds_storage;
 pub use namada_governance::pgf::storage as pgf_storage;
 pub use namada_governance::storage as gov_storage;
 pub use namada_macros::validity_predicate;
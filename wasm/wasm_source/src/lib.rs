@@ -6,8 +6,12 @@ pub mod tx_bond;
 pub mod tx_bridge_pool;
 #[cfg(feature = "tx_change_consensus_key")]
 pub mod tx_change_consensus_key;
+#[cfg(feature = "tx_change_representative")]
+pub mod tx_change_representative;
 #[cfg(feature = "tx_change_validator_commission")]
 pub mod tx_change_validator_commission;
+#[cfg(feature = "tx_change_validator_delegation_policy")]
+pub mod tx_change_validator_delegation_policy;
 #[cfg(feature = "tx_change_validator_metadata")]
 pub mod tx_change_validator_metadata;
 #[cfg(feature = "tx_claim_rewards")]
@@ -24,6 +28,8 @@ pub mod tx_init_proposal;
 pub mod tx_reactivate_validator;
 #[cfg(feature = "tx_redelegate")]
 pub mod tx_redelegate;
+#[cfg(feature = "tx_register_pgf_project")]
+pub mod tx_register_pgf_project;
 #[cfg(feature = "tx_resign_steward")]
 pub mod tx_resign_steward;
 #[cfg(feature = "tx_reveal_pk")]
@@ -38,6 +44,8 @@ pub mod tx_unjail_validator;
 pub mod tx_update_account;
 #[cfg(feature = "tx_update_steward_commission")]
 pub mod tx_update_steward_commission;
+#[cfg(feature = "tx_vote_pgf_funding_round")]
+pub mod tx_vote_pgf_funding_round;
 #[cfg(feature = "tx_vote_proposal")]
 pub mod tx_vote_proposal;
 #[cfg(feature = "tx_withdraw")]
@@ -0,0 +1,21 @@
+//! A tx for a delegator to assign its governance voting power to a
+//! representative, or to revoke it.
+
+use namada_tx_prelude::*;
+
+// TODO: need to benchmark gas!!!
+#[transaction(gas = 220000)]
+fn apply_tx(ctx: &mut Ctx, tx_data: Tx) -> TxResult {
+    let signed = tx_data;
+    let data = signed.data().ok_or_err_msg("Missing data").map_err(|err| {
+        ctx.set_commitment_sentinel();
+        err
+    })?;
+    let tx_data =
+        governance::ChangeRepresentativeData::try_from_slice(&data[..])
+            .wrap_err("failed to decode ChangeRepresentativeData")?;
+
+    debug_log!("apply_tx called to change a governance representative");
+
+    governance::change_representative(ctx, tx_data)
+}
@@ -0,0 +1,15 @@
+//! A tx for a validator to change who is allowed to delegate to it.
+
+use namada_tx_prelude::proof_of_stake::types::DelegationPolicyChange;
+use namada_tx_prelude::*;
+
+// TODO: need to benchmark gas!!!
+#[transaction(gas = 220000)]
+fn apply_tx(ctx: &mut Ctx, tx_data: Tx) -> TxResult {
+    let signed = tx_data;
+    let data = signed.data().ok_or_err_msg("Missing data")?;
+    let DelegationPolicyChange { validator, policy } =
+        DelegationPolicyChange::try_from_slice(&data[..])
+            .wrap_err("failed to decode DelegationPolicyChange")?;
+    ctx.change_validator_delegation_policy(&validator, &policy)
+}
@@ -28,10 +28,10 @@ fn apply_tx(ctx: &mut Ctx, tx_data: Tx) -> TxResult {
             err
         })?;
 
-    let address =
-        ctx.init_account(vp_code_sec.code.hash(), &vp_code_sec.tag)?;
+    let vp_code_hash = vp_code_sec.code.hash();
+    let address = ctx.init_account(vp_code_hash, &vp_code_sec.tag)?;
 
-    match account::init_account(ctx, &address, tx_data) {
+    match account::init_account(ctx, &address, &vp_code_hash, tx_data) {
         Ok(address) => {
             debug_log!("Created account {}", address.encode(),)
         }
@@ -0,0 +1,21 @@
+//! A tx to register a project in a PGF funding round
+
+use namada_tx_prelude::transaction::pgf::RegisterFundingRoundProject;
+use namada_tx_prelude::*;
+
+// TODO: need to benchmark gas!!!
+#[transaction(gas = 220000)]
+fn apply_tx(ctx: &mut Ctx, tx_data: Tx) -> TxResult {
+    let signed = tx_data;
+    let data = signed.data().ok_or_err_msg("Missing data").map_err(|err| {
+        ctx.set_commitment_sentinel();
+        err
+    })?;
+    let registration =
+        RegisterFundingRoundProject::try_from_slice(&data[..])
+            .wrap_err("failed to decode a RegisterFundingRoundProject")?;
+
+    pgf::register_funding_round_project(ctx, registration)?;
+
+    Ok(())
+}
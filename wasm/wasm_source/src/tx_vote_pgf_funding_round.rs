@@ -0,0 +1,20 @@
+//! A tx to vote for the projects of a PGF funding round
+
+use namada_tx_prelude::transaction::pgf::VoteFundingRound;
+use namada_tx_prelude::*;
+
+// TODO: need to benchmark gas!!!
+#[transaction(gas = 220000)]
+fn apply_tx(ctx: &mut Ctx, tx_data: Tx) -> TxResult {
+    let signed = tx_data;
+    let data = signed.data().ok_or_err_msg("Missing data").map_err(|err| {
+        ctx.set_commitment_sentinel();
+        err
+    })?;
+    let vote = VoteFundingRound::try_from_slice(&data[..])
+        .wrap_err("failed to decode a VoteFundingRound")?;
+
+    pgf::vote_funding_round(ctx, vote)?;
+
+    Ok(())
+}
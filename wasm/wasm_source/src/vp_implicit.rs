@@ -27,7 +27,9 @@ enum KeyType<'a> {
     PoS,
     Masp,
     PgfSteward(&'a Address),
+    PgfFundingRound(&'a Address),
     GovernanceVote(&'a Address),
+    GovernanceRepresentative(&'a Address),
     Ibc,
     Unknown,
 }
@@ -49,6 +51,14 @@ impl<'a> From<&'a storage::Key> for KeyType<'a> {
             Self::PoS
         } else if let Some(address) = pgf_storage::keys::is_stewards_key(key) {
             Self::PgfSteward(address)
+        } else if let Some((_, project)) =
+            pgf_rounds_storage::is_funding_round_project_key(key)
+        {
+            Self::PgfFundingRound(project)
+        } else if let Some((_, voter)) =
+            pgf_rounds_storage::is_funding_round_ballot_key(key)
+        {
+            Self::PgfFundingRound(voter)
         } else if gov_storage::keys::is_vote_key(key) {
             let voter_address = gov_storage::keys::get_voter_address(key);
             if let Some(address) = voter_address {
@@ -56,6 +66,18 @@ impl<'a> From<&'a storage::Key> for KeyType<'a> {
             } else {
                 Self::Unknown
             }
+        } else if let Some(representative) =
+            gov_storage::keys::get_representative_vote_address(key)
+        {
+            Self::GovernanceVote(representative)
+        } else if let Some(delegator) =
+            gov_storage::keys::is_representative_key(key)
+        {
+            Self::GovernanceRepresentative(delegator)
+        } else if let Some((_, delegator)) =
+            gov_storage::keys::is_represented_key(key)
+        {
+            Self::GovernanceRepresentative(delegator)
         } else if token::storage_key::is_masp_key(key) {
             Self::Masp
         } else if ibc::is_ibc_key(key) {
@@ -149,7 +171,13 @@ fn validate_tx(
             KeyType::TokenMinter(minter) => minter != &addr || *valid_sig,
             KeyType::PoS => validate_pos_changes(ctx, &addr, key, &valid_sig)?,
             KeyType::PgfSteward(address) => address != &addr || *valid_sig,
+            KeyType::PgfFundingRound(address) => {
+                address != &addr || *valid_sig
+            }
             KeyType::GovernanceVote(voter) => voter != &addr || *valid_sig,
+            KeyType::GovernanceRepresentative(delegator) => {
+                delegator != &addr || *valid_sig
+            }
             KeyType::Masp | KeyType::Ibc => true,
             KeyType::Unknown => {
                 // Unknown changes require a valid signature
@@ -6,8 +6,12 @@
 //! It allows to bond, unbond and withdraw tokens to and from PoS system with a
 //! valid signature(s).
 //!
-//! For validator a tx to change a validator's commission rate or metadata
-//! requires a valid signature(s) only from the validator.
+//! For validator a tx to change a validator's commission rate, metadata or
+//! delegation policy requires a valid signature(s) only from the validator.
+//!
+//! For an account with a vesting, tokens still locked by the vesting schedule
+//! cannot be sent, but they can be bonded. The vesting cannot be modified and
+//! the VP cannot be updated while some tokens are locked.
 //!
 //! Any other storage key changes are allowed only with a valid signature.
 
@@ -23,32 +27,39 @@ use proof_of_stake::storage_key::{
     is_pos_key, is_rewards_counter_key, is_total_consensus_stake_key,
     is_total_deltas_key, is_unbond_key, is_validator_address_raw_hash_key,
     is_validator_addresses_key, is_validator_commission_rate_key,
-    is_validator_deltas_key, is_validator_eth_cold_key_key,
-    is_validator_eth_hot_key_key, is_validator_max_commission_rate_change_key,
-    is_validator_metadata_key, is_validator_redelegations_key,
-    is_validator_set_positions_key, is_validator_state_epoched_meta_key,
-    is_validator_state_key, is_validator_total_bond_or_unbond_key,
+    is_validator_delegation_policy_key, is_validator_deltas_key,
+    is_validator_eth_cold_key_key, is_validator_eth_hot_key_key,
+    is_validator_max_commission_rate_change_key, is_validator_metadata_key,
+    is_validator_redelegations_key, is_validator_set_positions_key,
+    is_validator_state_epoched_meta_key, is_validator_state_key,
+    is_validator_total_bond_or_unbond_key,
 };
 
 enum KeyType<'a> {
-    TokenBalance { owner: &'a Address },
+    TokenBalance {
+        token: &'a Address,
+        owner: &'a Address,
+    },
     TokenMinted,
     TokenMinter(&'a Address),
     PoS,
     Vp(&'a Address),
     Masp,
+    Vesting(&'a Address),
     PgfSteward(&'a Address),
+    PgfFundingRound(&'a Address),
     GovernanceVote(&'a Address),
+    GovernanceRepresentative(&'a Address),
     Ibc,
     Unknown,
 }
 
 impl<'a> From<&'a storage::Key> for KeyType<'a> {
     fn from(key: &'a storage::Key) -> KeyType<'a> {
-        if let Some([_, owner]) =
+        if let Some([token, owner]) =
             token::storage_key::is_any_token_balance_key(key)
         {
-            Self::TokenBalance { owner }
+            Self::TokenBalance { token, owner }
         } else if token::storage_key::is_any_minted_balance_key(key).is_some() {
             Self::TokenMinted
         } else if let Some(minter) = token::storage_key::is_any_minter_key(key)
@@ -63,10 +74,32 @@ impl<'a> From<&'a storage::Key> for KeyType<'a> {
             } else {
                 Self::Unknown
             }
+        } else if let Some(representative) =
+            gov_storage::keys::get_representative_vote_address(key)
+        {
+            Self::GovernanceVote(representative)
+        } else if let Some(delegator) =
+            gov_storage::keys::is_representative_key(key)
+        {
+            Self::GovernanceRepresentative(delegator)
+        } else if let Some((_, delegator)) =
+            gov_storage::keys::is_represented_key(key)
+        {
+            Self::GovernanceRepresentative(delegator)
         } else if let Some(address) = pgf_storage::keys::is_stewards_key(key) {
             Self::PgfSteward(address)
+        } else if let Some((_, project)) =
+            pgf_rounds_storage::is_funding_round_project_key(key)
+        {
+            Self::PgfFundingRound(project)
+        } else if let Some((_, voter)) =
+            pgf_rounds_storage::is_funding_round_ballot_key(key)
+        {
+            Self::PgfFundingRound(voter)
         } else if let Some(address) = key.is_validity_predicate() {
             Self::Vp(address)
+        } else if let Some(address) = account::is_vesting_key(key) {
+            Self::Vesting(address)
         } else if token::storage_key::is_masp_key(key) {
             Self::Masp
         } else if ibc::is_ibc_key(key) {
@@ -99,15 +132,20 @@ fn validate_tx(
     for key in keys_changed.iter() {
         let key_type: KeyType = key.into();
         let is_valid = match key_type {
-            KeyType::TokenBalance { owner, .. } => {
+            KeyType::TokenBalance { token, owner } => {
                 if owner == &addr {
                     let pre: token::Amount =
                         ctx.read_pre(key)?.unwrap_or_default();
                     let post: token::Amount =
                         ctx.read_post(key)?.unwrap_or_default();
                     let change = post.change() - pre.change();
-                    // debit has to signed, credit doesn't
-                    let valid = change.non_negative() || *valid_sig;
+                    // debit has to signed and cannot spend locked tokens,
+                    // credit doesn't
+                    let valid = change.non_negative()
+                        || (*valid_sig
+                            && validate_vesting_debit(
+                                ctx, owner, token, post,
+                            )?);
                     debug_log!(
                         "token key: {}, change: {:?}, valid_sig: {}, valid \
                          modification: {}",
@@ -133,15 +171,24 @@ fn validate_tx(
             KeyType::TokenMinter(minter) => minter != &addr || *valid_sig,
             KeyType::PoS => validate_pos_changes(ctx, &addr, key, &valid_sig)?,
             KeyType::PgfSteward(address) => address != &addr || *valid_sig,
+            KeyType::PgfFundingRound(address) => {
+                address != &addr || *valid_sig
+            }
             KeyType::GovernanceVote(voter) => voter != &addr || *valid_sig,
+            KeyType::GovernanceRepresentative(delegator) => {
+                delegator != &addr || *valid_sig
+            }
             KeyType::Vp(owner) => {
                 let has_post: bool = ctx.has_key_post(key)?;
                 if owner == &addr {
-                    has_post && *valid_sig
+                    // The VP enforces the vesting so it cannot be changed
+                    // until all the tokens are unlocked
+                    has_post && *valid_sig && !has_locked_tokens(ctx, owner)?
                 } else {
                     true
                 }
             }
+            KeyType::Vesting(owner) => owner != &addr,
             KeyType::Masp | KeyType::Ibc => true,
             KeyType::Unknown => {
                 // Unknown changes require a valid signature
@@ -157,6 +204,63 @@ fn validate_tx(
     accept()
 }
 
+/// Check that a debit of the owner's balance doesn't spend tokens still locked
+/// by the account's vesting. Locked tokens that are bonded count towards the
+/// locked amount.
+fn validate_vesting_debit(
+    ctx: &Ctx,
+    owner: &Address,
+    token: &Address,
+    post_balance: token::Amount,
+) -> VpResult {
+    let vesting = match account::vesting(&ctx.pre(), owner)? {
+        Some(vesting) if &vesting.token == token => vesting,
+        _ => return accept(),
+    };
+    let epoch = ctx.get_block_epoch()?;
+    let locked = vesting.locked_amount(epoch);
+    if locked.is_zero() {
+        return accept();
+    }
+    let bonded: token::Amount = if token == &ctx.get_native_token()? {
+        let params = read_pos_params(&ctx.post())?;
+        let pipeline_epoch = epoch + params.pipeline_len;
+        proof_of_stake::queries::find_delegations(
+            &ctx.post(),
+            owner,
+            &pipeline_epoch,
+        )?
+        .into_values()
+        .sum()
+    } else {
+        token::Amount::zero()
+    };
+    let valid = match post_balance.checked_add(bonded) {
+        Some(total) => total >= locked,
+        None => true,
+    };
+    debug_log!(
+        "vesting token: {}, locked: {}, balance: {}, bonded: {}, valid: {}",
+        token,
+        locked.to_string_native(),
+        post_balance.to_string_native(),
+        bonded.to_string_native(),
+        valid
+    );
+    Ok(valid)
+}
+
+/// Check if the owner's vesting still locks some tokens
+fn has_locked_tokens(ctx: &Ctx, owner: &Address) -> VpResult {
+    match account::vesting(&ctx.pre(), owner)? {
+        Some(vesting) => {
+            let epoch = ctx.get_block_epoch()?;
+            Ok(!vesting.locked_amount(epoch).is_zero())
+        }
+        None => Ok(false),
+    }
+}
+
 fn validate_pos_changes(
     ctx: &Ctx,
     owner: &Address,
@@ -195,6 +299,14 @@ fn validate_pos_changes(
         }
     };
 
+    // Delegation policy changes must be signed by the validator whose
+    // policy is manipulated
+    let is_valid_delegation_policy_change =
+        || match is_validator_delegation_policy_key(key) {
+            Some(address) => address == owner && **valid_sig,
+            None => false,
+        };
+
     // Changes in validator state
     let is_valid_state_change = || {
         let state_change = is_validator_state_key(key);
@@ -325,6 +437,7 @@ fn validate_pos_changes(
         || is_valid_redelegation()
         || is_valid_commission_rate_change()
         || is_valid_metadata_change()
+        || is_valid_delegation_policy_change()
         || is_valid_become_validator()
         || **valid_sig)
 }
@@ -549,6 +662,219 @@ mod tests {
         );
     }
 
+    /// Run a signed transfer of the given amount from an account holding 15
+    /// tokens of which 10 are still locked by its vesting.
+    fn signed_vesting_debit_transfer(amount: u64) -> bool {
+        // Initialize a tx environment
+        let mut tx_env = TestTxEnv::default();
+
+        let vp_owner = address::testing::established_address_1();
+        let keypair = key::testing::keypair_1();
+        let public_key = keypair.ref_to();
+        let target = address::testing::established_address_2();
+        let token = address::testing::btc();
+
+        // Spawn the accounts to be able to modify their storage
+        tx_env.spawn_accounts([&vp_owner, &target, &token]);
+        tx_env.init_account_storage(&vp_owner, vec![public_key.clone()], 1);
+
+        tx_env.credit_tokens(&vp_owner, &token, token::Amount::from(15));
+        token::write_denom(&mut tx_env.state, &token, 0.into()).unwrap();
+        account::write_vesting(
+            &mut tx_env.state,
+            &vp_owner,
+            account::Vesting {
+                token: token.clone(),
+                amount: token::Amount::from(10),
+                schedule: account::VestingSchedule::Linear {
+                    start_epoch: Epoch(0),
+                    cliff_epoch: Epoch(5),
+                    end_epoch: Epoch(10),
+                },
+            },
+        )
+        .unwrap();
+
+        let amount = token::DenominatedAmount::new(
+            token::Amount::from(amount),
+            0.into(),
+        );
+
+        // Initialize VP environment from a transaction
+        vp_host_env::init_from_tx(vp_owner.clone(), tx_env, |address| {
+            // Apply transfer in a transaction
+            tx_host_env::token::transfer(
+                tx::ctx(),
+                address,
+                &target,
+                &token,
+                amount,
+            )
+            .unwrap();
+        });
+
+        let pks_map = AccountPublicKeysMap::from_iter(vec![public_key]);
+
+        let mut vp_env = vp_host_env::take();
+        let mut tx = vp_env.tx.clone();
+        tx.set_data(Data::new(vec![]));
+        tx.set_code(Code::new(vec![], None));
+        tx.add_section(Section::Signature(Signature::new(
+            vec![tx.raw_header_hash()],
+            pks_map.index_secret_keys(vec![keypair]),
+            None,
+        )));
+        let signed_tx = tx.clone();
+        vp_env.tx = signed_tx.clone();
+        let keys_changed: BTreeSet<storage::Key> =
+            vp_env.all_touched_storage_keys();
+        let verifiers: BTreeSet<Address> = BTreeSet::default();
+        vp_host_env::set(vp_env);
+        validate_tx(&CTX, signed_tx, vp_owner, keys_changed, verifiers).unwrap()
+    }
+
+    /// Test that a signed debit transfer of unlocked vesting tokens is
+    /// accepted.
+    #[test]
+    fn test_signed_unlocked_vesting_debit_transfer_accepted() {
+        assert!(signed_vesting_debit_transfer(5));
+    }
+
+    /// Test that a signed debit transfer of locked vesting tokens is rejected.
+    #[test]
+    fn test_signed_locked_vesting_debit_transfer_rejected() {
+        assert!(!signed_vesting_debit_transfer(6));
+    }
+
+    /// Run a signed tx that bonds the given amount and then transfers the given
+    /// amount from an account holding 15 native tokens of which 10 are still
+    /// locked by its vesting.
+    fn signed_vesting_bond_and_debit_transfer(
+        bond_amount: u64,
+        transfer_amount: u64,
+    ) -> bool {
+        // Init PoS genesis
+        let pos_params = PosParams::default();
+        let validator = address::testing::established_address_3();
+        let initial_stake = token::Amount::from_uint(10_098_123, 0).unwrap();
+        let consensus_key = key::testing::keypair_2().ref_to();
+        let protocol_key = key::testing::keypair_1().ref_to();
+        let commission_rate = Dec::new(5, 2).unwrap();
+        let max_commission_rate_change = Dec::new(1, 2).unwrap();
+
+        let genesis_validators = [GenesisValidator {
+            address: validator.clone(),
+            tokens: initial_stake,
+            consensus_key,
+            protocol_key,
+            commission_rate,
+            max_commission_rate_change,
+            eth_hot_key: key::common::PublicKey::Secp256k1(
+                key::testing::gen_keypair::<key::secp256k1::SigScheme>()
+                    .ref_to(),
+            ),
+            eth_cold_key: key::common::PublicKey::Secp256k1(
+                key::testing::gen_keypair::<key::secp256k1::SigScheme>()
+                    .ref_to(),
+            ),
+            metadata: Default::default(),
+        }];
+
+        init_pos(&genesis_validators[..], &pos_params, Epoch(0));
+
+        // Initialize a tx environment
+        let mut tx_env = tx_host_env::take();
+
+        let secret_key = key::testing::keypair_1();
+        let public_key = secret_key.ref_to();
+        let vp_owner: Address = address::testing::established_address_2();
+        let target = address::testing::established_address_1();
+        let token = address::testing::nam();
+
+        // Spawn the accounts to be able to modify their storage
+        tx_env.spawn_accounts([&target, &token]);
+        tx_env.init_account_storage(&vp_owner, vec![public_key.clone()], 1);
+
+        // write the denomination of NAM into storage
+        token::write_denom(
+            &mut tx_env.state,
+            &token,
+            token::NATIVE_MAX_DECIMAL_PLACES.into(),
+        )
+        .unwrap();
+
+        tx_env.credit_tokens(&vp_owner, &token, token::Amount::from(15));
+        account::write_vesting(
+            &mut tx_env.state,
+            &vp_owner,
+            account::Vesting {
+                token: token.clone(),
+                amount: token::Amount::from(10),
+                schedule: account::VestingSchedule::Linear {
+                    start_epoch: Epoch(0),
+                    cliff_epoch: Epoch(5),
+                    end_epoch: Epoch(10),
+                },
+            },
+        )
+        .unwrap();
+
+        let bond_amount = token::Amount::from(bond_amount);
+        let transfer_amount = token::DenominatedAmount::new(
+            token::Amount::from(transfer_amount),
+            token::NATIVE_MAX_DECIMAL_PLACES.into(),
+        );
+
+        // Initialize VP environment from a transaction
+        vp_host_env::init_from_tx(vp_owner.clone(), tx_env, |address| {
+            // Bond some of the tokens, then transfer some others
+            tx::ctx()
+                .bond_tokens(Some(address), &validator, bond_amount)
+                .unwrap();
+            tx_host_env::token::transfer(
+                tx::ctx(),
+                address,
+                &target,
+                &token,
+                transfer_amount,
+            )
+            .unwrap();
+        });
+
+        let pks_map = AccountPublicKeysMap::from_iter(vec![public_key]);
+
+        let mut vp_env = vp_host_env::take();
+        let mut tx = vp_env.tx.clone();
+        tx.set_data(Data::new(vec![]));
+        tx.set_code(Code::new(vec![], None));
+        tx.add_section(Section::Signature(Signature::new(
+            vec![tx.raw_header_hash()],
+            pks_map.index_secret_keys(vec![secret_key]),
+            None,
+        )));
+        let signed_tx = tx.clone();
+        vp_env.tx = signed_tx.clone();
+        let keys_changed: BTreeSet<storage::Key> =
+            vp_env.all_touched_storage_keys();
+        let verifiers: BTreeSet<Address> = BTreeSet::default();
+        vp_host_env::set(vp_env);
+        validate_tx(&CTX, signed_tx, vp_owner, keys_changed, verifiers).unwrap()
+    }
+
+    /// Test that the locked vesting tokens that are bonded count towards the
+    /// locked amount, so that the remaining unlocked tokens can be sent.
+    #[test]
+    fn test_signed_bonded_locked_vesting_debit_transfer_accepted() {
+        assert!(signed_vesting_bond_and_debit_transfer(4, 5));
+    }
+
+    /// Test that a transfer of locked vesting tokens that are neither in the
+    /// balance nor bonded is rejected.
+    #[test]
+    fn test_signed_bonded_locked_vesting_debit_transfer_rejected() {
+        assert!(!signed_vesting_bond_and_debit_transfer(4, 6));
+    }
+
     /// Test that a non-validator PoS action that must be authorized is rejected
     /// without a valid signature.
     #[test]